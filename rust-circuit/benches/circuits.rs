@@ -0,0 +1,71 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::Groth16;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::circuit::Circuit;
+use vortex::{prover, witness};
+
+/// Circuits under benchmark: name, empty circuit for setup, satisfied instance.
+fn circuits() -> Vec<(&'static str, Circuit, Circuit)> {
+    let (a, b) = (Fr::from(7919u64), Fr::from(104729u64));
+    vec![
+        (
+            "multiplication",
+            Circuit::empty(),
+            Circuit::new(a * b, a, b, false).unwrap(),
+        ),
+        (
+            "non_trivial",
+            Circuit::empty_non_trivial(),
+            Circuit::new(a * b, a, b, true).unwrap(),
+        ),
+    ]
+}
+
+fn num_constraints(circuit: Circuit) -> usize {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    circuit.generate_constraints(cs.clone()).unwrap();
+    cs.finalize();
+    cs.num_constraints()
+}
+
+fn bench_circuits(c: &mut Criterion) {
+    for (name, empty, instance) in circuits() {
+        // Encode the constraint count in the benchmark id so it shows up in reports
+        let id = format!("{}/{}-constraints", name, num_constraints(empty.clone()));
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+
+        c.bench_with_input(BenchmarkId::new("keygen", &id), &empty, |bench, empty| {
+            bench.iter(|| {
+                Groth16::<Bn254>::generate_random_parameters_with_reduction(empty.clone(), &mut rng)
+                    .unwrap()
+            })
+        });
+
+        c.bench_with_input(
+            BenchmarkId::new("witness", &id),
+            &instance,
+            |bench, instance| bench.iter(|| witness::generate(instance.clone()).unwrap()),
+        );
+
+        let pk =
+            Groth16::<Bn254>::generate_random_parameters_with_reduction(empty, &mut rng).unwrap();
+        c.bench_with_input(
+            BenchmarkId::new("prove", &id),
+            &instance,
+            |bench, instance| {
+                bench.iter(|| prover::prove(instance.clone(), &pk, &mut rng).unwrap())
+            },
+        );
+
+        let bundle = prover::prove(instance, &pk, &mut rng).unwrap();
+        c.bench_with_input(BenchmarkId::new("verify", &id), &bundle, |bench, bundle| {
+            bench.iter(|| prover::verify(bundle, &pk.vk).unwrap())
+        });
+    }
+}
+
+criterion_group!(benches, bench_circuits);
+criterion_main!(benches);
@@ -12,18 +12,25 @@ fi
 
 # Build for Node.js
 echo "Building for Node.js..."
-wasm-pack build --target nodejs --out-dir pkg/nodejs --release
+wasm-pack build --target nodejs --out-dir pkg/nodejs --release -- --no-default-features --features wasm
 
 # Build for web browsers
 echo "Building for web..."
-wasm-pack build --target web --out-dir pkg/web --release
+wasm-pack build --target web --out-dir pkg/web --release -- --no-default-features --features wasm
 
 # Build for bundlers (webpack, rollup, etc.)
 echo "Building for bundlers..."
-wasm-pack build --target bundler --out-dir pkg/bundler --release
+wasm-pack build --target bundler --out-dir pkg/bundler --release -- --no-default-features --features wasm
+
+# Size-optimized prover and verifier as separate modules for mobile web
+echo "Building small prover and verifier for web..."
+wasm-pack build --target web --out-dir pkg/web-prover --profile small-wasm -- --no-default-features --features wasm-prover,small-wasm
+wasm-pack build --target web --out-dir pkg/web-verifier --profile small-wasm -- --no-default-features --features wasm-verifier,small-wasm
 
 echo "✅ WASM build complete!"
 echo "Outputs:"
 echo "  - Node.js: pkg/nodejs/"
 echo "  - Web: pkg/web/"
-echo "  - Bundlers: pkg/bundler/"
\ No newline at end of file
+echo "  - Bundlers: pkg/bundler/"
+echo "  - Small web prover: pkg/web-prover/"
+echo "  - Small web verifier: pkg/web-verifier/"
\ No newline at end of file
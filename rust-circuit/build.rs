@@ -0,0 +1,27 @@
+fn main() {
+    // Node addons resolve N-API symbols from the host process at load time
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+}
+
+/// Regenerates `include/vortex.h` from the `extern "C"` functions in `src/ffi`.
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    println!("cargo:rerun-if-changed=src/ffi");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("Failed to read cbindgen.toml");
+
+    // Parse the FFI module directly; resolving the whole crate needs `cargo metadata`
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/ffi/mod.rs", crate_dir))
+        .generate()
+        .expect("Failed to generate C header")
+        .write_to_file(format!("{}/include/vortex.h", crate_dir));
+}
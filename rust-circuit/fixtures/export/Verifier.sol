@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: MIT
+// Generated by the vortex `export solidity` command. Do not edit.
+pragma solidity ^0.8.20;
+
+contract Verifier {
+    // Base field and scalar field moduli of BN254
+    uint256 constant Q = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    uint256 constant R = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    uint256 constant ALPHA_X = 5659261313302227808962845827735251451261678268437599156207670908983224745098;
+    uint256 constant ALPHA_Y = 9529968883614368773304460554679684211078703335942681381809592262741553869251;
+    uint256 constant BETA_X1 = 4335569062518497928076156137147058668901050811551553413481174768496640568267;
+    uint256 constant BETA_X0 = 11317014787955481694121199362319180748752520657302650602100804418617372957795;
+    uint256 constant BETA_Y1 = 21661323856798416399375751352793587587813332172290790967185999309730985974233;
+    uint256 constant BETA_Y0 = 21244364993402843296227643028416997654779095520341182791323057241302128477291;
+    uint256 constant GAMMA_X1 = 3478538194299658152567583394619128432398343994765315831968776555161193144161;
+    uint256 constant GAMMA_X0 = 888865501366419173981880750594842651115733619644999018753332828191427544404;
+    uint256 constant GAMMA_Y1 = 14336132859915912339002087016525052064411148972912257278129435201309471595104;
+    uint256 constant GAMMA_Y0 = 11788497481183315345707132887015605446461773177916231051858162927907526519276;
+    uint256 constant DELTA_X1 = 17144178262268751326153771704141408711288456634713348250685908907917271357201;
+    uint256 constant DELTA_X0 = 10870651749977354841827284753325483565659892330640104754976086413898557169601;
+    uint256 constant DELTA_Y1 = 4145032483420823174928619639151304418892961167430051225920030626994840470686;
+    uint256 constant DELTA_Y0 = 228067100771428857388671924021474894599180157905127261251909682816899592885;
+    uint256 constant IC0_X = 11313314995655142640660853023666772235608159361389863790612557148910009612963;
+    uint256 constant IC0_Y = 598833284394591535933938726817668808239009015776337885470489038053917746454;
+    uint256 constant IC1_X = 18314751787625337701164965489910139827071923335583846683684108698405389151236;
+    uint256 constant IC1_Y = 14846900399621867248296882469661800957213897394943474343053514102738168734955;
+
+    uint256 constant NUM_INPUTS = 1;
+
+    /// proof = [a.x, a.y, b.x.c1, b.x.c0, b.y.c1, b.y.c0, c.x, c.y]
+    function verifyProof(uint256[8] calldata proof, uint256[] calldata input) public view returns (bool) {
+        require(input.length == NUM_INPUTS, "Verifier: wrong number of inputs");
+        for (uint256 i = 0; i < input.length; i++) {
+            require(input[i] < R, "Verifier: input not in scalar field");
+        }
+
+        uint256[2] memory vkX = [IC0_X, IC0_Y];
+        vkX = ecAdd(vkX, ecMul([IC1_X, IC1_Y], input[0]));
+
+        uint256[24] memory pairing = [
+            proof[0], (Q - proof[1] % Q) % Q, proof[2], proof[3], proof[4], proof[5],
+            ALPHA_X, ALPHA_Y, BETA_X1, BETA_X0, BETA_Y1, BETA_Y0,
+            vkX[0], vkX[1], GAMMA_X1, GAMMA_X0, GAMMA_Y1, GAMMA_Y0,
+            proof[6], proof[7], DELTA_X1, DELTA_X0, DELTA_Y1, DELTA_Y0
+        ];
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x08, pairing, 0x300, out, 0x20)
+        }
+        return ok && out[0] == 1;
+    }
+
+    function ecAdd(uint256[2] memory p, uint256[2] memory q) internal view returns (uint256[2] memory r) {
+        uint256[4] memory data = [p[0], p[1], q[0], q[1]];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x06, data, 0x80, r, 0x40)
+        }
+        require(ok, "Verifier: ecAdd failed");
+    }
+
+    function ecMul(uint256[2] memory p, uint256 s) internal view returns (uint256[2] memory r) {
+        uint256[3] memory data = [p[0], p[1], s];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x07, data, 0x60, r, 0x40)
+        }
+        require(ok, "Verifier: ecMul failed");
+    }
+}
@@ -0,0 +1,42 @@
+// Generated by the vortex `export sui-move` command. Do not edit.
+module vortex::verifier;
+
+use sui::groth16;
+
+// === Errors ===
+
+const EInvalidProof: u64 = 0;
+
+// === Constants ===
+
+/// Number of public inputs the circuit takes.
+const NUM_PUBLIC_INPUTS: u64 = 1;
+
+// === Public Functions ===
+
+/// Verifies compressed proof points against concatenated 32-byte public inputs.
+public fun verify(proof_points: vector<u8>, public_inputs: vector<u8>): bool {
+    if (public_inputs.length() != NUM_PUBLIC_INPUTS * 32) return false;
+
+    groth16::verify_groth16_proof(
+        &groth16::bn254(),
+        &prepared_verifying_key(),
+        &groth16::public_proof_inputs_from_bytes(public_inputs),
+        &groth16::proof_points_from_bytes(proof_points),
+    )
+}
+
+entry fun assert_valid(proof_points: vector<u8>, public_inputs: vector<u8>) {
+    assert!(verify(proof_points, public_inputs), EInvalidProof);
+}
+
+// === Private Functions ===
+
+fun prepared_verifying_key(): groth16::PreparedVerifyingKey {
+    groth16::pvk_from_bytes(
+        x"a37aee07bd23e8b60a196ffa5eaab265a07c332e6ef1abd4dd165daaff1b0319042cef0a12e3cc401bff3dc693507d3097a0cd65fa9430c944f92bca3bc87da8",
+        x"11988fe58cf4512a344bf40501a13e6241af5e2701dfbd60a14b698e3d92fc229d23661c8342ea0fb7214f4ce90e12fb3d680cd16d6e62555244f4201b18b5273ac62b22ec7c960482b32f0de118be11cf06a72dec7ce34472afe81a793a6e17c16cc390f9d23b3f41760f1638ab10c1f792d124506a56ee9a9a836adf811e304b455f6104651c65f9f674df28fe23e9f7ce1950c7ab7c0942b82d907503c00f51dcd9094d41e5cd02e48d63f85b4be3b18ea2019e4765c16ce70572972964147a9518a7c34b8053894347c686d68fa7a05c9eeafaea8976c571ce3a1799f72810436e349250642190391edea576fda5d2d275b70a3e7aaa70e8dcf99b7cd325b782191df31cd38f673874f20573bfd7c636b84ff367fcde153b1b67c11a030fdaf2603df3a51f8c0c4797a96a7c4da9cfb04ff43ce68477d8225b28ec3915085f763f586d5bfbd5b97a6a4b91703198c1466af92fd6595f2ee1121c392fc2003b82eb4f74c34d62a5415c0647d3b77b6ca3a9ca7d4d9e0bd06c0c9e322a641f",
+        x"5445aab414e17fab2cae2ccb341b42181b3aca24f715ff4501f517d97d14f70161dfe981a5101f528c5b1abd54dd0c7eee2a99bac158aebf21742fa868c8b007",
+        x"c11fa867ffc856e7e60bd4b91dd3a4180ad2d4b74f2a5de084e778542392081811d75339fd7440a23509d461b63a90e6bb7f2e593e847370e963c196d242e7a5",
+    )
+}
@@ -0,0 +1,20 @@
+#![no_main]
+
+use ark_bn254::Fr;
+use libfuzzer_sys::fuzz_target;
+use vortex::field::{self, Encoding};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(s) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    for encoding in [Encoding::Decimal, Encoding::Hex, Encoding::LeBytes] {
+        // Whatever parses must survive a format/parse round trip
+        if let Ok(value) = field::parse::<Fr>(s, encoding) {
+            let formatted = field::format(&value, encoding);
+            assert_eq!(field::parse::<Fr>(&formatted, encoding).unwrap(), value);
+        }
+    }
+    let _ = field::parse_auto::<Fr>(s);
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vortex::keys::header::{KeyHeader, HEADER_LEN};
+
+fuzz_target!(|data: &[u8]| {
+    // A parsed header must serialize back to the bytes it was read from,
+    // up to the flag bits the format ignores
+    if let Ok(Some(header)) = KeyHeader::parse(data) {
+        let bytes = header.to_bytes();
+        assert_eq!(bytes[..7], data[..7]);
+        assert_eq!(bytes[8..], data[8..HEADER_LEN]);
+        assert_eq!(KeyHeader::parse(&bytes).unwrap(), Some(header));
+    }
+});
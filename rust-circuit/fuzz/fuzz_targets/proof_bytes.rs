@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vortex::api;
+use vortex::curve::Curve;
+
+const VERIFYING_KEY_HEX: &str = include_str!("../../keys/verification_key.hex");
+
+/// Size of a compressed BN254 proof: A (32) | B (64) | C (32).
+const PROOF_LEN: usize = 128;
+
+fuzz_target!(|data: &[u8]| {
+    let (proof, public_inputs) = data.split_at(data.len().min(PROOF_LEN));
+
+    let _ = api::verify_serialized(
+        Curve::Bn254,
+        &hex::encode(proof),
+        &hex::encode(public_inputs),
+        VERIFYING_KEY_HEX.trim(),
+    );
+});
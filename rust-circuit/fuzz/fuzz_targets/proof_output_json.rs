@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use vortex::api::{self, ProofOutput};
+
+const VERIFYING_KEY_HEX: &str = include_str!("../../keys/verification_key.hex");
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = api::verify_detailed(json, VERIFYING_KEY_HEX.trim());
+    if let Ok(proof) = serde_json::from_str::<ProofOutput>(json) {
+        let _ = api::verify_typed(&proof, VERIFYING_KEY_HEX.trim());
+    }
+});
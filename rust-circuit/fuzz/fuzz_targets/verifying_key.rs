@@ -0,0 +1,18 @@
+#![no_main]
+
+use ark_bn254::Bn254;
+use ark_groth16::VerifyingKey;
+use libfuzzer_sys::fuzz_target;
+use vortex::curve::Curve;
+use vortex::keys::convert::KeyKind;
+use vortex::keys::header::{self, Expected};
+
+fuzz_target!(|data: &[u8]| {
+    // Headered and headerless keys, compressed and uncompressed
+    let expected = Expected {
+        kind: KeyKind::Verifying,
+        curve: Curve::Bn254,
+        non_trivial: None,
+    };
+    let _ = header::decode::<VerifyingKey<Bn254>>(data, &expected);
+});
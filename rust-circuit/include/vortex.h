@@ -0,0 +1,49 @@
+#ifndef VORTEX_H
+#define VORTEX_H
+
+/* Generated by cbindgen from src/ffi; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Generates a proof for a JSON `ProofInput`, as the wasm `prove`.
+ *
+ * Returns 0 and sets `*proof_json` on success. Returns -1 and sets `*error`
+ * on failure. Strings written to the out-pointers must be released with
+ * [`vortex_free`].
+ *
+ * # Safety
+ * `input_json` and `proving_key_hex` must be NUL-terminated strings;
+ * `proof_json` and `error` must be valid pointers or NULL.
+ */
+int32_t vortex_prove(const char *input_json,
+                     const char *proving_key_hex,
+                     char **proof_json,
+                     char **error);
+
+/**
+ * Verifies a JSON `ProofOutput` against a hex-encoded verifying key.
+ *
+ * Returns 1 if the proof is valid, 0 if it is not, and -1 with `*error`
+ * set if the inputs cannot be parsed.
+ *
+ * # Safety
+ * `proof_json` and `verifying_key_hex` must be NUL-terminated strings;
+ * `error` must be a valid pointer or NULL.
+ */
+int32_t vortex_verify(const char *proof_json, const char *verifying_key_hex, char **error);
+
+/**
+ * Releases a string returned by this library. NULL is ignored.
+ *
+ * # Safety
+ * `s` must be NULL or a pointer written by a `vortex_*` function that has
+ * not been freed yet.
+ */
+void vortex_free(char *s);
+
+#endif  /* VORTEX_H */
@@ -0,0 +1,655 @@
+use crate::prover::{InputCountError, ProofBundle};
+use ark_bn254::Bn254;
+use ark_ec::pairing::{Pairing, PairingOutput};
+use ark_ec::{AdditiveGroup, AffineRepr, CurveGroup, PrimeGroup, ScalarMul, VariableBaseMSM};
+use ark_ff::{Field, UniformRand};
+use ark_groth16::VerifyingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use ark_std::Zero;
+use rand_core::{CryptoRng, RngCore};
+
+mod transcript;
+
+use transcript::Transcript;
+
+const TRANSCRIPT_DOMAIN: &[u8] = b"vortex/snarkpack/v1";
+
+/// Pair of target group elements committing to a vector under both key halves.
+type Commitment<E> = (PairingOutput<E>, PairingOutput<E>);
+
+/// Structured reference string for SnarkPack aggregation.
+///
+/// Holds powers of two independent secrets `alpha` and `beta` in G1 and G2.
+/// The SRS is independent of the circuit, so one SRS serves every verifying key.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregationSrs<E: Pairing = Bn254> {
+    g_alpha_powers: Vec<E::G1Affine>,
+    g_beta_powers: Vec<E::G1Affine>,
+    h_alpha_powers: Vec<E::G2Affine>,
+    h_beta_powers: Vec<E::G2Affine>,
+}
+
+/// The constant-size part of the SRS needed to check aggregate proofs.
+#[derive(Debug, Clone, Copy, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregationVerifierKey<E: Pairing = Bn254> {
+    pub g: E::G1Affine,
+    pub h: E::G2Affine,
+    pub g_alpha: E::G1Affine,
+    pub g_beta: E::G1Affine,
+    pub h_alpha: E::G2Affine,
+    pub h_beta: E::G2Affine,
+}
+
+/// A single proof attesting to the validity of a batch of Groth16 proofs.
+///
+/// Its size grows logarithmically with the number of proofs aggregated.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct AggregateProof<E: Pairing = Bn254> {
+    /// Commitment to the A and B points of every proof.
+    pub com_ab: Commitment<E>,
+    /// Commitment to the C points of every proof.
+    pub com_c: Commitment<E>,
+    /// `prod e(A_i, B_i)^(r^i)`
+    pub ip_ab: PairingOutput<E>,
+    /// `sum C_i * r^i`
+    pub agg_c: E::G1Affine,
+    pub gipa: GipaProof<E>,
+    /// KZG openings showing the final commitment keys were folded honestly.
+    pub vkey_opening: (E::G2Affine, E::G2Affine),
+    pub wkey_opening: (E::G1Affine, E::G1Affine),
+}
+
+/// Combined TIPP/MIPP argument: one `(left, right)` entry per halving round.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct GipaProof<E: Pairing = Bn254> {
+    pub comms_ab: Vec<(Commitment<E>, Commitment<E>)>,
+    pub comms_c: Vec<(Commitment<E>, Commitment<E>)>,
+    pub z_ab: Vec<(PairingOutput<E>, PairingOutput<E>)>,
+    pub z_c: Vec<(E::G1Affine, E::G1Affine)>,
+    pub final_a: E::G1Affine,
+    pub final_b: E::G2Affine,
+    pub final_c: E::G1Affine,
+    pub final_vkey: (E::G2Affine, E::G2Affine),
+    pub final_wkey: (E::G1Affine, E::G1Affine),
+}
+
+impl<E: Pairing> AggregationSrs<E> {
+    /// Samples a fresh SRS able to aggregate up to `max_proofs` proofs.
+    ///
+    /// Whoever runs this learns the secrets and can forge aggregates, so it is
+    /// only suitable for testing. Production deployments should derive the SRS
+    /// from two independent powers-of-tau ceremonies.
+    ///
+    /// # Errors
+    /// Returns error if `max_proofs` is zero.
+    pub fn setup<R: RngCore + CryptoRng>(max_proofs: usize, rng: &mut R) -> anyhow::Result<Self> {
+        if max_proofs == 0 {
+            anyhow::bail!("Aggregation SRS must support at least one proof");
+        }
+        let n = max_proofs.next_power_of_two();
+
+        let alpha = E::ScalarField::rand(rng);
+        let beta = E::ScalarField::rand(rng);
+        let g = E::G1::generator();
+        let h = E::G2::generator();
+
+        Ok(Self {
+            g_alpha_powers: g.batch_mul(&powers(alpha, 2 * n)),
+            g_beta_powers: g.batch_mul(&powers(beta, 2 * n)),
+            h_alpha_powers: h.batch_mul(&powers(alpha, n)),
+            h_beta_powers: h.batch_mul(&powers(beta, n)),
+        })
+    }
+
+    /// Largest number of proofs this SRS can aggregate.
+    pub fn max_proofs(&self) -> usize {
+        self.h_alpha_powers.len()
+    }
+
+    pub fn verifier_key(&self) -> AggregationVerifierKey<E> {
+        AggregationVerifierKey {
+            g: self.g_alpha_powers[0],
+            h: self.h_alpha_powers[0],
+            g_alpha: self.g_alpha_powers[1],
+            g_beta: self.g_beta_powers[1],
+            h_alpha: self.h_alpha_powers[1],
+            h_beta: self.h_beta_powers[1],
+        }
+    }
+
+    /// Commitment keys for vectors of length `n`.
+    fn commitment_keys(&self, n: usize) -> (Key<E::G2Affine>, Key<E::G1Affine>) {
+        let vkey = Key {
+            a: self.h_alpha_powers[..n].to_vec(),
+            b: self.h_beta_powers[..n].to_vec(),
+        };
+        let wkey = Key {
+            a: self.g_alpha_powers[n..2 * n].to_vec(),
+            b: self.g_beta_powers[n..2 * n].to_vec(),
+        };
+        (vkey, wkey)
+    }
+}
+
+/// Aggregates Groth16 proofs for the same verifying key into one proof.
+///
+/// Batches whose size is not a power of two are padded by repeating the last
+/// proof; [`verify_aggregate`] applies the same padding to the public inputs.
+/// The proofs are not checked individually, so a single invalid proof makes
+/// the whole aggregate fail verification.
+///
+/// # Errors
+/// Returns error if `bundles` is empty or larger than the SRS supports.
+pub fn aggregate<E: Pairing>(
+    srs: &AggregationSrs<E>,
+    bundles: &[ProofBundle<E>],
+) -> anyhow::Result<AggregateProof<E>> {
+    if bundles.is_empty() {
+        anyhow::bail!("Cannot aggregate zero proofs");
+    }
+    let n = bundles.len().next_power_of_two();
+    if n > srs.max_proofs() {
+        anyhow::bail!(
+            "Aggregating {} proofs needs an SRS for {}, but it supports {}",
+            bundles.len(),
+            n,
+            srs.max_proofs()
+        );
+    }
+
+    let bundles: Vec<&ProofBundle<E>> = padded(bundles, n).collect();
+    let a: Vec<E::G1Affine> = bundles.iter().map(|bundle| bundle.proof.a).collect();
+    let b: Vec<E::G2Affine> = bundles.iter().map(|bundle| bundle.proof.b).collect();
+    let c: Vec<E::G1Affine> = bundles.iter().map(|bundle| bundle.proof.c).collect();
+
+    let (vkey, wkey) = srs.commitment_keys(n);
+    let com_ab = commit_pair::<E>(&vkey, &wkey, &a, &b);
+    let com_c = commit_single::<E>(&vkey, &c);
+
+    let mut transcript = Transcript::new(TRANSCRIPT_DOMAIN);
+    let r = statement_challenge::<E>(
+        &mut transcript,
+        bundles.iter().map(|bundle| &bundle.public_inputs),
+        &com_ab,
+        &com_c,
+    );
+    let r_inv = r.inverse().expect("challenges are non-zero");
+    let r_powers = powers(r, n);
+
+    // Fold r into B and undo it in the B commitment key, so com_ab still
+    // commits to the rescaled vector.
+    let b = scale(&b, &r_powers);
+    let wkey = wkey.scale(&powers(r_inv, n));
+
+    let ip_ab = E::multi_pairing(&a, &b);
+    let agg_c = E::G1::msm_unchecked(&c, &r_powers).into_affine();
+    transcript.append(b"ip-ab", &ip_ab);
+    transcript.append(b"agg-c", &agg_c);
+
+    let (gipa, challenges) = prove_gipa::<E>(&mut transcript, a, b, c, r_powers, vkey, wkey);
+    let z = final_challenge(&mut transcript, &gipa);
+
+    let challenges_inv: Vec<E::ScalarField> = challenges
+        .iter()
+        .map(|x| x.inverse().expect("challenges are non-zero"))
+        .collect();
+    let vkey_poly = key_poly_coeffs(&challenges_inv);
+    let mut wkey_poly = vec![E::ScalarField::ZERO; n];
+    wkey_poly.extend(key_poly_coeffs(&wkey_factors(&challenges, r_inv, n)));
+
+    Ok(AggregateProof {
+        com_ab,
+        com_c,
+        ip_ab,
+        agg_c,
+        gipa,
+        vkey_opening: (
+            open::<E::G2>(&srs.h_alpha_powers, &vkey_poly, z),
+            open::<E::G2>(&srs.h_beta_powers, &vkey_poly, z),
+        ),
+        wkey_opening: (
+            open::<E::G1>(&srs.g_alpha_powers, &wkey_poly, z),
+            open::<E::G1>(&srs.g_beta_powers, &wkey_poly, z),
+        ),
+    })
+}
+
+/// Verifies an aggregate of proofs for `public_inputs`, one entry per proof
+/// in the order they were passed to [`aggregate`].
+///
+/// # Errors
+/// Returns error if `public_inputs` is empty, or [`InputCountError`] if an
+/// entry has the wrong number of inputs for `vk`; an invalid aggregate
+/// yields `Ok(false)`.
+pub fn verify_aggregate<E: Pairing>(
+    vk: &VerifyingKey<E>,
+    avk: &AggregationVerifierKey<E>,
+    public_inputs: &[Vec<E::ScalarField>],
+    proof: &AggregateProof<E>,
+) -> anyhow::Result<bool> {
+    if public_inputs.is_empty() {
+        anyhow::bail!("Cannot verify an aggregate of zero proofs");
+    }
+    for inputs in public_inputs {
+        InputCountError::check(vk, inputs.len())?;
+    }
+    // A malformed key may have no gamma_abc_g1 entries at all
+    let num_inputs = vk.gamma_abc_g1.len().saturating_sub(1);
+
+    let n = public_inputs.len().next_power_of_two();
+    let rounds = n.trailing_zeros() as usize;
+    let gipa = &proof.gipa;
+    if gipa.comms_ab.len() != rounds
+        || gipa.comms_c.len() != rounds
+        || gipa.z_ab.len() != rounds
+        || gipa.z_c.len() != rounds
+    {
+        return Ok(false);
+    }
+
+    // Replay the transcript
+    let mut transcript = Transcript::new(TRANSCRIPT_DOMAIN);
+    let r = statement_challenge::<E>(
+        &mut transcript,
+        padded(public_inputs, n),
+        &proof.com_ab,
+        &proof.com_c,
+    );
+    transcript.append(b"ip-ab", &proof.ip_ab);
+    transcript.append(b"agg-c", &proof.agg_c);
+    let challenges: Vec<E::ScalarField> = (0..rounds)
+        .map(|i| {
+            round_challenge::<E>(
+                &mut transcript,
+                &gipa.comms_ab[i],
+                &gipa.comms_c[i],
+                &gipa.z_ab[i],
+                &gipa.z_c[i],
+            )
+        })
+        .collect();
+    let z = final_challenge(&mut transcript, gipa);
+
+    let r_inv = r.inverse().expect("challenges are non-zero");
+    let challenges_inv: Vec<E::ScalarField> = challenges
+        .iter()
+        .map(|x| x.inverse().expect("challenges are non-zero"))
+        .collect();
+
+    // Fold the commitments and inner products down to the final round
+    let mut com_ab = proof.com_ab;
+    let mut com_c = proof.com_c;
+    let mut z_ab = proof.ip_ab;
+    let mut z_c = proof.agg_c.into_group();
+    for i in 0..rounds {
+        let (x, x_inv) = (challenges[i], challenges_inv[i]);
+        let (left_ab, right_ab) = &gipa.comms_ab[i];
+        let (left_c, right_c) = &gipa.comms_c[i];
+        let (left_z_ab, right_z_ab) = &gipa.z_ab[i];
+        let (left_z_c, right_z_c) = &gipa.z_c[i];
+
+        com_ab = (
+            com_ab.0 + left_ab.0 * x + right_ab.0 * x_inv,
+            com_ab.1 + left_ab.1 * x + right_ab.1 * x_inv,
+        );
+        com_c = (
+            com_c.0 + left_c.0 * x + right_c.0 * x_inv,
+            com_c.1 + left_c.1 * x + right_c.1 * x_inv,
+        );
+        z_ab = z_ab + *left_z_ab * x + *right_z_ab * x_inv;
+        z_c += *left_z_c * x + *right_z_c * x_inv;
+    }
+
+    let final_r = eval_key_poly(&challenges_inv, n, r);
+    let gipa_valid = com_ab.0
+        == E::multi_pairing(
+            [gipa.final_a, gipa.final_wkey.0],
+            [gipa.final_vkey.0, gipa.final_b],
+        )
+        && com_ab.1
+            == E::multi_pairing(
+                [gipa.final_a, gipa.final_wkey.1],
+                [gipa.final_vkey.1, gipa.final_b],
+            )
+        && z_ab == E::pairing(gipa.final_a, gipa.final_b)
+        && com_c.0 == E::pairing(gipa.final_c, gipa.final_vkey.0)
+        && com_c.1 == E::pairing(gipa.final_c, gipa.final_vkey.1)
+        && z_c == gipa.final_c * final_r;
+
+    // The final keys must be the honest folding of the SRS keys
+    let vkey_eval = eval_key_poly(&challenges_inv, n, z);
+    let wkey_eval = z.pow([n as u64]) * eval_key_poly(&challenges, n, z * r_inv);
+    let keys_valid = check_g2_opening(
+        avk,
+        avk.g_alpha,
+        gipa.final_vkey.0,
+        proof.vkey_opening.0,
+        z,
+        vkey_eval,
+    ) && check_g2_opening(
+        avk,
+        avk.g_beta,
+        gipa.final_vkey.1,
+        proof.vkey_opening.1,
+        z,
+        vkey_eval,
+    ) && check_g1_opening(
+        avk,
+        avk.h_alpha,
+        gipa.final_wkey.0,
+        proof.wkey_opening.0,
+        z,
+        wkey_eval,
+    ) && check_g1_opening(
+        avk,
+        avk.h_beta,
+        gipa.final_wkey.1,
+        proof.wkey_opening.1,
+        z,
+        wkey_eval,
+    );
+
+    // Random linear combination of the Groth16 equations:
+    // prod e(A_i, B_i)^(r^i) == e(alpha, beta)^(sum r^i) * e(sum S_i * r^i, gamma) * e(agg_c, delta)
+    let r_powers = powers(r, n);
+    let r_sum: E::ScalarField = r_powers.iter().sum();
+    let mut input_scalars = vec![r_sum];
+    input_scalars.extend((0..num_inputs).map(|k| {
+        padded(public_inputs, n)
+            .zip(&r_powers)
+            .map(|(inputs, r_i)| inputs[k] * r_i)
+            .sum::<E::ScalarField>()
+    }));
+    let acc_inputs = E::G1::msm_unchecked(&vk.gamma_abc_g1, &input_scalars);
+    let groth16_valid = proof.ip_ab
+        == E::pairing(vk.alpha_g1, vk.beta_g2) * r_sum
+            + E::multi_pairing(
+                [acc_inputs.into_affine(), proof.agg_c],
+                [vk.gamma_g2, vk.delta_g2],
+            );
+
+    Ok(gipa_valid && keys_valid && groth16_valid)
+}
+
+/// Pair of commitment keys, one per SRS secret.
+#[derive(Clone)]
+struct Key<G> {
+    a: Vec<G>,
+    b: Vec<G>,
+}
+
+impl<G: AffineRepr> Key<G> {
+    fn split(&self, at: usize) -> (Self, Self) {
+        let (a_left, a_right) = self.a.split_at(at);
+        let (b_left, b_right) = self.b.split_at(at);
+        (
+            Self {
+                a: a_left.to_vec(),
+                b: b_left.to_vec(),
+            },
+            Self {
+                a: a_right.to_vec(),
+                b: b_right.to_vec(),
+            },
+        )
+    }
+
+    fn fold(left: &Self, right: &Self, x: G::ScalarField) -> Self {
+        Self {
+            a: fold(&left.a, &right.a, x),
+            b: fold(&left.b, &right.b, x),
+        }
+    }
+
+    fn scale(&self, factors: &[G::ScalarField]) -> Self {
+        Self {
+            a: scale(&self.a, factors),
+            b: scale(&self.b, factors),
+        }
+    }
+}
+
+fn prove_gipa<E: Pairing>(
+    transcript: &mut Transcript,
+    mut a: Vec<E::G1Affine>,
+    mut b: Vec<E::G2Affine>,
+    mut c: Vec<E::G1Affine>,
+    mut r: Vec<E::ScalarField>,
+    mut vkey: Key<E::G2Affine>,
+    mut wkey: Key<E::G1Affine>,
+) -> (GipaProof<E>, Vec<E::ScalarField>) {
+    let mut comms_ab = Vec::new();
+    let mut comms_c = Vec::new();
+    let mut z_ab = Vec::new();
+    let mut z_c = Vec::new();
+    let mut challenges = Vec::new();
+
+    while a.len() > 1 {
+        let split = a.len() / 2;
+        let (a_left, a_right) = a.split_at(split);
+        let (b_left, b_right) = b.split_at(split);
+        let (c_left, c_right) = c.split_at(split);
+        let (r_left, r_right) = r.split_at(split);
+        let (vkey_left, vkey_right) = vkey.split(split);
+        let (wkey_left, wkey_right) = wkey.split(split);
+
+        let comm_ab = (
+            commit_pair::<E>(&vkey_left, &wkey_right, a_right, b_left),
+            commit_pair::<E>(&vkey_right, &wkey_left, a_left, b_right),
+        );
+        let comm_c = (
+            commit_single::<E>(&vkey_left, c_right),
+            commit_single::<E>(&vkey_right, c_left),
+        );
+        let round_z_ab = (
+            E::multi_pairing(a_right, b_left),
+            E::multi_pairing(a_left, b_right),
+        );
+        let round_z_c = (
+            E::G1::msm_unchecked(c_right, r_left).into_affine(),
+            E::G1::msm_unchecked(c_left, r_right).into_affine(),
+        );
+
+        let x = round_challenge::<E>(transcript, &comm_ab, &comm_c, &round_z_ab, &round_z_c);
+        let x_inv = x.inverse().expect("challenges are non-zero");
+
+        a = fold(a_left, a_right, x);
+        b = fold(b_left, b_right, x_inv);
+        c = fold(c_left, c_right, x);
+        r = r_left
+            .iter()
+            .zip(r_right)
+            .map(|(left, right)| *left + *right * x_inv)
+            .collect();
+        vkey = Key::fold(&vkey_left, &vkey_right, x_inv);
+        wkey = Key::fold(&wkey_left, &wkey_right, x);
+
+        comms_ab.push(comm_ab);
+        comms_c.push(comm_c);
+        z_ab.push(round_z_ab);
+        z_c.push(round_z_c);
+        challenges.push(x);
+    }
+
+    let gipa = GipaProof {
+        comms_ab,
+        comms_c,
+        z_ab,
+        z_c,
+        final_a: a[0],
+        final_b: b[0],
+        final_c: c[0],
+        final_vkey: (vkey.a[0], vkey.b[0]),
+        final_wkey: (wkey.a[0], wkey.b[0]),
+    };
+    (gipa, challenges)
+}
+
+fn statement_challenge<'a, E: Pairing>(
+    transcript: &mut Transcript,
+    public_inputs: impl Iterator<Item = &'a Vec<E::ScalarField>>,
+    com_ab: &Commitment<E>,
+    com_c: &Commitment<E>,
+) -> E::ScalarField {
+    for inputs in public_inputs {
+        transcript.append(b"public-inputs", inputs);
+    }
+    transcript.append(b"com-ab", com_ab);
+    transcript.append(b"com-c", com_c);
+    transcript.challenge(b"r")
+}
+
+fn round_challenge<E: Pairing>(
+    transcript: &mut Transcript,
+    comm_ab: &(Commitment<E>, Commitment<E>),
+    comm_c: &(Commitment<E>, Commitment<E>),
+    z_ab: &(PairingOutput<E>, PairingOutput<E>),
+    z_c: &(E::G1Affine, E::G1Affine),
+) -> E::ScalarField {
+    transcript.append(b"comm-ab", comm_ab);
+    transcript.append(b"comm-c", comm_c);
+    transcript.append(b"z-ab", z_ab);
+    transcript.append(b"z-c", z_c);
+    transcript.challenge(b"x")
+}
+
+fn final_challenge<E: Pairing>(transcript: &mut Transcript, gipa: &GipaProof<E>) -> E::ScalarField {
+    transcript.append(b"final-a", &gipa.final_a);
+    transcript.append(b"final-b", &gipa.final_b);
+    transcript.append(b"final-c", &gipa.final_c);
+    transcript.append(b"final-vkey", &gipa.final_vkey);
+    transcript.append(b"final-wkey", &gipa.final_wkey);
+    transcript.challenge(b"z")
+}
+
+fn commit_pair<E: Pairing>(
+    vkey: &Key<E::G2Affine>,
+    wkey: &Key<E::G1Affine>,
+    a: &[E::G1Affine],
+    b: &[E::G2Affine],
+) -> Commitment<E> {
+    (
+        E::multi_pairing(a.iter().chain(&wkey.a), vkey.a.iter().chain(b)),
+        E::multi_pairing(a.iter().chain(&wkey.b), vkey.b.iter().chain(b)),
+    )
+}
+
+fn commit_single<E: Pairing>(vkey: &Key<E::G2Affine>, c: &[E::G1Affine]) -> Commitment<E> {
+    (E::multi_pairing(c, &vkey.a), E::multi_pairing(c, &vkey.b))
+}
+
+/// Checks a KZG opening of a G2 commitment key against `g_tau = g^tau`.
+fn check_g2_opening<E: Pairing>(
+    avk: &AggregationVerifierKey<E>,
+    g_tau: E::G1Affine,
+    commitment: E::G2Affine,
+    opening: E::G2Affine,
+    point: E::ScalarField,
+    eval: E::ScalarField,
+) -> bool {
+    // e(g^tau / g^z, opening) == e(g, commitment / h^eval)
+    E::multi_pairing(
+        [(g_tau - avk.g * point).into_affine(), avk.g],
+        [opening, (avk.h * eval - commitment).into_affine()],
+    )
+    .is_zero()
+}
+
+/// Checks a KZG opening of a G1 commitment key against `h_tau = h^tau`.
+fn check_g1_opening<E: Pairing>(
+    avk: &AggregationVerifierKey<E>,
+    h_tau: E::G2Affine,
+    commitment: E::G1Affine,
+    opening: E::G1Affine,
+    point: E::ScalarField,
+    eval: E::ScalarField,
+) -> bool {
+    // e(commitment / g^eval, h) == e(opening, h^tau / h^z)
+    E::multi_pairing(
+        [(commitment - avk.g * eval).into_affine(), opening],
+        [avk.h, (avk.h * point - h_tau).into_affine()],
+    )
+    .is_zero()
+}
+
+/// Opens the polynomial with `coeffs` at `point` against `powers = [g^(tau^i)]`.
+fn open<G: CurveGroup>(
+    powers: &[G::Affine],
+    coeffs: &[G::ScalarField],
+    point: G::ScalarField,
+) -> G::Affine {
+    // (f(X) - f(point)) / (X - point) by synthetic division
+    let mut quotient = vec![G::ScalarField::ZERO; coeffs.len().saturating_sub(1)];
+    let mut acc = G::ScalarField::ZERO;
+    for i in (1..coeffs.len()).rev() {
+        acc = acc * point + coeffs[i];
+        quotient[i - 1] = acc;
+    }
+    G::msm_unchecked(&powers[..quotient.len()], &quotient).into_affine()
+}
+
+/// Coefficients of `prod_j (1 + factors[j] * X^(n / 2^(j+1)))`, the exponent
+/// a commitment key ends up with after folding with `factors`.
+fn key_poly_coeffs<F: Field>(factors: &[F]) -> Vec<F> {
+    let mut coeffs = vec![F::ONE];
+    for factor in factors.iter().rev() {
+        let high: Vec<F> = coeffs.iter().map(|coeff| *coeff * factor).collect();
+        coeffs.extend(high);
+    }
+    coeffs
+}
+
+/// Evaluates the polynomial from [`key_poly_coeffs`] at `point`.
+fn eval_key_poly<F: Field>(factors: &[F], n: usize, point: F) -> F {
+    factors
+        .iter()
+        .zip(round_lengths(n))
+        .map(|(factor, half)| F::ONE + *factor * point.pow([half as u64]))
+        .product()
+}
+
+/// Folding factors for the B commitment key, which was rescaled by `r^-i`.
+fn wkey_factors<F: Field>(challenges: &[F], r_inv: F, n: usize) -> Vec<F> {
+    challenges
+        .iter()
+        .zip(round_lengths(n))
+        .map(|(x, half)| *x * r_inv.pow([half as u64]))
+        .collect()
+}
+
+/// Half-vector length in each GIPA round: `n/2, n/4, ..., 1`.
+fn round_lengths(n: usize) -> impl Iterator<Item = usize> {
+    std::iter::successors(Some(n / 2), |half| Some(half / 2)).take_while(|half| *half > 0)
+}
+
+fn fold<G: AffineRepr>(left: &[G], right: &[G], x: G::ScalarField) -> Vec<G> {
+    let folded: Vec<G::Group> = left
+        .iter()
+        .zip(right)
+        .map(|(left, right)| *right * x + left)
+        .collect();
+    G::Group::normalize_batch(&folded)
+}
+
+fn scale<G: AffineRepr>(points: &[G], factors: &[G::ScalarField]) -> Vec<G> {
+    let scaled: Vec<G::Group> = points
+        .iter()
+        .zip(factors)
+        .map(|(point, factor)| *point * factor)
+        .collect();
+    G::Group::normalize_batch(&scaled)
+}
+
+fn powers<F: Field>(base: F, n: usize) -> Vec<F> {
+    std::iter::successors(Some(F::ONE), |power| Some(*power * base))
+        .take(n)
+        .collect()
+}
+
+/// Pads `items` to `n` entries by repeating the last one.
+fn padded<T>(items: &[T], n: usize) -> impl Iterator<Item = &T> + Clone {
+    let last = items.last();
+    items
+        .iter()
+        .chain(std::iter::repeat_n(last, n - items.len()).flatten())
+}
@@ -0,0 +1,50 @@
+use ark_ff::PrimeField;
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+
+/// Fiat-Shamir transcript shared by the aggregator and the verifier.
+pub(super) struct Transcript {
+    state: [u8; 32],
+}
+
+impl Transcript {
+    pub(super) fn new(domain: &[u8]) -> Self {
+        Self {
+            state: Sha256::digest(domain).into(),
+        }
+    }
+
+    pub(super) fn append<T: CanonicalSerialize>(&mut self, label: &[u8], value: &T) {
+        let mut bytes = Vec::new();
+        value
+            .serialize_compressed(&mut bytes)
+            .expect("serializing into a Vec cannot fail");
+
+        self.state = Sha256::new()
+            .chain_update(self.state)
+            .chain_update(label)
+            .chain_update(&bytes)
+            .finalize()
+            .into();
+    }
+
+    /// Squeezes a non-zero challenge, so callers can always invert it.
+    pub(super) fn challenge<F: PrimeField>(&mut self, label: &[u8]) -> F {
+        let mut counter = 0u32;
+        loop {
+            let digest: [u8; 32] = Sha256::new()
+                .chain_update(self.state)
+                .chain_update(label)
+                .chain_update(counter.to_le_bytes())
+                .finalize()
+                .into();
+
+            let challenge = F::from_le_bytes_mod_order(&digest);
+            if challenge != F::ZERO {
+                self.state = digest;
+                return challenge;
+            }
+            counter += 1;
+        }
+    }
+}
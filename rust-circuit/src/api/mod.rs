@@ -0,0 +1,1136 @@
+use crate::circom;
+use crate::circuit::{sizes, Circuit};
+use crate::curve::{point, Curve, SupportedCurve};
+use crate::field;
+use crate::inspect;
+use crate::keys;
+use crate::keys::circuit_id::{CircuitId, CircuitMismatchError};
+use crate::keys::convert::KeyKind;
+use crate::keys::header::{self, Expected};
+use crate::prover::{self, InputCountError, ProofBundle, ProvingPhase};
+use crate::sui::bcs::{self, BcsProof};
+use crate::witness;
+#[cfg(feature = "bls12-381")]
+use ark_bls12_381::Bls12_381;
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use ark_ff::{AdditiveGroup, PrimeField};
+use ark_groth16::{Groth16, ProvingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use rand::rngs::OsRng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::sync::Arc;
+use timings::{PhaseTimer, Stopwatch};
+use zeroize::Zeroize;
+
+mod timings;
+
+pub use timings::Timings;
+
+/// Proof output structure that matches the expected format for Sui Move contracts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOutput {
+    /// Curve the proof was generated on; absent in proofs from older versions
+    #[serde(default)]
+    pub curve: Curve,
+    /// Proof component A (compressed: 32 bytes)
+    pub proof_a: Vec<u8>,
+    /// Proof component B (compressed: 64 bytes)
+    pub proof_b: Vec<u8>,
+    /// Proof component C (compressed: 32 bytes)
+    pub proof_c: Vec<u8>,
+    /// Proof points are uncompressed (twice the sizes above); absent when
+    /// compressed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub uncompressed: bool,
+    /// All public inputs in order expected by Move contract
+    pub public_inputs: Vec<String>,
+    pub proof_serialized_hex: String,
+    pub public_inputs_serialized_hex: String,
+    /// Circuit the proof was generated for; absent in proofs from older
+    /// versions, which are not checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_id: Option<CircuitId>,
+    /// Optional proving metadata; absent unless requested in the input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ProofMeta>,
+}
+
+impl ProofOutput {
+    /// BCS-encodes the serialized proof points and public inputs as the two
+    /// `vector<u8>` arguments of the `sui::groth16` byte parsers.
+    ///
+    /// # Errors
+    /// Returns error if the proof is uncompressed, which `sui::groth16` does
+    /// not accept, or the serialized hex fields are not valid hex.
+    pub fn to_bcs(&self) -> anyhow::Result<BcsProof> {
+        if self.uncompressed {
+            anyhow::bail!("Sui only accepts compressed proof points; prove without `uncompressed`");
+        }
+        let proof_points = hex::decode(&self.proof_serialized_hex)
+            .map_err(|e| anyhow::anyhow!("Failed to decode proof hex: {}", e))?;
+        let public_inputs = hex::decode(&self.public_inputs_serialized_hex)
+            .map_err(|e| anyhow::anyhow!("Failed to decode public inputs hex: {}", e))?;
+
+        Ok(BcsProof {
+            proof_points_bytes: bcs::encode_bytes(&proof_points),
+            public_inputs_bytes: bcs::encode_bytes(&public_inputs),
+        })
+    }
+}
+
+/// Optional metadata attached to a proof
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofMeta {
+    /// Nonce the prover randomness was derived from (audit mode only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub randomness_nonce: Option<String>,
+    /// Commitment to the prover randomness (audit mode only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub randomness_commitment: Option<String>,
+    /// Duration of each proving step (only if requested with `timings`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timings: Option<Timings>,
+}
+
+/// Input structure for proof generation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofInput {
+    // Public inputs
+    pub c: String,
+
+    // Private inputs
+    pub a: String,
+    pub b: String,
+
+    // Options
+    /// Curve to prove on (`"bn254"` or `"bls12-381"`); must match the proving key
+    #[serde(default)]
+    pub curve: Curve,
+    /// Enforce a non-trivial factorization (requires matching keys)
+    #[serde(default)]
+    pub non_trivial: bool,
+    /// Hex-encoded 32-byte session key; enables the randomness audit log
+    #[serde(default)]
+    pub audit_session_key: Option<String>,
+    /// Include the duration of each proving step in the output metadata
+    #[serde(default)]
+    pub timings: bool,
+    /// Output uncompressed proof points: twice the size but faster to
+    /// verify, and not accepted by `sui::groth16`
+    #[serde(default)]
+    pub uncompressed: bool,
+}
+
+/// The private factors are wiped when the input is dropped.
+impl Drop for ProofInput {
+    fn drop(&mut self) {
+        self.a.zeroize();
+        self.b.zeroize();
+    }
+}
+
+/// Input to [`compute_expected_public`]: a [`ProofInput`] without `c`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PrivateInput {
+    a: String,
+    b: String,
+    #[serde(default)]
+    curve: Curve,
+    #[serde(default)]
+    non_trivial: bool,
+}
+
+impl Drop for PrivateInput {
+    fn drop(&mut self) {
+        self.a.zeroize();
+        self.b.zeroize();
+    }
+}
+
+/// Public inputs implied by the private inputs, as computed by [`compute_expected_public`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpectedPublic {
+    /// Decimal `a * b`, ready to use as [`ProofInput::c`].
+    pub c: String,
+}
+
+/// Description of the circuit compiled into this build, see [`circuit_info`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CircuitInfo {
+    pub name: String,
+    pub version: String,
+    pub non_trivial: bool,
+    pub num_constraints: usize,
+    pub num_public_inputs: usize,
+    /// Public input names, in the order they appear in `publicInputs`.
+    pub public_input_names: Vec<String>,
+    /// Fingerprint of the proving key shipped with this build; absent for
+    /// circuit variants without a shipped key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proving_key_fingerprint: Option<String>,
+}
+
+/// Description of one compiled-in size of a parameterized circuit, see
+/// [`sized_circuit_info`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizedCircuitInfo {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    /// Const parameters, e.g. `{ "depth": 20 }`.
+    pub params: std::collections::BTreeMap<String, usize>,
+    pub num_constraints: usize,
+    pub num_public_inputs: usize,
+}
+
+/// Hex-encoded compressed Groth16 keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPair {
+    pub proving_key_hex: String,
+    pub verifying_key_hex: String,
+}
+
+/// Generates a proof for a JSON [`ProofInput`] and returns it as a JSON [`ProofOutput`].
+///
+/// `progress` is called as each proving phase starts, ending with [`ProvingPhase::Done`].
+///
+/// # Errors
+/// Returns error if the input or proving key cannot be parsed or proving fails.
+pub fn prove<F>(input_json: &str, proving_key_hex: &str, progress: F) -> anyhow::Result<String>
+where
+    F: Fn(ProvingPhase) + Copy,
+{
+    // OS entropy natively, `crypto.getRandomValues` in the browser (via getrandom)
+    prove_with_rng(input_json, proving_key_hex, &mut OsRng, progress)
+}
+
+/// Like [`prove`], but draws the prover randomness from a ChaCha20 stream
+/// seeded with the hex-encoded 32-byte `seed_hex`, so the same input, key
+/// and seed always produce the same proof.
+///
+/// Only for reproducible test vectors: anyone who knows the seed can strip
+/// the zero-knowledge blinding and recover the private inputs.
+///
+/// # Errors
+/// Returns error if the seed, input or proving key cannot be parsed or
+/// proving fails.
+pub fn prove_deterministic<F>(
+    input_json: &str,
+    proving_key_hex: &str,
+    seed_hex: &str,
+    progress: F,
+) -> anyhow::Result<String>
+where
+    F: Fn(ProvingPhase) + Copy,
+{
+    let seed: [u8; 32] = hex::decode(seed_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode seed hex: {}", e))?
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Seed must be 32 bytes"))?;
+
+    prove_with_rng(
+        input_json,
+        proving_key_hex,
+        &mut ChaCha20Rng::from_seed(seed),
+        progress,
+    )
+}
+
+fn prove_with_rng<R, F>(
+    input_json: &str,
+    proving_key_hex: &str,
+    rng: &mut R,
+    progress: F,
+) -> anyhow::Result<String>
+where
+    R: RngCore + CryptoRng,
+    F: Fn(ProvingPhase) + Copy,
+{
+    let input = parse_input(input_json)?;
+    let output = prove_parsed(&input, proving_key_hex, rng, progress)?;
+
+    serialize_output(&output)
+}
+
+/// Like [`prove`], but on an already parsed [`ProofInput`], returning the
+/// [`ProofOutput`] itself rather than JSON.
+///
+/// # Errors
+/// Returns error if the proving key cannot be parsed or proving fails.
+pub fn prove_typed<F>(
+    input: &ProofInput,
+    proving_key_hex: &str,
+    progress: F,
+) -> anyhow::Result<ProofOutput>
+where
+    F: Fn(ProvingPhase) + Copy,
+{
+    prove_parsed(input, proving_key_hex, &mut OsRng, progress)
+}
+
+fn prove_parsed<R, F>(
+    input: &ProofInput,
+    proving_key_hex: &str,
+    rng: &mut R,
+    progress: F,
+) -> anyhow::Result<ProofOutput>
+where
+    R: RngCore + CryptoRng,
+    F: Fn(ProvingPhase) + Copy,
+{
+    let output = match input.curve {
+        Curve::Bn254 => prove_on::<Bn254, _, F>(input, proving_key_hex, rng, progress)?,
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => prove_on::<Bls12_381, _, F>(input, proving_key_hex, rng, progress)?,
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => return Err(unsupported_curve(input.curve)),
+    };
+    progress(ProvingPhase::Done);
+
+    Ok(output)
+}
+
+/// Like [`prove`], but awaits `checkpoint` as each proving phase starts,
+/// ending with [`ProvingPhase::Done`].
+///
+/// Async callers can yield to their event loop in the checkpoint and cancel
+/// proving by returning an error from it, which is passed through as is.
+///
+/// # Errors
+/// Returns error if the input or proving key cannot be parsed, a checkpoint
+/// fails or proving fails.
+pub async fn prove_async<F, Fut>(
+    input_json: &str,
+    proving_key_hex: &str,
+    mut checkpoint: F,
+) -> anyhow::Result<String>
+where
+    F: FnMut(ProvingPhase) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let input = parse_input(input_json)?;
+
+    let output = match input.curve {
+        Curve::Bn254 => {
+            let clock = Stopwatch::start();
+            let pk = deserialize_proving_key::<Bn254>(proving_key_hex, input.non_trivial)?;
+            let key_load_ms = clock.elapsed_ms();
+            prove_input_with(&input, &pk, key_load_ms, &mut OsRng, &mut checkpoint).await?
+        }
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => {
+            let clock = Stopwatch::start();
+            let pk = deserialize_proving_key::<Bls12_381>(proving_key_hex, input.non_trivial)?;
+            let key_load_ms = clock.elapsed_ms();
+            prove_input_with(&input, &pk, key_load_ms, &mut OsRng, &mut checkpoint).await?
+        }
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => return Err(unsupported_curve(input.curve)),
+    };
+    checkpoint(ProvingPhase::Done).await?;
+
+    serialize_output(&output)
+}
+
+/// Like [`prove`], but with an already deserialized proving key so callers
+/// proving repeatedly do not pay for parsing the key every time.
+///
+/// Requested [`Timings`] report a `keyLoadMs` of 0.
+///
+/// # Errors
+/// Returns error if the input cannot be parsed, targets a different curve
+/// than `pk`, or proving fails.
+pub fn prove_with_key<E, F>(
+    input_json: &str,
+    pk: &ProvingKey<E>,
+    progress: F,
+) -> anyhow::Result<String>
+where
+    E: SupportedCurve,
+    F: Fn(ProvingPhase) + Copy,
+{
+    let input = parse_input(input_json)?;
+    if input.curve != E::CURVE {
+        anyhow::bail!(
+            "Input is for curve {}, but the proving key is for {}",
+            input.curve.name(),
+            E::CURVE.name()
+        );
+    }
+
+    let output = prove_input(&input, pk, 0.0, &mut OsRng, progress)?;
+    progress(ProvingPhase::Done);
+
+    serialize_output(&output)
+}
+
+fn parse_input(input_json: &str) -> anyhow::Result<ProofInput> {
+    serde_json::from_str(input_json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse input JSON: {}", e))
+}
+
+fn serialize_output(output: &ProofOutput) -> anyhow::Result<String> {
+    serde_json::to_string(output).map_err(|e| anyhow::anyhow!("Failed to serialize output: {}", e))
+}
+
+fn prove_on<E, R, F>(
+    input: &ProofInput,
+    proving_key_hex: &str,
+    rng: &mut R,
+    progress: F,
+) -> anyhow::Result<ProofOutput>
+where
+    E: SupportedCurve,
+    R: RngCore + CryptoRng,
+    F: Fn(ProvingPhase) + Copy,
+{
+    // Parse proving key
+    let clock = Stopwatch::start();
+    let pk = deserialize_proving_key::<E>(proving_key_hex, input.non_trivial)?;
+
+    prove_input(input, &pk, clock.elapsed_ms(), rng, progress)
+}
+
+fn prove_input<E, R, F>(
+    input: &ProofInput,
+    pk: &ProvingKey<E>,
+    key_load_ms: f64,
+    rng: &mut R,
+    progress: F,
+) -> anyhow::Result<ProofOutput>
+where
+    E: SupportedCurve,
+    R: RngCore + CryptoRng,
+    F: Fn(ProvingPhase) + Copy,
+{
+    prover::run_ready(prove_input_with(
+        input,
+        pk,
+        key_load_ms,
+        rng,
+        prover::reporting(progress),
+    ))
+}
+
+/// `key_load_ms` is reported in the [`Timings`], if requested.
+async fn prove_input_with<E, R, F, Fut>(
+    input: &ProofInput,
+    pk: &ProvingKey<E>,
+    key_load_ms: f64,
+    rng: &mut R,
+    mut checkpoint: F,
+) -> anyhow::Result<ProofOutput>
+where
+    E: SupportedCurve,
+    R: RngCore + CryptoRng,
+    F: FnMut(ProvingPhase) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let mut timer = PhaseTimer::start(key_load_ms);
+    let mut checkpoint = |phase| {
+        timer.phase(phase);
+        checkpoint(phase)
+    };
+    let circuit = circuit_from_input(input)?;
+    let compress = if input.uncompressed {
+        Compress::No
+    } else {
+        Compress::Yes
+    };
+
+    let mut output = match &input.audit_session_key {
+        Some(session_key) => {
+            let session_key = parse_session_key(session_key)?;
+            let (bundle, record) = prover::prove_audited_with_checkpoints(
+                circuit,
+                pk,
+                &session_key,
+                rng,
+                &mut checkpoint,
+            )
+            .await?;
+
+            checkpoint(ProvingPhase::Serialize).await?;
+            let mut output = ProofOutput::encode(&bundle, compress)?;
+            output.meta = Some(ProofMeta {
+                randomness_nonce: Some(hex::encode(record.nonce)),
+                randomness_commitment: Some(hex::encode(record.commitment)),
+                ..Default::default()
+            });
+            output
+        }
+        None => {
+            let bundle = prover::prove_with_checkpoints(circuit, pk, rng, &mut checkpoint).await?;
+
+            checkpoint(ProvingPhase::Serialize).await?;
+            ProofOutput::encode(&bundle, compress)?
+        }
+    };
+    if input.timings {
+        output.meta.get_or_insert_with(ProofMeta::default).timings = Some(timer.finish());
+    }
+
+    Ok(ProofOutput {
+        circuit_id: Some(CircuitId::for_circuit(input.non_trivial)),
+        ..output
+    })
+}
+
+fn circuit_from_input<F: PrimeField>(input: &ProofInput) -> anyhow::Result<Circuit<F>> {
+    // Convert input strings to field elements
+    let c = field::parse_auto(&input.c)?;
+    let a = field::parse_auto(&input.a)?;
+    let b = field::parse_auto(&input.b)?;
+
+    Circuit::new(c, a, b, input.non_trivial)
+        .map_err(|e| anyhow::anyhow!("Failed to create circuit: {}", e))
+}
+
+/// Evaluates the circuit natively on the private inputs of a JSON
+/// [`ProofInput`] (`c` may be omitted) and returns the public inputs a proof
+/// would commit to as a JSON [`ExpectedPublic`].
+///
+/// Lets frontends fill in or check `c` before spending time on proving.
+///
+/// # Errors
+/// Returns error if the input cannot be parsed or the factors violate the
+/// `nonTrivial` option.
+pub fn compute_expected_public(input_json: &str) -> anyhow::Result<String> {
+    let input: PrivateInput = serde_json::from_str(input_json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse input JSON: {}", e))?;
+
+    let c = match input.curve {
+        Curve::Bn254 => expected_public_on::<Bn254>(&input)?,
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => expected_public_on::<Bls12_381>(&input)?,
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => return Err(unsupported_curve(input.curve)),
+    };
+
+    serde_json::to_string(&ExpectedPublic { c })
+        .map_err(|e| anyhow::anyhow!("Failed to serialize output: {}", e))
+}
+
+fn expected_public_on<E: SupportedCurve>(input: &PrivateInput) -> anyhow::Result<String> {
+    let a = field::parse_auto::<E::ScalarField>(&input.a)?;
+    let b = field::parse_auto::<E::ScalarField>(&input.b)?;
+    let c = Circuit::compute_public(a, b);
+
+    // Apply the same checks as proving, so an accepted input is provable
+    let circuit = Circuit::new(c, a, b, input.non_trivial)
+        .map_err(|e| anyhow::anyhow!("Failed to create circuit: {}", e))?;
+
+    Ok(circuit.c.to_string())
+}
+
+/// Computes the full assignment for a JSON [`ProofInput`] and returns it as a
+/// circom `.wtns` file; see [`circom::write_wtns`].
+///
+/// # Errors
+/// Returns error if the input cannot be parsed or does not satisfy the circuit.
+pub fn witness_file(input_json: &str) -> anyhow::Result<Vec<u8>> {
+    let input = parse_input(input_json)?;
+
+    match input.curve {
+        Curve::Bn254 => witness_file_on::<Bn254>(&input),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => witness_file_on::<Bls12_381>(&input),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => Err(unsupported_curve(input.curve)),
+    }
+}
+
+fn witness_file_on<E: SupportedCurve>(input: &ProofInput) -> anyhow::Result<Vec<u8>> {
+    let circuit = circuit_from_input::<E::ScalarField>(input)?;
+    let witness = witness::generate(circuit)?;
+
+    let mut bytes = Vec::new();
+    circom::write_wtns(&witness, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Verifies a JSON [`ProofOutput`] against a hex-encoded verifying key.
+///
+/// # Errors
+/// Returns error if the proof or verifying key cannot be parsed,
+/// [`CircuitMismatchError`] if the proof names a circuit other than the
+/// key's (or, for a key without a header, this build's), or
+/// [`InputCountError`] if the proof has the wrong number of public inputs;
+/// an invalid proof yields `Ok(false)`.
+pub fn verify(proof_json: &str, verifying_key_hex: &str) -> anyhow::Result<bool> {
+    // Parse proof output
+    let proof_output: ProofOutput = serde_json::from_str(proof_json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse proof JSON: {}", e))?;
+
+    verify_typed(&proof_output, verifying_key_hex)
+}
+
+/// Like [`verify`], but on an already parsed [`ProofOutput`].
+///
+/// # Errors
+/// Returns error if the proof or verifying key cannot be parsed,
+/// [`CircuitMismatchError`] if the proof names a circuit other than the
+/// key's (or, for a key without a header, this build's), or
+/// [`InputCountError`] if the proof has the wrong number of public inputs;
+/// an invalid proof yields `Ok(false)`.
+pub fn verify_typed(proof_output: &ProofOutput, verifying_key_hex: &str) -> anyhow::Result<bool> {
+    check_circuit_id(proof_output, verifying_key_hex)?;
+
+    match proof_output.curve {
+        Curve::Bn254 => verify_on::<Bn254>(proof_output, verifying_key_hex),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => verify_on::<Bls12_381>(proof_output, verifying_key_hex),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => Err(unsupported_curve(proof_output.curve)),
+    }
+}
+
+/// Proofs without a circuit id are from older versions and not checked.
+fn check_circuit_id(
+    proof_output: &ProofOutput,
+    verifying_key_hex: &str,
+) -> Result<(), CircuitMismatchError> {
+    match proof_output.circuit_id {
+        Some(circuit_id) => circuit_id.check(CircuitId::from_key_hex(verifying_key_hex)),
+        None => Ok(()),
+    }
+}
+
+fn verify_on<E: SupportedCurve>(
+    proof_output: &ProofOutput,
+    verifying_key_hex: &str,
+) -> anyhow::Result<bool> {
+    let vk = deserialize_verifying_key::<E>(verifying_key_hex)?;
+    let bundle = ProofBundle::<E>::try_from(proof_output)?;
+
+    prover::verify(&bundle, &vk)
+}
+
+/// Outcome of [`verify_detailed`]: whether the proof is valid and, if not, why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationResult {
+    pub valid: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<VerificationFailure>,
+}
+
+/// Why a proof was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationFailure {
+    /// Well-formed, but the pairing check failed: the proof does not match
+    /// the public inputs or the key.
+    PairingMismatch,
+    /// A proof point is malformed, the identity or outside the subgroup.
+    BadPoint,
+    /// The proof has a different number of public inputs than the key expects.
+    InputCountMismatch,
+    /// The proof names a circuit other than the verifying key's.
+    CircuitMismatch,
+}
+
+impl VerificationResult {
+    fn rejected(reason: VerificationFailure) -> Self {
+        Self {
+            valid: false,
+            reason: Some(reason),
+        }
+    }
+
+    /// Classifies the outcome of a boolean verify call such as
+    /// [`verify_with_inputs`]: [`PointError`](point::PointError) and
+    /// [`InputCountError`] become rejections, other errors are passed through.
+    pub fn from_outcome(outcome: anyhow::Result<bool>) -> anyhow::Result<Self> {
+        match outcome {
+            Ok(true) => Ok(Self {
+                valid: true,
+                reason: None,
+            }),
+            Ok(false) => Ok(Self::rejected(VerificationFailure::PairingMismatch)),
+            Err(e) if e.is::<point::PointError>() => {
+                Ok(Self::rejected(VerificationFailure::BadPoint))
+            }
+            Err(e) if e.is::<InputCountError>() => {
+                Ok(Self::rejected(VerificationFailure::InputCountMismatch))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Like [`verify`], but reports why a proof was rejected, so callers can
+/// tell an invalid proof from malformed proof data.
+///
+/// # Errors
+/// Returns error if the proof JSON or the verifying key cannot be parsed, or
+/// a public input is not a field element.
+pub fn verify_detailed(
+    proof_json: &str,
+    verifying_key_hex: &str,
+) -> anyhow::Result<VerificationResult> {
+    let proof_output: ProofOutput = serde_json::from_str(proof_json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse proof JSON: {}", e))?;
+
+    if check_circuit_id(&proof_output, verifying_key_hex).is_err() {
+        return Ok(VerificationResult::rejected(
+            VerificationFailure::CircuitMismatch,
+        ));
+    }
+
+    match proof_output.curve {
+        Curve::Bn254 => verify_detailed_on::<Bn254>(&proof_output, verifying_key_hex),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => verify_detailed_on::<Bls12_381>(&proof_output, verifying_key_hex),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => Err(unsupported_curve(proof_output.curve)),
+    }
+}
+
+fn verify_detailed_on<E: SupportedCurve>(
+    proof_output: &ProofOutput,
+    verifying_key_hex: &str,
+) -> anyhow::Result<VerificationResult> {
+    let vk = deserialize_verifying_key::<E>(verifying_key_hex)?;
+
+    VerificationResult::from_outcome(
+        ProofBundle::<E>::try_from(proof_output).and_then(|bundle| prover::verify(&bundle, &vk)),
+    )
+}
+
+/// Verifies a hex-encoded proof (`proofSerializedHex`) against
+/// public inputs supplied by the caller, e.g. taken from a chain event.
+///
+/// Public inputs are decimal or `0x`-hex integers below the field order, in
+/// the order allocated by the circuit.
+///
+/// # Errors
+/// Returns error if the proof, inputs or verifying key cannot be parsed, or
+/// [`InputCountError`] if the number of inputs does not match the key; an
+/// invalid proof yields `Ok(false)`.
+pub fn verify_with_inputs(
+    curve: Curve,
+    proof_hex: &str,
+    public_inputs: &[String],
+    verifying_key_hex: &str,
+) -> anyhow::Result<bool> {
+    match curve {
+        Curve::Bn254 => verify_with_inputs_on::<Bn254>(proof_hex, public_inputs, verifying_key_hex),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => {
+            verify_with_inputs_on::<Bls12_381>(proof_hex, public_inputs, verifying_key_hex)
+        }
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => Err(unsupported_curve(curve)),
+    }
+}
+
+fn verify_with_inputs_on<E: SupportedCurve>(
+    proof_hex: &str,
+    public_inputs: &[String],
+    verifying_key_hex: &str,
+) -> anyhow::Result<bool> {
+    let vk = deserialize_verifying_key::<E>(verifying_key_hex)?;
+
+    let public_inputs = public_inputs
+        .iter()
+        .map(|s| field::parse_canonical(s))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    prover::verify(
+        &ProofBundle {
+            proof: deserialize_proof::<E>(proof_hex)?,
+            public_inputs,
+        },
+        &vk,
+    )
+}
+
+/// Verifies the compact representation of a proof: the `proofSerializedHex`
+/// and `publicInputsSerializedHex` fields of [`ProofOutput`], exactly as they
+/// are passed to `sui::groth16`.
+///
+/// # Errors
+/// Returns error if the proof, inputs or verifying key cannot be parsed, or
+/// [`InputCountError`] if the number of inputs does not match the key; an
+/// invalid proof yields `Ok(false)`.
+pub fn verify_serialized(
+    curve: Curve,
+    proof_serialized_hex: &str,
+    public_inputs_serialized_hex: &str,
+    verifying_key_hex: &str,
+) -> anyhow::Result<bool> {
+    match curve {
+        Curve::Bn254 => verify_serialized_on::<Bn254>(
+            proof_serialized_hex,
+            public_inputs_serialized_hex,
+            verifying_key_hex,
+        ),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => verify_serialized_on::<Bls12_381>(
+            proof_serialized_hex,
+            public_inputs_serialized_hex,
+            verifying_key_hex,
+        ),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => Err(unsupported_curve(curve)),
+    }
+}
+
+fn verify_serialized_on<E: SupportedCurve>(
+    proof_serialized_hex: &str,
+    public_inputs_serialized_hex: &str,
+    verifying_key_hex: &str,
+) -> anyhow::Result<bool> {
+    let vk = deserialize_verifying_key::<E>(verifying_key_hex)?;
+
+    let inputs_bytes = hex::decode(public_inputs_serialized_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode public inputs hex: {}", e))?;
+    let size = E::ScalarField::ZERO.compressed_size();
+    if inputs_bytes.len() % size != 0 {
+        anyhow::bail!(
+            "Serialized public inputs must be a multiple of {} bytes, got {}",
+            size,
+            inputs_bytes.len()
+        );
+    }
+    let public_inputs = inputs_bytes
+        .chunks(size)
+        .map(|chunk| {
+            E::ScalarField::deserialize_compressed(chunk)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize public input: {}", e))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    prover::verify(
+        &ProofBundle {
+            proof: deserialize_proof::<E>(proof_serialized_hex)?,
+            public_inputs,
+        },
+        &vk,
+    )
+}
+
+/// Re-randomizes a JSON [`ProofOutput`] and returns the new proof as JSON.
+///
+/// Relayers can use this to unlink a proof from the prover before
+/// broadcasting it. The public inputs are unchanged; proving metadata is
+/// dropped since it describes the original randomness.
+///
+/// # Errors
+/// Returns error if the proof or verifying key cannot be parsed.
+pub fn rerandomize(proof_json: &str, verifying_key_hex: &str) -> anyhow::Result<String> {
+    let proof_output: ProofOutput = serde_json::from_str(proof_json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse proof JSON: {}", e))?;
+
+    let output = match proof_output.curve {
+        Curve::Bn254 => rerandomize_on::<Bn254>(&proof_output, verifying_key_hex)?,
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => rerandomize_on::<Bls12_381>(&proof_output, verifying_key_hex)?,
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => return Err(unsupported_curve(proof_output.curve)),
+    };
+
+    serialize_output(&output)
+}
+
+fn rerandomize_on<E: SupportedCurve>(
+    proof_output: &ProofOutput,
+    verifying_key_hex: &str,
+) -> anyhow::Result<ProofOutput> {
+    let vk = deserialize_verifying_key::<E>(verifying_key_hex)?;
+    let bundle = ProofBundle::<E>::try_from(proof_output)?;
+
+    Ok(ProofOutput {
+        circuit_id: proof_output.circuit_id,
+        ..ProofOutput::encode(
+            &prover::rerandomize(&bundle, &vk, &mut OsRng),
+            proof_output.compress(),
+        )?
+    })
+}
+
+/// Describes the circuit so clients can check they loaded matching artifacts.
+///
+/// # Errors
+/// Returns error if the circuit cannot be synthesized.
+pub fn circuit_info(non_trivial: bool) -> anyhow::Result<CircuitInfo> {
+    let circuit = if non_trivial {
+        Circuit::<ark_bn254::Fr>::empty_non_trivial()
+    } else {
+        Circuit::<ark_bn254::Fr>::empty()
+    };
+    let stats = inspect::inspect(circuit)?;
+
+    Ok(CircuitInfo {
+        name: Circuit::NAME.to_string(),
+        version: Circuit::VERSION.to_string(),
+        non_trivial,
+        num_constraints: stats.num_constraints,
+        num_public_inputs: stats.num_public_inputs,
+        public_input_names: Circuit::PUBLIC_INPUT_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        proving_key_fingerprint: (!non_trivial).then(keys::expected_proving_key_fingerprint),
+    })
+}
+
+/// Describes the compiled-in size `id` of a parameterized circuit, e.g.
+/// `semaphore/depth=20`; see [`sizes::SIZED_CIRCUITS`].
+///
+/// # Errors
+/// Returns error if `id` is not compiled in or cannot be synthesized.
+pub fn sized_circuit_info(id: &str) -> anyhow::Result<SizedCircuitInfo> {
+    let circuit = sizes::find(id)?;
+    let stats = circuit.inspect()?;
+
+    Ok(SizedCircuitInfo {
+        id: circuit.id(),
+        name: circuit.name.to_string(),
+        version: circuit.version.to_string(),
+        params: circuit
+            .params
+            .iter()
+            .map(|(param, value)| (param.to_string(), *value))
+            .collect(),
+        num_constraints: stats.num_constraints,
+        num_public_inputs: stats.num_public_inputs,
+    })
+}
+
+/// Runs a single-party BN254 Groth16 setup for the compiled-in size `id` of
+/// a parameterized circuit; development and testing only, as [`keygen`].
+///
+/// # Errors
+/// Returns error if `id` is not compiled in or the setup fails.
+pub fn keygen_sized(id: &str) -> anyhow::Result<KeyPair> {
+    let pk = sizes::find(id)?.setup(&mut ChaCha20Rng::from_entropy())?;
+    encode_keys(&pk)
+}
+
+/// Runs a single-party Groth16 setup on `curve`.
+///
+/// Whoever runs this knows the toxic waste, so the keys are only suitable
+/// for development and testing.
+///
+/// # Errors
+/// Returns error if `curve` is not enabled or the setup fails.
+pub fn keygen(curve: Curve, non_trivial: bool) -> anyhow::Result<KeyPair> {
+    match curve {
+        Curve::Bn254 => keygen_on::<Bn254>(non_trivial),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => keygen_on::<Bls12_381>(non_trivial),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => Err(unsupported_curve(curve)),
+    }
+}
+
+fn keygen_on<E: SupportedCurve>(non_trivial: bool) -> anyhow::Result<KeyPair> {
+    let circuit = if non_trivial {
+        Circuit::<E::ScalarField>::empty_non_trivial()
+    } else {
+        Circuit::<E::ScalarField>::empty()
+    };
+
+    let mut rng = ChaCha20Rng::from_entropy();
+    let pk = Groth16::<E>::generate_random_parameters_with_reduction(circuit, &mut rng)
+        .map_err(|e| anyhow::anyhow!("Failed to generate keys: {}", e))?;
+    encode_keys(&pk)
+}
+
+fn encode_keys<E: Pairing>(pk: &ProvingKey<E>) -> anyhow::Result<KeyPair> {
+    let mut pk_bytes = Vec::new();
+    pk.serialize_compressed(&mut pk_bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize proving key: {}", e))?;
+    let mut vk_bytes = Vec::new();
+    pk.vk
+        .serialize_compressed(&mut vk_bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize verifying key: {}", e))?;
+
+    Ok(KeyPair {
+        proving_key_hex: hex::encode(pk_bytes),
+        verifying_key_hex: hex::encode(vk_bytes),
+    })
+}
+
+impl ProofOutput {
+    /// Encodes `bundle` with its proof points in the given encoding.
+    ///
+    /// # Errors
+    /// Returns error if the proof or public inputs cannot be serialized.
+    pub fn encode<E: SupportedCurve>(
+        bundle: &ProofBundle<E>,
+        compress: Compress,
+    ) -> anyhow::Result<Self> {
+        let _span = tracing::debug_span!("serialize_proof").entered();
+
+        let [proof_a, proof_b, proof_c] = bundle.proof_points_with(compress)?;
+        Ok(Self {
+            curve: E::CURVE,
+            proof_a,
+            proof_b,
+            proof_c,
+            uncompressed: compress == Compress::No,
+            public_inputs: bundle
+                .public_inputs
+                .iter()
+                .map(|input| input.to_string())
+                .collect(),
+            proof_serialized_hex: hex::encode(bundle.proof_serialized_with(compress)?),
+            public_inputs_serialized_hex: hex::encode(bundle.public_inputs_serialized()?),
+            circuit_id: None,
+            meta: None,
+        })
+    }
+
+    /// Encoding of the proof points.
+    pub fn compress(&self) -> Compress {
+        if self.uncompressed {
+            Compress::No
+        } else {
+            Compress::Yes
+        }
+    }
+}
+
+impl<E: SupportedCurve> TryFrom<&ProofBundle<E>> for ProofOutput {
+    type Error = anyhow::Error;
+
+    fn try_from(bundle: &ProofBundle<E>) -> anyhow::Result<Self> {
+        Self::encode(bundle, Compress::Yes)
+    }
+}
+
+impl<E: SupportedCurve> TryFrom<&ProofOutput> for ProofBundle<E> {
+    type Error = anyhow::Error;
+
+    fn try_from(output: &ProofOutput) -> anyhow::Result<Self> {
+        let _span = tracing::debug_span!("deserialize_proof").entered();
+
+        if output.curve != E::CURVE {
+            anyhow::bail!(
+                "Proof is for curve {}, expected {}",
+                output.curve.name(),
+                E::CURVE.name()
+            );
+        }
+
+        // Deserialize proof components, rejecting low-order and identity points
+        let compress = output.compress();
+        let proof_a = point::deserialize_with(&output.proof_a, compress, "proof.a")?;
+        let proof_b = point::deserialize_with(&output.proof_b, compress, "proof.b")?;
+        let proof_c = point::deserialize_with(&output.proof_c, compress, "proof.c")?;
+
+        // Parse public inputs
+        let public_inputs = output
+            .public_inputs
+            .iter()
+            .map(|s| field::parse_canonical(s))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            proof: ark_groth16::Proof {
+                a: proof_a,
+                b: proof_b,
+                c: proof_c,
+            },
+            public_inputs,
+        })
+    }
+}
+
+#[cfg(not(feature = "bls12-381"))]
+fn unsupported_curve(curve: Curve) -> anyhow::Error {
+    curve
+        .ensure_enabled()
+        .err()
+        .unwrap_or_else(|| anyhow::anyhow!("Curve {} is not supported", curve.name()))
+}
+
+/// Goes through [`keys::cache`], so a key passed again is not deserialized again.
+fn deserialize_proving_key<E: SupportedCurve>(
+    proving_key_hex: &str,
+    non_trivial: bool,
+) -> anyhow::Result<Arc<ProvingKey<E>>> {
+    let pk_bytes = hex::decode(proving_key_hex)
+        .map_err(|e| anyhow::anyhow!("Failed to decode proving key hex: {}", e))?;
+
+    keys::cache::decode(
+        &pk_bytes,
+        &Expected {
+            kind: KeyKind::Proving,
+            curve: E::CURVE,
+            non_trivial: Some(non_trivial),
+        },
+    )
+}
+
+/// Reads compressed or uncompressed proof points, told apart by their length.
+/// Any other length is rejected, so each proof has exactly two encodings.
+fn deserialize_proof<E: SupportedCurve>(proof_hex: &str) -> anyhow::Result<ark_groth16::Proof<E>> {
+    let _span = tracing::debug_span!("deserialize_proof").entered();
+
+    let proof_bytes = hex::decode(proof_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode proof hex: {}", e))?;
+
+    let empty = ark_groth16::Proof::<E>::default();
+    let compressed_size = empty.compressed_size();
+    let uncompressed_size = empty.uncompressed_size();
+    let compress = match proof_bytes.len() {
+        len if len == compressed_size => Compress::Yes,
+        len if len == uncompressed_size => Compress::No,
+        len => anyhow::bail!(
+            "Proof must be {} bytes compressed or {} bytes uncompressed, got {}",
+            compressed_size,
+            uncompressed_size,
+            len
+        ),
+    };
+    let proof =
+        ark_groth16::Proof::<E>::deserialize_with_mode(&proof_bytes[..], compress, Validate::No)
+            .map_err(|e| anyhow::anyhow!("Failed to deserialize proof: {}", e))?;
+    point::check_proof(&proof)?;
+
+    Ok(proof)
+}
+
+fn deserialize_verifying_key<E: SupportedCurve>(
+    verifying_key_hex: &str,
+) -> anyhow::Result<ark_groth16::VerifyingKey<E>> {
+    let vk_bytes = hex::decode(verifying_key_hex)
+        .map_err(|e| anyhow::anyhow!("Failed to decode verifying key hex: {}", e))?;
+
+    let vk: ark_groth16::VerifyingKey<E> = header::decode(
+        &vk_bytes,
+        &Expected {
+            kind: KeyKind::Verifying,
+            curve: E::CURVE,
+            non_trivial: None,
+        },
+    )?;
+    point::check_verifying_key(&vk)?;
+
+    Ok(vk)
+}
+
+fn parse_session_key(session_key_hex: &str) -> anyhow::Result<[u8; 32]> {
+    let bytes = hex::decode(session_key_hex.trim_start_matches("0x"))
+        .map_err(|e| anyhow::anyhow!("Failed to decode audit session key hex: {}", e))?;
+
+    bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Audit session key must be 32 bytes"))
+}
@@ -0,0 +1,86 @@
+use crate::prover::ProvingPhase;
+use serde::{Deserialize, Serialize};
+
+/// Wall-clock duration of each step of [`super::prove`], in milliseconds.
+///
+/// Included in [`super::ProofMeta`] when the input sets `timings`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Timings {
+    /// Decoding the proving key; 0 when proving with an already loaded key
+    pub key_load_ms: f64,
+    /// Synthesizing the circuit and computing the QAP witness
+    pub witness_ms: f64,
+    /// Multi-scalar multiplications for the proof components
+    pub prove_ms: f64,
+    /// Encoding the proof for output
+    pub serialize_ms: f64,
+}
+
+/// Monotonic clock: `std::time::Instant` natively, `Date.now()` in the
+/// browser, where `Instant` is not available.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Stopwatch {
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm-bindings")))]
+    start: std::time::Instant,
+    #[cfg(all(target_arch = "wasm32", feature = "wasm-bindings"))]
+    start: f64,
+}
+
+impl Stopwatch {
+    pub(crate) fn start() -> Self {
+        Self {
+            #[cfg(not(all(target_arch = "wasm32", feature = "wasm-bindings")))]
+            start: std::time::Instant::now(),
+            #[cfg(all(target_arch = "wasm32", feature = "wasm-bindings"))]
+            start: js_sys::Date::now(),
+        }
+    }
+
+    pub(crate) fn elapsed_ms(&self) -> f64 {
+        #[cfg(not(all(target_arch = "wasm32", feature = "wasm-bindings")))]
+        return self.start.elapsed().as_secs_f64() * 1000.0;
+        #[cfg(all(target_arch = "wasm32", feature = "wasm-bindings"))]
+        return js_sys::Date::now() - self.start;
+    }
+}
+
+/// Splits the time since it was started at the phases passed to proving
+/// checkpoints.
+#[derive(Debug)]
+pub(crate) struct PhaseTimer {
+    clock: Stopwatch,
+    key_load_ms: f64,
+    witness_end: f64,
+    prove_end: f64,
+}
+
+impl PhaseTimer {
+    pub(crate) fn start(key_load_ms: f64) -> Self {
+        Self {
+            clock: Stopwatch::start(),
+            key_load_ms,
+            witness_end: 0.0,
+            prove_end: 0.0,
+        }
+    }
+
+    /// Marks the start of `phase`.
+    pub(crate) fn phase(&mut self, phase: ProvingPhase) {
+        match phase {
+            ProvingPhase::AMsm => self.witness_end = self.clock.elapsed_ms(),
+            ProvingPhase::Serialize => self.prove_end = self.clock.elapsed_ms(),
+            _ => {}
+        }
+    }
+
+    /// Timings up to now, which is taken as the end of serialization.
+    pub(crate) fn finish(&self) -> Timings {
+        Timings {
+            key_load_ms: self.key_load_ms,
+            witness_ms: self.witness_end,
+            prove_ms: self.prove_end - self.witness_end,
+            serialize_ms: self.clock.elapsed_ms() - self.prove_end,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use ark_ff::PrimeField;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+const RANDOMNESS_DOMAIN: &[u8] = b"vortex/groth16-randomness/v1";
+const COMMITMENT_DOMAIN: &[u8] = b"vortex/groth16-randomness-commitment/v1";
+
+/// Public audit record attached to a proof generated in audit mode.
+///
+/// The record commits to the Groth16 blinding factors `(r, s)` without
+/// revealing them. Only the holder of the session key can re-derive `(r, s)`
+/// from the nonce and open the commitment, so proofs remain zero-knowledge
+/// unless the user discloses their session key to an auditor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuditRecord {
+    pub nonce: [u8; 32],
+    pub commitment: [u8; 32],
+}
+
+impl AuditRecord {
+    /// Checks that this record was produced by the session owning `session_key`.
+    ///
+    /// `F` is the scalar field of the curve the proof was generated on.
+    pub fn open<F: PrimeField>(&self, session_key: &[u8; 32]) -> bool {
+        let (r, s) = derive_randomness::<F>(session_key, &self.nonce);
+        commit(&r, &s) == self.commitment
+    }
+}
+
+/// Derives the Groth16 blinding factors for `nonce` within a session.
+pub fn derive_randomness<F: PrimeField>(session_key: &[u8; 32], nonce: &[u8; 32]) -> (F, F) {
+    let mut seed: [u8; 32] = Sha256::new()
+        .chain_update(RANDOMNESS_DOMAIN)
+        .chain_update(session_key)
+        .chain_update(nonce)
+        .finalize()
+        .into();
+
+    let mut rng = ChaCha20Rng::from_seed(seed);
+    seed.zeroize();
+    (F::rand(&mut rng), F::rand(&mut rng))
+}
+
+/// Hiding commitment to the blinding factors `(r, s)`.
+pub fn commit<F: PrimeField>(r: &F, s: &F) -> [u8; 32] {
+    let mut bytes = Vec::new();
+    r.serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    s.serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+
+    Sha256::new()
+        .chain_update(COMMITMENT_DOMAIN)
+        .chain_update(&bytes)
+        .finalize()
+        .into()
+}
@@ -0,0 +1,218 @@
+use crate::poseidon;
+use ark_ec::{
+    models::CurveConfig,
+    twisted_edwards::{self, MontCurveConfig, TECurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{
+    fields::{Fp256, MontBackend, MontConfig},
+    BigInteger, MontFp, PrimeField, Zero,
+};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::HashMap;
+use zeroize::Zeroize;
+
+/// Domain of the hash-to-curve deriving [`blinding_generator`].
+pub const BLINDING_GENERATOR_DOMAIN: &[u8] = b"vortex/pedersen-blinding-generator/v1";
+
+/// Base field of Baby Jubjub: the BN254 scalar field, so points are native to
+/// BN254 circuits.
+pub type Fq = ark_bn254::Fr;
+
+#[derive(MontConfig)]
+#[modulus = "2736030358979909402780800718157159386076813972158567259200215660948447373041"]
+#[generator = "31"]
+pub struct FrConfig;
+
+/// Scalar field of the prime-order subgroup.
+pub type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+/// Baby Jubjub, `168700 x^2 + y^2 = 1 + 168696 x^2 y^2`, as in circomlib and
+/// EIP-2494.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Config;
+
+pub type Affine = twisted_edwards::Affine<Config>;
+pub type Projective = twisted_edwards::Projective<Config>;
+
+impl CurveConfig for Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[8];
+    const COFACTOR_INV: Fr =
+        MontFp!("2394026564107420727433200628387514462817212225638746351800188703329891451411");
+}
+
+impl TECurveConfig for Config {
+    const COEFF_A: Fq = MontFp!("168700");
+    const COEFF_D: Fq = MontFp!("168696");
+    /// circomlib's `Base8`, which generates the prime-order subgroup.
+    const GENERATOR: Affine = Affine::new_unchecked(
+        MontFp!("5299619240641551281634865583518297030282874472190772894086521144482721001553"),
+        MontFp!("16950150798460657717958625567821834550301663161624707787222815936182638968203"),
+    );
+
+    type MontCurveConfig = Config;
+}
+
+impl MontCurveConfig for Config {
+    const COEFF_A: Fq = MontFp!("168698");
+    const COEFF_B: Fq = MontFp!("1");
+
+    type TECurveConfig = Config;
+}
+
+/// The second Pedersen generator `H`, whose discrete logarithm to the base
+/// point is unknown: the first point in the subgroup found by hashing
+/// [`BLINDING_GENERATOR_DOMAIN`] and a counter to a `y` coordinate.
+pub fn blinding_generator() -> Affine {
+    (0u32..)
+        .find_map(|counter| {
+            let digest = Sha256::new()
+                .chain_update(BLINDING_GENERATOR_DOMAIN)
+                .chain_update(counter.to_be_bytes())
+                .finalize();
+            let point =
+                Affine::get_point_from_y_unchecked(Fq::from_be_bytes_mod_order(&digest), false)?
+                    .clear_cofactor();
+            (!point.is_zero()).then_some(point)
+        })
+        .expect("half of all y coordinates are on the curve")
+}
+
+/// The exponent a BN254 field element stands for: the integer it encodes,
+/// reduced modulo the subgroup order, as multiplying by its bits in-circuit
+/// does.
+pub fn to_scalar(x: Fq) -> Fr {
+    Fr::from_le_bytes_mod_order(&x.into_bigint().to_bytes_le())
+}
+
+/// Pedersen commitment `value * G + blinding * H`, with `G` the base point
+/// and `H` the [`blinding_generator`].
+///
+/// Values are committed modulo the subgroup order, about `2^251`, so two
+/// values below it never share a commitment.
+pub fn commit(value: Fq, blinding: Fq) -> Affine {
+    (Affine::generator() * to_scalar(value) + blinding_generator() * to_scalar(blinding))
+        .into_affine()
+}
+
+/// Re-randomizes a Pedersen [`commit`]ment: `commitment + randomness * H`
+/// commits to the same value with the blinding shifted by `randomness`.
+pub fn rerandomize(commitment: &Affine, randomness: Fq) -> Affine {
+    (*commitment + blinding_generator() * to_scalar(randomness)).into_affine()
+}
+
+/// An exponential ElGamal ciphertext `(r * G, m * G + r * P)` of `m` under the
+/// public key `P`. Encrypting `m * G` keeps it additively homomorphic, but only
+/// small messages can be decrypted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ciphertext {
+    pub c1: Affine,
+    pub c2: Affine,
+}
+
+/// The ElGamal public key `secret * G`.
+pub fn public_key(secret: Fr) -> Affine {
+    (Affine::generator() * secret).into_affine()
+}
+
+/// Encrypts `message` under `public_key` with the one-time `randomness`.
+pub fn encrypt(public_key: &Affine, message: Fq, randomness: Fq) -> Ciphertext {
+    let randomness = to_scalar(randomness);
+    Ciphertext {
+        c1: (Affine::generator() * randomness).into_affine(),
+        c2: (Affine::generator() * to_scalar(message) + *public_key * randomness).into_affine(),
+    }
+}
+
+/// Decrypts a message of at most `max`, by baby-step giant-step search for
+/// `m` in `m * G = c2 - secret * c1`; costs about `sqrt(max)` time and memory.
+///
+/// Returns `None` if the message is larger than `max`.
+pub fn decrypt(secret: Fr, ciphertext: &Ciphertext, max: u64) -> Option<u64> {
+    let target = ciphertext.c2.into_group() - ciphertext.c1 * secret;
+    let step = (max as f64).sqrt() as u64 + 1;
+
+    let mut baby_steps = HashMap::new();
+    let mut point = Projective::zero();
+    for j in 0..step {
+        baby_steps.entry(point.into_affine()).or_insert(j);
+        point += Affine::generator();
+    }
+
+    let giant_step = Affine::generator() * Fr::from(step);
+    let mut point = target;
+    for i in 0..=step {
+        if let Some(j) = baby_steps.get(&point.into_affine()) {
+            let message = i * step + j;
+            return (message <= max).then_some(message);
+        }
+        point -= giant_step;
+    }
+    None
+}
+
+/// An EdDSA-Poseidon signature, as signed by circomlibjs' `signPoseidon` and
+/// checked by circomlib's `EdDSAPoseidonVerifier`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    pub r8: Affine,
+    pub s: Fr,
+}
+
+/// Resets `r8` to the identity rather than to `(0, 0)`, which is off the curve.
+impl Zeroize for Signature {
+    fn zeroize(&mut self) {
+        self.r8 = Affine::zero();
+        self.s.zeroize();
+    }
+}
+
+/// The challenge `Poseidon(R8.x, R8.y, A.x, A.y, message)` of a signature by
+/// the key `A`, hashed with circomlib's Poseidon.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn challenge(r8: &Affine, public_key: &Affine, message: Fq) -> anyhow::Result<Fq> {
+    poseidon::circom_hash(&[r8.x, r8.y, public_key.x, public_key.y, message])
+}
+
+/// Signs `message` under the key [`public_key`]`(secret)`, with a nonce
+/// derived from SHA-512 of the secret and message.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn sign(secret: Fr, message: Fq) -> anyhow::Result<Signature> {
+    let digest = Sha512::new()
+        .chain_update(secret.into_bigint().to_bytes_le())
+        .chain_update(message.into_bigint().to_bytes_le())
+        .finalize();
+    let nonce = Fr::from_le_bytes_mod_order(&digest);
+
+    let r8 = public_key(nonce);
+    let challenge = to_scalar(challenge(&r8, &public_key(secret), message)?);
+    Ok(Signature {
+        r8,
+        s: nonce + Fr::from(8u64) * challenge * secret,
+    })
+}
+
+/// Checks `S * G == R8 + 8 * challenge * A`, for a non-zero key `A` of the
+/// prime-order subgroup.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn verify(public_key: &Affine, message: Fq, signature: &Signature) -> anyhow::Result<bool> {
+    if public_key.is_zero()
+        || !public_key.is_on_curve()
+        || !public_key.is_in_correct_subgroup_assuming_on_curve()
+    {
+        return Ok(false);
+    }
+
+    let challenge = to_scalar(challenge(&signature.r8, public_key, message)?);
+    Ok(Affine::generator() * signature.s
+        == signature.r8 + *public_key * (Fr::from(8u64) * challenge))
+}
@@ -0,0 +1,92 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, ProvingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::rngs::OsRng;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use vortex::dsl::{self, CircuitSpec, DslCircuit};
+use vortex::{field, prover};
+
+const USAGE: &str = "Usage:
+  dsl keygen <spec.json|spec.toml> [keys_dir]
+  dsl prove <spec.json|spec.toml> <assignment.json> <proving_key.hex>";
+
+pub fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    let arg = |i: usize| {
+        args.get(i)
+            .map(String::as_str)
+            .ok_or_else(|| anyhow::anyhow!("{}", USAGE))
+    };
+
+    match arg(1)? {
+        "keygen" => {
+            let spec = read_spec(Path::new(arg(2)?))?;
+            let keys_dir = args
+                .get(3)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| Path::new("keys").join(&spec.name));
+            keygen(spec, &keys_dir)
+        }
+        "prove" => {
+            let spec = read_spec(Path::new(arg(2)?))?;
+            let assignment = dsl::parse_assignment::<Fr>(&fs::read_to_string(arg(3)?)?)?;
+            let pk_bytes = hex::decode(fs::read_to_string(arg(4)?)?.trim())?;
+            let pk = ProvingKey::<Bn254>::deserialize_compressed(&pk_bytes[..])
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize proving key: {}", e))?;
+
+            let bundle = prover::prove(DslCircuit::new(spec, &assignment)?, &pk, &mut OsRng)?;
+            let output = json!({
+                "publicInputs": bundle
+                    .public_inputs
+                    .iter()
+                    .map(|x| field::format(x, field::Encoding::Decimal))
+                    .collect::<Vec<_>>(),
+                "proofSerializedHex": hex::encode(bundle.proof_serialized()?),
+                "publicInputsSerializedHex": hex::encode(bundle.public_inputs_serialized()?),
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            Ok(())
+        }
+        _ => anyhow::bail!("{}", USAGE),
+    }
+}
+
+/// Reads a spec, as TOML if the file name ends in `.toml` and JSON otherwise.
+fn read_spec(path: &Path) -> anyhow::Result<CircuitSpec> {
+    let source = fs::read_to_string(path)?;
+    if path.extension().is_some_and(|ext| ext == "toml") {
+        CircuitSpec::from_toml(&source)
+    } else {
+        CircuitSpec::from_json(&source)
+    }
+}
+
+fn keygen(spec: CircuitSpec, keys_dir: &Path) -> anyhow::Result<()> {
+    let name = spec.name.clone();
+    let circuit = DslCircuit::<Fr>::empty(spec)?;
+
+    // Use deterministic RNG for reproducibility (test mode)
+    let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(circuit, &mut rng)?;
+
+    let mut pk_bytes = Vec::new();
+    pk.serialize_compressed(&mut pk_bytes)?;
+    let mut vk_bytes = Vec::new();
+    pk.vk.serialize_compressed(&mut vk_bytes)?;
+
+    fs::create_dir_all(keys_dir)?;
+    fs::write(keys_dir.join("proving_key.hex"), hex::encode(&pk_bytes))?;
+    fs::write(
+        keys_dir.join("verification_key.hex"),
+        hex::encode(&vk_bytes),
+    )?;
+
+    println!("✅ Keys for {} written to ./{}/", name, keys_dir.display());
+    println!("    - proving_key.hex");
+    println!("    - verification_key.hex");
+    Ok(())
+}
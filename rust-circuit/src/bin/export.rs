@@ -0,0 +1,142 @@
+use ark_bn254::Bn254;
+use ark_groth16::VerifyingKey;
+use std::fs;
+use vortex::api::ProofOutput;
+use vortex::curve::{Curve, SupportedCurve};
+use vortex::keys::convert::KeyKind;
+use vortex::keys::header::{self, Expected};
+use vortex::prover::ProofBundle;
+use vortex::solidity;
+use vortex::sui::verifier;
+
+const USAGE: &str = "Usage:
+  export solidity [--vk <verification-key.bin>] [<output.sol>]
+  export calldata <proof.json> [<output.json>]
+  export sui-move [--curve <curve>] [--vk <verification-key.bin>] [--address <name>]
+                  [--module <name>] [<output.move>]
+
+Solidity verifiers are BN254 only. The verifying key defaults to
+keys/verification_key.bin; the Move module defaults to vortex::verifier.";
+
+pub fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("solidity") => export_solidity(&args[1..]),
+        Some("calldata") => export_calldata(&args[1..]),
+        Some("sui-move") => export_sui_move(&args[1..]),
+        _ => anyhow::bail!("{}", USAGE),
+    }
+}
+
+fn export_solidity(args: &[String]) -> anyhow::Result<()> {
+    let vk = read_verifying_key(flag(args, "--vk")?.unwrap_or("keys/verification_key.bin"))?;
+    let output = positional(args).first().copied().unwrap_or("Verifier.sol");
+
+    fs::write(output, solidity::verifier_contract(&vk))
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output, e))?;
+
+    println!("✅ Solidity verifier written to {}", output);
+
+    Ok(())
+}
+
+fn export_calldata(args: &[String]) -> anyhow::Result<()> {
+    let paths = positional(args);
+    let (input, output) = match paths[..] {
+        [input] => (input, None),
+        [input, output] => (input, Some(output)),
+        _ => anyhow::bail!("Expected a proof path\n{}", USAGE),
+    };
+
+    let json = fs::read_to_string(input)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", input, e))?;
+    let proof_output: ProofOutput = serde_json::from_str(&json)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", input, e))?;
+    let bundle = ProofBundle::<Bn254>::try_from(&proof_output)?;
+
+    let calldata = solidity::encode_calldata(&bundle.proof, &bundle.public_inputs);
+    let calldata = serde_json::to_string_pretty(&calldata)?;
+
+    match output {
+        Some(output) => {
+            fs::write(output, calldata)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output, e))?;
+            println!("✅ Calldata written to {}", output);
+        }
+        None => println!("{}", calldata),
+    }
+
+    Ok(())
+}
+
+fn export_sui_move(args: &[String]) -> anyhow::Result<()> {
+    let curve = match flag(args, "--curve")? {
+        Some(curve) => curve.parse::<Curve>()?,
+        None => Curve::default(),
+    };
+    curve.ensure_enabled()?;
+
+    let vk_path = flag(args, "--vk")?.unwrap_or("keys/verification_key.bin");
+    let address = flag(args, "--address")?.unwrap_or("vortex");
+    let module = flag(args, "--module")?.unwrap_or("verifier");
+
+    let source = match curve {
+        Curve::Bn254 => {
+            verifier::verifier_module(&read_verifying_key::<Bn254>(vk_path)?, address, module)?
+        }
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => verifier::verifier_module(
+            &read_verifying_key::<ark_bls12_381::Bls12_381>(vk_path)?,
+            address,
+            module,
+        )?,
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+    };
+
+    let default_output = format!("{}.move", module);
+    let output = positional(args).first().copied().unwrap_or(&default_output);
+    fs::write(output, source).map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output, e))?;
+
+    println!(
+        "✅ Move module {}::{} written to {}",
+        address, module, output
+    );
+
+    Ok(())
+}
+
+fn read_verifying_key<E: SupportedCurve>(path: &str) -> anyhow::Result<VerifyingKey<E>> {
+    let bytes = fs::read(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e))?;
+
+    header::decode(
+        &bytes,
+        &Expected {
+            kind: KeyKind::Verifying,
+            curve: E::CURVE,
+            non_trivial: None,
+        },
+    )
+    .map_err(|e| anyhow::anyhow!("{}: {}", path, e))
+}
+
+/// Positional arguments: whatever is not a flag or the value of a flag.
+fn positional(args: &[String]) -> Vec<&str> {
+    args.iter()
+        .enumerate()
+        .filter(|(i, arg)| !arg.starts_with("--") && (*i == 0 || !args[i - 1].starts_with("--")))
+        .map(|(_, arg)| arg.as_str())
+        .collect()
+}
+
+/// Returns the value following `name`, if the flag is present.
+fn flag<'a>(args: &'a [String], name: &str) -> anyhow::Result<Option<&'a str>> {
+    match args.iter().position(|arg| arg == name) {
+        Some(i) => args
+            .get(i + 1)
+            .map(|value| Some(value.as_str()))
+            .ok_or_else(|| anyhow::anyhow!("Missing value for {}", name)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,31 @@
+use ark_bn254::Fr;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use vortex::circom;
+use vortex::circuit::Circuit;
+
+pub fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Pass --non-trivial to export the non-trivial factorization circuit
+    let non_trivial = args.iter().any(|arg| arg == "--non-trivial");
+    let circuit = if non_trivial {
+        Circuit::<Fr>::empty_non_trivial()
+    } else {
+        Circuit::<Fr>::empty()
+    };
+
+    let out_path = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("circuit.r1cs"));
+
+    let mut writer = BufWriter::new(File::create(&out_path)?);
+    circom::write_r1cs(circuit, &mut writer)?;
+
+    println!("✅ R1CS written to {}", out_path.display());
+
+    Ok(())
+}
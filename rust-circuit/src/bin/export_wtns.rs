@@ -0,0 +1,23 @@
+use std::fs;
+use std::path::PathBuf;
+use vortex::api;
+
+pub fn main() -> anyhow::Result<()> {
+    let mut args = std::env::args().skip(1);
+
+    let input_path = args
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("Usage: export-wtns <input.json> [output.wtns]"))?;
+    let out_path = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("witness.wtns"));
+
+    let input_json = fs::read_to_string(&input_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", input_path, e))?;
+    fs::write(&out_path, api::witness_file(&input_json)?)?;
+
+    println!("✅ Witness written to {}", out_path.display());
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::Groth16;
+use ark_serialize::CanonicalSerialize;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use vortex::circuit::Circuit;
+use vortex::prover;
+
+/// Bump whenever the circuit, the serialization format or the seeds change.
+const FIXTURES_VERSION: u32 = 1;
+
+const KEYGEN_SEED: [u8; 32] = [0u8; 32];
+const PROOF_SEED: [u8; 32] = [1u8; 32];
+
+/// (a, b) pairs proved by the fixtures; `c` is always `a * b`.
+const CASES: [(u64, u64); 3] = [(5, 6), (3, 11), (7919, 104729)];
+
+pub fn main() -> anyhow::Result<()> {
+    let out_dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new("fixtures").join(format!("v{}", FIXTURES_VERSION)));
+
+    println!(
+        "Generating fixtures v{} in {}...",
+        FIXTURES_VERSION,
+        out_dir.display()
+    );
+    fs::create_dir_all(&out_dir)?;
+
+    let mut rng = ChaCha20Rng::from_seed(KEYGEN_SEED);
+    let pk =
+        Groth16::<Bn254>::generate_random_parameters_with_reduction(Circuit::empty(), &mut rng)?;
+
+    let mut pk_bytes = Vec::new();
+    pk.serialize_compressed(&mut pk_bytes)?;
+    let mut vk_bytes = Vec::new();
+    pk.vk.serialize_compressed(&mut vk_bytes)?;
+
+    let mut files = Vec::new();
+    files.push(write(&out_dir, "proving_key.hex", hex::encode(&pk_bytes))?);
+    files.push(write(
+        &out_dir,
+        "verification_key.hex",
+        hex::encode(&vk_bytes),
+    )?);
+
+    let mut rng = ChaCha20Rng::from_seed(PROOF_SEED);
+    let mut cases = Vec::new();
+    for (i, (a, b)) in CASES.iter().enumerate() {
+        let (a, b) = (Fr::from(*a), Fr::from(*b));
+        let circuit = Circuit::new(a * b, a, b, false)?;
+
+        let bundle = prover::prove(circuit, &pk, &mut rng)?;
+        if !prover::verify(&bundle, &pk.vk)? {
+            anyhow::bail!("Fixture proof {} does not verify", i);
+        }
+
+        let name = format!("case_{}.json", i);
+        let fixture = json!({
+            "input": {
+                "c": (a * b).to_string(),
+                "a": a.to_string(),
+                "b": b.to_string(),
+            },
+            "publicInputs": bundle.public_inputs.iter().map(|x| x.to_string()).collect::<Vec<_>>(),
+            "proofSerializedHex": hex::encode(bundle.proof_serialized()?),
+            "publicInputsSerializedHex": hex::encode(bundle.public_inputs_serialized()?),
+        });
+        files.push(write(
+            &out_dir,
+            &name,
+            serde_json::to_string_pretty(&fixture)?,
+        )?);
+        cases.push(name);
+    }
+
+    let manifest = json!({
+        "version": FIXTURES_VERSION,
+        "circuit": "multiplication",
+        "curve": "bn254",
+        "keygenSeed": hex::encode(KEYGEN_SEED),
+        "proofSeed": hex::encode(PROOF_SEED),
+        "cases": cases,
+        "files": files,
+    });
+    write(
+        &out_dir,
+        "manifest.json",
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+
+    println!("✅ Fixtures generated successfully!");
+
+    Ok(())
+}
+
+/// Writes `contents` to `dir/name` and returns its manifest entry.
+fn write(dir: &Path, name: &str, contents: String) -> anyhow::Result<serde_json::Value> {
+    fs::write(dir.join(name), &contents)?;
+    println!("    - {}", name);
+
+    Ok(json!({
+        "name": name,
+        "size": contents.len(),
+        "sha256": hex::encode(Sha256::digest(contents.as_bytes())),
+    }))
+}
@@ -0,0 +1,182 @@
+use ark_bn254::Fr;
+use std::fs;
+use std::path::Path;
+use vortex::circuit::{sizes, Circuit};
+use vortex::inspect::{self, CircuitStats, Summary};
+use vortex::keys::circuit_id::CircuitId;
+use vortex::keys::manifest::{self, Manifest};
+
+pub fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+
+    // Pass --list to list the compiled-in sizes of the parameterized circuits
+    if args.iter().any(|arg| arg == "--list") {
+        for circuit in sizes::SIZED_CIRCUITS {
+            println!("{}", circuit.id());
+        }
+        return Ok(());
+    }
+
+    // Pass --check-digest <keys-dir> to fail if the keys were generated for a
+    // different constraint system than the one compiled into this build
+    if let Some(i) = args.iter().position(|arg| arg == "--check-digest") {
+        let dir = args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("Missing value for --check-digest"))?;
+        return check_digest(Path::new(dir));
+    }
+
+    // Pass --diff <before> <after> to compare two circuits, each a circuit
+    // spec as below or a summary saved with --json, e.g. from the main branch
+    if let Some(i) = args.iter().position(|arg| arg == "--diff") {
+        let [before, after] = [i + 1, i + 2].map(|i| args.get(i));
+        let (Some(before), Some(after)) = (before, after) else {
+            anyhow::bail!("Expected --diff <before> <after>");
+        };
+        return diff(&summary(before)?, &summary(after)?);
+    }
+
+    // Pass --depth N to break constraints down N namespace levels deep (default 1)
+    let depth = match args.iter().position(|arg| arg == "--depth") {
+        Some(i) => args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("Missing value for --depth"))?
+            .parse::<usize>()
+            .map_err(|e| anyhow::anyhow!("Invalid --depth: {}", e))?,
+        None => 1,
+    };
+
+    // Pass --circuit <id> to inspect a sized circuit, e.g. semaphore/depth=20;
+    // otherwise --non-trivial inspects the non-trivial factorization circuit
+    let spec = match args.iter().position(|arg| arg == "--circuit") {
+        Some(i) => args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("Missing value for --circuit"))?
+            .as_str(),
+        None if args.iter().any(|arg| arg == "--non-trivial") => "multiplication/non-trivial",
+        None => "multiplication",
+    };
+    let (stats, summary) = synthesize(spec)?;
+
+    // Pass --json to print the summary that --diff compares
+    if args.iter().any(|arg| arg == "--json") {
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    println!("Circuit: {} v{}", summary.name, summary.version);
+    println!("  Constraints:       {}", stats.num_constraints);
+    println!("  Witness variables: {}", stats.num_witness_variables);
+    println!("  Public inputs:     {}", stats.num_public_inputs);
+    println!("  CS digest:         {}", summary.cs_digest);
+
+    let namespaces = stats.namespaces(depth);
+    if !namespaces.is_empty() {
+        println!();
+        println!("Constraints by namespace:");
+        for namespace in namespaces {
+            let name = namespace.path.rsplit('/').next().unwrap_or_default();
+            println!(
+                "  {:>8}  {}{}",
+                namespace.num_constraints,
+                "  ".repeat(namespace.depth - 1),
+                name
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Synthesizes `multiplication`, `multiplication/non-trivial` or a sized
+/// circuit id.
+fn synthesize(spec: &str) -> anyhow::Result<(CircuitStats, Summary)> {
+    let (circuit, name) = match spec {
+        "multiplication" => (Circuit::<Fr>::empty(), "multiplication"),
+        "multiplication/non-trivial" => (
+            Circuit::<Fr>::empty_non_trivial(),
+            "multiplication (non-trivial)",
+        ),
+        id => {
+            let circuit = sizes::find(id)?;
+            let stats = circuit.inspect()?;
+            // Parameterized circuits have as many inputs as their size
+            let summary = Summary::new(
+                &circuit.id(),
+                circuit.version,
+                &stats,
+                &[],
+                circuit.cs_digest()?,
+            );
+            return Ok((stats, summary));
+        }
+    };
+
+    let stats = inspect::inspect(circuit.clone())?;
+    let summary = Summary::new(
+        name,
+        Circuit::VERSION,
+        &stats,
+        Circuit::PUBLIC_INPUT_NAMES,
+        circuit.cs_digest()?,
+    );
+    Ok((stats, summary))
+}
+
+/// Reads `spec` as a saved summary if it names a `.json` file, otherwise
+/// synthesizes it.
+fn summary(spec: &str) -> anyhow::Result<Summary> {
+    if !spec.ends_with(".json") {
+        return Ok(synthesize(spec)?.1);
+    }
+
+    let json =
+        fs::read_to_string(spec).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", spec, e))?;
+    serde_json::from_str(&json).map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", spec, e))
+}
+
+fn diff(before: &Summary, after: &Summary) -> anyhow::Result<()> {
+    println!(
+        "Comparing {} v{} -> {} v{}",
+        before.name, before.version, after.name, after.version
+    );
+    for change in before.diff(after) {
+        println!("  {}", change);
+    }
+
+    if before.requires_setup(after) {
+        println!("⚠️  The constraint system changed: a new trusted setup is required");
+    } else {
+        println!("✅ Same constraint system: existing keys remain valid");
+    }
+
+    Ok(())
+}
+
+fn check_digest(dir: &Path) -> anyhow::Result<()> {
+    let manifest = Manifest::read(dir)?;
+    let found = manifest.cs_digest.ok_or_else(|| {
+        anyhow::anyhow!(
+            "{} has no constraint system digest; regenerate the keys to record one",
+            dir.join(Manifest::FILE_NAME).display()
+        )
+    })?;
+    let expected = manifest::cs_digest(manifest.curve, manifest.non_trivial)?;
+
+    if found != expected {
+        anyhow::bail!(
+            "Constraint system drift: keys in {} are for {}, this build is {}",
+            dir.display(),
+            found,
+            expected
+        );
+    }
+
+    println!(
+        "✅ {} matches the constraint system of {}",
+        dir.display(),
+        CircuitId::for_circuit(manifest.non_trivial)
+    );
+
+    Ok(())
+}
@@ -1,53 +1,151 @@
 use ark_bn254::Bn254;
 use ark_groth16::Groth16;
-use ark_serialize::CanonicalSerialize;
+use ark_serialize::{CanonicalSerialize, Compress};
 use rand_chacha::ChaCha20Rng;
 use rand_core::SeedableRng;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use vortex::circuit::Circuit;
+use vortex::curve::{Curve, SupportedCurve};
+use vortex::keys::convert::KeyKind;
+use vortex::keys::header::{self, KeyHeader};
+use vortex::keys::manifest::{FileEntry, Manifest};
 
 pub fn main() -> anyhow::Result<()> {
-    println!("Generating Groth16 proving and verifying keys...");
+    let args: Vec<String> = std::env::args().collect();
 
-    let circuit = Circuit::empty();
+    // Pass --curve bls12-381 to generate keys on BLS12-381 (requires the `bls12-381` feature)
+    let curve = match args.iter().position(|arg| arg == "--curve") {
+        Some(i) => args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("Missing value for --curve"))?
+            .parse::<Curve>()?,
+        None => Curve::default(),
+    };
+    curve.ensure_enabled()?;
+
+    // Pass --non-trivial to generate keys for the non-trivial factorization circuit
+    let non_trivial = args.iter().any(|arg| arg == "--non-trivial");
+
+    // Pass --uncompressed to write uncompressed .bin keys: about twice the size,
+    // but they load without decompressing every point
+    let compress = if args.iter().any(|arg| arg == "--uncompressed") {
+        Compress::No
+    } else {
+        Compress::Yes
+    };
+
+    // BN254 keys keep their original location; other curves get a subdirectory
+    let mut keys_dir = PathBuf::from("keys");
+    if curve != Curve::Bn254 {
+        keys_dir.push(curve.name().replace('-', "_"));
+    }
+    if non_trivial {
+        keys_dir.push("non_trivial");
+    }
+
+    println!(
+        "Generating Groth16 proving and verifying keys on {}...",
+        curve.name()
+    );
+
+    match curve {
+        Curve::Bn254 => generate_keys::<Bn254>(non_trivial, compress, &keys_dir)?,
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => {
+            generate_keys::<ark_bls12_381::Bls12_381>(non_trivial, compress, &keys_dir)?
+        }
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+    }
+
+    println!("✅ Keys generated successfully!");
+    println!("  Keys written to ./{}/", keys_dir.display());
+    println!("    - proving_key.bin / .hex");
+    println!("    - verification_key.bin / .hex");
+    println!("    - {}", Manifest::FILE_NAME);
+
+    Ok(())
+}
+
+fn generate_keys<E: SupportedCurve>(
+    non_trivial: bool,
+    compress: Compress,
+    keys_dir: &Path,
+) -> anyhow::Result<()> {
+    let circuit = if non_trivial {
+        Circuit::<E::ScalarField>::empty_non_trivial()
+    } else {
+        Circuit::<E::ScalarField>::empty()
+    };
 
     // Use deterministic RNG for reproducibility (test mode)
     let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
 
+    #[cfg(feature = "parallel")]
+    println!(
+        "Running setup on {} threads (this may take several minutes)...",
+        rayon::current_num_threads()
+    );
+    #[cfg(not(feature = "parallel"))]
     println!("Running setup (this may take several minutes)...");
-    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(circuit, &mut rng)?;
+    let pk = Groth16::<E>::generate_random_parameters_with_reduction(circuit, &mut rng)?;
     let vk = pk.vk.clone();
 
     // Prepare keys directory
-    let keys_dir = Path::new("keys");
     if !keys_dir.exists() {
         fs::create_dir_all(keys_dir)?;
     }
 
-    // Serialize verifying key (compressed for smaller size)
+    // Serialize both keys compressed for the .hex files; the .bin files
+    // reuse these bytes unless --uncompressed was passed
     let mut vk_bytes = Vec::new();
     vk.serialize_compressed(&mut vk_bytes)?;
 
-    // Serialize proving key (compressed for smaller size)
     let mut pk_bytes = Vec::new();
     pk.serialize_compressed(&mut pk_bytes)?;
 
+    // .bin files carry a header naming the key kind, curve, circuit and
+    // encoding; .hex files stay raw and compressed as Sui consumes them directly
+    let header = |kind| KeyHeader {
+        kind,
+        curve: E::CURVE,
+        circuit_id: header::circuit_id(non_trivial),
+        compressed: compress == Compress::Yes,
+    };
+    let mut vk_bin = header(KeyKind::Verifying).to_bytes().to_vec();
+    let mut pk_bin = header(KeyKind::Proving).to_bytes().to_vec();
+    if compress == Compress::Yes {
+        vk_bin.extend_from_slice(&vk_bytes);
+        pk_bin.extend_from_slice(&pk_bytes);
+    } else {
+        vk.serialize_uncompressed(&mut vk_bin)?;
+        pk.serialize_uncompressed(&mut pk_bin)?;
+    }
+
     // Write verifying key (bin + hex)
-    fs::write(keys_dir.join("verification_key.bin"), &vk_bytes)?;
+    fs::write(keys_dir.join("verification_key.bin"), &vk_bin)?;
     fs::write(
         keys_dir.join("verification_key.hex"),
         hex::encode(&vk_bytes),
     )?;
 
     // Write proving key (bin + hex)
-    fs::write(keys_dir.join("proving_key.bin"), &pk_bytes)?;
+    fs::write(keys_dir.join("proving_key.bin"), &pk_bin)?;
     fs::write(keys_dir.join("proving_key.hex"), hex::encode(&pk_bytes))?;
 
-    println!("✅ Keys generated successfully!");
-    println!("  Keys written to ./keys/");
-    println!("    - proving_key.bin / .hex");
-    println!("    - verification_key.bin / .hex");
+    // Record digests so deployments can detect swapped or truncated keys
+    let mut manifest = Manifest::for_circuit(E::CURVE, non_trivial)?;
+    manifest.files = vec![
+        FileEntry::new("proving_key.bin", &pk_bin),
+        FileEntry::new("proving_key.hex", hex::encode(&pk_bytes).as_bytes()),
+        FileEntry::new("verification_key.bin", &vk_bin),
+        FileEntry::new("verification_key.hex", hex::encode(&vk_bytes).as_bytes()),
+    ];
+    fs::write(
+        keys_dir.join(Manifest::FILE_NAME),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
 
     Ok(())
 }
@@ -0,0 +1,160 @@
+use ark_bn254::Bn254;
+use ark_groth16::{ProvingKey, VerifyingKey};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use std::fs;
+use std::path::Path;
+use vortex::curve::{Curve, SupportedCurve};
+use vortex::keys::check;
+use vortex::keys::convert::{self, KeyFormat, KeyKind};
+use vortex::keys::header::{self, Expected};
+use vortex::keys::manifest::Manifest;
+
+const USAGE: &str = "Usage:
+  keys convert --key <pk|vk> --from <format> --to <format> [--curve <curve>] <input> <output>
+  keys verify-manifest [<keys-dir>]
+  keys check [--curve <curve>] [--non-trivial] [<proving-key.bin> <verification-key.bin>]
+
+Formats: bin, bin-uncompressed, hex, base64, snarkjs (vk only), zkey (input only)";
+
+pub fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("convert") => convert_key(&args[1..]),
+        Some("verify-manifest") => verify_manifest(&args[1..]),
+        Some("check") => check_keys(&args[1..]),
+        _ => anyhow::bail!("{}", USAGE),
+    }
+}
+
+fn convert_key(args: &[String]) -> anyhow::Result<()> {
+    let kind = flag(args, "--key")?
+        .ok_or_else(|| anyhow::anyhow!("Missing --key\n{}", USAGE))?
+        .parse::<KeyKind>()?;
+    let from = flag(args, "--from")?
+        .ok_or_else(|| anyhow::anyhow!("Missing --from\n{}", USAGE))?
+        .parse::<KeyFormat>()?;
+    let to = flag(args, "--to")?
+        .ok_or_else(|| anyhow::anyhow!("Missing --to\n{}", USAGE))?
+        .parse::<KeyFormat>()?;
+    let curve = curve(args)?;
+
+    let paths = positional(args, &[]);
+    let [input, output] = paths[..] else {
+        anyhow::bail!("Expected an input and an output path\n{}", USAGE);
+    };
+
+    let data = fs::read(input).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", input, e))?;
+    let converted = match curve {
+        Curve::Bn254 => convert::convert::<Bn254>(&data, kind, from, to)?,
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => convert::convert::<ark_bls12_381::Bls12_381>(&data, kind, from, to)?,
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+    };
+    fs::write(output, converted)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", output, e))?;
+
+    println!("✅ Key written to {}", output);
+
+    Ok(())
+}
+
+fn verify_manifest(args: &[String]) -> anyhow::Result<()> {
+    let dir = Path::new(args.first().map(String::as_str).unwrap_or("keys"));
+    let manifest = Manifest::read(dir)?;
+    manifest.verify(dir)?;
+
+    println!(
+        "✅ {} matches {} v{} on {} ({} files)",
+        dir.display(),
+        manifest.circuit,
+        manifest.circuit_version,
+        manifest.curve.name(),
+        manifest.files.len()
+    );
+
+    Ok(())
+}
+
+fn check_keys(args: &[String]) -> anyhow::Result<()> {
+    let curve = curve(args)?;
+    let non_trivial = args.iter().any(|arg| arg == "--non-trivial");
+
+    let paths = positional(args, &["--non-trivial"]);
+    let (pk_path, vk_path) = match paths[..] {
+        [pk, vk] => (pk.as_str(), vk.as_str()),
+        [] => ("keys/proving_key.bin", "keys/verification_key.bin"),
+        _ => anyhow::bail!("Expected a proving and a verifying key path\n{}", USAGE),
+    };
+
+    match curve {
+        Curve::Bn254 => check_pair::<Bn254>(pk_path, vk_path, non_trivial)?,
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => check_pair::<ark_bls12_381::Bls12_381>(pk_path, vk_path, non_trivial)?,
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+    }
+
+    println!("✅ {} and {} form a key pair", pk_path, vk_path);
+
+    Ok(())
+}
+
+fn check_pair<E: SupportedCurve>(
+    pk_path: &str,
+    vk_path: &str,
+    non_trivial: bool,
+) -> anyhow::Result<()> {
+    let read =
+        |path: &str| fs::read(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path, e));
+    let expected = |kind| Expected {
+        kind,
+        curve: E::CURVE,
+        non_trivial: Some(non_trivial),
+    };
+
+    let pk: ProvingKey<E> = header::decode(&read(pk_path)?, &expected(KeyKind::Proving))
+        .map_err(|e| anyhow::anyhow!("{}: {}", pk_path, e))?;
+    let vk: VerifyingKey<E> = header::decode(&read(vk_path)?, &expected(KeyKind::Verifying))
+        .map_err(|e| anyhow::anyhow!("{}: {}", vk_path, e))?;
+
+    check::check_pair(&pk, &vk, non_trivial, &mut ChaCha20Rng::from_entropy())
+}
+
+/// Parses `--curve`, defaulting to BN254.
+fn curve(args: &[String]) -> anyhow::Result<Curve> {
+    let curve = match flag(args, "--curve")? {
+        Some(curve) => curve.parse::<Curve>()?,
+        None => Curve::default(),
+    };
+    curve.ensure_enabled()?;
+    Ok(curve)
+}
+
+/// Positional arguments: whatever is not a flag or the value of a flag.
+/// `switches` lists the flags that take no value.
+fn positional<'a>(args: &'a [String], switches: &[&str]) -> Vec<&'a String> {
+    args.iter()
+        .enumerate()
+        .filter(|(i, arg)| {
+            !arg.starts_with("--")
+                && (*i == 0
+                    || !args[i - 1].starts_with("--")
+                    || switches.contains(&args[i - 1].as_str()))
+        })
+        .map(|(_, arg)| arg)
+        .collect()
+}
+
+/// Returns the value following `name`, if the flag is present.
+fn flag<'a>(args: &'a [String], name: &str) -> anyhow::Result<Option<&'a str>> {
+    match args.iter().position(|arg| arg == name) {
+        Some(i) => args
+            .get(i + 1)
+            .map(|value| Some(value.as_str()))
+            .ok_or_else(|| anyhow::anyhow!("Missing value for {}", name)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,241 @@
+use ark_bn254::Bn254;
+use ark_groth16::ProvingKey;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use std::fs;
+use std::path::Path;
+use vortex::circuit::Circuit;
+use vortex::curve::{Curve, SupportedCurve};
+use vortex::keys::convert::KeyKind;
+use vortex::keys::header::{self, Expected, KeyHeader};
+use vortex::setup::{self, Contribution};
+
+const USAGE: &str = "Usage:
+  setup init [--curve <curve>] [--non-trivial] <ptau-file> <output-dir>
+  setup contribute [--curve <curve>] <input-dir> <output-dir>
+  setup verify-contribution [--curve <curve>] <before-dir> <after-dir>
+
+Directories hold proving_key.bin (as written by keygen or init) and, after
+the first contribution, transcript.bin. Keys from init must receive at least
+one contribution before use.";
+
+const TRANSCRIPT_FILE: &str = "transcript.bin";
+
+pub fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let curve = match args.iter().position(|arg| arg == "--curve") {
+        Some(i) => args
+            .get(i + 1)
+            .ok_or_else(|| anyhow::anyhow!("Missing value for --curve"))?
+            .parse::<Curve>()?,
+        None => Curve::default(),
+    };
+    curve.ensure_enabled()?;
+
+    // Pass --non-trivial to set up the non-trivial factorization circuit (init only)
+    let non_trivial = args.iter().any(|arg| arg == "--non-trivial");
+
+    let positional = args
+        .iter()
+        .enumerate()
+        .filter(|(i, arg)| !arg.starts_with("--") && (*i == 0 || args[i - 1] != "--curve"))
+        .map(|(_, arg)| arg.as_str())
+        .collect::<Vec<_>>();
+    let [command, input, output] = positional[..] else {
+        anyhow::bail!("{}", USAGE);
+    };
+    let (input, output) = (Path::new(input), Path::new(output));
+
+    match curve {
+        Curve::Bn254 => run::<Bn254>(command, input, output, non_trivial),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => run::<ark_bls12_381::Bls12_381>(command, input, output, non_trivial),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+    }
+}
+
+fn run<E: SupportedCurve>(
+    command: &str,
+    input: &Path,
+    output: &Path,
+    non_trivial: bool,
+) -> anyhow::Result<()> {
+    match command {
+        "init" => init::<E>(input, output, non_trivial),
+        "contribute" => contribute::<E>(input, output),
+        "verify-contribution" => verify_contribution::<E>(input, output),
+        _ => anyhow::bail!("{}", USAGE),
+    }
+}
+
+fn init<E: SupportedCurve>(
+    ptau_path: &Path,
+    output: &Path,
+    non_trivial: bool,
+) -> anyhow::Result<()> {
+    let circuit = if non_trivial {
+        Circuit::<E::ScalarField>::empty_non_trivial()
+    } else {
+        Circuit::<E::ScalarField>::empty()
+    };
+
+    println!(
+        "Deriving phase-2 keys on {} from {}...",
+        E::CURVE.name(),
+        ptau_path.display()
+    );
+    let ptau = fs::read(ptau_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", ptau_path.display(), e))?;
+    let pk = setup::from_ptau::<E, _>(circuit, &ptau)?;
+    write_keys(output, &pk, Some(header::circuit_id(non_trivial)))?;
+
+    println!("✅ Initial keys written to {}", output.display());
+    println!("  ⚠️  Run `setup contribute` at least once before using them");
+
+    Ok(())
+}
+
+fn contribute<E: SupportedCurve>(input: &Path, output: &Path) -> anyhow::Result<()> {
+    let (mut pk, circuit_id) = read_proving_key::<E>(input)?;
+    let mut transcript = read_transcript::<E>(input)?;
+    let previous_hash = match transcript.last() {
+        Some(contribution) => contribution.hash()?,
+        None => setup::initial_hash(&pk)?,
+    };
+
+    println!(
+        "Contributing to the phase-2 setup on {}...",
+        E::CURVE.name()
+    );
+    let contribution = setup::contribute(&mut pk, previous_hash, &mut ChaCha20Rng::from_entropy());
+    let hash = contribution.hash()?;
+    transcript.push(contribution);
+
+    write_keys(output, &pk, circuit_id)?;
+    let mut transcript_bytes = Vec::new();
+    transcript.serialize_compressed(&mut transcript_bytes)?;
+    fs::write(output.join(TRANSCRIPT_FILE), &transcript_bytes)?;
+
+    println!(
+        "✅ Contribution #{} written to {}",
+        transcript.len(),
+        output.display()
+    );
+    println!("  Transcript hash: {}", hex::encode(hash));
+
+    Ok(())
+}
+
+fn verify_contribution<E: SupportedCurve>(
+    before_dir: &Path,
+    after_dir: &Path,
+) -> anyhow::Result<()> {
+    let (before, _) = read_proving_key::<E>(before_dir)?;
+    let (after, _) = read_proving_key::<E>(after_dir)?;
+    let before_transcript = read_transcript::<E>(before_dir)?;
+    let after_transcript = read_transcript::<E>(after_dir)?;
+
+    let contribution = setup::next_contribution(&before, &before_transcript, &after_transcript)
+        .map_err(|e| {
+            anyhow::anyhow!(
+                "{} does not follow {}: {}",
+                after_dir.display(),
+                before_dir.display(),
+                e
+            )
+        })?;
+
+    if !setup::verify_contribution(
+        &before,
+        &after,
+        contribution,
+        &mut ChaCha20Rng::from_entropy(),
+    ) {
+        anyhow::bail!("Contribution #{} is invalid", after_transcript.len());
+    }
+
+    println!("✅ Contribution #{} is valid", after_transcript.len());
+    println!("  Transcript hash: {}", hex::encode(contribution.hash()?));
+
+    Ok(())
+}
+
+/// Writes `pk` and its verifying key in the keygen layout (bin + hex).
+///
+/// The `.bin` files get a key header when the circuit id is known; keys read
+/// from headerless files are written back without one.
+fn write_keys<E: SupportedCurve>(
+    dir: &Path,
+    pk: &ProvingKey<E>,
+    circuit_id: Option<[u8; 8]>,
+) -> anyhow::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let mut pk_bytes = Vec::new();
+    pk.serialize_compressed(&mut pk_bytes)?;
+    let mut vk_bytes = Vec::new();
+    pk.vk.serialize_compressed(&mut vk_bytes)?;
+
+    let bin = |kind, key_bytes: &[u8]| match circuit_id {
+        Some(circuit_id) => {
+            let header = KeyHeader {
+                kind,
+                curve: E::CURVE,
+                circuit_id,
+                compressed: true,
+            };
+            [&header.to_bytes()[..], key_bytes].concat()
+        }
+        None => key_bytes.to_vec(),
+    };
+
+    fs::write(
+        dir.join("proving_key.bin"),
+        bin(KeyKind::Proving, &pk_bytes),
+    )?;
+    fs::write(dir.join("proving_key.hex"), hex::encode(&pk_bytes))?;
+    fs::write(
+        dir.join("verification_key.bin"),
+        bin(KeyKind::Verifying, &vk_bytes),
+    )?;
+    fs::write(dir.join("verification_key.hex"), hex::encode(&vk_bytes))?;
+
+    Ok(())
+}
+
+/// Reads `proving_key.bin` and the circuit id from its header, if it has one.
+fn read_proving_key<E: SupportedCurve>(
+    dir: &Path,
+) -> anyhow::Result<(ProvingKey<E>, Option<[u8; 8]>)> {
+    let path = dir.join("proving_key.bin");
+    let bytes =
+        fs::read(&path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+
+    let pk = header::decode(
+        &bytes,
+        &Expected {
+            kind: KeyKind::Proving,
+            curve: E::CURVE,
+            non_trivial: None,
+        },
+    )
+    .map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+    let circuit_id = KeyHeader::parse(&bytes)?.map(|header| header.circuit_id);
+
+    Ok((pk, circuit_id))
+}
+
+fn read_transcript<E: SupportedCurve>(dir: &Path) -> anyhow::Result<Vec<Contribution<E>>> {
+    let path = dir.join(TRANSCRIPT_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let bytes =
+        fs::read(&path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+
+    Vec::<Contribution<E>>::deserialize_compressed(&bytes[..])
+        .map_err(|e| anyhow::anyhow!("Failed to deserialize transcript: {}", e))
+}
@@ -0,0 +1,122 @@
+use ark_groth16::Groth16;
+use ark_serialize::CanonicalSerialize;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use serde_json::json;
+use std::fs;
+use std::path::{Path, PathBuf};
+use vortex::api::{self, ProofOutput};
+use vortex::circuit::Circuit;
+use vortex::curve::{Curve, SupportedCurve};
+
+/// Bump whenever the circuit, the serialization format or the seeds change.
+const TEST_VECTORS_VERSION: u32 = 3;
+
+const KEYGEN_SEED: [u8; 32] = [0u8; 32];
+const PROOF_SEED: [u8; 32] = [1u8; 32];
+
+/// (a, b) pairs proved by the vectors; `c` is always `a * b`.
+const CASES: [(u64, u64); 3] = [(5, 6), (3, 11), (7919, 104729)];
+
+pub fn main() -> anyhow::Result<()> {
+    let out_dir = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("testvectors"));
+
+    println!(
+        "Generating test vectors v{} in {}...",
+        TEST_VECTORS_VERSION,
+        out_dir.display()
+    );
+    fs::create_dir_all(&out_dir)?;
+
+    // Vectors for BLS12-381 need the `bls12-381` feature
+    for curve in [Curve::Bn254, Curve::Bls12_381] {
+        if curve.ensure_enabled().is_err() {
+            println!("    (skipping {}: not enabled)", curve.name());
+            continue;
+        }
+        for non_trivial in [false, true] {
+            match curve {
+                Curve::Bn254 => generate::<ark_bn254::Bn254>(non_trivial, &out_dir)?,
+                #[cfg(feature = "bls12-381")]
+                Curve::Bls12_381 => generate::<ark_bls12_381::Bls12_381>(non_trivial, &out_dir)?,
+                #[cfg(not(feature = "bls12-381"))]
+                Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+            }
+        }
+    }
+
+    println!("✅ Test vectors generated successfully!");
+
+    Ok(())
+}
+
+fn generate<E: SupportedCurve>(non_trivial: bool, out_dir: &Path) -> anyhow::Result<()> {
+    let circuit = if non_trivial {
+        Circuit::<E::ScalarField>::empty_non_trivial()
+    } else {
+        Circuit::<E::ScalarField>::empty()
+    };
+
+    let mut rng = ChaCha20Rng::from_seed(KEYGEN_SEED);
+    let pk = Groth16::<E>::generate_random_parameters_with_reduction(circuit, &mut rng)?;
+
+    let mut pk_bytes = Vec::new();
+    pk.serialize_compressed(&mut pk_bytes)?;
+    let mut vk_bytes = Vec::new();
+    pk.vk.serialize_compressed(&mut vk_bytes)?;
+    let (pk_hex, vk_hex) = (hex::encode(pk_bytes), hex::encode(vk_bytes));
+
+    let mut cases = Vec::new();
+    for (i, (a, b)) in CASES.iter().enumerate() {
+        let c = Circuit::compute_public(E::ScalarField::from(*a), E::ScalarField::from(*b));
+        let input = json!({
+            "c": c.to_string(),
+            "a": a.to_string(),
+            "b": b.to_string(),
+            "curve": E::CURVE,
+            "nonTrivial": non_trivial,
+        });
+
+        let proof_json = api::prove_deterministic(
+            &input.to_string(),
+            &pk_hex,
+            &hex::encode(PROOF_SEED),
+            |_| {},
+        )?;
+        if !api::verify(&proof_json, &vk_hex)? {
+            anyhow::bail!("Test vector proof {} does not verify", i);
+        }
+
+        let proof: ProofOutput = serde_json::from_str(&proof_json)?;
+        cases.push(json!({ "input": input, "proof": proof }));
+    }
+
+    let vectors = json!({
+        "version": TEST_VECTORS_VERSION,
+        "circuit": Circuit::NAME,
+        "circuitVersion": Circuit::VERSION,
+        "curve": E::CURVE,
+        "nonTrivial": non_trivial,
+        "keygenSeed": hex::encode(KEYGEN_SEED),
+        "proofSeed": hex::encode(PROOF_SEED),
+        "provingKeyHex": pk_hex,
+        "verifyingKeyHex": vk_hex,
+        "cases": cases,
+    });
+
+    let mut name = E::CURVE.name().to_string();
+    if non_trivial {
+        name.push_str("-non-trivial");
+    }
+    let name = format!("{}.json", name);
+    fs::write(
+        out_dir.join(&name),
+        serde_json::to_string_pretty(&vectors)? + "\n",
+    )?;
+    println!("    - {}", name);
+
+    Ok(())
+}
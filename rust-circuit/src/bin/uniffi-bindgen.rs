@@ -0,0 +1,4 @@
+// Generates the Swift and Kotlin bindings, see MOBILE_README.md
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
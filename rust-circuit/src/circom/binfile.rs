@@ -0,0 +1,129 @@
+use super::point_from_xy;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+use std::collections::HashMap;
+
+/// Splits an iden3 binary container into its sections, keyed by type.
+pub(super) fn read_sections<'a>(
+    bytes: &'a [u8],
+    magic: &[u8; 4],
+) -> anyhow::Result<HashMap<u32, &'a [u8]>> {
+    let mut reader = Reader::new(bytes);
+    if reader.bytes(4)? != magic {
+        anyhow::bail!("Not a {} file", String::from_utf8_lossy(magic));
+    }
+    let _version = reader.u32()?;
+    let num_sections = reader.u32()?;
+
+    let mut sections = HashMap::new();
+    for _ in 0..num_sections {
+        let section_type = reader.u32()?;
+        let size = usize::try_from(reader.u64()?)?;
+        sections.insert(section_type, reader.bytes(size)?);
+    }
+
+    Ok(sections)
+}
+
+pub(super) fn section<'a>(
+    sections: &HashMap<u32, &'a [u8]>,
+    section_type: u32,
+) -> anyhow::Result<&'a [u8]> {
+    sections
+        .get(&section_type)
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("File is missing section {}", section_type))
+}
+
+pub(super) fn read_points<G: AffineRepr>(
+    sections: &HashMap<u32, &[u8]>,
+    section_type: u32,
+    count: usize,
+) -> anyhow::Result<Vec<G>> {
+    let mut reader = Reader::new(section(sections, section_type)?);
+    (0..count)
+        .map(|i| {
+            reader.point().map_err(|e| {
+                anyhow::anyhow!("Invalid point {} in section {}: {}", i, section_type, e)
+            })
+        })
+        .collect()
+}
+
+pub(super) fn is_modulus<F: PrimeField>(le_bytes: &[u8]) -> bool {
+    let modulus = F::MODULUS.to_bytes_le();
+    le_bytes.len() >= modulus.len()
+        && le_bytes[..modulus.len()] == modulus[..]
+        && le_bytes[modulus.len()..].iter().all(|byte| *byte == 0)
+}
+
+pub(super) struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub(super) fn bytes(&mut self, len: usize) -> anyhow::Result<&'a [u8]> {
+        if self.bytes.len() < len {
+            anyhow::bail!("Unexpected end of file");
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    pub(super) fn u32(&mut self) -> anyhow::Result<u32> {
+        Ok(u32::from_le_bytes(self.bytes(4)?.try_into()?))
+    }
+
+    pub(super) fn u64(&mut self) -> anyhow::Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes(8)?.try_into()?))
+    }
+
+    /// Reads an `n8`-byte little-endian field element in standard form,
+    /// rejecting values that are not reduced.
+    pub(super) fn field<F: PrimeField>(&mut self, n8: usize) -> anyhow::Result<F> {
+        let raw = self.bytes(n8)?;
+        let value = F::from_le_bytes_mod_order(raw);
+        if super::field_bytes(&value.into_bigint().to_bytes_le(), n8) != raw {
+            anyhow::bail!("Field element is not reduced");
+        }
+        Ok(value)
+    }
+
+    /// Reads a point as little-endian Montgomery-form `x, y` coordinates;
+    /// all-zero coordinates encode the point at infinity.
+    pub(super) fn point<G: AffineRepr>(&mut self) -> anyhow::Result<G> {
+        let x = self.coordinate::<G::BaseField>()?;
+        let y = self.coordinate::<G::BaseField>()?;
+        if x.is_zero() && y.is_zero() {
+            return Ok(G::zero());
+        }
+
+        point_from_xy(x, y)
+    }
+
+    fn coordinate<F: Field>(&mut self) -> anyhow::Result<F> {
+        let elements = (0..F::extension_degree())
+            .map(|_| self.montgomery::<F::BasePrimeField>())
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        F::from_base_prime_field_elems(elements)
+            .ok_or_else(|| anyhow::anyhow!("Invalid field element"))
+    }
+
+    /// Reads `a·R mod p`, with `R = 2^(8·n8)`, and returns `a`.
+    fn montgomery<F: PrimeField>(&mut self) -> anyhow::Result<F> {
+        let n8 = super::field_size::<F>();
+        let raw = self.bytes(n8)?;
+        let r_inv = F::from(2u64)
+            .pow([8 * n8 as u64])
+            .inverse()
+            .expect("2 is invertible");
+
+        Ok(F::from_le_bytes_mod_order(raw) * r_inv)
+    }
+}
@@ -0,0 +1,257 @@
+mod binfile;
+pub mod ptau;
+pub mod snarkjs;
+pub mod zkey;
+
+use crate::witness::Witness;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisMode,
+};
+use ark_serialize::CanonicalSerialize;
+use binfile::{is_modulus, read_sections, section, Reader};
+use std::io::Write;
+
+const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+const R1CS_VERSION: u32 = 1;
+const R1CS_HEADER_SECTION: u32 = 1;
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+const R1CS_WIRE_TO_LABEL_SECTION: u32 = 3;
+
+const WTNS_MAGIC: &[u8; 4] = b"wtns";
+const WTNS_VERSION: u32 = 2;
+const WTNS_HEADER_SECTION: u32 = 1;
+const WTNS_VALUES_SECTION: u32 = 2;
+
+/// Writes the constraint system of `circuit` in the circom `.r1cs` binary
+/// format read by snarkjs and circomspect.
+///
+/// Wires follow the arkworks variable order: wire 0 is the constant `1`, then
+/// the public inputs, then every witness variable. arkworks does not tell
+/// private inputs apart from intermediate witnesses, so the header reports
+/// zero private inputs.
+///
+/// # Errors
+/// Returns error if constraint generation or writing fails.
+pub fn write_r1cs<F, C, W>(circuit: C, writer: &mut W) -> anyhow::Result<()>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+    W: Write,
+{
+    let matrices = synthesize_matrices(circuit)?;
+    let n8 = field_size::<F>();
+    let num_wires = matrices.num_instance_variables + matrices.num_witness_variables;
+
+    let mut header = Vec::new();
+    header.extend((n8 as u32).to_le_bytes());
+    header.extend(field_bytes(&F::MODULUS.to_bytes_le(), n8));
+    header.extend((num_wires as u32).to_le_bytes());
+    header.extend(0u32.to_le_bytes()); // public outputs
+    header.extend(((matrices.num_instance_variables - 1) as u32).to_le_bytes());
+    header.extend(0u32.to_le_bytes()); // private inputs
+    header.extend((num_wires as u64).to_le_bytes()); // labels
+    header.extend((matrices.num_constraints as u32).to_le_bytes());
+
+    let mut constraints = Vec::new();
+    for i in 0..matrices.num_constraints {
+        for row in [&matrices.a[i], &matrices.b[i], &matrices.c[i]] {
+            constraints.extend((row.len() as u32).to_le_bytes());
+            for (coeff, index) in row {
+                constraints.extend((*index as u32).to_le_bytes());
+                constraints.extend(field_bytes(&coeff.into_bigint().to_bytes_le(), n8));
+            }
+        }
+    }
+
+    let mut wire_to_label = Vec::new();
+    for wire in 0..num_wires as u64 {
+        wire_to_label.extend(wire.to_le_bytes());
+    }
+
+    write_sections(
+        writer,
+        R1CS_MAGIC,
+        R1CS_VERSION,
+        &[
+            (R1CS_HEADER_SECTION, header),
+            (R1CS_CONSTRAINTS_SECTION, constraints),
+            (R1CS_WIRE_TO_LABEL_SECTION, wire_to_label),
+        ],
+    )
+}
+
+/// Parses a circom `.r1cs` file into constraint matrices, the inverse of
+/// [`write_r1cs`].
+///
+/// Wire 0 and the public outputs and inputs become instance variables, all
+/// other wires witness variables.
+///
+/// # Errors
+/// Returns error if the file is not an r1cs file over `F`, is truncated, or
+/// refers to a wire that does not exist.
+pub fn read_r1cs<F: PrimeField>(bytes: &[u8]) -> anyhow::Result<ConstraintMatrices<F>> {
+    let sections = read_sections(bytes, R1CS_MAGIC)?;
+
+    let mut header = Reader::new(section(&sections, R1CS_HEADER_SECTION)?);
+    let n8 = header.u32()? as usize;
+    if !is_modulus::<F>(header.bytes(n8)?) {
+        anyhow::bail!("r1cs file is not over the expected field");
+    }
+    let num_wires = header.u32()? as usize;
+    let num_outputs = header.u32()? as usize;
+    let num_inputs = header.u32()? as usize;
+    let _num_private_inputs = header.u32()?;
+    let _num_labels = header.u64()?;
+    let num_constraints = header.u32()? as usize;
+
+    let num_instance_variables = 1 + num_outputs + num_inputs;
+    let num_witness_variables = num_wires
+        .checked_sub(num_instance_variables)
+        .ok_or_else(|| anyhow::anyhow!("r1cs file has fewer wires than public signals"))?;
+
+    let mut reader = Reader::new(section(&sections, R1CS_CONSTRAINTS_SECTION)?);
+    let mut matrices: [Vec<Vec<(F, usize)>>; 3] = Default::default();
+    for _ in 0..num_constraints {
+        for matrix in &mut matrices {
+            let len = reader.u32()?;
+            let row = (0..len)
+                .map(|_| {
+                    let wire = reader.u32()? as usize;
+                    if wire >= num_wires {
+                        anyhow::bail!("Constraint refers to wire {} of {}", wire, num_wires);
+                    }
+                    Ok((reader.field::<F>(n8)?, wire))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            matrix.push(row);
+        }
+    }
+
+    let [a, b, c] = matrices;
+    let non_zero = |matrix: &[Vec<(F, usize)>]| matrix.iter().map(Vec::len).sum();
+    Ok(ConstraintMatrices {
+        num_instance_variables,
+        num_witness_variables,
+        num_constraints,
+        a_num_non_zero: non_zero(&a),
+        b_num_non_zero: non_zero(&b),
+        c_num_non_zero: non_zero(&c),
+        a,
+        b,
+        c,
+    })
+}
+
+/// Writes `witness` in the circom `.wtns` binary format, with wires in the
+/// same order as [`write_r1cs`], so snarkjs can prove from it.
+///
+/// # Errors
+/// Returns error if writing fails.
+pub fn write_wtns<F, W>(witness: &Witness<F>, writer: &mut W) -> anyhow::Result<()>
+where
+    F: PrimeField,
+    W: Write,
+{
+    let n8 = field_size::<F>();
+    let num_wires = witness.instance.len() + witness.witness.len();
+
+    let mut header = Vec::new();
+    header.extend((n8 as u32).to_le_bytes());
+    header.extend(field_bytes(&F::MODULUS.to_bytes_le(), n8));
+    header.extend((num_wires as u32).to_le_bytes());
+
+    let mut values = Vec::with_capacity(num_wires * n8);
+    for value in witness.instance.iter().chain(&witness.witness) {
+        values.extend(field_bytes(&value.into_bigint().to_bytes_le(), n8));
+    }
+
+    write_sections(
+        writer,
+        WTNS_MAGIC,
+        WTNS_VERSION,
+        &[(WTNS_HEADER_SECTION, header), (WTNS_VALUES_SECTION, values)],
+    )
+}
+
+/// Parses a circom `.wtns` file into its wire values, wire 0 first; the
+/// inverse of [`write_wtns`].
+///
+/// # Errors
+/// Returns error if the file is not a wtns file over `F` or is truncated.
+pub fn read_wtns<F: PrimeField>(bytes: &[u8]) -> anyhow::Result<Vec<F>> {
+    let sections = read_sections(bytes, WTNS_MAGIC)?;
+
+    let mut header = Reader::new(section(&sections, WTNS_HEADER_SECTION)?);
+    let n8 = header.u32()? as usize;
+    if !is_modulus::<F>(header.bytes(n8)?) {
+        anyhow::bail!("wtns file is not over the expected field");
+    }
+    let num_wires = header.u32()? as usize;
+
+    let mut values = Reader::new(section(&sections, WTNS_VALUES_SECTION)?);
+    (0..num_wires).map(|_| values.field::<F>(n8)).collect()
+}
+
+pub(crate) fn synthesize_matrices<F, C>(circuit: C) -> anyhow::Result<ConstraintMatrices<F>>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+{
+    let cs = ConstraintSystem::<F>::new_ref();
+    cs.set_mode(SynthesisMode::Setup);
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    circuit
+        .generate_constraints(cs.clone())
+        .map_err(|e| anyhow::anyhow!("Failed to generate constraints: {}", e))?;
+    cs.finalize();
+
+    cs.to_matrices()
+        .ok_or_else(|| anyhow::anyhow!("Failed to build constraint matrices"))
+}
+
+/// Writes an iden3 binary container: magic, version, then `(type, size, data)` sections.
+fn write_sections<W: Write>(
+    writer: &mut W,
+    magic: &[u8; 4],
+    version: u32,
+    sections: &[(u32, Vec<u8>)],
+) -> anyhow::Result<()> {
+    let mut bytes = Vec::new();
+    bytes.extend(magic);
+    bytes.extend(version.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (section_type, data) in sections {
+        bytes.extend(section_type.to_le_bytes());
+        bytes.extend((data.len() as u64).to_le_bytes());
+        bytes.extend(data);
+    }
+
+    writer
+        .write_all(&bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", String::from_utf8_lossy(magic), e))
+}
+
+/// Bytes per field element: the modulus size rounded up to 64-bit limbs.
+fn field_size<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize).div_ceil(64) * 8
+}
+
+/// Little-endian bytes padded or truncated to `n8`.
+fn field_bytes(le_bytes: &[u8], n8: usize) -> Vec<u8> {
+    let mut bytes = le_bytes.to_vec();
+    bytes.resize(n8, 0);
+    bytes
+}
+
+/// Builds an affine point from its coordinates, checking that it is on the
+/// curve and in the prime-order subgroup.
+fn point_from_xy<G: AffineRepr>(x: G::BaseField, y: G::BaseField) -> anyhow::Result<G> {
+    // Round-trip through the uncompressed encoding so arkworks runs its checks
+    let mut bytes = Vec::new();
+    x.serialize_uncompressed(&mut bytes)?;
+    y.serialize_uncompressed(&mut bytes)?;
+
+    G::deserialize_uncompressed(&bytes[..]).map_err(|e| anyhow::anyhow!("{}", e))
+}
@@ -0,0 +1,70 @@
+use super::binfile::{is_modulus, read_points, read_sections, section, Reader};
+use crate::curve::SupportedCurve;
+use ark_ec::pairing::Pairing;
+use ark_ff::Field;
+
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+
+const HEADER_SECTION: u32 = 1;
+const TAU_G1_SECTION: u32 = 2;
+const TAU_G2_SECTION: u32 = 3;
+const ALPHA_TAU_G1_SECTION: u32 = 4;
+const BETA_TAU_G1_SECTION: u32 = 5;
+const BETA_G2_SECTION: u32 = 6;
+
+/// Phase-1 powers of tau, truncated to what a domain of `n` points needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PowersOfTau<E: Pairing> {
+    /// `τ^i·G1` for `i < 2n - 1`.
+    pub tau_g1: Vec<E::G1Affine>,
+    /// `τ^i·G2` for `i < n`.
+    pub tau_g2: Vec<E::G2Affine>,
+    /// `α·τ^i·G1` for `i < n`.
+    pub alpha_tau_g1: Vec<E::G1Affine>,
+    /// `β·τ^i·G1` for `i < n`.
+    pub beta_tau_g1: Vec<E::G1Affine>,
+    pub beta_g2: E::G2Affine,
+}
+
+/// Parses a snarkjs / perpetual-powers-of-tau `.ptau` file, keeping only
+/// the powers needed for an evaluation domain of `domain_size` points.
+///
+/// Both raw and `prepare phase2` outputs are accepted; the Lagrange
+/// sections of the latter are ignored.
+///
+/// # Errors
+/// Returns error if the file is not a ptau file for curve `E`, has fewer
+/// than `domain_size` powers, or contains an invalid point.
+pub fn read_ptau<E: SupportedCurve>(
+    bytes: &[u8],
+    domain_size: usize,
+) -> anyhow::Result<PowersOfTau<E>> {
+    let sections = read_sections(bytes, PTAU_MAGIC)?;
+
+    let mut header = Reader::new(section(&sections, HEADER_SECTION)?);
+    let n8 = header.u32()? as usize;
+    let q = header.bytes(n8)?;
+    if !is_modulus::<<E::BaseField as Field>::BasePrimeField>(q) {
+        anyhow::bail!("ptau file is not for curve {}", E::CURVE.name());
+    }
+    let power = header.u32()?;
+
+    let max_size = 1usize
+        .checked_shl(power)
+        .ok_or_else(|| anyhow::anyhow!("Invalid ptau power {}", power))?;
+    if domain_size > max_size {
+        anyhow::bail!(
+            "ptau file supports domains of up to 2^{} points, circuit needs {}",
+            power,
+            domain_size
+        );
+    }
+
+    Ok(PowersOfTau {
+        tau_g1: read_points(&sections, TAU_G1_SECTION, 2 * domain_size - 1)?,
+        tau_g2: read_points(&sections, TAU_G2_SECTION, domain_size)?,
+        alpha_tau_g1: read_points(&sections, ALPHA_TAU_G1_SECTION, domain_size)?,
+        beta_tau_g1: read_points(&sections, BETA_TAU_G1_SECTION, domain_size)?,
+        beta_g2: read_points(&sections, BETA_G2_SECTION, 1)?[0],
+    })
+}
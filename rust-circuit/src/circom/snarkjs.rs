@@ -0,0 +1,143 @@
+use super::point_from_xy;
+use crate::curve::{Curve, SupportedCurve};
+use crate::field::{self, Encoding};
+use ark_ec::{AdditiveGroup, AffineRepr};
+use ark_ff::Field;
+use ark_groth16::VerifyingKey;
+use serde_json::{json, Value};
+
+/// Converts `vk` into a snarkjs `verification_key.json` document.
+///
+/// Coordinates are decimal strings in projective form (`[x, y, "1"]`), and
+/// G2 coordinates are `[c0, c1]` pairs, as written by `snarkjs zkey export
+/// verificationkey`.
+pub fn verifying_key_to_json<E: SupportedCurve>(vk: &VerifyingKey<E>) -> Value {
+    json!({
+        "protocol": "groth16",
+        "curve": curve_name(E::CURVE),
+        "nPublic": vk.gamma_abc_g1.len() - 1,
+        "vk_alpha_1": point_to_json(&vk.alpha_g1),
+        "vk_beta_2": point_to_json(&vk.beta_g2),
+        "vk_gamma_2": point_to_json(&vk.gamma_g2),
+        "vk_delta_2": point_to_json(&vk.delta_g2),
+        "IC": vk.gamma_abc_g1.iter().map(point_to_json).collect::<Vec<_>>(),
+    })
+}
+
+/// Parses a snarkjs `verification_key.json` document, checking every point.
+///
+/// # Errors
+/// Returns error if the document is malformed, targets another curve or
+/// protocol, or contains a point that is not in the expected subgroup.
+pub fn verifying_key_from_json<E: SupportedCurve>(json: &Value) -> anyhow::Result<VerifyingKey<E>> {
+    if json["protocol"] != "groth16" {
+        anyhow::bail!("Unsupported snarkjs protocol {}", json["protocol"]);
+    }
+    if json["curve"] != curve_name(E::CURVE) {
+        anyhow::bail!(
+            "Verification key is for curve {}, expected {}",
+            json["curve"],
+            curve_name(E::CURVE)
+        );
+    }
+
+    let gamma_abc_g1 = json["IC"]
+        .as_array()
+        .ok_or_else(|| anyhow::anyhow!("Missing IC"))?
+        .iter()
+        .map(|point| point_from_json(point, "IC"))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    if let Some(num_public) = json["nPublic"].as_u64() {
+        if gamma_abc_g1.len() as u64 != num_public + 1 {
+            anyhow::bail!(
+                "Verification key declares {} public inputs but has {} IC points",
+                num_public,
+                gamma_abc_g1.len()
+            );
+        }
+    }
+
+    Ok(VerifyingKey {
+        alpha_g1: point_from_json(&json["vk_alpha_1"], "vk_alpha_1")?,
+        beta_g2: point_from_json(&json["vk_beta_2"], "vk_beta_2")?,
+        gamma_g2: point_from_json(&json["vk_gamma_2"], "vk_gamma_2")?,
+        delta_g2: point_from_json(&json["vk_delta_2"], "vk_delta_2")?,
+        gamma_abc_g1,
+    })
+}
+
+/// Curve identifier used by snarkjs and ffjavascript.
+pub fn curve_name(curve: Curve) -> &'static str {
+    match curve {
+        Curve::Bn254 => "bn128",
+        Curve::Bls12_381 => "bls12381",
+    }
+}
+
+fn point_to_json<G: AffineRepr>(point: &G) -> Value {
+    match point.xy() {
+        Some((x, y)) => json!([
+            coordinate_to_json(&x),
+            coordinate_to_json(&y),
+            coordinate_to_json(&G::BaseField::ONE)
+        ]),
+        None => json!([
+            coordinate_to_json(&G::BaseField::ZERO),
+            coordinate_to_json(&G::BaseField::ONE),
+            coordinate_to_json(&G::BaseField::ZERO)
+        ]),
+    }
+}
+
+fn coordinate_to_json<F: Field>(value: &F) -> Value {
+    let mut elements = value
+        .to_base_prime_field_elements()
+        .map(|element| Value::String(element.to_string()))
+        .collect::<Vec<_>>();
+
+    if elements.len() == 1 {
+        elements.remove(0)
+    } else {
+        Value::Array(elements)
+    }
+}
+
+fn point_from_json<G: AffineRepr>(json: &Value, name: &str) -> anyhow::Result<G> {
+    let coordinates = json
+        .as_array()
+        .filter(|coordinates| coordinates.len() == 3)
+        .ok_or_else(|| anyhow::anyhow!("{} must be an [x, y, z] array", name))?;
+
+    let x = coordinate_from_json::<G::BaseField>(&coordinates[0], name)?;
+    let y = coordinate_from_json::<G::BaseField>(&coordinates[1], name)?;
+    let z = coordinate_from_json::<G::BaseField>(&coordinates[2], name)?;
+
+    if z == G::BaseField::ZERO {
+        return Ok(G::zero());
+    }
+    let z_inv = z.inverse().expect("z is non-zero");
+
+    point_from_xy(x * z_inv, y * z_inv)
+        .map_err(|e| anyhow::anyhow!("Invalid point {}: {}", name, e))
+}
+
+fn coordinate_from_json<F: Field>(json: &Value, name: &str) -> anyhow::Result<F> {
+    let elements = match json {
+        Value::Array(elements) => elements.iter().collect::<Vec<_>>(),
+        element => vec![element],
+    };
+
+    let elements = elements
+        .into_iter()
+        .map(|element| {
+            element
+                .as_str()
+                .ok_or_else(|| anyhow::anyhow!("{} coordinates must be decimal strings", name))
+                .and_then(|element| field::parse(element, Encoding::Decimal))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    F::from_base_prime_field_elems(elements)
+        .ok_or_else(|| anyhow::anyhow!("{} has a coordinate of the wrong degree", name))
+}
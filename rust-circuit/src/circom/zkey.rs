@@ -0,0 +1,120 @@
+use super::binfile::{is_modulus, read_points, read_sections, section, Reader};
+use crate::curve::SupportedCurve;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::Field;
+use ark_groth16::{ProvingKey, VerifyingKey};
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+
+const ZKEY_MAGIC: &[u8; 4] = b"zkey";
+const GROTH16_PROTOCOL: u32 = 1;
+
+const HEADER_SECTION: u32 = 1;
+const GROTH16_HEADER_SECTION: u32 = 2;
+const IC_SECTION: u32 = 3;
+const A_SECTION: u32 = 5;
+const B1_SECTION: u32 = 6;
+const B2_SECTION: u32 = 7;
+const C_SECTION: u32 = 8;
+const H_SECTION: u32 = 9;
+
+/// Parses a snarkjs Groth16 `.zkey` file into an arkworks proving key.
+///
+/// The key must come from a setup over this crate's constraint system (see
+/// [`super::write_r1cs`]); it is then interchangeable with one from the
+/// `keygen` binary and works with every prover in this crate. snarkjs
+/// commits to `h(x)` in a Lagrange basis over the odd points of a domain of
+/// twice the size, so the H section is converted into the monomial basis
+/// arkworks expects.
+///
+/// # Errors
+/// Returns error if the file is not a Groth16 zkey for curve `E`, is
+/// truncated, or contains a point that is not in the expected subgroup.
+pub fn read_zkey<E: SupportedCurve>(bytes: &[u8]) -> anyhow::Result<ProvingKey<E>> {
+    let sections = read_sections(bytes, ZKEY_MAGIC)?;
+
+    let mut header = Reader::new(section(&sections, HEADER_SECTION)?);
+    let protocol = header.u32()?;
+    if protocol != GROTH16_PROTOCOL {
+        anyhow::bail!("Unsupported zkey protocol {} (expected Groth16)", protocol);
+    }
+
+    let mut header = Reader::new(section(&sections, GROTH16_HEADER_SECTION)?);
+    let n8q = header.u32()? as usize;
+    let q = header.bytes(n8q)?;
+    let n8r = header.u32()? as usize;
+    let r = header.bytes(n8r)?;
+    if !is_modulus::<<E::BaseField as Field>::BasePrimeField>(q) || !is_modulus::<E::ScalarField>(r)
+    {
+        anyhow::bail!("zkey is not for curve {}", E::CURVE.name());
+    }
+
+    let num_vars = header.u32()? as usize;
+    let num_public = header.u32()? as usize;
+    let domain_size = header.u32()? as usize;
+
+    let alpha_g1 = header.point::<E::G1Affine>()?;
+    let beta_g1 = header.point::<E::G1Affine>()?;
+    let beta_g2 = header.point::<E::G2Affine>()?;
+    let gamma_g2 = header.point::<E::G2Affine>()?;
+    let delta_g1 = header.point::<E::G1Affine>()?;
+    let delta_g2 = header.point::<E::G2Affine>()?;
+
+    let num_private = num_vars
+        .checked_sub(num_public + 1)
+        .ok_or_else(|| anyhow::anyhow!("zkey has fewer variables than public inputs"))?;
+
+    let h_query = lagrange_to_monomial_h::<E>(&read_points::<E::G1Affine>(
+        &sections,
+        H_SECTION,
+        domain_size,
+    )?)?;
+
+    Ok(ProvingKey {
+        vk: VerifyingKey {
+            alpha_g1,
+            beta_g2,
+            gamma_g2,
+            delta_g2,
+            gamma_abc_g1: read_points(&sections, IC_SECTION, num_public + 1)?,
+        },
+        beta_g1,
+        delta_g1,
+        a_query: read_points(&sections, A_SECTION, num_vars)?,
+        b_g1_query: read_points(&sections, B1_SECTION, num_vars)?,
+        b_g2_query: read_points(&sections, B2_SECTION, num_vars)?,
+        h_query,
+        l_query: read_points(&sections, C_SECTION, num_private)?,
+    })
+}
+
+/// Converts snarkjs H points `L_{2i+1}(τ)/δ`, Lagrange polynomials over the
+/// `2n`-th roots of unity `ω^k`, into arkworks' `τ^j·Z(τ)/δ` for `j < n - 1`.
+///
+/// `x^j·Z(x)` has degree below `2n` and vanishes on the even points, while
+/// `Z(ω^(2i+1)) = -2`, so `τ^j·Z(τ)/δ = -2·ω^j·Σ_i (ω^2)^(ij)·H_i`: an FFT
+/// over the size-`n` domain followed by a scaling.
+fn lagrange_to_monomial_h<E: SupportedCurve>(
+    points: &[E::G1Affine],
+) -> anyhow::Result<Vec<E::G1Affine>> {
+    let n = points.len();
+    let domain = Radix2EvaluationDomain::<E::ScalarField>::new(n)
+        .filter(|domain| domain.size() == n)
+        .ok_or_else(|| anyhow::anyhow!("zkey domain size {} is not a power of two", n))?;
+    let omega = Radix2EvaluationDomain::<E::ScalarField>::new(2 * n)
+        .ok_or_else(|| anyhow::anyhow!("zkey domain size {} is too large", n))?
+        .group_gen();
+
+    let mut h = points
+        .iter()
+        .map(|point| point.into_group())
+        .collect::<Vec<_>>();
+    domain.fft_in_place(&mut h);
+    Radix2EvaluationDomain::distribute_powers_and_mul_by_const(
+        &mut h,
+        omega,
+        -E::ScalarField::from(2u64),
+    );
+    h.truncate(n.saturating_sub(1));
+
+    Ok(E::G1::normalize_batch(&h))
+}
@@ -0,0 +1,137 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::comparison;
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Years are range checked to this many bits, which covers any calendar year.
+pub const YEAR_BITS: usize = 16;
+
+/// Proves `birth_year <= current_year - threshold` without revealing the birth year.
+///
+/// The birth year is bound to `commitment = Poseidon(birth_year, salt)`,
+/// which an attester (e.g. a KYC provider) signs or publishes; without it
+/// the prover could claim any birth year.
+#[derive(Debug, Clone)]
+pub struct AgeCircuit<F: PrimeField = Fr> {
+    // Public inputs
+    pub current_year: F,
+    pub threshold: F,
+    pub commitment: F,
+
+    // Private inputs
+    pub birth_year: F,
+    pub salt: F,
+}
+
+metadata! {
+    impl AgeCircuit {
+        name: "age-over-threshold",
+        version: "1.0.0",
+        public_inputs: ["current_year", "threshold", "commitment"],
+    }
+}
+
+impl<F: PrimeField + Absorb> AgeCircuit<F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            current_year: F::ZERO,
+            threshold: F::ZERO,
+            commitment: F::ZERO,
+            birth_year: F::ZERO,
+            salt: F::ZERO,
+        }
+    }
+
+    /// Creates a circuit, computing the commitment to `birth_year`.
+    ///
+    /// # Errors
+    /// Returns error if a year does not fit in [`YEAR_BITS`] bits or
+    /// `birth_year` is after `current_year - threshold`.
+    pub fn new(
+        current_year: u64,
+        threshold: u64,
+        birth_year: u64,
+        salt: F,
+    ) -> anyhow::Result<Self> {
+        for (name, year) in [
+            ("current_year", current_year),
+            ("threshold", threshold),
+            ("birth_year", birth_year),
+        ] {
+            if year >> YEAR_BITS != 0 {
+                anyhow::bail!("{} must be below 2^{}", name, YEAR_BITS);
+            }
+        }
+        if birth_year + threshold > current_year {
+            anyhow::bail!(
+                "Birth year {} is less than {} years before {}",
+                birth_year,
+                threshold,
+                current_year
+            );
+        }
+
+        let birth_year = F::from(birth_year);
+        Ok(Self {
+            current_year: F::from(current_year),
+            threshold: F::from(threshold),
+            commitment: Self::commit(birth_year, salt)?,
+            birth_year,
+            salt,
+        })
+    }
+
+    /// The commitment an attester issues for `birth_year`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commit(birth_year: F, salt: F) -> anyhow::Result<F> {
+        poseidon::hash(&[birth_year, salt])
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.current_year, self.threshold, self.commitment]
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField] AgeCircuit<F> { birth_year, salt }
+}
+
+impl<F: PrimeField + Absorb> ConstraintSynthesizer<F> for AgeCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let current_year = FpVar::new_input(ns!(cs, "current_year"), || Ok(self.current_year))?;
+        let threshold = FpVar::new_input(ns!(cs, "threshold"), || Ok(self.threshold))?;
+        let commitment = FpVar::new_input(ns!(cs, "commitment"), || Ok(self.commitment))?;
+
+        // Private inputs
+        let birth_year = FpVar::new_witness(ns!(cs, "birth_year"), || Ok(self.birth_year))?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "commitment == Poseidon(birth_year, salt)");
+            poseidon::hash_gadget(cs.clone(), &[birth_year.clone(), salt])?
+                .enforce_equal(&commitment)?;
+        }
+
+        // Years are small, so the difference below cannot wrap into range
+        let _ns = ns!(cs, "birth_year + threshold <= current_year");
+        for year in [&current_year, &threshold, &birth_year] {
+            comparison::enforce_bit_length(year, YEAR_BITS)?;
+        }
+        comparison::enforce_less_or_equal(&(birth_year + threshold), &current_year, YEAR_BITS + 1)?;
+
+        Ok(())
+    }
+}
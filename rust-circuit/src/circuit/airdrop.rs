@@ -0,0 +1,210 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::merkle::{MerklePath, MerklePathVar, MerkleTree};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// An address on the eligibility list with the identity its owner registered,
+/// `identity = Poseidon(secret)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eligible<F: PrimeField> {
+    pub address: [u8; 32],
+    pub identity: F,
+}
+
+/// Hashes a 32-byte Sui address into the field as `Poseidon(high, low)` over
+/// its two 16-byte halves, so distinct addresses never reduce to the same element.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn address_hash<F: PrimeField + Absorb>(address: &[u8; 32]) -> anyhow::Result<F> {
+    let (high, low) = address.split_at(16);
+    poseidon::hash(&[
+        F::from_be_bytes_mod_order(high),
+        F::from_be_bytes_mod_order(low),
+    ])
+}
+
+/// The public identity derived from a claimant's `secret`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn identity<F: PrimeField + Absorb>(secret: F) -> anyhow::Result<F> {
+    poseidon::hash(&[secret])
+}
+
+/// The tree leaf of an eligible address: `Poseidon(address_hash, identity)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn leaf<F: PrimeField + Absorb>(eligible: &Eligible<F>) -> anyhow::Result<F> {
+    poseidon::hash(&[address_hash(&eligible.address)?, eligible.identity])
+}
+
+/// Builds the eligibility tree whose root is published for the airdrop, with
+/// one leaf per entry in list order.
+///
+/// # Errors
+/// Returns error if the list does not fit in a tree of `depth` levels or
+/// hashing fails.
+pub fn build_tree<F: PrimeField + Absorb>(
+    depth: usize,
+    eligible: &[Eligible<F>],
+) -> anyhow::Result<MerkleTree<F>> {
+    let leaves = eligible.iter().map(leaf).collect::<anyhow::Result<_>>()?;
+    MerkleTree::new(depth, leaves)
+}
+
+/// The nullifier published with a claim: `Poseidon(secret, airdrop_id)`.
+///
+/// It is the same for every claim by one identity in one airdrop, so a
+/// second claim is rejected, yet cannot be linked to the address without `secret`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn nullifier<F: PrimeField + Absorb>(secret: F, airdrop_id: F) -> anyhow::Result<F> {
+    poseidon::hash(&[secret, airdrop_id])
+}
+
+/// Proves that an address on the eligibility list behind `root` belongs to the
+/// prover, without revealing which one, and exposes a per-airdrop nullifier.
+///
+/// `recipient` is bound into the proof so a claim cannot be replayed to
+/// another recipient.
+#[derive(Debug, Clone)]
+pub struct AirdropCircuit<const DEPTH: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub root: F,
+    pub nullifier: F,
+    pub airdrop_id: F,
+    pub recipient: F,
+
+    // Private inputs
+    pub address_hash: F,
+    pub secret: F,
+    pub path: MerklePath<F>,
+}
+
+metadata! {
+    impl<const DEPTH: usize> AirdropCircuit<DEPTH> {
+        name: "airdrop-claim",
+        version: "1.0.0",
+        public_inputs: ["root", "nullifier", "airdrop_id", "recipient"],
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> AirdropCircuit<DEPTH, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            root: F::ZERO,
+            nullifier: F::ZERO,
+            airdrop_id: F::ZERO,
+            recipient: F::ZERO,
+            address_hash: F::ZERO,
+            secret: F::ZERO,
+            path: MerklePath::empty(DEPTH),
+        }
+    }
+
+    /// Creates a claim for the leaf at `index` of `tree`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `tree` does not have `DEPTH` levels
+    /// - `index` is out of range, or the leaf there is not `address` with
+    ///   the identity of `secret`
+    pub fn new(
+        tree: &MerkleTree<F>,
+        index: usize,
+        address: &[u8; 32],
+        secret: F,
+        airdrop_id: F,
+        recipient: F,
+    ) -> anyhow::Result<Self> {
+        if tree.depth() != DEPTH {
+            anyhow::bail!("Expected a tree of depth {}, got {}", DEPTH, tree.depth());
+        }
+        let path = tree.path(index)?;
+        let eligible = Eligible {
+            address: *address,
+            identity: identity(secret)?,
+        };
+        if tree.leaves()[index] != leaf(&eligible)? {
+            anyhow::bail!(
+                "Leaf {} is not address 0x{} with the given secret",
+                index,
+                hex::encode(address)
+            );
+        }
+
+        Ok(Self {
+            root: tree.root(),
+            nullifier: nullifier(secret, airdrop_id)?,
+            airdrop_id,
+            recipient,
+            address_hash: address_hash(address)?,
+            secret,
+            path,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.root, self.nullifier, self.airdrop_id, self.recipient]
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEPTH: usize, F: PrimeField] AirdropCircuit<DEPTH, F> { address_hash, secret, path }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for AirdropCircuit<DEPTH, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.path.siblings.len() != DEPTH {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let root = FpVar::new_input(ns!(cs, "root"), || Ok(self.root))?;
+        let nullifier = FpVar::new_input(ns!(cs, "nullifier"), || Ok(self.nullifier))?;
+        let airdrop_id = FpVar::new_input(ns!(cs, "airdrop_id"), || Ok(self.airdrop_id))?;
+        let recipient = FpVar::new_input(ns!(cs, "recipient"), || Ok(self.recipient))?;
+
+        // Private inputs
+        let address_hash = FpVar::new_witness(ns!(cs, "address_hash"), || Ok(self.address_hash))?;
+        let secret = FpVar::new_witness(ns!(cs, "secret"), || Ok(self.secret))?;
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+
+        {
+            let _ns = ns!(
+                cs,
+                "root == MerkleRoot(Poseidon(address_hash, Poseidon(secret)))"
+            );
+            let identity = poseidon::hash_gadget(cs.clone(), std::slice::from_ref(&secret))?;
+            let leaf = poseidon::hash_gadget(cs.clone(), &[address_hash, identity])?;
+            path.root(cs.clone(), &leaf)?.enforce_equal(&root)?;
+        }
+
+        {
+            let _ns = ns!(cs, "nullifier == Poseidon(secret, airdrop_id)");
+            poseidon::hash_gadget(cs.clone(), &[secret, airdrop_id])?.enforce_equal(&nullifier)?;
+        }
+
+        // Square the recipient so it takes part in a constraint and cannot be
+        // swapped out without invalidating the proof
+        let _ns = ns!(cs, "recipient binding");
+        let _ = &recipient * &recipient;
+
+        Ok(())
+    }
+}
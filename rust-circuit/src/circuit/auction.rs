@@ -0,0 +1,259 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::comparison;
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Bids are range checked to this many bits, the width of a Sui `u64` coin value.
+pub const BID_BITS: usize = 64;
+
+/// The commitment a bid is sealed with: `Poseidon(bid, salt)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn commit_bid<F: PrimeField + Absorb>(bid: F, salt: F) -> anyhow::Result<F> {
+    poseidon::hash(&[bid, salt])
+}
+
+/// Proves that the bid sealed in `commitment` lies in `[min_bid, max_bid]`.
+///
+/// Submitted with each sealed bid, so the auction only accepts bids it can
+/// settle.
+#[derive(Debug, Clone)]
+pub struct BidCircuit<F: PrimeField = Fr> {
+    // Public inputs
+    pub min_bid: F,
+    pub max_bid: F,
+    pub commitment: F,
+
+    // Private inputs
+    pub bid: F,
+    pub salt: F,
+}
+
+metadata! {
+    impl BidCircuit {
+        name: "sealed-bid",
+        version: "1.0.0",
+        public_inputs: ["min_bid", "max_bid", "commitment"],
+    }
+}
+
+impl<F: PrimeField + Absorb> BidCircuit<F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            min_bid: F::ZERO,
+            max_bid: F::ZERO,
+            commitment: F::ZERO,
+            bid: F::ZERO,
+            salt: F::ZERO,
+        }
+    }
+
+    /// Creates a circuit, sealing `bid` with `salt`.
+    ///
+    /// # Errors
+    /// Returns error if `bid` is outside `[min_bid, max_bid]`.
+    pub fn new(min_bid: u64, max_bid: u64, bid: u64, salt: F) -> anyhow::Result<Self> {
+        if bid < min_bid || bid > max_bid {
+            anyhow::bail!("Bid {} is outside [{}, {}]", bid, min_bid, max_bid);
+        }
+
+        let bid = F::from(bid);
+        Ok(Self {
+            min_bid: F::from(min_bid),
+            max_bid: F::from(max_bid),
+            commitment: commit_bid(bid, salt)?,
+            bid,
+            salt,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.min_bid, self.max_bid, self.commitment]
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField] BidCircuit<F> { bid, salt }
+}
+
+impl<F: PrimeField + Absorb> ConstraintSynthesizer<F> for BidCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let min_bid = FpVar::new_input(ns!(cs, "min_bid"), || Ok(self.min_bid))?;
+        let max_bid = FpVar::new_input(ns!(cs, "max_bid"), || Ok(self.max_bid))?;
+        let commitment = FpVar::new_input(ns!(cs, "commitment"), || Ok(self.commitment))?;
+
+        // Private inputs
+        let bid = FpVar::new_witness(ns!(cs, "bid"), || Ok(self.bid))?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "commitment == Poseidon(bid, salt)");
+            poseidon::hash_gadget(cs.clone(), &[bid.clone(), salt])?.enforce_equal(&commitment)?;
+        }
+
+        let _ns = ns!(cs, "min_bid <= bid <= max_bid");
+        for value in [&min_bid, &max_bid, &bid] {
+            comparison::enforce_bit_length(value, BID_BITS)?;
+        }
+        comparison::enforce_less_or_equal(&min_bid, &bid, BID_BITS)?;
+        comparison::enforce_less_or_equal(&bid, &max_bid, BID_BITS)?;
+
+        Ok(())
+    }
+}
+
+/// Proves that the revealed `winning_bid`, opening `winning_commitment`, is
+/// strictly greater than the bids sealed in every one of `other_commitments`.
+///
+/// Run by the auctioneer, who receives the bid openings privately once
+/// bidding closes; losing bids stay hidden. Ties must be broken before
+/// proving (e.g. by earliest commitment), as an equal bid fails the check.
+/// There are `OTHERS` other bids.
+#[derive(Debug, Clone)]
+pub struct AuctionCircuit<const OTHERS: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub winning_bid: F,
+    pub winning_commitment: F,
+    pub other_commitments: Vec<F>,
+
+    // Private inputs
+    pub winning_salt: F,
+    pub other_bids: Vec<F>,
+    pub other_salts: Vec<F>,
+}
+
+metadata! {
+    impl<const OTHERS: usize> AuctionCircuit<OTHERS> {
+        name: "sealed-bid-auction",
+        version: "1.0.0",
+    }
+}
+
+impl<const OTHERS: usize, F: PrimeField + Absorb> AuctionCircuit<OTHERS, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            winning_bid: F::ZERO,
+            winning_commitment: F::ZERO,
+            other_commitments: vec![F::ZERO; OTHERS],
+            winning_salt: F::ZERO,
+            other_bids: vec![F::ZERO; OTHERS],
+            other_salts: vec![F::ZERO; OTHERS],
+        }
+    }
+
+    /// Creates a circuit from the winning opening and the openings of the
+    /// other bids, computing their commitments.
+    ///
+    /// # Errors
+    /// Returns error if there are not `OTHERS` other bids, or one is not
+    /// below `winning_bid`.
+    pub fn new(winning_bid: u64, winning_salt: F, others: &[(u64, F)]) -> anyhow::Result<Self> {
+        if others.len() != OTHERS {
+            anyhow::bail!("Expected {} other bids, got {}", OTHERS, others.len());
+        }
+        if let Some((bid, _)) = others.iter().find(|(bid, _)| *bid >= winning_bid) {
+            anyhow::bail!("Bid {} is not below the winning bid {}", bid, winning_bid);
+        }
+
+        let winning_bid = F::from(winning_bid);
+        let other_bids: Vec<F> = others.iter().map(|(bid, _)| F::from(*bid)).collect();
+        let other_salts: Vec<F> = others.iter().map(|(_, salt)| *salt).collect();
+        let other_commitments = other_bids
+            .iter()
+            .zip(&other_salts)
+            .map(|(bid, salt)| commit_bid(*bid, *salt))
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            winning_bid,
+            winning_commitment: commit_bid(winning_bid, winning_salt)?,
+            other_commitments,
+            winning_salt,
+            other_bids,
+            other_salts,
+        })
+    }
+
+    /// The winning bid and commitment, followed by the other commitments in order.
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        let mut inputs = vec![self.winning_bid, self.winning_commitment];
+        inputs.extend_from_slice(&self.other_commitments);
+        inputs
+    }
+}
+
+zeroize_on_drop! {
+    impl[const OTHERS: usize, F: PrimeField] AuctionCircuit<OTHERS, F> {
+        winning_salt,
+        other_bids,
+        other_salts,
+    }
+}
+
+impl<const OTHERS: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for AuctionCircuit<OTHERS, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.other_commitments.len() != OTHERS
+            || self.other_bids.len() != OTHERS
+            || self.other_salts.len() != OTHERS
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let winning_bid = FpVar::new_input(ns!(cs, "winning_bid"), || Ok(self.winning_bid))?;
+        let winning_commitment =
+            FpVar::new_input(
+                ns!(cs, "winning_commitment"),
+                || Ok(self.winning_commitment),
+            )?;
+        let other_commitments = self
+            .other_commitments
+            .iter()
+            .map(|commitment| FpVar::new_input(ns!(cs, "other_commitment"), || Ok(*commitment)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        // Private inputs
+        let winning_salt = FpVar::new_witness(ns!(cs, "winning_salt"), || Ok(self.winning_salt))?;
+
+        {
+            let _ns = ns!(
+                cs,
+                "winning_commitment == Poseidon(winning_bid, winning_salt)"
+            );
+            poseidon::hash_gadget(cs.clone(), &[winning_bid.clone(), winning_salt])?
+                .enforce_equal(&winning_commitment)?;
+            comparison::enforce_bit_length(&winning_bid, BID_BITS)?;
+        }
+
+        for ((commitment, bid), salt) in other_commitments
+            .iter()
+            .zip(&self.other_bids)
+            .zip(&self.other_salts)
+        {
+            // Private inputs
+            let bid = FpVar::new_witness(ns!(cs, "other_bid"), || Ok(*bid))?;
+            let salt = FpVar::new_witness(ns!(cs, "other_salt"), || Ok(*salt))?;
+
+            let _ns = ns!(cs, "other_bid < winning_bid");
+            poseidon::hash_gadget(cs.clone(), &[bid.clone(), salt])?.enforce_equal(commitment)?;
+            comparison::enforce_bit_length(&bid, BID_BITS)?;
+            comparison::enforce_less_than(&bid, &winning_bid, BID_BITS)?;
+        }
+
+        Ok(())
+    }
+}
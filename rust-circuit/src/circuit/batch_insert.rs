@@ -0,0 +1,123 @@
+use crate::circuit::metadata;
+use crate::merkle::{self, MerklePath, MerklePathVar, MerkleTree};
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::AllocVar};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Proves that appending `leaves` at `start_index` moves an on-chain tree
+/// from `old_root` to `new_root`, so the contract can accept the new root
+/// without hashing.
+///
+/// Batches of `BATCH_SIZE` leaves go into a tree of `DEPTH` levels. Nothing
+/// here is secret, so the paths are not zeroized.
+#[derive(Debug, Clone)]
+pub struct BatchInsertCircuit<const DEPTH: usize, const BATCH_SIZE: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub old_root: F,
+    pub new_root: F,
+    pub start_index: F,
+    pub leaves: Vec<F>,
+
+    // Private inputs
+    pub paths: Vec<MerklePath<F>>,
+}
+
+metadata! {
+    impl<const DEPTH: usize, const BATCH_SIZE: usize> BatchInsertCircuit<DEPTH, BATCH_SIZE> {
+        name: "merkle-batch-insert",
+        version: "1.0.0",
+    }
+}
+
+impl<const DEPTH: usize, const BATCH_SIZE: usize, F: PrimeField + Absorb>
+    BatchInsertCircuit<DEPTH, BATCH_SIZE, F>
+{
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            old_root: F::ZERO,
+            new_root: F::ZERO,
+            start_index: F::ZERO,
+            leaves: vec![F::ZERO; BATCH_SIZE],
+            paths: vec![MerklePath::empty(DEPTH); BATCH_SIZE],
+        }
+    }
+
+    /// Appends `leaves` to `tree`, recording the update.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `tree` does not have `DEPTH` levels or there are not `BATCH_SIZE` leaves
+    /// - the leaves do not fit or hashing fails
+    pub fn new(tree: &mut MerkleTree<F>, leaves: Vec<F>) -> anyhow::Result<Self> {
+        if tree.depth() != DEPTH {
+            anyhow::bail!("Expected a tree of depth {}, got {}", DEPTH, tree.depth());
+        }
+        if leaves.len() != BATCH_SIZE {
+            anyhow::bail!("Expected {} leaves, got {}", BATCH_SIZE, leaves.len());
+        }
+
+        let old_root = tree.root();
+        let start_index = F::from(tree.leaves().len() as u64);
+        let paths = tree.insert_batch(&leaves)?;
+
+        Ok(Self {
+            old_root,
+            new_root: tree.root(),
+            start_index,
+            leaves,
+            paths,
+        })
+    }
+
+    /// The roots and start index, followed by the leaves in order.
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        let mut inputs = vec![self.old_root, self.new_root, self.start_index];
+        inputs.extend_from_slice(&self.leaves);
+        inputs
+    }
+}
+
+impl<const DEPTH: usize, const BATCH_SIZE: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for BatchInsertCircuit<DEPTH, BATCH_SIZE, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.leaves.len() != BATCH_SIZE
+            || self.paths.len() != BATCH_SIZE
+            || self.paths.iter().any(|path| path.siblings.len() != DEPTH)
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let old_root = FpVar::new_input(ns!(cs, "old_root"), || Ok(self.old_root))?;
+        let new_root = FpVar::new_input(ns!(cs, "new_root"), || Ok(self.new_root))?;
+        let start_index = FpVar::new_input(ns!(cs, "start_index"), || Ok(self.start_index))?;
+        let leaves = self
+            .leaves
+            .iter()
+            .map(|leaf| FpVar::new_input(ns!(cs, "leaf"), || Ok(*leaf)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        // Private inputs
+        let paths = self
+            .paths
+            .iter()
+            .map(|path| MerklePathVar::new_witness(ns!(cs, "path").cs(), path))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        let _ns = ns!(cs, "new_root == Insert(old_root, leaves)");
+        merkle::enforce_batch_insert(
+            cs.clone(),
+            &old_root,
+            &new_root,
+            &start_index,
+            &leaves,
+            &paths,
+        )
+    }
+}
@@ -0,0 +1,156 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::merkle::{
+    sparse::{self, SparseMerkleTree},
+    MerklePath, MerklePathVar,
+};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// The sparse tree key of a blocklisted identifier: `Poseidon(identifier)`,
+/// which spreads identifiers uniformly over the slots.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn key<F: PrimeField + Absorb>(identifier: F) -> anyhow::Result<F> {
+    poseidon::hash(&[identifier])
+}
+
+/// Builds the blocklist whose root is published, storing `1` for each identifier.
+///
+/// # Errors
+/// Returns error if two identifiers share a slot or hashing fails.
+pub fn build_blocklist<F: PrimeField + Absorb>(
+    depth: usize,
+    identifiers: &[F],
+) -> anyhow::Result<SparseMerkleTree<F>> {
+    let mut tree = SparseMerkleTree::new(depth)?;
+    for identifier in identifiers {
+        tree.insert(key(*identifier)?, F::ONE)?;
+    }
+    Ok(tree)
+}
+
+/// Proves that the identifier committed to in `commitment = Poseidon(identifier,
+/// salt)` is not on the blocklist behind `blocklist_root`.
+///
+/// The commitment is the value a private transfer (or any other statement)
+/// already binds the sender to, so compliance is checked without revealing
+/// the identifier.
+#[derive(Debug, Clone)]
+pub struct BlocklistCircuit<const DEPTH: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub blocklist_root: F,
+    pub commitment: F,
+
+    // Private inputs
+    pub identifier: F,
+    pub salt: F,
+    pub path: MerklePath<F>,
+}
+
+metadata! {
+    impl<const DEPTH: usize> BlocklistCircuit<DEPTH> {
+        name: "blocklist-non-membership",
+        version: "1.0.0",
+        public_inputs: ["blocklist_root", "commitment"],
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> BlocklistCircuit<DEPTH, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            blocklist_root: F::ZERO,
+            commitment: F::ZERO,
+            identifier: F::ZERO,
+            salt: F::ZERO,
+            path: MerklePath::empty(DEPTH),
+        }
+    }
+
+    /// Creates a circuit, computing the commitment to `identifier`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `blocklist` does not have `DEPTH` levels
+    /// - `identifier` is on the blocklist, or its slot is taken by a
+    ///   blocklisted identifier
+    pub fn new(blocklist: &SparseMerkleTree<F>, identifier: F, salt: F) -> anyhow::Result<Self> {
+        if blocklist.depth() != DEPTH {
+            anyhow::bail!(
+                "Expected a tree of depth {}, got {}",
+                DEPTH,
+                blocklist.depth()
+            );
+        }
+        let key = key(identifier)?;
+        if blocklist.get(&key).is_some() {
+            anyhow::bail!("Identifier is on the blocklist");
+        }
+
+        Ok(Self {
+            blocklist_root: blocklist.root(),
+            commitment: Self::commit(identifier, salt)?,
+            identifier,
+            salt,
+            path: blocklist.path(&key)?,
+        })
+    }
+
+    /// The public commitment to `identifier`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commit(identifier: F, salt: F) -> anyhow::Result<F> {
+        poseidon::hash(&[identifier, salt])
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.blocklist_root, self.commitment]
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEPTH: usize, F: PrimeField] BlocklistCircuit<DEPTH, F> { identifier, salt, path }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for BlocklistCircuit<DEPTH, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.path.siblings.len() != DEPTH {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let blocklist_root =
+            FpVar::new_input(ns!(cs, "blocklist_root"), || Ok(self.blocklist_root))?;
+        let commitment = FpVar::new_input(ns!(cs, "commitment"), || Ok(self.commitment))?;
+
+        // Private inputs
+        let identifier = FpVar::new_witness(ns!(cs, "identifier"), || Ok(self.identifier))?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+
+        {
+            let _ns = ns!(cs, "commitment == Poseidon(identifier, salt)");
+            poseidon::hash_gadget(cs.clone(), &[identifier.clone(), salt])?
+                .enforce_equal(&commitment)?;
+        }
+
+        let _ns = ns!(cs, "Poseidon(identifier) not in blocklist");
+        let key = poseidon::hash_gadget(cs.clone(), &[identifier])?;
+        sparse::enforce_non_membership(cs.clone(), &blocklist_root, &key, &path)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,361 @@
+use crate::babyjubjub::{self, Affine, Fq};
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::{babyjubjub::PointVar, comparison, eddsa};
+use crate::merkle::{MerklePath, MerklePathVar};
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::twisted_edwards::TECurveConfig;
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+use zeroize::Zeroize;
+
+/// Values compared by [`Range`] are range checked to this many bits.
+pub const RANGE_BITS: usize = 64;
+
+/// A sub-statement that [`CircuitBuilder`] composes with others into one
+/// circuit.
+///
+/// Statements share private values by name, e.g. a leaf that is both a tree
+/// member and in range, and allocate any other witnesses themselves.
+pub trait Statement<F: PrimeField> {
+    /// Names and values of the public inputs, in allocation order.
+    fn public_inputs(&self) -> Vec<(&'static str, F)>;
+
+    /// Names of the shared private values the statement constrains.
+    fn witnesses(&self) -> Vec<&str>;
+
+    /// Enforces the statement, given its public inputs and shared values
+    /// allocated in the orders above.
+    fn enforce(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        public_inputs: &[FpVar<F>],
+        witnesses: &[FpVar<F>],
+    ) -> r1cs::Result<()>;
+}
+
+/// Composes named [`Statement`]s over shared private values into a
+/// [`CompositeCircuit`], e.g. membership AND range AND signature, without
+/// writing `generate_constraints` by hand.
+///
+/// The public inputs of each statement follow those of the statements added
+/// before it, named `<statement>.<input>`.
+pub struct CircuitBuilder<F: PrimeField = Fr> {
+    witnesses: Vec<(String, F)>,
+    statements: Vec<(String, Arc<dyn Statement<F>>)>,
+}
+
+impl<F: PrimeField> Default for CircuitBuilder<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField> CircuitBuilder<F> {
+    pub fn new() -> Self {
+        Self {
+            witnesses: Vec::new(),
+            statements: Vec::new(),
+        }
+    }
+
+    /// Declares a private value that statements refer to as `name`.
+    pub fn witness(mut self, name: impl Into<String>, value: F) -> Self {
+        self.witnesses.push((name.into(), value));
+        self
+    }
+
+    /// Adds `statement` under `name`.
+    pub fn statement(
+        mut self,
+        name: impl Into<String>,
+        statement: impl Statement<F> + 'static,
+    ) -> Self {
+        self.statements.push((name.into(), Arc::new(statement)));
+        self
+    }
+
+    /// Checks the composition and builds the circuit. The same composition
+    /// with any values serves for the setup phase.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - there are no statements
+    /// - two witnesses or two statements share a name
+    /// - a statement refers to an undeclared witness
+    pub fn build(self) -> anyhow::Result<CompositeCircuit<F>> {
+        if self.statements.is_empty() {
+            anyhow::bail!("Circuit has no statements");
+        }
+        if let Some(name) = duplicate(self.witnesses.iter().map(|(name, _)| name)) {
+            anyhow::bail!("Duplicate witness: {}", name);
+        }
+        if let Some(name) = duplicate(self.statements.iter().map(|(name, _)| name)) {
+            anyhow::bail!("Duplicate statement: {}", name);
+        }
+        for (name, statement) in &self.statements {
+            for witness in statement.witnesses() {
+                if !self
+                    .witnesses
+                    .iter()
+                    .any(|(declared, _)| declared == witness)
+                {
+                    anyhow::bail!(
+                        "Statement {} refers to undeclared witness {}",
+                        name,
+                        witness
+                    );
+                }
+            }
+        }
+
+        Ok(CompositeCircuit {
+            witnesses: self.witnesses,
+            statements: self.statements,
+        })
+    }
+}
+
+fn duplicate<'a>(mut names: impl Iterator<Item = &'a String>) -> Option<&'a String> {
+    let mut seen = HashSet::new();
+    names.find(|name| !seen.insert(*name))
+}
+
+/// A circuit composed by [`CircuitBuilder`].
+#[derive(Clone)]
+pub struct CompositeCircuit<F: PrimeField = Fr> {
+    witnesses: Vec<(String, F)>,
+    statements: Vec<(String, Arc<dyn Statement<F>>)>,
+}
+
+metadata! {
+    impl CompositeCircuit {
+        name: "composite",
+        version: "1.0.0",
+    }
+}
+
+impl<F: PrimeField> CompositeCircuit<F> {
+    /// Names of the public inputs, `<statement>.<input>`, in the order of
+    /// [`CompositeCircuit::get_public_inputs`].
+    pub fn public_input_names(&self) -> Vec<String> {
+        self.statements
+            .iter()
+            .flat_map(|(name, statement)| {
+                statement
+                    .public_inputs()
+                    .into_iter()
+                    .map(move |(input, _)| format!("{}.{}", name, input))
+            })
+            .collect()
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        self.statements
+            .iter()
+            .flat_map(|(_, statement)| statement.public_inputs())
+            .map(|(_, value)| value)
+            .collect()
+    }
+}
+
+impl<F: PrimeField> fmt::Debug for CompositeCircuit<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompositeCircuit")
+            .field(
+                "witnesses",
+                &self
+                    .witnesses
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "statements",
+                &self
+                    .statements
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// Clears the shared private values; statements clear their own.
+impl<F: PrimeField> Zeroize for CompositeCircuit<F> {
+    fn zeroize(&mut self) {
+        for (_, value) in &mut self.witnesses {
+            value.zeroize();
+        }
+    }
+}
+
+zeroize_on_drop!(impl[F: PrimeField] CompositeCircuit<F>);
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for CompositeCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let public_inputs = self
+            .statements
+            .iter()
+            .map(|(_, statement)| {
+                statement
+                    .public_inputs()
+                    .into_iter()
+                    .map(|(_, value)| FpVar::new_input(ns!(cs, "public_input"), || Ok(value)))
+                    .collect::<r1cs::Result<Vec<_>>>()
+            })
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        // Private inputs
+        let witnesses = self
+            .witnesses
+            .iter()
+            .map(|(name, value)| {
+                let var = FpVar::new_witness(ns!(cs, "witness"), || Ok(*value))?;
+                Ok((name.as_str(), var))
+            })
+            .collect::<r1cs::Result<HashMap<_, _>>>()?;
+
+        for ((_, statement), inputs) in self.statements.iter().zip(&public_inputs) {
+            let _ns = ns!(cs, "statement");
+            let shared = statement
+                .witnesses()
+                .into_iter()
+                .map(|name| witnesses.get(name).cloned())
+                .collect::<Option<Vec<_>>>()
+                .ok_or(r1cs::SynthesisError::AssignmentMissing)?;
+            statement.enforce(cs.clone(), inputs, &shared)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The shared value `leaf` is in the Poseidon Merkle tree with public `root`.
+#[derive(Debug, Clone)]
+pub struct Membership<F: PrimeField> {
+    pub leaf: String,
+    pub root: F,
+    pub path: MerklePath<F>,
+}
+
+impl<F: PrimeField + Absorb> Statement<F> for Membership<F> {
+    fn public_inputs(&self) -> Vec<(&'static str, F)> {
+        vec![("root", self.root)]
+    }
+
+    fn witnesses(&self) -> Vec<&str> {
+        vec![&self.leaf]
+    }
+
+    fn enforce(
+        &self,
+        cs: ConstraintSystemRef<F>,
+        public_inputs: &[FpVar<F>],
+        witnesses: &[FpVar<F>],
+    ) -> r1cs::Result<()> {
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+        path.root(cs, &witnesses[0])?
+            .enforce_equal(&public_inputs[0])
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField] Membership<F> { path }
+}
+
+/// `min <= value <= max` for the shared `value` and public bounds, all below
+/// `2^RANGE_BITS`.
+#[derive(Debug, Clone)]
+pub struct Range {
+    pub value: String,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl<F: PrimeField> Statement<F> for Range {
+    fn public_inputs(&self) -> Vec<(&'static str, F)> {
+        vec![("min", F::from(self.min)), ("max", F::from(self.max))]
+    }
+
+    fn witnesses(&self) -> Vec<&str> {
+        vec![&self.value]
+    }
+
+    fn enforce(
+        &self,
+        _cs: ConstraintSystemRef<F>,
+        public_inputs: &[FpVar<F>],
+        witnesses: &[FpVar<F>],
+    ) -> r1cs::Result<()> {
+        let (min, max, value) = (&public_inputs[0], &public_inputs[1], &witnesses[0]);
+        for x in [min, max, value] {
+            comparison::enforce_bit_length(x, RANGE_BITS)?;
+        }
+        comparison::enforce_less_or_equal(min, value, RANGE_BITS)?;
+        comparison::enforce_less_or_equal(value, max, RANGE_BITS)
+    }
+}
+
+/// The shared value `message` is signed under the public Baby Jubjub key
+/// `public_key` with EdDSA-Poseidon; see [`babyjubjub::sign`].
+#[derive(Debug, Clone)]
+pub struct EdDsaSignature {
+    pub message: String,
+    pub public_key: Affine,
+    pub signature: babyjubjub::Signature,
+}
+
+impl Statement<Fq> for EdDsaSignature {
+    fn public_inputs(&self) -> Vec<(&'static str, Fq)> {
+        vec![
+            ("public_key_x", self.public_key.x),
+            ("public_key_y", self.public_key.y),
+        ]
+    }
+
+    fn witnesses(&self) -> Vec<&str> {
+        vec![&self.message]
+    }
+
+    fn enforce(
+        &self,
+        cs: ConstraintSystemRef<Fq>,
+        public_inputs: &[FpVar<Fq>],
+        witnesses: &[FpVar<Fq>],
+    ) -> r1cs::Result<()> {
+        let public_key = PointVar::new(public_inputs[0].clone(), public_inputs[1].clone());
+        {
+            // a x^2 + y^2 == 1 + d x^2 y^2, as `PointVar::new_input` checks
+            let _ns = ns!(cs, "public key on curve");
+            let x2 = public_key.x.square()?;
+            let y2 = public_key.y.square()?;
+            (&x2 * babyjubjub::Config::COEFF_D - Fq::ONE)
+                .mul_equals(&y2, &(&x2 * babyjubjub::Config::COEFF_A - Fq::ONE))?;
+        }
+
+        let r8 = PointVar::new_witness(ns!(cs, "r8"), || Ok(self.signature.r8))?;
+        let s = FpVar::new_witness(ns!(cs, "s"), || {
+            Ok(Fq::from_le_bytes_mod_order(
+                &self.signature.s.into_bigint().to_bytes_le(),
+            ))
+        })?;
+        let s_bits = comparison::enforce_bit_length(&s, babyjubjub::Fr::MODULUS_BIT_SIZE as usize)?;
+        eddsa::enforce_valid_signature(&public_key, &witnesses[0], &r8, &s_bits)
+    }
+}
+
+zeroize_on_drop! {
+    impl[] EdDsaSignature { signature }
+}
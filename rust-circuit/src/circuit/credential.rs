@@ -0,0 +1,297 @@
+use crate::babyjubjub::{self, Affine, Fq, Signature};
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::{babyjubjub::PointVar, comparison, eddsa, lookup};
+use crate::poseidon;
+use ark_ec::AffineRepr;
+use ark_ff::{AdditiveGroup, BigInteger, PrimeField};
+use ark_r1cs_std::{
+    boolean::Boolean,
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Attributes and bounds compared by predicates are range checked to this
+/// many bits.
+pub const ATTRIBUTE_BITS: usize = 64;
+
+/// The message an issuer signs for a credential: circomlib's Poseidon of the
+/// attributes, so credentials can be issued with circomlibjs.
+///
+/// # Errors
+/// Returns error if there are not between 1 and 16 attributes.
+pub fn credential_hash(attributes: &[Fq]) -> anyhow::Result<Fq> {
+    poseidon::circom_hash(attributes)
+}
+
+/// Issues a credential over `attributes`, signed with the issuer's `secret`.
+///
+/// # Errors
+/// Returns error if there are not between 1 and 16 attributes.
+pub fn issue(secret: babyjubjub::Fr, attributes: &[Fq]) -> anyhow::Result<Signature> {
+    babyjubjub::sign(secret, credential_hash(attributes)?)
+}
+
+/// A statement about one attribute, proved without disclosing it.
+///
+/// The kind, the attribute and the size of a set are fixed at setup; bounds
+/// and set members are public inputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+    /// `attributes[attribute] > bound`, e.g. an age over 18.
+    GreaterThan { attribute: usize, bound: Fq },
+    /// `attributes[attribute] < bound`, e.g. an expiry after today.
+    LessThan { attribute: usize, bound: Fq },
+    /// `attributes[attribute]` is one of `set`, e.g. an allowed country code.
+    InSet { attribute: usize, set: Vec<Fq> },
+}
+
+impl Predicate {
+    /// The attribute the predicate is about.
+    pub fn attribute(&self) -> usize {
+        match self {
+            Self::GreaterThan { attribute, .. }
+            | Self::LessThan { attribute, .. }
+            | Self::InSet { attribute, .. } => *attribute,
+        }
+    }
+
+    /// Evaluates the predicate natively, as the circuit does.
+    pub fn holds(&self, attributes: &[Fq]) -> bool {
+        let Some(&value) = attributes.get(self.attribute()) else {
+            return false;
+        };
+        let fits = |x: &Fq| x.into_bigint().num_bits() as usize <= ATTRIBUTE_BITS;
+        match self {
+            Self::GreaterThan { bound, .. } => fits(&value) && fits(bound) && value > *bound,
+            Self::LessThan { bound, .. } => fits(&value) && fits(bound) && value < *bound,
+            Self::InSet { set, .. } => set.contains(&value),
+        }
+    }
+
+    /// The same predicate with its public values zeroed, for the setup phase.
+    pub fn empty(&self) -> Self {
+        match self {
+            Self::GreaterThan { attribute, .. } => Self::GreaterThan {
+                attribute: *attribute,
+                bound: Fq::ZERO,
+            },
+            Self::LessThan { attribute, .. } => Self::LessThan {
+                attribute: *attribute,
+                bound: Fq::ZERO,
+            },
+            Self::InSet { attribute, set } => Self::InSet {
+                attribute: *attribute,
+                set: vec![Fq::ZERO; set.len()],
+            },
+        }
+    }
+
+    fn public_inputs(&self) -> &[Fq] {
+        match self {
+            Self::GreaterThan { bound, .. } | Self::LessThan { bound, .. } => {
+                std::slice::from_ref(bound)
+            }
+            Self::InSet { set, .. } => set,
+        }
+    }
+}
+
+/// Proves possession of a credential signed by `issuer` (EdDSA-Poseidon over
+/// [`credential_hash`] of the attributes), disclosing only the attributes
+/// flagged in `disclosed` and proving `predicates` over the others.
+///
+/// The disclosure flags are public inputs, so one key serves every choice of
+/// attributes to reveal; undisclosed attributes are revealed as 0. The
+/// number of attributes and the predicates' shape are fixed at setup.
+#[derive(Debug, Clone)]
+pub struct CredentialCircuit {
+    // Public inputs
+    pub issuer: Affine,
+    pub disclosed: Vec<bool>,
+    /// The attribute where disclosed, 0 elsewhere.
+    pub revealed: Vec<Fq>,
+    pub predicates: Vec<Predicate>,
+
+    // Private inputs
+    pub attributes: Vec<Fq>,
+    pub signature: Signature,
+}
+
+metadata! {
+    impl CredentialCircuit {
+        name: "credential-disclosure",
+        version: "1.0.0",
+    }
+}
+
+impl CredentialCircuit {
+    /// Creates an empty circuit for the setup phase of credentials with
+    /// `attributes` attributes, proving predicates of the same shape as
+    /// `predicates`.
+    pub fn empty(attributes: usize, predicates: &[Predicate]) -> Self {
+        Self {
+            issuer: Affine::generator(),
+            disclosed: vec![false; attributes],
+            revealed: vec![Fq::ZERO; attributes],
+            predicates: predicates.iter().map(Predicate::empty).collect(),
+            attributes: vec![Fq::ZERO; attributes],
+            signature: Signature {
+                r8: Affine::zero(),
+                s: babyjubjub::Fr::ZERO,
+            },
+        }
+    }
+
+    /// Creates a circuit disclosing the attributes flagged in `disclosed`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - there are not between 1 and 16 attributes, or `disclosed` has a
+    ///   different length
+    /// - the signature does not verify under `issuer`
+    /// - a predicate does not hold
+    pub fn new(
+        issuer: Affine,
+        attributes: Vec<Fq>,
+        signature: Signature,
+        disclosed: Vec<bool>,
+        predicates: Vec<Predicate>,
+    ) -> anyhow::Result<Self> {
+        if disclosed.len() != attributes.len() {
+            anyhow::bail!(
+                "Expected {} disclosure flags, got {}",
+                attributes.len(),
+                disclosed.len()
+            );
+        }
+        if !babyjubjub::verify(&issuer, credential_hash(&attributes)?, &signature)? {
+            anyhow::bail!("Credential signature does not verify under the issuer key");
+        }
+        if let Some(predicate) = predicates.iter().find(|p| !p.holds(&attributes)) {
+            anyhow::bail!(
+                "Predicate on attribute {} does not hold",
+                predicate.attribute()
+            );
+        }
+
+        let revealed = attributes
+            .iter()
+            .zip(&disclosed)
+            .map(|(&attribute, &disclosed)| if disclosed { attribute } else { Fq::ZERO })
+            .collect();
+        Ok(Self {
+            issuer,
+            disclosed,
+            revealed,
+            predicates,
+            attributes,
+            signature,
+        })
+    }
+
+    /// The issuer key coordinates, each attribute's disclosure flag and
+    /// revealed value, then each predicate's bound or set.
+    pub fn get_public_inputs(&self) -> Vec<Fq> {
+        [self.issuer.x, self.issuer.y]
+            .into_iter()
+            .chain(
+                self.disclosed
+                    .iter()
+                    .zip(&self.revealed)
+                    .flat_map(|(&disclosed, &revealed)| [Fq::from(disclosed), revealed]),
+            )
+            .chain(
+                self.predicates
+                    .iter()
+                    .flat_map(Predicate::public_inputs)
+                    .copied(),
+            )
+            .collect()
+    }
+}
+
+zeroize_on_drop! {
+    impl[] CredentialCircuit { attributes, signature }
+}
+
+impl ConstraintSynthesizer<Fq> for CredentialCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fq>) -> r1cs::Result<()> {
+        if self.disclosed.len() != self.attributes.len()
+            || self.revealed.len() != self.attributes.len()
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let issuer = PointVar::new_input(ns!(cs, "issuer"), || Ok(self.issuer))?;
+        let mut disclosed = Vec::with_capacity(self.disclosed.len());
+        let mut revealed = Vec::with_capacity(self.revealed.len());
+        for (flag, value) in self.disclosed.iter().zip(&self.revealed) {
+            disclosed.push(Boolean::new_input(ns!(cs, "disclosed"), || Ok(*flag))?);
+            revealed.push(FpVar::new_input(ns!(cs, "revealed"), || Ok(*value))?);
+        }
+        let predicate_inputs = self
+            .predicates
+            .iter()
+            .map(|predicate| {
+                predicate
+                    .public_inputs()
+                    .iter()
+                    .map(|x| FpVar::new_input(ns!(cs, "predicate"), || Ok(*x)))
+                    .collect::<r1cs::Result<Vec<_>>>()
+            })
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        // Private inputs
+        let attributes = self
+            .attributes
+            .iter()
+            .map(|x| FpVar::new_witness(ns!(cs, "attribute"), || Ok(*x)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let r8 = PointVar::new_witness(ns!(cs, "r8"), || Ok(self.signature.r8))?;
+        let s = FpVar::new_witness(ns!(cs, "s"), || {
+            Ok(Fq::from_le_bytes_mod_order(
+                &self.signature.s.into_bigint().to_bytes_le(),
+            ))
+        })?;
+
+        {
+            let _ns = ns!(cs, "signature by issuer on Poseidon(attributes)");
+            let message = poseidon::circom_hash_gadget(cs.clone(), &attributes)?;
+            let s_bits =
+                comparison::enforce_bit_length(&s, babyjubjub::Fr::MODULUS_BIT_SIZE as usize)?;
+            eddsa::enforce_valid_signature(&issuer, &message, &r8, &s_bits)?;
+        }
+
+        {
+            let _ns = ns!(cs, "revealed == disclosed * attribute");
+            for ((flag, value), attribute) in disclosed.iter().zip(&revealed).zip(&attributes) {
+                value.enforce_equal(&(FpVar::from(flag.clone()) * attribute))?;
+            }
+        }
+
+        let _ns = ns!(cs, "predicates");
+        for (predicate, inputs) in self.predicates.iter().zip(&predicate_inputs) {
+            let attribute = attributes
+                .get(predicate.attribute())
+                .ok_or(r1cs::SynthesisError::Unsatisfiable)?;
+            match predicate {
+                Predicate::GreaterThan { .. } | Predicate::LessThan { .. } => {
+                    comparison::enforce_bit_length(attribute, ATTRIBUTE_BITS)?;
+                    comparison::enforce_bit_length(&inputs[0], ATTRIBUTE_BITS)?;
+                    if matches!(predicate, Predicate::GreaterThan { .. }) {
+                        comparison::enforce_less_than(&inputs[0], attribute, ATTRIBUTE_BITS)?;
+                    } else {
+                        comparison::enforce_less_than(attribute, &inputs[0], ATTRIBUTE_BITS)?;
+                    }
+                }
+                Predicate::InSet { .. } => lookup::enforce_in_set(attribute, inputs)?,
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::ecdsa::{self, PointVar, ScalarVar};
+use crate::poseidon;
+use crate::secp256k1::{self, Affine, Signature};
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    boolean::Boolean,
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget, ToBitsGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use zeroize::Zeroize;
+
+/// Poseidon hash identifying a secp256k1 public key, the in-circuit analogue
+/// of an address: `Poseidon(x_hi, x_lo, y_hi, y_lo)` over the 128-bit halves
+/// of its coordinates.
+///
+/// # Errors
+/// Returns error if `public_key` is the point at infinity or hashing fails.
+pub fn key_hash<F: PrimeField + Absorb>(public_key: &Affine) -> anyhow::Result<F> {
+    let (x, y) = public_key
+        .xy()
+        .ok_or_else(|| anyhow::anyhow!("Public key is the point at infinity"))?;
+    let (x_hi, x_lo) = limbs(&x.into_bigint().to_bytes_le());
+    let (y_hi, y_lo) = limbs(&y.into_bigint().to_bytes_le());
+
+    poseidon::hash(&[x_hi, x_lo, y_hi, y_lo])
+}
+
+/// Splits a 32-byte little-endian integer into its high and low 128 bits.
+fn limbs<F: PrimeField>(bytes_le: &[u8]) -> (F, F) {
+    let (low, high) = bytes_le.split_at(16);
+    (
+        F::from_le_bytes_mod_order(high),
+        F::from_le_bytes_mod_order(low),
+    )
+}
+
+/// Proves that the secp256k1 key identified by `key_hash` signed the 32-byte
+/// digest `digest_hi || digest_lo`, without revealing the key or signature.
+///
+/// Lets an Ethereum or Bitcoin key holder make claims on Sui ("I control the
+/// key behind this hash") from an ordinary wallet signature. Costs about
+/// 1.5 million constraints, as secp256k1 arithmetic is emulated.
+#[derive(Debug, Clone)]
+pub struct EcdsaCircuit<F: PrimeField = Fr> {
+    // Public inputs
+    pub digest_hi: F,
+    pub digest_lo: F,
+    pub key_hash: F,
+
+    // Private inputs
+    pub public_key: Affine,
+    pub signature: Signature,
+}
+
+metadata! {
+    impl EcdsaCircuit {
+        name: "ecdsa-secp256k1",
+        version: "1.0.0",
+        public_inputs: ["digest_hi", "digest_lo", "key_hash"],
+    }
+}
+
+impl<F: PrimeField + Absorb> EcdsaCircuit<F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            digest_hi: F::ZERO,
+            digest_lo: F::ZERO,
+            key_hash: F::ZERO,
+            public_key: Affine::generator(),
+            signature: Signature {
+                r: secp256k1::Fr::from(1u64),
+                s: secp256k1::Fr::from(1u64),
+            },
+        }
+    }
+
+    /// Creates a circuit for `signature` over the big-endian `digest`.
+    ///
+    /// # Errors
+    /// Returns error if the signature does not verify, or `digest` is not
+    /// below the secp256k1 group order (probability about 2^-128).
+    pub fn new(
+        public_key: Affine,
+        digest: &[u8; 32],
+        signature: Signature,
+    ) -> anyhow::Result<Self> {
+        if !secp256k1::verify(&public_key, digest, &signature) {
+            anyhow::bail!("Invalid ECDSA signature");
+        }
+
+        let mut digest_le = *digest;
+        digest_le.reverse();
+        if secp256k1::message_scalar(digest)
+            .into_bigint()
+            .to_bytes_le()
+            != digest_le
+        {
+            anyhow::bail!("Digest is not below the secp256k1 group order");
+        }
+
+        let (digest_hi, digest_lo) = limbs(&digest_le);
+        Ok(Self {
+            digest_hi,
+            digest_lo,
+            key_hash: key_hash(&public_key)?,
+            public_key,
+            signature,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.digest_hi, self.digest_lo, self.key_hash]
+    }
+}
+
+impl<F: PrimeField> Zeroize for EcdsaCircuit<F> {
+    fn zeroize(&mut self) {
+        self.public_key = Affine::generator();
+        self.signature.r.zeroize();
+        self.signature.s.zeroize();
+    }
+}
+
+zeroize_on_drop!(impl[F: PrimeField] EcdsaCircuit<F>);
+
+impl<F: PrimeField + Absorb> ConstraintSynthesizer<F> for EcdsaCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let digest_hi = FpVar::new_input(ns!(cs, "digest_hi"), || Ok(self.digest_hi))?;
+        let digest_lo = FpVar::new_input(ns!(cs, "digest_lo"), || Ok(self.digest_lo))?;
+        let key_hash = FpVar::new_input(ns!(cs, "key_hash"), || Ok(self.key_hash))?;
+
+        // Private inputs
+        let public_key = PointVar::new_witness(ns!(cs, "public_key").cs(), || Ok(self.public_key))?;
+        let z = ScalarVar::new_witness(ns!(cs, "z"), || {
+            let mut digest = self.digest_lo.into_bigint().to_bytes_le()[..16].to_vec();
+            digest.extend_from_slice(&self.digest_hi.into_bigint().to_bytes_le()[..16]);
+            Ok(secp256k1::Fr::from_le_bytes_mod_order(&digest))
+        })?;
+        let r = ScalarVar::new_witness(ns!(cs, "r"), || Ok(self.signature.r))?;
+        let s = ScalarVar::new_witness(ns!(cs, "s"), || Ok(self.signature.s))?;
+
+        {
+            let _ns = ns!(cs, "key_hash == Poseidon(limbs(public_key))");
+            let mut limbs = Vec::with_capacity(4);
+            for coordinate in [&public_key.x, &public_key.y] {
+                let bits = coordinate.to_bits_le()?;
+                limbs.push(Boolean::le_bits_to_fp(&bits[128..])?);
+                limbs.push(Boolean::le_bits_to_fp(&bits[..128])?);
+            }
+            poseidon::hash_gadget(cs.clone(), &limbs)?.enforce_equal(&key_hash)?;
+        }
+
+        {
+            let _ns = ns!(cs, "z == digest_hi || digest_lo");
+            let bits = z.to_bits_le()?;
+            Boolean::le_bits_to_fp(&bits[128..])?.enforce_equal(&digest_hi)?;
+            Boolean::le_bits_to_fp(&bits[..128])?.enforce_equal(&digest_lo)?;
+        }
+
+        let _ns = ns!(cs, "EcdsaVerify(public_key, z, r, s)");
+        ecdsa::enforce_valid_signature(cs.clone(), &public_key, &z, &r, &s)
+    }
+}
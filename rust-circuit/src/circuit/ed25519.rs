@@ -0,0 +1,228 @@
+use crate::circuit::{airdrop, metadata, zeroize_on_drop};
+use crate::ed25519::{self, PUBLIC_KEY_LEN, SIGNATURE_LEN};
+use crate::gadgets::ed25519::{PointVar, ScalarVar};
+use crate::merkle::{MerklePath, MerklePathVar, MerkleTree};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{
+    boolean::Boolean,
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget, ToBitsGadget},
+    uint8::UInt8,
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef, SynthesisError},
+};
+
+/// Length of a signed message: Sui signs the 32-byte Blake2b digest of an
+/// intent message.
+pub const MESSAGE_LEN: usize = 32;
+
+/// Hashes an encoded public key into the field, as [`airdrop::address_hash`]
+/// does for Sui addresses: the registry tree leaf of the key.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn key_hash<F: PrimeField + Absorb>(public_key: &[u8; PUBLIC_KEY_LEN]) -> anyhow::Result<F> {
+    airdrop::address_hash(public_key)
+}
+
+/// Builds the registry tree whose root is published, with one leaf per key
+/// in list order.
+///
+/// # Errors
+/// Returns error if a key is not a point of the prime-order subgroup, the
+/// list does not fit in a tree of `depth` levels, or hashing fails.
+pub fn build_tree<F: PrimeField + Absorb>(
+    depth: usize,
+    public_keys: &[[u8; PUBLIC_KEY_LEN]],
+) -> anyhow::Result<MerkleTree<F>> {
+    let leaves = public_keys
+        .iter()
+        .map(|public_key| {
+            if let Err(e) = ed25519::decompress(public_key) {
+                anyhow::bail!("Invalid Ed25519 key 0x{}: {}", hex::encode(public_key), e);
+            }
+            key_hash(public_key)
+        })
+        .collect::<anyhow::Result<_>>()?;
+    MerkleTree::new(depth, leaves)
+}
+
+/// The public inputs a message is given as: its first and last 16 bytes as
+/// big-endian integers.
+pub fn message_inputs<F: PrimeField>(message: &[u8; MESSAGE_LEN]) -> (F, F) {
+    let (high, low) = message.split_at(16);
+    (
+        F::from_be_bytes_mod_order(high),
+        F::from_be_bytes_mod_order(low),
+    )
+}
+
+/// The big-endian integer of `bytes`, which must fit in the field.
+fn pack_be<F: PrimeField>(bytes: &[UInt8<F>]) -> r1cs::Result<FpVar<F>> {
+    let mut bits = Vec::with_capacity(bytes.len() * 8);
+    for byte in bytes.iter().rev() {
+        bits.extend(byte.to_bits_le()?);
+    }
+    Boolean::le_bits_to_fp(&bits)
+}
+
+/// Proves that a valid Ed25519 signature over the 32-byte `message` exists
+/// for some key in the registry behind `root`, without revealing the key or
+/// the signature.
+///
+/// The signature is verified in the circuit as [`ed25519::verify`] does,
+/// with edwards25519 arithmetic emulated in the circuit's field. Keys are
+/// checked to be in the prime-order subgroup by [`build_tree`], not in the
+/// circuit, so `root` must come from it. Costs several million constraints.
+#[derive(Debug, Clone)]
+pub struct Ed25519SignatureCircuit<const DEPTH: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub root: F,
+    pub message_hi: F,
+    pub message_lo: F,
+
+    // Private inputs
+    pub public_key: [u8; PUBLIC_KEY_LEN],
+    pub signature: [u8; SIGNATURE_LEN],
+    pub path: MerklePath<F>,
+}
+
+metadata! {
+    impl<const DEPTH: usize> Ed25519SignatureCircuit<DEPTH> {
+        name: "ed25519-signature",
+        version: "2.0.0",
+        public_inputs: ["root", "message_hi", "message_lo"],
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> Ed25519SignatureCircuit<DEPTH, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        let base_point = ed25519::compress(&ed25519::Affine::generator());
+        let mut signature = [0u8; SIGNATURE_LEN];
+        signature[..32].copy_from_slice(&base_point);
+        Self {
+            root: F::ZERO,
+            message_hi: F::ZERO,
+            message_lo: F::ZERO,
+            public_key: base_point,
+            signature,
+            path: MerklePath::empty(DEPTH),
+        }
+    }
+
+    /// Creates a proof that the key at `index` of `tree` signed `message`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `tree` does not have `DEPTH` levels
+    /// - `index` is out of range, or the leaf there is not `public_key`
+    /// - the signature does not verify
+    pub fn new(
+        tree: &MerkleTree<F>,
+        index: usize,
+        public_key: &[u8; PUBLIC_KEY_LEN],
+        message: &[u8; MESSAGE_LEN],
+        signature: &[u8; SIGNATURE_LEN],
+    ) -> anyhow::Result<Self> {
+        if tree.depth() != DEPTH {
+            anyhow::bail!("Expected a tree of depth {}, got {}", DEPTH, tree.depth());
+        }
+        let path = tree.path(index)?;
+        if tree.leaves()[index] != key_hash(public_key)? {
+            anyhow::bail!("Leaf {} is not key 0x{}", index, hex::encode(public_key));
+        }
+        if !ed25519::verify(public_key, message, signature) {
+            anyhow::bail!(
+                "Invalid Ed25519 signature for key 0x{}",
+                hex::encode(public_key)
+            );
+        }
+
+        let (message_hi, message_lo) = message_inputs(message);
+        Ok(Self {
+            root: tree.root(),
+            message_hi,
+            message_lo,
+            public_key: *public_key,
+            signature: *signature,
+            path,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.root, self.message_hi, self.message_lo]
+    }
+
+    /// The message bytes behind `message_hi` and `message_lo`.
+    fn message(&self) -> Vec<u8> {
+        [self.message_hi, self.message_lo]
+            .iter()
+            .flat_map(|half| {
+                let bytes = half.into_bigint().to_bytes_be();
+                bytes[bytes.len() - 16..].to_vec()
+            })
+            .collect()
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEPTH: usize, F: PrimeField] Ed25519SignatureCircuit<DEPTH, F> {
+        public_key,
+        signature,
+        path,
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for Ed25519SignatureCircuit<DEPTH, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.path.siblings.len() != DEPTH {
+            return Err(SynthesisError::Unsatisfiable);
+        }
+
+        let root = FpVar::new_input(ns!(cs, "root"), || Ok(self.root))?;
+        let message_hi = FpVar::new_input(ns!(cs, "message_hi"), || Ok(self.message_hi))?;
+        let message_lo = FpVar::new_input(ns!(cs, "message_lo"), || Ok(self.message_lo))?;
+
+        // Private inputs
+        let decompress = |bytes: &[u8]| {
+            let bytes = bytes
+                .try_into()
+                .map_err(|_| SynthesisError::Unsatisfiable)?;
+            ed25519::decompress(bytes).map_err(|_| SynthesisError::Unsatisfiable)
+        };
+        let public_key =
+            PointVar::new_witness(ns!(cs, "public_key").cs(), || decompress(&self.public_key))?;
+        let r = PointVar::new_witness(ns!(cs, "R").cs(), || decompress(&self.signature[..32]))?;
+        let s = ScalarVar::new_witness(ns!(cs, "S"), || {
+            Ok(ed25519::Fr::from_le_bytes_mod_order(&self.signature[32..]))
+        })?;
+        let message = UInt8::new_witness_vec(ns!(cs, "message"), &self.message())?;
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+
+        {
+            let _ns = ns!(cs, "message == message_hi || message_lo");
+            pack_be(&message[..16])?.enforce_equal(&message_hi)?;
+            pack_be(&message[16..])?.enforce_equal(&message_lo)?;
+        }
+
+        {
+            let _ns = ns!(cs, "root == MerkleRoot(Poseidon(public_key))");
+            let key = public_key.to_bytes()?;
+            let leaf =
+                poseidon::hash_gadget(cs.clone(), &[pack_be(&key[..16])?, pack_be(&key[16..])?])?;
+            path.root(cs.clone(), &leaf)?.enforce_equal(&root)?;
+        }
+
+        let _ns = ns!(cs, "Ed25519Verify(public_key, message, R, S)");
+        crate::gadgets::ed25519::enforce_valid_signature(cs.clone(), &public_key, &message, &r, &s)
+    }
+}
@@ -0,0 +1,152 @@
+use crate::babyjubjub::{self, Affine, Ciphertext};
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::{babyjubjub::PointVar, comparison, elgamal};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_ec::AffineRepr;
+use ark_ff::AdditiveGroup;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget, ToBitsGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Messages are range checked to this many bits, the width of a Sui `u64`
+/// coin value, so that the key holder can always decrypt them.
+pub const MESSAGE_BITS: usize = 64;
+
+/// Proves that `ciphertext` is an exponential ElGamal encryption, under the
+/// public key `public_key`, of the message committed to in
+/// `commitment = Poseidon(message, salt)`.
+///
+/// With a regulator's key, transfers stay private while the regulator can
+/// decrypt the amount with [`babyjubjub::decrypt`]. Points are Baby Jubjub
+/// points, so the circuit only exists over BN254.
+#[derive(Debug, Clone)]
+pub struct ElGamalCircuit {
+    // Public inputs
+    pub public_key: Affine,
+    pub ciphertext: Ciphertext,
+    pub commitment: Fr,
+
+    // Private inputs
+    pub message: Fr,
+    pub randomness: Fr,
+    pub salt: Fr,
+}
+
+metadata! {
+    impl ElGamalCircuit {
+        name: "elgamal-encryption",
+        version: "1.0.0",
+        public_inputs: [
+            "public_key_x",
+            "public_key_y",
+            "c1_x",
+            "c1_y",
+            "c2_x",
+            "c2_y",
+            "commitment",
+        ],
+    }
+}
+
+impl ElGamalCircuit {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            public_key: Affine::generator(),
+            ciphertext: Ciphertext {
+                c1: Affine::zero(),
+                c2: Affine::zero(),
+            },
+            commitment: Fr::ZERO,
+            message: Fr::ZERO,
+            randomness: Fr::ZERO,
+            salt: Fr::ZERO,
+        }
+    }
+
+    /// Creates a circuit, encrypting and committing to `message`.
+    ///
+    /// # Errors
+    /// Returns error if `public_key` is not a non-zero point of the prime-order
+    /// subgroup, or hashing fails.
+    pub fn new(public_key: Affine, message: u64, randomness: Fr, salt: Fr) -> anyhow::Result<Self> {
+        if public_key.is_zero()
+            || !public_key.is_on_curve()
+            || !public_key.is_in_correct_subgroup_assuming_on_curve()
+        {
+            anyhow::bail!("Public key is not in the Baby Jubjub subgroup");
+        }
+
+        let message = Fr::from(message);
+        Ok(Self {
+            public_key,
+            ciphertext: babyjubjub::encrypt(&public_key, message, randomness),
+            commitment: Self::commit(message, salt)?,
+            message,
+            randomness,
+            salt,
+        })
+    }
+
+    /// The public commitment to `message`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commit(message: Fr, salt: Fr) -> anyhow::Result<Fr> {
+        poseidon::hash(&[message, salt])
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<Fr> {
+        [self.public_key, self.ciphertext.c1, self.ciphertext.c2]
+            .iter()
+            .flat_map(|point| [point.x, point.y])
+            .chain([self.commitment])
+            .collect()
+    }
+}
+
+zeroize_on_drop! {
+    impl[] ElGamalCircuit { message, randomness, salt }
+}
+
+impl ConstraintSynthesizer<Fr> for ElGamalCircuit {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> r1cs::Result<()> {
+        let public_key = PointVar::new_input(ns!(cs, "public_key"), || Ok(self.public_key))?;
+        let c1 = PointVar::new_input(ns!(cs, "c1"), || Ok(self.ciphertext.c1))?;
+        let c2 = PointVar::new_input(ns!(cs, "c2"), || Ok(self.ciphertext.c2))?;
+        let commitment = FpVar::new_input(ns!(cs, "commitment"), || Ok(self.commitment))?;
+
+        // Private inputs
+        let message = FpVar::new_witness(ns!(cs, "message"), || Ok(self.message))?;
+        let randomness = FpVar::new_witness(ns!(cs, "randomness"), || Ok(self.randomness))?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "commitment == Poseidon(message, salt)");
+            poseidon::hash_gadget(cs.clone(), &[message.clone(), salt])?
+                .enforce_equal(&commitment)?;
+        }
+
+        let message_bits = {
+            let _ns = ns!(cs, "message < 2^64");
+            comparison::enforce_bit_length(&message, MESSAGE_BITS)?
+        };
+
+        let _ns = ns!(cs, "(c1, c2) == ElGamal(public_key, message, randomness)");
+        elgamal::enforce_encryption(
+            &public_key,
+            &c1,
+            &c2,
+            &message_bits,
+            &randomness.to_bits_le()?,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use crate::circuit::metadata;
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Proves `output = H^STEPS(input)`, with `H` the one-input Poseidon hash.
+///
+/// Computing the chain takes `STEPS` sequential hashes while checking the
+/// proof does not, which makes it a lightweight sequential-work commitment,
+/// e.g. a VDF-lite delay or a rate-limiting nullifier. Nothing here is
+/// secret; each step costs about 240 constraints.
+#[derive(Debug, Clone)]
+pub struct HashChainCircuit<const STEPS: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub input: F,
+    pub output: F,
+}
+
+metadata! {
+    impl<const STEPS: usize> HashChainCircuit<STEPS> {
+        name: "hash-chain",
+        version: "1.0.0",
+        public_inputs: ["input", "output"],
+    }
+}
+
+impl<const STEPS: usize, F: PrimeField + Absorb> HashChainCircuit<STEPS, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            input: F::ZERO,
+            output: F::ZERO,
+        }
+    }
+
+    /// Creates a circuit, hashing `input` `STEPS` times.
+    ///
+    /// # Errors
+    /// Returns error if `STEPS` is 0 or hashing fails.
+    pub fn new(input: F) -> anyhow::Result<Self> {
+        if STEPS == 0 {
+            anyhow::bail!("Hash chain must have at least one step");
+        }
+
+        Ok(Self {
+            input,
+            output: Self::compute(input)?,
+        })
+    }
+
+    /// Evaluates the chain natively: `H^STEPS(input)`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn compute(input: F) -> anyhow::Result<F> {
+        (0..STEPS).try_fold(input, |x, _| poseidon::hash(&[x]))
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.input, self.output]
+    }
+}
+
+impl<const STEPS: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for HashChainCircuit<STEPS, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let input = FpVar::new_input(ns!(cs, "input"), || Ok(self.input))?;
+        let output = FpVar::new_input(ns!(cs, "output"), || Ok(self.output))?;
+
+        let _ns = ns!(cs, "output == H^STEPS(input)");
+        (0..STEPS)
+            .try_fold(input, |x, _| poseidon::hash_gadget(cs.clone(), &[x]))?
+            .enforce_equal(&output)?;
+
+        Ok(())
+    }
+}
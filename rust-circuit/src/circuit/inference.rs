@@ -0,0 +1,219 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::{
+    comparison,
+    signed::{self, Int32Var},
+};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, CondSelectGadget, EqGadget, R1CSVar},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Fixed-point values are `i32`s scaled by `2^FRACTION_BITS`, so `1.5` is
+/// `3 << 15`.
+pub const FRACTION_BITS: usize = 16;
+
+/// Proves `outputs = ReLU(weights * input + biases)` for a single dense layer
+/// of fixed-point values, with the input kept private and committed to in
+/// `input_commitment = Poseidon(input, salt)`.
+///
+/// E.g. a credit score or risk tier computed by a published model from
+/// private features. Products are rescaled by flooring to `FRACTION_BITS`
+/// fractional bits; an output outside the `i32` range leaves the circuit
+/// unsatisfied. Range checks cost 33 constraints per weight, bias and input,
+/// and each output about 90 more.
+#[derive(Debug, Clone)]
+pub struct InferenceCircuit<const INPUTS: usize, const OUTPUTS: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub weights: [[i32; INPUTS]; OUTPUTS],
+    pub biases: [i32; OUTPUTS],
+    pub outputs: [i32; OUTPUTS],
+    pub input_commitment: F,
+
+    // Private inputs
+    pub input: [i32; INPUTS],
+    pub salt: F,
+}
+
+metadata! {
+    impl<const INPUTS: usize, const OUTPUTS: usize> InferenceCircuit<INPUTS, OUTPUTS> {
+        name: "dense-relu-inference",
+        version: "1.0.0",
+    }
+}
+
+impl<const INPUTS: usize, const OUTPUTS: usize, F: PrimeField + Absorb>
+    InferenceCircuit<INPUTS, OUTPUTS, F>
+{
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            weights: [[0; INPUTS]; OUTPUTS],
+            biases: [0; OUTPUTS],
+            outputs: [0; OUTPUTS],
+            input_commitment: F::ZERO,
+            input: [0; INPUTS],
+            salt: F::ZERO,
+        }
+    }
+
+    /// Creates a circuit, evaluating the layer on `input` and committing to it.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - an output overflows the `i32` range
+    /// - hashing fails
+    pub fn new(
+        weights: [[i32; INPUTS]; OUTPUTS],
+        biases: [i32; OUTPUTS],
+        input: [i32; INPUTS],
+        salt: F,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            outputs: Self::evaluate(&weights, &biases, &input)?,
+            input_commitment: Self::commit(&input, salt)?,
+            weights,
+            biases,
+            input,
+            salt,
+        })
+    }
+
+    /// `ReLU(weights * input + biases)`, as computed in-circuit.
+    ///
+    /// # Errors
+    /// Returns error if an output overflows the `i32` range.
+    pub fn evaluate(
+        weights: &[[i32; INPUTS]; OUTPUTS],
+        biases: &[i32; OUTPUTS],
+        input: &[i32; INPUTS],
+    ) -> anyhow::Result<[i32; OUTPUTS]> {
+        let mut outputs = [0; OUTPUTS];
+        for (output, (row, bias)) in outputs.iter_mut().zip(weights.iter().zip(biases)) {
+            let sum = row
+                .iter()
+                .zip(input)
+                .map(|(&w, &x)| i128::from(w) * i128::from(x))
+                .sum::<i128>()
+                + (i128::from(*bias) << FRACTION_BITS);
+            let rescaled = i32::try_from(sum >> FRACTION_BITS)
+                .map_err(|_| anyhow::anyhow!("Output overflows the i32 range"))?;
+            *output = rescaled.max(0);
+        }
+        Ok(outputs)
+    }
+
+    /// The public commitment to `input`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commit(input: &[i32; INPUTS], salt: F) -> anyhow::Result<F> {
+        let mut inputs = input
+            .iter()
+            .map(|&x| signed::to_field(x.into()))
+            .collect::<Vec<_>>();
+        inputs.push(salt);
+        poseidon::hash(&inputs)
+    }
+
+    /// The weights row by row, the biases, the outputs, then the input
+    /// commitment; signed values in their [`signed::to_field`] encoding.
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        self.weights
+            .iter()
+            .flatten()
+            .chain(&self.biases)
+            .chain(&self.outputs)
+            .map(|&x| signed::to_field(x.into()))
+            .chain([self.input_commitment])
+            .collect()
+    }
+}
+
+zeroize_on_drop! {
+    impl[const INPUTS: usize, const OUTPUTS: usize, F: PrimeField]
+        InferenceCircuit<INPUTS, OUTPUTS, F> { input, salt }
+}
+
+impl<const INPUTS: usize, const OUTPUTS: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for InferenceCircuit<INPUTS, OUTPUTS, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let weights = self
+            .weights
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&w| Int32Var::new_input(ns!(cs, "weight"), || Ok(i128::from(w))))
+                    .collect::<r1cs::Result<Vec<_>>>()
+            })
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let biases = self
+            .biases
+            .iter()
+            .map(|&b| Int32Var::new_input(ns!(cs, "bias"), || Ok(i128::from(b))))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|&y| FpVar::new_input(ns!(cs, "output"), || Ok(signed::to_field::<F>(y.into()))))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let input_commitment =
+            FpVar::new_input(ns!(cs, "input_commitment"), || Ok(self.input_commitment))?;
+
+        // Private inputs
+        let input = self
+            .input
+            .iter()
+            .map(|&x| Int32Var::new_witness(ns!(cs, "input"), || Ok(i128::from(x))))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "input_commitment == Poseidon(input, salt)");
+            let mut inputs = input.iter().map(|x| x.to_fp().clone()).collect::<Vec<_>>();
+            inputs.push(salt);
+            poseidon::hash_gadget(cs.clone(), &inputs)?.enforce_equal(&input_commitment)?;
+        }
+
+        let _ns = ns!(cs, "outputs == ReLU(weights * input + biases)");
+        let scale = F::from(1u64 << FRACTION_BITS);
+        for ((row, bias), output) in weights.iter().zip(&biases).zip(&outputs) {
+            // Products of 32-bit values cannot wrap around the modulus
+            let sum = row
+                .iter()
+                .zip(&input)
+                .map(|(w, x)| w.to_fp() * x.to_fp())
+                .fold(bias.to_fp() * scale, |acc, product| acc + product);
+
+            // sum == rescaled * 2^FRACTION_BITS + remainder, flooring the division
+            let value = sum
+                .value()
+                .and_then(|sum| signed::from_field(sum).ok_or(r1cs::SynthesisError::Unsatisfiable));
+            let rescaled = Int32Var::new_witness(ns!(cs, "rescaled"), || {
+                value.map(|sum| sum >> FRACTION_BITS)
+            })?;
+            let remainder = FpVar::new_witness(ns!(cs, "remainder"), || {
+                value.map(|sum| F::from((sum & ((1 << FRACTION_BITS) - 1)) as u64))
+            })?;
+            comparison::enforce_bit_length(&remainder, FRACTION_BITS)?;
+            sum.enforce_equal(&(rescaled.to_fp() * scale + &remainder))?;
+
+            FpVar::conditionally_select(
+                &rescaled.is_negative()?,
+                &FpVar::zero(),
+                rescaled.to_fp(),
+            )?
+            .enforce_equal(output)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets;
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::{crh::sha256::constraints::Sha256Gadget, sponge::Absorb};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    boolean::Boolean,
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget, FieldVar},
+    uint8::UInt8,
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+/// Bytes packed into one field element, so that the packing never wraps.
+const PACKED_BYTES: usize = 31;
+
+/// The shape of the tokens a [`JwtClaimCircuit`] is generated for. Keys only
+/// prove tokens of exactly this shape, e.g. the ID tokens of one issuer and
+/// audience.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwtLayout {
+    /// Length of the base64url header segment.
+    pub header_len: usize,
+    /// Length of the base64url payload segment.
+    pub payload_len: usize,
+    /// Name of the committed string claim, e.g. `sub` or `aud`.
+    pub claim_name: String,
+    /// Length of the committed claim's value.
+    pub claim_len: usize,
+    /// Length of the `nonce` claim's value, at most 31.
+    pub nonce_len: usize,
+}
+
+impl JwtLayout {
+    /// The layout of `token` when committing to the claim `claim_name`.
+    ///
+    /// # Errors
+    /// Returns error if `token` is malformed or lacks either claim.
+    pub fn of(token: &str, claim_name: &str) -> anyhow::Result<Self> {
+        let (header, payload) = split(token)?;
+        let decoded = decode_payload(payload)?;
+
+        Ok(Self {
+            header_len: header.len(),
+            payload_len: payload.len(),
+            claim_name: claim_name.to_string(),
+            claim_len: find_claim(&decoded, claim_name)?.1.len(),
+            nonce_len: find_claim(&decoded, "nonce")?.1.len(),
+        })
+    }
+}
+
+/// SHA-256 of the signing input `header.payload` as two 16-byte big-endian
+/// limbs, the public form of the digest the issuer signs.
+///
+/// # Errors
+/// Returns error if `token` is malformed.
+pub fn digest_limbs<F: PrimeField>(token: &str) -> anyhow::Result<(F, F)> {
+    let (header, payload) = split(token)?;
+    let digest = Sha256::new()
+        .chain_update(header)
+        .chain_update(".")
+        .chain_update(payload)
+        .finalize();
+
+    Ok((
+        F::from_be_bytes_mod_order(&digest[..16]),
+        F::from_be_bytes_mod_order(&digest[16..]),
+    ))
+}
+
+/// The public form of a `nonce` claim: its bytes as a big-endian integer.
+///
+/// # Errors
+/// Returns error if the nonce is longer than 31 bytes.
+pub fn pack_nonce<F: PrimeField>(nonce: &str) -> anyhow::Result<F> {
+    if nonce.len() > PACKED_BYTES {
+        anyhow::bail!(
+            "Nonce of {} bytes exceeds {} bytes",
+            nonce.len(),
+            PACKED_BYTES
+        );
+    }
+    Ok(F::from_be_bytes_mod_order(nonce.as_bytes()))
+}
+
+/// The public commitment to a claim value: `Poseidon(chunks..., salt)` over
+/// its 31-byte big-endian chunks.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn commit_claim<F: PrimeField + Absorb>(value: &[u8], salt: F) -> anyhow::Result<F> {
+    let mut inputs = value
+        .chunks(PACKED_BYTES)
+        .map(F::from_be_bytes_mod_order)
+        .collect::<Vec<_>>();
+    inputs.push(salt);
+    poseidon::hash(&inputs)
+}
+
+/// Proves knowledge of a JWT with SHA-256 signing-input digest
+/// `(digest_hi, digest_lo)` whose payload carries the public `nonce` and a
+/// string claim (e.g. `sub` or `aud`) committed to in `claim_commitment`.
+///
+/// As in Sui zkLogin, the nonce binds the token to a key or action chosen by
+/// the prover. The issuer's RS256/ES256 signature over the digest is checked
+/// against its published key outside the circuit; the digest reveals nothing
+/// about the payload.
+///
+/// Claims are matched as `"name":"value"` in compact JSON, preceded by `{` or
+/// `,` and followed by `,` or `}`, so a match cannot start inside another
+/// string, and values may not contain `"`.
+#[derive(Debug, Clone)]
+pub struct JwtClaimCircuit<F: PrimeField = Fr> {
+    // Public inputs
+    pub digest_hi: F,
+    pub digest_lo: F,
+    pub nonce: F,
+    pub claim_commitment: F,
+
+    // Private inputs
+    /// `header.payload`, base64url-encoded.
+    pub signing_input: Vec<u8>,
+    /// Offsets in the decoded payload of the `{` or `,` before each claim.
+    pub claim_offset: usize,
+    pub nonce_offset: usize,
+    pub salt: F,
+
+    // Options
+    pub layout: JwtLayout,
+}
+
+metadata! {
+    impl JwtClaimCircuit {
+        name: "jwt-claim",
+        version: "1.0.0",
+        public_inputs: ["digest_hi", "digest_lo", "nonce", "claim_commitment"],
+    }
+}
+
+impl<F: PrimeField + Absorb> JwtClaimCircuit<F> {
+    /// Creates an empty circuit for the setup phase of tokens shaped as `layout`.
+    pub fn empty(layout: JwtLayout) -> Self {
+        Self {
+            digest_hi: F::ZERO,
+            digest_lo: F::ZERO,
+            nonce: F::ZERO,
+            claim_commitment: F::ZERO,
+            signing_input: vec![0; layout.header_len + 1 + layout.payload_len],
+            claim_offset: 0,
+            nonce_offset: 0,
+            salt: F::ZERO,
+            layout,
+        }
+    }
+
+    /// Creates a circuit for `token`, committing to its `layout.claim_name`
+    /// claim with `salt`.
+    ///
+    /// # Errors
+    /// Returns error if `token` is malformed, lacks either claim, or is not
+    /// shaped as `layout`.
+    pub fn new(layout: &JwtLayout, token: &str, salt: F) -> anyhow::Result<Self> {
+        let actual = JwtLayout::of(token, &layout.claim_name)?;
+        if actual != *layout {
+            anyhow::bail!(
+                "Token layout {:?} does not match the circuit layout {:?}",
+                actual,
+                layout
+            );
+        }
+        if layout.nonce_len > PACKED_BYTES {
+            anyhow::bail!("Nonce exceeds {} bytes", PACKED_BYTES);
+        }
+
+        let (header, payload) = split(token)?;
+        let decoded = decode_payload(payload)?;
+        let (claim_offset, claim) = find_claim(&decoded, &layout.claim_name)?;
+        let (nonce_offset, nonce) = find_claim(&decoded, "nonce")?;
+        let (digest_hi, digest_lo) = digest_limbs(token)?;
+
+        Ok(Self {
+            digest_hi,
+            digest_lo,
+            nonce: F::from_be_bytes_mod_order(nonce),
+            claim_commitment: commit_claim(claim, salt)?,
+            signing_input: format!("{}.{}", header, payload).into_bytes(),
+            claim_offset,
+            nonce_offset,
+            salt,
+            layout: layout.clone(),
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![
+            self.digest_hi,
+            self.digest_lo,
+            self.nonce,
+            self.claim_commitment,
+        ]
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField] JwtClaimCircuit<F> { signing_input, claim_offset, nonce_offset, salt }
+}
+
+impl<F: PrimeField + Absorb> ConstraintSynthesizer<F> for JwtClaimCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let layout = &self.layout;
+        if self.signing_input.len() != layout.header_len + 1 + layout.payload_len
+            || layout.nonce_len > PACKED_BYTES
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let digest_hi = FpVar::new_input(ns!(cs, "digest_hi"), || Ok(self.digest_hi))?;
+        let digest_lo = FpVar::new_input(ns!(cs, "digest_lo"), || Ok(self.digest_lo))?;
+        let nonce = FpVar::new_input(ns!(cs, "nonce"), || Ok(self.nonce))?;
+        let claim_commitment =
+            FpVar::new_input(ns!(cs, "claim_commitment"), || Ok(self.claim_commitment))?;
+
+        // Private inputs
+        let signing_input = UInt8::new_witness_vec(ns!(cs, "signing_input"), &self.signing_input)?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "(digest_hi, digest_lo) == SHA-256(signing_input)");
+            let digest = Sha256Gadget::digest(&signing_input)?;
+            gadgets::bytes::pack_be(&digest.0[..16])?.enforce_equal(&digest_hi)?;
+            gadgets::bytes::pack_be(&digest.0[16..])?.enforce_equal(&digest_lo)?;
+        }
+
+        let payload = {
+            let _ns = ns!(cs, "payload == base64url_decode(signing_input)");
+            signing_input[layout.header_len].enforce_equal(&UInt8::constant(b'.'))?;
+            gadgets::base64::decode_url(&signing_input[layout.header_len + 1..])?
+                .iter()
+                .map(gadgets::bytes::to_fp)
+                .collect::<r1cs::Result<Vec<_>>>()?
+        };
+
+        {
+            let _ns = ns!(cs, "claim_commitment == Poseidon(claim, salt)");
+            let claim = enforce_claim(
+                cs.clone(),
+                &payload,
+                &layout.claim_name,
+                layout.claim_len,
+                self.claim_offset,
+            )?;
+            let mut inputs = claim.chunks(PACKED_BYTES).map(pack_fp).collect::<Vec<_>>();
+            inputs.push(salt);
+            poseidon::hash_gadget(cs.clone(), &inputs)?.enforce_equal(&claim_commitment)?;
+        }
+
+        let _ns = ns!(cs, "nonce claim == nonce");
+        let claim = enforce_claim(
+            cs.clone(),
+            &payload,
+            "nonce",
+            layout.nonce_len,
+            self.nonce_offset,
+        )?;
+        pack_fp(&claim).enforce_equal(&nonce)?;
+
+        Ok(())
+    }
+}
+
+/// Enforces that `payload` contains `"name":"value"` with a `len`-byte value
+/// at the private `offset` of the preceding `{` or `,`, and returns the value.
+///
+/// The window is selected with a one-hot vector, costing one constraint per
+/// payload byte and window byte.
+fn enforce_claim<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    payload: &[FpVar<F>],
+    name: &str,
+    len: usize,
+    offset: usize,
+) -> r1cs::Result<Vec<FpVar<F>>> {
+    let key = format!("\"{}\":\"", name).into_bytes();
+    let width = 1 + key.len() + len + 2;
+    if width > payload.len() {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    let positions = payload.len() - width + 1;
+    let selectors = (0..positions)
+        .map(|i| Boolean::new_witness(cs.clone(), || Ok(i == offset)))
+        .collect::<r1cs::Result<Vec<_>>>()?;
+    selectors
+        .iter()
+        .map(|s| FpVar::from(s.clone()))
+        .sum::<FpVar<F>>()
+        .enforce_equal(&FpVar::one())?;
+
+    let window = (0..width)
+        .map(|j| {
+            let mut byte = FpVar::zero();
+            for (i, selector) in selectors.iter().enumerate() {
+                byte += payload[i + j].clone() * FpVar::from(selector.clone());
+            }
+            byte
+        })
+        .collect::<Vec<_>>();
+
+    let byte = |b: u8| FpVar::constant(F::from(b));
+    (&window[0] - byte(b'{')).mul_equals(&(&window[0] - byte(b',')), &FpVar::zero())?;
+    for (actual, expected) in window[1..].iter().zip(&key) {
+        actual.enforce_equal(&byte(*expected))?;
+    }
+    let value = window[1 + key.len()..1 + key.len() + len].to_vec();
+    for char in &value {
+        char.enforce_not_equal(&byte(b'"'))?;
+    }
+    window[width - 2].enforce_equal(&byte(b'"'))?;
+    (&window[width - 1] - byte(b','))
+        .mul_equals(&(&window[width - 1] - byte(b'}')), &FpVar::zero())?;
+
+    Ok(value)
+}
+
+/// Packs selected byte values big-endian, as `gadgets::bytes::pack_be` does.
+fn pack_fp<F: PrimeField>(bytes: &[FpVar<F>]) -> FpVar<F> {
+    bytes
+        .iter()
+        .fold(FpVar::zero(), |acc, byte| acc * F::from(256u16) + byte)
+}
+
+/// Splits a token into its header and payload segments.
+fn split(token: &str) -> anyhow::Result<(&str, &str)> {
+    let mut segments = token.split('.');
+    match (segments.next(), segments.next()) {
+        (Some(header), Some(payload)) => Ok((header, payload)),
+        _ => anyhow::bail!("Malformed JWT: expected header.payload.signature"),
+    }
+}
+
+fn decode_payload(payload: &str) -> anyhow::Result<Vec<u8>> {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|e| anyhow::anyhow!("Failed to decode JWT payload: {}", e))
+}
+
+/// Finds the claim `"name":"value"` as matched by the circuit, returning the
+/// offset of the preceding `{` or `,` and the value.
+fn find_claim<'a>(payload: &'a [u8], name: &str) -> anyhow::Result<(usize, &'a [u8])> {
+    let key = format!("\"{}\":\"", name).into_bytes();
+    (1..payload.len())
+        .filter(|&start| {
+            payload[start..].starts_with(&key) && matches!(payload[start - 1], b'{' | b',')
+        })
+        .find_map(|start| {
+            let value_start = start + key.len();
+            let len = payload[value_start..].iter().position(|&b| b == b'"')?;
+            let end = value_start + len;
+            matches!(payload.get(end + 1), Some(b',' | b'}'))
+                .then(|| (start - 1, &payload[value_start..end]))
+        })
+        .ok_or_else(|| anyhow::anyhow!("JWT payload has no string claim '{}'", name))
+}
@@ -1,34 +1,134 @@
+pub mod age;
+pub mod airdrop;
+pub mod auction;
+pub mod batch_insert;
+pub mod blocklist;
+pub mod builder;
+pub mod credential;
+pub mod ecdsa;
+pub mod ed25519;
+pub mod elgamal;
+pub mod hash_chain;
+pub mod inference;
+pub mod jwt;
+pub mod opening;
+pub mod polynomial;
+pub mod semaphore;
+pub mod shuffle;
+pub mod sizes;
+pub mod solvency;
+pub mod transfer;
+pub mod vote;
+
+use crate::inspect;
 use ark_bn254::Fr;
-use ark_ff::AdditiveGroup;
+use ark_ff::PrimeField;
 use ark_r1cs_std::{
-    fields::fp::FpVar,
+    fields::{fp::FpVar, FieldVar},
     prelude::{AllocVar, EqGadget},
 };
 use ark_relations::{
     ns,
     r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
 };
-use ark_serialize::CanonicalSerialize;
 
+/// Declares the metadata constants of a circuit: the `NAME` of its
+/// statement, its `VERSION`, and, for circuits with a fixed set of public
+/// inputs, their `PUBLIC_INPUT_NAMES`.
+macro_rules! metadata {
+    (
+        impl$(<$(const $param:ident: usize),+>)? $circuit:ident$(<$($arg:ident),+>)? {
+            name: $name:literal,
+            version: $version:literal
+            $(, public_inputs: [$($input:literal),+ $(,)?])?
+            $(,)?
+        }
+    ) => {
+        impl$(<$(const $param: usize),+>)? $circuit$(<$($arg),+>)? {
+            /// Name of the statement proved by this circuit.
+            pub const NAME: &'static str = $name;
+            /// Bumped whenever the constraints change and the keys must be regenerated.
+            pub const VERSION: &'static str = $version;
+            $(
+                /// Names of the public inputs, in the order allocated by `generate_constraints()`.
+                pub const PUBLIC_INPUT_NAMES: &'static [&'static str] = &[$($input),+];
+            )?
+        }
+    };
+}
+pub(crate) use metadata;
+
+/// Implements `Drop` by zeroizing, and, given the private fields, `Zeroize`
+/// by zeroizing each of them. Without fields, `Zeroize` is left to the caller.
+macro_rules! zeroize_on_drop {
+    (
+        $(#[$attr:meta])*
+        impl[$($generics:tt)*] $circuit:ty { $($field:ident),+ $(,)? }
+    ) => {
+        $(#[$attr])*
+        impl<$($generics)*> zeroize::Zeroize for $circuit {
+            fn zeroize(&mut self) {
+                $(zeroize::Zeroize::zeroize(&mut self.$field);)+
+            }
+        }
+
+        $crate::circuit::zeroize_on_drop!(impl[$($generics)*] $circuit);
+    };
+    (impl[$($generics:tt)*] $circuit:ty) => {
+        impl<$($generics)*> Drop for $circuit {
+            fn drop(&mut self) {
+                zeroize::Zeroize::zeroize(self);
+            }
+        }
+    };
+}
+pub(crate) use zeroize_on_drop;
+
+/// Proves knowledge of factors `a`, `b` of the public value `c`.
+///
+/// Generic over the scalar field so that the same statement can be proved on
+/// any supported curve; defaults to BN254, the curve Sui verifies.
 #[derive(Debug, Clone)]
-pub struct Circuit {
+pub struct Circuit<F: PrimeField = Fr> {
     // Public Inputs
-    pub c: Fr,
+    pub c: F,
 
     // Private inputs
-    pub a: Fr,
-    pub b: Fr,
-    //
+    pub a: F,
+    pub b: F,
+
+    // Options
+    /// Additionally enforce `a, b ∉ {0, 1}`, so that the proof attests to a
+    /// non-trivial factorization of `c`. Changes the constraint system, so
+    /// keys must be generated for the same setting.
+    pub non_trivial: bool,
+}
+
+metadata! {
+    impl Circuit {
+        name: "multiplication",
+        version: "1.0.0",
+        public_inputs: ["c"],
+    }
 }
 
-impl Circuit {
+impl<F: PrimeField> Circuit<F> {
     /// Creates an empty circuit with all values set to zero.
     /// Used for setup phase and testing.
     pub fn empty() -> Self {
         Self {
-            c: Fr::ZERO,
-            a: Fr::ZERO,
-            b: Fr::ZERO,
+            c: F::ZERO,
+            a: F::ZERO,
+            b: F::ZERO,
+            non_trivial: false,
+        }
+    }
+
+    /// Creates an empty non-trivial factorization circuit for the setup phase.
+    pub fn empty_non_trivial() -> Self {
+        Self {
+            non_trivial: true,
+            ..Self::empty()
         }
     }
 
@@ -36,16 +136,47 @@ impl Circuit {
     ///
     /// # Errors
     /// Returns error if:
-    /// - Path indices exceed tree capacity (>= 2^LEVEL)
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(c: Fr, a: Fr, b: Fr) -> anyhow::Result<Self> {
-        Ok(Self { c, a, b })
+    /// - `non_trivial` is set and `a` or `b` is 0 or 1
+    pub fn new(c: F, a: F, b: F, non_trivial: bool) -> anyhow::Result<Self> {
+        if non_trivial {
+            for (name, factor) in [("a", a), ("b", b)] {
+                if factor == F::ZERO || factor == F::ONE {
+                    anyhow::bail!(
+                        "Factor {} must not be 0 or 1 in a non-trivial circuit",
+                        name
+                    );
+                }
+            }
+        }
+
+        Ok(Self {
+            c,
+            a,
+            b,
+            non_trivial,
+        })
     }
 
-    pub fn get_public_inputs(&self) -> Vec<Fr> {
+    /// Evaluates the statement natively: the public `c` for factors `a`, `b`.
+    pub fn compute_public(a: F, b: F) -> F {
+        a * b
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
         vec![self.c]
     }
 
+    /// SHA-256 of the synthesized R1CS matrices (see [`inspect::digest`]).
+    ///
+    /// Depends only on `non_trivial`, not on the values, so it identifies the
+    /// constraint system that keys must be generated for.
+    ///
+    /// # Errors
+    /// Returns error if constraint generation fails.
+    pub fn cs_digest(&self) -> anyhow::Result<[u8; 32]> {
+        inspect::digest(self.clone())
+    }
+
     pub fn get_public_inputs_serialized(&self) -> anyhow::Result<Vec<u8>> {
         let public_inputs = self.get_public_inputs();
         let mut serialized = Vec::new();
@@ -58,14 +189,29 @@ impl Circuit {
     }
 }
 
-impl ConstraintSynthesizer<Fr> for Circuit {
-    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> r1cs::Result<()> {
+zeroize_on_drop! {
+    /// Clears the private factors; the public `c` is left as is.
+    impl[F: PrimeField] Circuit<F> { a, b }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for Circuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
         let c = FpVar::new_input(ns!(cs, "c"), || Ok(self.c))?;
 
         // Private inputs
         let a = FpVar::new_witness(ns!(cs, "a"), || Ok(self.a))?;
         let b = FpVar::new_witness(ns!(cs, "b"), || Ok(self.b))?;
 
+        if self.non_trivial {
+            // x != 0 and x != 1 hold iff x and x - 1 have inverses
+            let _ns = ns!(cs, "non-trivial factors");
+            for factor in [&a, &b] {
+                let _ = factor.inverse()?;
+                let _ = (factor - F::ONE).inverse()?;
+            }
+        }
+
+        let _ns = ns!(cs, "c == a * b");
         c.enforce_equal(&(a * b))?;
 
         Ok(())
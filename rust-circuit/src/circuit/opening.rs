@@ -0,0 +1,95 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::commitment::{CommitmentScheme, PoseidonCommitment};
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use std::marker::PhantomData;
+
+/// Proves knowledge of `(value, blinding)` opening the public `commitment`
+/// under the scheme `S`.
+///
+/// On its own this shows the prover holds a committed value; statements
+/// about that value (ranges, membership, ...) reuse the same opening.
+#[derive(Debug, Clone)]
+pub struct CommitmentOpeningCircuit<F: PrimeField = Fr, S = PoseidonCommitment> {
+    // Public inputs
+    pub commitment: Vec<F>,
+
+    // Private inputs
+    pub value: F,
+    pub blinding: F,
+
+    _scheme: PhantomData<S>,
+}
+
+metadata! {
+    impl CommitmentOpeningCircuit {
+        name: "commitment-opening",
+        version: "1.0.0",
+    }
+}
+
+impl<F: PrimeField, S: CommitmentScheme<F>> CommitmentOpeningCircuit<F, S> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            commitment: vec![F::ZERO; S::OUTPUT_LEN],
+            value: F::ZERO,
+            blinding: F::ZERO,
+            _scheme: PhantomData,
+        }
+    }
+
+    /// Creates a circuit, computing the commitment to `value`.
+    ///
+    /// # Errors
+    /// Returns error if the commitment cannot be computed.
+    pub fn new(value: F, blinding: F) -> anyhow::Result<Self> {
+        Ok(Self {
+            commitment: S::commit(value, blinding)?,
+            value,
+            blinding,
+            _scheme: PhantomData,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        self.commitment.clone()
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField, S] CommitmentOpeningCircuit<F, S> { value, blinding }
+}
+
+impl<F: PrimeField, S: CommitmentScheme<F>> ConstraintSynthesizer<F>
+    for CommitmentOpeningCircuit<F, S>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.commitment.len() != S::OUTPUT_LEN {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let commitment = self
+            .commitment
+            .iter()
+            .map(|element| FpVar::new_input(ns!(cs, "commitment"), || Ok(*element)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        // Private inputs
+        let value = FpVar::new_witness(ns!(cs, "value"), || Ok(self.value))?;
+        let blinding = FpVar::new_witness(ns!(cs, "blinding"), || Ok(self.blinding))?;
+
+        let _ns = ns!(cs, "commitment == Commit(value, blinding)");
+        S::commit_gadget(cs.clone(), &value, &blinding)?.enforce_equal(&commitment)?;
+
+        Ok(())
+    }
+}
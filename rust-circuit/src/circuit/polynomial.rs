@@ -0,0 +1,142 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Proves `y = P(x)` for the polynomial `P` of degree at most `DEGREE` whose
+/// coefficients are committed to in `commitment = Poseidon(c_0, ..., c_DEGREE, salt)`.
+///
+/// E.g. a pricing curve published only as a commitment, with each quote
+/// proved against it. Evaluation costs `DEGREE` constraints by Horner's rule.
+#[derive(Debug, Clone)]
+pub struct PolynomialCircuit<const DEGREE: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub x: F,
+    pub y: F,
+    pub commitment: F,
+
+    // Private inputs
+    /// `c_0, ..., c_DEGREE`, lowest degree first.
+    pub coefficients: Vec<F>,
+    pub salt: F,
+}
+
+metadata! {
+    impl<const DEGREE: usize> PolynomialCircuit<DEGREE> {
+        name: "polynomial-evaluation",
+        version: "1.0.0",
+        public_inputs: ["x", "y", "commitment"],
+    }
+}
+
+impl<const DEGREE: usize, F: PrimeField + Absorb> PolynomialCircuit<DEGREE, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            x: F::ZERO,
+            y: F::ZERO,
+            commitment: F::ZERO,
+            coefficients: vec![F::ZERO; DEGREE + 1],
+            salt: F::ZERO,
+        }
+    }
+
+    /// Creates a circuit, evaluating and committing to `coefficients`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - there are more than `DEGREE + 1` coefficients
+    /// - hashing fails
+    pub fn new(coefficients: &[F], x: F, salt: F) -> anyhow::Result<Self> {
+        if coefficients.len() > DEGREE + 1 {
+            anyhow::bail!(
+                "Expected at most {} coefficients, got {}",
+                DEGREE + 1,
+                coefficients.len()
+            );
+        }
+
+        let mut padded = coefficients.to_vec();
+        padded.resize(DEGREE + 1, F::ZERO);
+        Ok(Self {
+            x,
+            y: Self::evaluate(&padded, x),
+            commitment: Self::commit(&padded, salt)?,
+            coefficients: padded,
+            salt,
+        })
+    }
+
+    /// `P(x)` for coefficients lowest degree first.
+    pub fn evaluate(coefficients: &[F], x: F) -> F {
+        coefficients
+            .iter()
+            .rev()
+            .fold(F::ZERO, |acc, coefficient| acc * x + coefficient)
+    }
+
+    /// The public commitment to the `DEGREE + 1` coefficients.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commit(coefficients: &[F], salt: F) -> anyhow::Result<F> {
+        let mut inputs = coefficients.to_vec();
+        inputs.push(salt);
+        poseidon::hash(&inputs)
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.x, self.y, self.commitment]
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEGREE: usize, F: PrimeField] PolynomialCircuit<DEGREE, F> { coefficients, salt }
+}
+
+impl<const DEGREE: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for PolynomialCircuit<DEGREE, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.coefficients.len() != DEGREE + 1 {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let x = FpVar::new_input(ns!(cs, "x"), || Ok(self.x))?;
+        let y = FpVar::new_input(ns!(cs, "y"), || Ok(self.y))?;
+        let commitment = FpVar::new_input(ns!(cs, "commitment"), || Ok(self.commitment))?;
+
+        // Private inputs
+        let coefficients = self
+            .coefficients
+            .iter()
+            .map(|c| FpVar::new_witness(ns!(cs, "coefficient"), || Ok(*c)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "commitment == Poseidon(coefficients, salt)");
+            let mut inputs = coefficients.clone();
+            inputs.push(salt);
+            poseidon::hash_gadget(cs.clone(), &inputs)?.enforce_equal(&commitment)?;
+        }
+
+        let _ns = ns!(cs, "y == P(x)");
+        coefficients
+            .iter()
+            .rev()
+            .fold(FpVar::zero(), |acc, coefficient| acc * &x + coefficient)
+            .enforce_equal(&y)?;
+
+        Ok(())
+    }
+}
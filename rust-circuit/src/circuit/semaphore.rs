@@ -0,0 +1,226 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::select;
+use crate::merkle::{MerklePath, MerklePathVar};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use sha3::{Digest, Keccak256};
+
+/// Depth of Semaphore groups unless configured otherwise.
+pub const DEFAULT_DEPTH: usize = 20;
+
+/// Semaphore's `hash`: Keccak-256 of `data` shifted right by 8 bits to fit
+/// the field, applied to signals and external nullifiers before proving.
+///
+/// `data` is usually the 32-byte big-endian encoding of the value, as
+/// `toBeHex(value, 32)` in the Semaphore JS libraries.
+pub fn hash_to_field<F: PrimeField>(data: &[u8]) -> F {
+    F::from_be_bytes_mod_order(&Keccak256::digest(data)[..31])
+}
+
+/// The identity commitment added to groups:
+/// `Poseidon(Poseidon(identity_nullifier, identity_trapdoor))`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn identity_commitment<F: PrimeField + Absorb>(
+    identity_nullifier: F,
+    identity_trapdoor: F,
+) -> anyhow::Result<F> {
+    let secret = poseidon::circom_hash(&[identity_nullifier, identity_trapdoor])?;
+    poseidon::circom_hash(&[secret])
+}
+
+/// The nullifier hash revealed once per external nullifier:
+/// `Poseidon(external_nullifier, identity_nullifier)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn nullifier_hash<F: PrimeField + Absorb>(
+    external_nullifier: F,
+    identity_nullifier: F,
+) -> anyhow::Result<F> {
+    poseidon::circom_hash(&[external_nullifier, identity_nullifier])
+}
+
+/// The root of a Semaphore group containing `leaf` at `path`, hashing pairs
+/// with circomlib's Poseidon as `@semaphore-protocol/group` does.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn group_root<F: PrimeField + Absorb>(path: &MerklePath<F>, leaf: F) -> anyhow::Result<F> {
+    path.siblings
+        .iter()
+        .enumerate()
+        .try_fold(leaf, |node, (level, sibling)| {
+            if (path.index >> level) & 1 == 1 {
+                poseidon::circom_hash(&[*sibling, node])
+            } else {
+                poseidon::circom_hash(&[node, *sibling])
+            }
+        })
+}
+
+/// Proves membership of a Semaphore identity in the group with root
+/// `merkle_tree_root` and signals `signal_hash` under `external_nullifier`,
+/// revealing only `nullifier_hash`.
+///
+/// Matches the hashing of Semaphore v3's circuit, so identities and groups
+/// created with the Semaphore libraries can be reused as is; the proving and
+/// verifying keys are this crate's. Public inputs are in Semaphore's order.
+/// Groups have `DEPTH` levels, [`DEFAULT_DEPTH`] in Semaphore's deployments.
+#[derive(Debug, Clone)]
+pub struct SemaphoreCircuit<const DEPTH: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub merkle_tree_root: F,
+    pub nullifier_hash: F,
+    pub signal_hash: F,
+    pub external_nullifier: F,
+
+    // Private inputs
+    pub identity_nullifier: F,
+    pub identity_trapdoor: F,
+    pub path: MerklePath<F>,
+}
+
+metadata! {
+    impl<const DEPTH: usize> SemaphoreCircuit<DEPTH> {
+        name: "semaphore",
+        version: "1.0.0",
+        public_inputs: ["merkle_tree_root", "nullifier_hash", "signal_hash", "external_nullifier"],
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> SemaphoreCircuit<DEPTH, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            merkle_tree_root: F::ZERO,
+            nullifier_hash: F::ZERO,
+            signal_hash: F::ZERO,
+            external_nullifier: F::ZERO,
+            identity_nullifier: F::ZERO,
+            identity_trapdoor: F::ZERO,
+            path: MerklePath::empty(DEPTH),
+        }
+    }
+
+    /// Creates a circuit signalling `signal_hash` from the identity at `path`.
+    /// Both `signal_hash` and `external_nullifier` are already hashed with
+    /// [`hash_to_field`].
+    ///
+    /// # Errors
+    /// Returns error if `path` does not have `DEPTH` levels or hashing fails.
+    pub fn new(
+        identity_nullifier: F,
+        identity_trapdoor: F,
+        path: MerklePath<F>,
+        signal_hash: F,
+        external_nullifier: F,
+    ) -> anyhow::Result<Self> {
+        if path.siblings.len() != DEPTH {
+            anyhow::bail!(
+                "Expected a path of depth {}, got {}",
+                DEPTH,
+                path.siblings.len()
+            );
+        }
+        let commitment = identity_commitment(identity_nullifier, identity_trapdoor)?;
+
+        Ok(Self {
+            merkle_tree_root: group_root(&path, commitment)?,
+            nullifier_hash: nullifier_hash(external_nullifier, identity_nullifier)?,
+            signal_hash,
+            external_nullifier,
+            identity_nullifier,
+            identity_trapdoor,
+            path,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![
+            self.merkle_tree_root,
+            self.nullifier_hash,
+            self.signal_hash,
+            self.external_nullifier,
+        ]
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEPTH: usize, F: PrimeField] SemaphoreCircuit<DEPTH, F> {
+        identity_nullifier,
+        identity_trapdoor,
+        path,
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for SemaphoreCircuit<DEPTH, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.path.siblings.len() != DEPTH {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let merkle_tree_root =
+            FpVar::new_input(ns!(cs, "merkle_tree_root"), || Ok(self.merkle_tree_root))?;
+        let nullifier_hash =
+            FpVar::new_input(ns!(cs, "nullifier_hash"), || Ok(self.nullifier_hash))?;
+        // Bound by the proof as a public input, without constraints of its own
+        let _signal_hash = FpVar::new_input(ns!(cs, "signal_hash"), || Ok(self.signal_hash))?;
+        let external_nullifier =
+            FpVar::new_input(
+                ns!(cs, "external_nullifier"),
+                || Ok(self.external_nullifier),
+            )?;
+
+        // Private inputs
+        let identity_nullifier =
+            FpVar::new_witness(
+                ns!(cs, "identity_nullifier"),
+                || Ok(self.identity_nullifier),
+            )?;
+        let identity_trapdoor =
+            FpVar::new_witness(ns!(cs, "identity_trapdoor"), || Ok(self.identity_trapdoor))?;
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+
+        let commitment = {
+            let _ns = ns!(cs, "commitment == Poseidon(Poseidon(nullifier, trapdoor))");
+            let secret = poseidon::circom_hash_gadget(
+                cs.clone(),
+                &[identity_nullifier.clone(), identity_trapdoor],
+            )?;
+            poseidon::circom_hash_gadget(cs.clone(), &[secret])?
+        };
+
+        {
+            let _ns = ns!(cs, "merkle_tree_root == Root(path, commitment)");
+            let mut node = commitment;
+            for (sibling, is_right) in path.siblings.iter().zip(&path.index_bits) {
+                let (left, right) = select::conditionally_swap(is_right, &node, sibling)?;
+                node = poseidon::circom_hash_gadget(cs.clone(), &[left, right])?;
+            }
+            node.enforce_equal(&merkle_tree_root)?;
+        }
+
+        let _ns = ns!(
+            cs,
+            "nullifier_hash == Poseidon(external_nullifier, identity_nullifier)"
+        );
+        poseidon::circom_hash_gadget(cs.clone(), &[external_nullifier, identity_nullifier])?
+            .enforce_equal(&nullifier_hash)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use crate::babyjubjub::{self, Affine};
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::{babyjubjub::PointVar, shuffle};
+use ark_bn254::Fr;
+use ark_ff::AdditiveGroup;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::AllocVar};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Proves that `outputs` are the Pedersen commitments `inputs` in a secret
+/// order, each re-randomized, so that no output can be linked to its input
+/// while every committed value is kept.
+///
+/// The building block of mixers, card shuffles and vote mixing. Lists of
+/// `SIZE` commitments cost about 1,700 constraints per commitment plus
+/// `3 SIZE^2` for the permutation.
+#[derive(Debug, Clone)]
+pub struct ShuffleCircuit<const SIZE: usize> {
+    // Public inputs
+    pub inputs: Vec<Affine>,
+    pub outputs: Vec<Affine>,
+
+    // Private inputs
+    /// `outputs[i]` re-randomizes `inputs[permutation[i]]`.
+    pub permutation: Vec<usize>,
+    pub randomness: Vec<Fr>,
+}
+
+metadata! {
+    impl<const SIZE: usize> ShuffleCircuit<SIZE> {
+        name: "shuffle",
+        version: "1.0.0",
+    }
+}
+
+impl<const SIZE: usize> ShuffleCircuit<SIZE> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            inputs: vec![Affine::zero(); SIZE],
+            outputs: vec![Affine::zero(); SIZE],
+            permutation: (0..SIZE).collect(),
+            randomness: vec![Fr::ZERO; SIZE],
+        }
+    }
+
+    /// Creates a circuit, shuffling `inputs` by `permutation` and
+    /// re-randomizing each with its `randomness`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - there are not `SIZE` inputs
+    /// - `permutation` is not a permutation of `0..SIZE`
+    /// - `randomness` has a different length
+    pub fn new(
+        inputs: Vec<Affine>,
+        permutation: Vec<usize>,
+        randomness: Vec<Fr>,
+    ) -> anyhow::Result<Self> {
+        if inputs.len() != SIZE {
+            anyhow::bail!("Expected {} inputs, got {}", SIZE, inputs.len());
+        }
+        let mut sorted = permutation.clone();
+        sorted.sort_unstable();
+        if !sorted.iter().copied().eq(0..inputs.len()) {
+            anyhow::bail!("Permutation is not a permutation of 0..{}", inputs.len());
+        }
+        if randomness.len() != inputs.len() {
+            anyhow::bail!(
+                "Expected {} randomness values, got {}",
+                inputs.len(),
+                randomness.len()
+            );
+        }
+
+        let outputs = permutation
+            .iter()
+            .zip(&randomness)
+            .map(|(&i, r)| babyjubjub::rerandomize(&inputs[i], *r))
+            .collect();
+        Ok(Self {
+            inputs,
+            outputs,
+            permutation,
+            randomness,
+        })
+    }
+
+    /// The coordinates `x, y` of the inputs, then of the outputs.
+    pub fn get_public_inputs(&self) -> Vec<Fr> {
+        self.inputs
+            .iter()
+            .chain(&self.outputs)
+            .flat_map(|point| [point.x, point.y])
+            .collect()
+    }
+}
+
+zeroize_on_drop! {
+    impl[const SIZE: usize] ShuffleCircuit<SIZE> { permutation, randomness }
+}
+
+impl<const SIZE: usize> ConstraintSynthesizer<Fr> for ShuffleCircuit<SIZE> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> r1cs::Result<()> {
+        if self.inputs.len() != SIZE
+            || self.outputs.len() != SIZE
+            || self.permutation.len() != SIZE
+            || self.randomness.len() != SIZE
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|point| PointVar::new_input(ns!(cs, "input"), || Ok(*point)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let outputs = self
+            .outputs
+            .iter()
+            .map(|point| PointVar::new_input(ns!(cs, "output"), || Ok(*point)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        // Private inputs
+        let permutation = self
+            .permutation
+            .iter()
+            .map(|&i| FpVar::new_witness(ns!(cs, "permutation"), || Ok(Fr::from(i as u64))))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let randomness = self
+            .randomness
+            .iter()
+            .map(|r| FpVar::new_witness(ns!(cs, "randomness"), || Ok(*r)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        let _ns = ns!(cs, "outputs == Shuffle(inputs, permutation, randomness)");
+        shuffle::enforce_shuffle(&inputs, &outputs, &permutation, &randomness)
+    }
+}
@@ -0,0 +1,150 @@
+use super::airdrop::AirdropCircuit;
+use super::auction::AuctionCircuit;
+use super::batch_insert::BatchInsertCircuit;
+use super::blocklist::BlocklistCircuit;
+use super::ed25519::Ed25519SignatureCircuit;
+use super::hash_chain::HashChainCircuit;
+use super::semaphore::SemaphoreCircuit;
+use super::shuffle::ShuffleCircuit;
+use super::transfer::TransferCircuit;
+use super::vote::{TallyCircuit, VoteCircuit};
+use crate::inspect::{self, CircuitStats};
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, ProvingKey};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use rand_chacha::ChaCha20Rng;
+use std::fmt;
+
+/// One size of a const-generic circuit, compiled in so that a size chosen at
+/// runtime (e.g. from wasm) can be set up and inspected.
+#[derive(Clone, Copy)]
+pub struct SizedCircuit {
+    pub name: &'static str,
+    pub version: &'static str,
+    /// Const parameters in declaration order, e.g. `[("depth", 20)]`.
+    pub params: &'static [(&'static str, usize)],
+    inspect: fn() -> anyhow::Result<CircuitStats>,
+    digest: fn() -> anyhow::Result<[u8; 32]>,
+    setup: fn(&mut ChaCha20Rng) -> anyhow::Result<ProvingKey<Bn254>>,
+}
+
+impl SizedCircuit {
+    /// `<name>/<param>=<value>,...`, e.g. `semaphore/depth=20`.
+    pub fn id(&self) -> String {
+        let params: Vec<String> = self
+            .params
+            .iter()
+            .map(|(param, value)| format!("{}={}", param, value))
+            .collect();
+        format!("{}/{}", self.name, params.join(","))
+    }
+
+    /// Synthesizes the empty circuit in setup mode and reports its size.
+    ///
+    /// # Errors
+    /// Returns error if constraint generation fails.
+    pub fn inspect(&self) -> anyhow::Result<CircuitStats> {
+        (self.inspect)()
+    }
+
+    /// SHA-256 of the R1CS matrices of this size (see [`inspect::digest`]).
+    ///
+    /// # Errors
+    /// Returns error if constraint generation fails.
+    pub fn cs_digest(&self) -> anyhow::Result<[u8; 32]> {
+        (self.digest)()
+    }
+
+    /// Runs a single-party Groth16 setup for this size.
+    ///
+    /// # Errors
+    /// Returns error if the setup fails.
+    pub fn setup(&self, rng: &mut ChaCha20Rng) -> anyhow::Result<ProvingKey<Bn254>> {
+        (self.setup)(rng)
+    }
+}
+
+impl fmt::Debug for SizedCircuit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SizedCircuit")
+            .field("name", &self.name)
+            .field("version", &self.version)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+fn setup<C: ConstraintSynthesizer<Fr>>(
+    circuit: C,
+    rng: &mut ChaCha20Rng,
+) -> anyhow::Result<ProvingKey<Bn254>> {
+    Groth16::<Bn254>::generate_random_parameters_with_reduction(circuit, rng)
+        .map_err(|e| anyhow::anyhow!("Failed to generate keys: {}", e))
+}
+
+macro_rules! sized_circuits {
+    ($($circuit:ident<$($param:ident = $value:literal),+>),* $(,)?) => {
+        &[$(SizedCircuit {
+            name: <$circuit<$($value),+>>::NAME,
+            version: <$circuit<$($value),+>>::VERSION,
+            params: &[$((stringify!($param), $value)),+],
+            inspect: || inspect::inspect(<$circuit<$($value),+>>::empty()),
+            digest: || inspect::digest(<$circuit<$($value),+>>::empty()),
+            setup: |rng| setup(<$circuit<$($value),+>>::empty(), rng),
+        }),*]
+    };
+}
+
+/// The sizes of the parameterized circuits available without recompiling,
+/// by name and then by size.
+pub static SIZED_CIRCUITS: &[SizedCircuit] = sized_circuits![
+    AirdropCircuit<depth = 16>,
+    AirdropCircuit<depth = 20>,
+    AirdropCircuit<depth = 24>,
+    AirdropCircuit<depth = 32>,
+    AuctionCircuit<others = 4>,
+    AuctionCircuit<others = 8>,
+    AuctionCircuit<others = 16>,
+    BatchInsertCircuit<depth = 20, batch_size = 4>,
+    BatchInsertCircuit<depth = 20, batch_size = 8>,
+    BatchInsertCircuit<depth = 20, batch_size = 16>,
+    BatchInsertCircuit<depth = 32, batch_size = 16>,
+    BlocklistCircuit<depth = 32>,
+    BlocklistCircuit<depth = 64>,
+    Ed25519SignatureCircuit<depth = 16>,
+    Ed25519SignatureCircuit<depth = 20>,
+    Ed25519SignatureCircuit<depth = 24>,
+    Ed25519SignatureCircuit<depth = 32>,
+    HashChainCircuit<steps = 16>,
+    HashChainCircuit<steps = 64>,
+    HashChainCircuit<steps = 256>,
+    SemaphoreCircuit<depth = 16>,
+    SemaphoreCircuit<depth = 20>,
+    SemaphoreCircuit<depth = 24>,
+    SemaphoreCircuit<depth = 32>,
+    ShuffleCircuit<size = 4>,
+    ShuffleCircuit<size = 16>,
+    ShuffleCircuit<size = 52>,
+    TallyCircuit<votes = 16>,
+    TallyCircuit<votes = 64>,
+    TallyCircuit<votes = 256>,
+    TransferCircuit<depth = 16>,
+    TransferCircuit<depth = 20>,
+    TransferCircuit<depth = 24>,
+    TransferCircuit<depth = 32>,
+    VoteCircuit<depth = 16>,
+    VoteCircuit<depth = 20>,
+    VoteCircuit<depth = 24>,
+    VoteCircuit<depth = 32>,
+];
+
+/// Looks up a size by its [`SizedCircuit::id`].
+///
+/// # Errors
+/// Returns error if no such size is compiled in.
+pub fn find(id: &str) -> anyhow::Result<&'static SizedCircuit> {
+    SIZED_CIRCUITS
+        .iter()
+        .find(|circuit| circuit.id() == id)
+        .ok_or_else(|| anyhow::anyhow!("Unknown circuit size: {}", id))
+}
@@ -0,0 +1,108 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::comparison;
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::fp::FpVar,
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Balances are range checked to this many bits, the width of a Sui `u64` coin value.
+pub const BALANCE_BITS: usize = 64;
+
+/// Proves that the balance committed to in `commitment = Poseidon(balance, salt)`
+/// is at least `minimum`, without revealing it.
+#[derive(Debug, Clone)]
+pub struct SolvencyCircuit<F: PrimeField = Fr> {
+    // Public inputs
+    pub minimum: F,
+    pub commitment: F,
+
+    // Private inputs
+    pub balance: F,
+    pub salt: F,
+}
+
+metadata! {
+    impl SolvencyCircuit {
+        name: "solvency",
+        version: "1.0.0",
+        public_inputs: ["minimum", "commitment"],
+    }
+}
+
+impl<F: PrimeField + Absorb> SolvencyCircuit<F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            minimum: F::ZERO,
+            commitment: F::ZERO,
+            balance: F::ZERO,
+            salt: F::ZERO,
+        }
+    }
+
+    /// Creates a circuit, computing the commitment to `balance`.
+    ///
+    /// # Errors
+    /// Returns error if `balance` is below `minimum`.
+    pub fn new(minimum: u64, balance: u64, salt: F) -> anyhow::Result<Self> {
+        if balance < minimum {
+            anyhow::bail!("Balance {} is below the minimum {}", balance, minimum);
+        }
+
+        let balance = F::from(balance);
+        Ok(Self {
+            minimum: F::from(minimum),
+            commitment: Self::commit(balance, salt)?,
+            balance,
+            salt,
+        })
+    }
+
+    /// The public commitment to `balance`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commit(balance: F, salt: F) -> anyhow::Result<F> {
+        poseidon::hash(&[balance, salt])
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![self.minimum, self.commitment]
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField] SolvencyCircuit<F> { balance, salt }
+}
+
+impl<F: PrimeField + Absorb> ConstraintSynthesizer<F> for SolvencyCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        let minimum = FpVar::new_input(ns!(cs, "minimum"), || Ok(self.minimum))?;
+        let commitment = FpVar::new_input(ns!(cs, "commitment"), || Ok(self.commitment))?;
+
+        // Private inputs
+        let balance = FpVar::new_witness(ns!(cs, "balance"), || Ok(self.balance))?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+
+        {
+            let _ns = ns!(cs, "commitment == Poseidon(balance, salt)");
+            poseidon::hash_gadget(cs.clone(), &[balance.clone(), salt])?
+                .enforce_equal(&commitment)?;
+        }
+
+        let _ns = ns!(cs, "minimum <= balance");
+        comparison::enforce_bit_length(&minimum, BALANCE_BITS)?;
+        comparison::enforce_bit_length(&balance, BALANCE_BITS)?;
+        comparison::enforce_less_or_equal(&minimum, &balance, BALANCE_BITS)?;
+
+        Ok(())
+    }
+}
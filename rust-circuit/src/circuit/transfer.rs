@@ -0,0 +1,346 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::{comparison, signed};
+use crate::merkle::{MerklePath, MerklePathVar, MerkleTree};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use zeroize::Zeroize;
+
+/// Notes spent by one transfer.
+pub const NUM_INPUTS: usize = 2;
+/// Notes created by one transfer.
+pub const NUM_OUTPUTS: usize = 2;
+/// Note values are range checked to this many bits, the width of a Sui `u64` coin value.
+pub const VALUE_BITS: usize = 64;
+
+/// The owner field of notes spendable with `spending_key`: `Poseidon(spending_key)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn owner<F: PrimeField + Absorb>(spending_key: F) -> anyhow::Result<F> {
+    poseidon::hash(&[spending_key])
+}
+
+/// The nullifier revealed when spending the note `commitment` at leaf `index`:
+/// `Poseidon(spending_key, commitment, index)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn nullifier<F: PrimeField + Absorb>(
+    spending_key: F,
+    commitment: F,
+    index: u64,
+) -> anyhow::Result<F> {
+    poseidon::hash(&[spending_key, commitment, F::from(index)])
+}
+
+/// A shielded note: `value` units of the asset, spendable by `owner`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Note<F: PrimeField> {
+    pub value: F,
+    pub owner: F,
+    pub blinding: F,
+}
+
+impl<F: PrimeField + Absorb> Note<F> {
+    pub fn new(value: u64, owner: F, blinding: F) -> Self {
+        Self {
+            value: F::from(value),
+            owner,
+            blinding,
+        }
+    }
+
+    /// A zero-value note, used to fill unused input or output slots.
+    pub fn zero(owner: F, blinding: F) -> Self {
+        Self::new(0, owner, blinding)
+    }
+
+    /// The note commitment stored in the tree: `Poseidon(value, owner, blinding)`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn commitment(&self) -> anyhow::Result<F> {
+        poseidon::hash(&[self.value, self.owner, self.blinding])
+    }
+}
+
+impl<F: PrimeField> Zeroize for Note<F> {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
+        self.owner.zeroize();
+        self.blinding.zeroize();
+    }
+}
+
+/// A note being spent: the note, the key that owns it and its Merkle path.
+#[derive(Debug, Clone)]
+pub struct SpentNote<F: PrimeField> {
+    pub note: Note<F>,
+    pub spending_key: F,
+    pub path: MerklePath<F>,
+}
+
+impl<F: PrimeField> Zeroize for SpentNote<F> {
+    fn zeroize(&mut self) {
+        self.note.zeroize();
+        self.spending_key.zeroize();
+        self.path.zeroize();
+    }
+}
+
+/// Spends two notes from the tree behind `root` and creates two new ones,
+/// in the style of a Sapling/Tornado Nova shielded transfer.
+///
+/// Proves that:
+/// - each input note is in the tree (unless its value is zero) and owned by
+///   the prover, and its nullifier is the one published
+/// - each output commitment opens to a note with a 64-bit value
+/// - `sum(inputs) + public_amount == sum(outputs)`
+///
+/// `public_amount` is the value deposited (positive) or withdrawn (negative,
+/// i.e. `-amount` in the field) by the Move contract. `ext_data_hash` binds
+/// data the contract acts on, such as the withdrawal recipient and relayer fee.
+#[derive(Debug, Clone)]
+pub struct TransferCircuit<const DEPTH: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub root: F,
+    pub public_amount: F,
+    pub ext_data_hash: F,
+    pub nullifiers: [F; NUM_INPUTS],
+    pub output_commitments: [F; NUM_OUTPUTS],
+
+    // Private inputs
+    pub inputs: [SpentNote<F>; NUM_INPUTS],
+    pub outputs: [Note<F>; NUM_OUTPUTS],
+}
+
+metadata! {
+    impl<const DEPTH: usize> TransferCircuit<DEPTH> {
+        name: "utxo-transfer-2x2",
+        version: "1.0.0",
+        public_inputs: [
+            "root",
+            "public_amount",
+            "ext_data_hash",
+            "input_nullifier_0",
+            "input_nullifier_1",
+            "output_commitment_0",
+            "output_commitment_1",
+        ],
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> TransferCircuit<DEPTH, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        let spent = || SpentNote {
+            note: Note::zero(F::ZERO, F::ZERO),
+            spending_key: F::ZERO,
+            path: MerklePath::empty(DEPTH),
+        };
+
+        Self {
+            root: F::ZERO,
+            public_amount: F::ZERO,
+            ext_data_hash: F::ZERO,
+            nullifiers: [F::ZERO; NUM_INPUTS],
+            output_commitments: [F::ZERO; NUM_OUTPUTS],
+            inputs: [spent(), spent()],
+            outputs: [Note::zero(F::ZERO, F::ZERO); NUM_OUTPUTS],
+        }
+    }
+
+    /// Creates a transfer spending the notes at leaves `(index, note,
+    /// spending_key)` of `tree`.
+    ///
+    /// Zero-value inputs need not be in the tree, so a deposit can spend two
+    /// fresh zero notes; their `index` only enters the nullifier.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `tree` does not have `DEPTH` levels
+    /// - An index does not fit in `tree`
+    /// - An input with a value is not at its index in `tree`, or is not owned by its key
+    /// - The values do not balance with `public_amount`
+    pub fn new(
+        tree: &MerkleTree<F>,
+        inputs: [(u64, Note<F>, F); NUM_INPUTS],
+        outputs: [Note<F>; NUM_OUTPUTS],
+        public_amount: i64,
+        ext_data_hash: F,
+    ) -> anyhow::Result<Self> {
+        if tree.depth() != DEPTH {
+            anyhow::bail!("Expected a tree of depth {}, got {}", DEPTH, tree.depth());
+        }
+
+        let mut nullifiers = [F::ZERO; NUM_INPUTS];
+        let mut spent = Vec::with_capacity(NUM_INPUTS);
+        for (slot, (index, note, spending_key)) in inputs.into_iter().enumerate() {
+            if note.owner != owner(spending_key)? {
+                anyhow::bail!("Input {} is not owned by its spending key", slot);
+            }
+
+            if tree.depth() < u64::BITS as usize && index >> tree.depth() != 0 {
+                anyhow::bail!("Leaf index {} does not fit in the tree", index);
+            }
+
+            let commitment = note.commitment()?;
+            let path = if note.value.is_zero() {
+                MerklePath {
+                    index,
+                    ..MerklePath::empty(DEPTH)
+                }
+            } else {
+                let path = usize::try_from(index)
+                    .map_err(|_| anyhow::anyhow!("Leaf index {} is out of range", index))
+                    .and_then(|index| tree.path(index))?;
+                if tree.leaves()[index as usize] != commitment {
+                    anyhow::bail!("Input {} is not the note at leaf {}", slot, index);
+                }
+                path
+            };
+
+            nullifiers[slot] = nullifier(spending_key, commitment, index)?;
+            spent.push(SpentNote {
+                note,
+                spending_key,
+                path,
+            });
+        }
+
+        let total = |notes: &mut dyn Iterator<Item = &Note<F>>| -> i128 {
+            notes
+                .map(|note| note.value.into_bigint().as_ref()[0] as i128)
+                .sum()
+        };
+        let total_in = total(&mut spent.iter().map(|spent| &spent.note));
+        let total_out = total(&mut outputs.iter());
+        if total_in + public_amount as i128 != total_out {
+            anyhow::bail!(
+                "Inputs {} plus public amount {} do not equal outputs {}",
+                total_in,
+                public_amount,
+                total_out
+            );
+        }
+
+        Ok(Self {
+            root: tree.root(),
+            public_amount: signed::to_field(public_amount.into()),
+            ext_data_hash,
+            nullifiers,
+            output_commitments: [outputs[0].commitment()?, outputs[1].commitment()?],
+            inputs: spent
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Expected {} inputs", NUM_INPUTS))?,
+            outputs,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        let mut inputs = vec![self.root, self.public_amount, self.ext_data_hash];
+        inputs.extend_from_slice(&self.nullifiers);
+        inputs.extend_from_slice(&self.output_commitments);
+        inputs
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEPTH: usize, F: PrimeField] TransferCircuit<DEPTH, F> { inputs, outputs }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for TransferCircuit<DEPTH, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self
+            .inputs
+            .iter()
+            .any(|spent| spent.path.siblings.len() != DEPTH)
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let root = FpVar::new_input(ns!(cs, "root"), || Ok(self.root))?;
+        let public_amount = FpVar::new_input(ns!(cs, "public_amount"), || Ok(self.public_amount))?;
+        let ext_data_hash = FpVar::new_input(ns!(cs, "ext_data_hash"), || Ok(self.ext_data_hash))?;
+        let nullifiers = self
+            .nullifiers
+            .iter()
+            .map(|nullifier| FpVar::new_input(ns!(cs, "input_nullifier"), || Ok(*nullifier)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let output_commitments = self
+            .output_commitments
+            .iter()
+            .map(|commitment| FpVar::new_input(ns!(cs, "output_commitment"), || Ok(*commitment)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        let mut balance = public_amount;
+
+        for (spent, nullifier) in self.inputs.iter().zip(&nullifiers) {
+            // Private inputs
+            let value = FpVar::new_witness(ns!(cs, "input_value"), || Ok(spent.note.value))?;
+            let blinding =
+                FpVar::new_witness(ns!(cs, "input_blinding"), || Ok(spent.note.blinding))?;
+            let spending_key =
+                FpVar::new_witness(ns!(cs, "spending_key"), || Ok(spent.spending_key))?;
+            let path = MerklePathVar::new_witness(ns!(cs, "input_path").cs(), &spent.path)?;
+
+            let _ns = ns!(cs, "input note");
+            comparison::enforce_bit_length(&value, VALUE_BITS)?;
+            let owner = poseidon::hash_gadget(cs.clone(), std::slice::from_ref(&spending_key))?;
+            let commitment = poseidon::hash_gadget(cs.clone(), &[value.clone(), owner, blinding])?;
+            poseidon::hash_gadget(
+                cs.clone(),
+                &[spending_key, commitment.clone(), path.index()?],
+            )?
+            .enforce_equal(nullifier)?;
+
+            // Membership is only required of notes carrying value
+            let computed_root = path.root(cs.clone(), &commitment)?;
+            ((computed_root - &root) * &value).enforce_equal(&FpVar::zero())?;
+
+            balance += value;
+        }
+
+        for (note, commitment) in self.outputs.iter().zip(&output_commitments) {
+            // Private inputs
+            let value = FpVar::new_witness(ns!(cs, "output_value"), || Ok(note.value))?;
+            let owner = FpVar::new_witness(ns!(cs, "output_owner"), || Ok(note.owner))?;
+            let blinding = FpVar::new_witness(ns!(cs, "output_blinding"), || Ok(note.blinding))?;
+
+            let _ns = ns!(cs, "output note");
+            comparison::enforce_bit_length(&value, VALUE_BITS)?;
+            poseidon::hash_gadget(cs.clone(), &[value.clone(), owner, blinding])?
+                .enforce_equal(commitment)?;
+
+            balance -= value;
+        }
+
+        {
+            let _ns = ns!(cs, "sum(inputs) + public_amount == sum(outputs)");
+            balance.enforce_equal(&FpVar::zero())?;
+        }
+
+        {
+            // A note could otherwise be spent twice in one transfer
+            let _ns = ns!(cs, "distinct nullifiers");
+            nullifiers[0].enforce_not_equal(&nullifiers[1])?;
+        }
+
+        let _ns = ns!(cs, "ext_data_hash binding");
+        let _ = &ext_data_hash * &ext_data_hash;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,309 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::gadgets::comparison;
+use crate::merkle::{MerklePath, MerklePathVar, MerkleTree};
+use crate::poseidon;
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+
+/// Votes are range checked to this many bits, allowing up to 256 options.
+pub const CHOICE_BITS: usize = 8;
+
+/// The voter's leaf in the voter registry: `Poseidon(secret)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn voter_identity<F: PrimeField + Absorb>(secret: F) -> anyhow::Result<F> {
+    poseidon::hash(&[secret])
+}
+
+/// The nullifier published with a vote: `Poseidon(secret, proposal_id)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn nullifier<F: PrimeField + Absorb>(secret: F, proposal_id: F) -> anyhow::Result<F> {
+    poseidon::hash(&[secret, proposal_id])
+}
+
+/// The commitment a vote is cast as: `Poseidon(vote, salt)`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn commit_vote<F: PrimeField + Absorb>(vote: F, salt: F) -> anyhow::Result<F> {
+    poseidon::hash(&[vote, salt])
+}
+
+/// Proves that a registered voter cast `vote_commitment` on `proposal_id`,
+/// with a vote in `{0, 1, ..., max_choice}`, without revealing who voted or how.
+///
+/// Voters are the identities in the tree behind `root`; the nullifier is
+/// the same for every vote by one voter on one proposal, so double votes
+/// are rejected on chain. The vote and salt are sent privately to the
+/// tallier, who later proves the result with [`TallyCircuit`].
+#[derive(Debug, Clone)]
+pub struct VoteCircuit<const DEPTH: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub root: F,
+    pub nullifier: F,
+    pub proposal_id: F,
+    pub max_choice: F,
+    pub vote_commitment: F,
+
+    // Private inputs
+    pub secret: F,
+    pub vote: F,
+    pub salt: F,
+    pub path: MerklePath<F>,
+}
+
+metadata! {
+    impl<const DEPTH: usize> VoteCircuit<DEPTH> {
+        name: "anonymous-vote",
+        version: "1.0.0",
+        public_inputs: ["root", "nullifier", "proposal_id", "max_choice", "vote_commitment"],
+    }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> VoteCircuit<DEPTH, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            root: F::ZERO,
+            nullifier: F::ZERO,
+            proposal_id: F::ZERO,
+            max_choice: F::ZERO,
+            vote_commitment: F::ZERO,
+            secret: F::ZERO,
+            vote: F::ZERO,
+            salt: F::ZERO,
+            path: MerklePath::empty(DEPTH),
+        }
+    }
+
+    /// Creates a vote by the voter at `index` of `registry`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `registry` does not have `DEPTH` levels
+    /// - `max_choice` does not fit in [`CHOICE_BITS`] bits or `vote` exceeds it
+    /// - `index` is out of range or the leaf there is not the identity of `secret`
+    pub fn new(
+        registry: &MerkleTree<F>,
+        index: usize,
+        secret: F,
+        proposal_id: F,
+        max_choice: u64,
+        vote: u64,
+        salt: F,
+    ) -> anyhow::Result<Self> {
+        if registry.depth() != DEPTH {
+            anyhow::bail!(
+                "Expected a tree of depth {}, got {}",
+                DEPTH,
+                registry.depth()
+            );
+        }
+        if max_choice >> CHOICE_BITS != 0 {
+            anyhow::bail!("max_choice must be below 2^{}", CHOICE_BITS);
+        }
+        if vote > max_choice {
+            anyhow::bail!("Vote {} is above the maximum choice {}", vote, max_choice);
+        }
+
+        let path = registry.path(index)?;
+        if registry.leaves()[index] != voter_identity(secret)? {
+            anyhow::bail!("Leaf {} is not the identity of the given secret", index);
+        }
+
+        let vote = F::from(vote);
+        Ok(Self {
+            root: registry.root(),
+            nullifier: nullifier(secret, proposal_id)?,
+            proposal_id,
+            max_choice: F::from(max_choice),
+            vote_commitment: commit_vote(vote, salt)?,
+            secret,
+            vote,
+            salt,
+            path,
+        })
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        vec![
+            self.root,
+            self.nullifier,
+            self.proposal_id,
+            self.max_choice,
+            self.vote_commitment,
+        ]
+    }
+}
+
+zeroize_on_drop! {
+    impl[const DEPTH: usize, F: PrimeField] VoteCircuit<DEPTH, F> { secret, vote, salt, path }
+}
+
+impl<const DEPTH: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for VoteCircuit<DEPTH, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.path.siblings.len() != DEPTH {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let root = FpVar::new_input(ns!(cs, "root"), || Ok(self.root))?;
+        let nullifier = FpVar::new_input(ns!(cs, "nullifier"), || Ok(self.nullifier))?;
+        let proposal_id = FpVar::new_input(ns!(cs, "proposal_id"), || Ok(self.proposal_id))?;
+        let max_choice = FpVar::new_input(ns!(cs, "max_choice"), || Ok(self.max_choice))?;
+        let vote_commitment =
+            FpVar::new_input(ns!(cs, "vote_commitment"), || Ok(self.vote_commitment))?;
+
+        // Private inputs
+        let secret = FpVar::new_witness(ns!(cs, "secret"), || Ok(self.secret))?;
+        let vote = FpVar::new_witness(ns!(cs, "vote"), || Ok(self.vote))?;
+        let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(self.salt))?;
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+
+        {
+            let _ns = ns!(cs, "root == MerkleRoot(Poseidon(secret))");
+            let identity = poseidon::hash_gadget(cs.clone(), std::slice::from_ref(&secret))?;
+            path.root(cs.clone(), &identity)?.enforce_equal(&root)?;
+        }
+
+        {
+            let _ns = ns!(cs, "nullifier == Poseidon(secret, proposal_id)");
+            poseidon::hash_gadget(cs.clone(), &[secret, proposal_id])?.enforce_equal(&nullifier)?;
+        }
+
+        {
+            let _ns = ns!(cs, "vote_commitment == Poseidon(vote, salt)");
+            poseidon::hash_gadget(cs.clone(), &[vote.clone(), salt])?
+                .enforce_equal(&vote_commitment)?;
+        }
+
+        let _ns = ns!(cs, "vote <= max_choice");
+        comparison::enforce_bit_length(&vote, CHOICE_BITS)?;
+        comparison::enforce_bit_length(&max_choice, CHOICE_BITS)?;
+        comparison::enforce_less_or_equal(&vote, &max_choice, CHOICE_BITS)?;
+
+        Ok(())
+    }
+}
+
+/// Proves that the votes opening `vote_commitments` sum to `total`.
+///
+/// Run by the tallier over the commitments of accepted [`VoteCircuit`]
+/// proofs, which already range checked each vote; with yes/no votes
+/// (`max_choice = 1`) the total is the number of yes votes. There are
+/// `VOTES` slots, so unused ones are filled with commitments to zero votes.
+#[derive(Debug, Clone)]
+pub struct TallyCircuit<const VOTES: usize, F: PrimeField = Fr> {
+    // Public inputs
+    pub vote_commitments: Vec<F>,
+    pub total: F,
+
+    // Private inputs
+    pub votes: Vec<F>,
+    pub salts: Vec<F>,
+}
+
+metadata! {
+    impl<const VOTES: usize> TallyCircuit<VOTES> {
+        name: "vote-tally",
+        version: "1.0.0",
+    }
+}
+
+impl<const VOTES: usize, F: PrimeField + Absorb> TallyCircuit<VOTES, F> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty() -> Self {
+        Self {
+            vote_commitments: vec![F::ZERO; VOTES],
+            total: F::ZERO,
+            votes: vec![F::ZERO; VOTES],
+            salts: vec![F::ZERO; VOTES],
+        }
+    }
+
+    /// Creates a tally over opened votes, computing their commitments and total.
+    ///
+    /// # Errors
+    /// Returns error if there are not `VOTES` openings or hashing fails.
+    pub fn new(openings: &[(u64, F)]) -> anyhow::Result<Self> {
+        if openings.len() != VOTES {
+            anyhow::bail!("Expected {} votes, got {}", VOTES, openings.len());
+        }
+
+        let votes: Vec<F> = openings.iter().map(|(vote, _)| F::from(*vote)).collect();
+        let salts: Vec<F> = openings.iter().map(|(_, salt)| *salt).collect();
+        let vote_commitments = votes
+            .iter()
+            .zip(&salts)
+            .map(|(vote, salt)| commit_vote(*vote, *salt))
+            .collect::<anyhow::Result<_>>()?;
+
+        Ok(Self {
+            vote_commitments,
+            total: votes.iter().sum(),
+            votes,
+            salts,
+        })
+    }
+
+    /// The commitments in order, followed by the total.
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        let mut inputs = self.vote_commitments.clone();
+        inputs.push(self.total);
+        inputs
+    }
+}
+
+zeroize_on_drop! {
+    impl[const VOTES: usize, F: PrimeField] TallyCircuit<VOTES, F> { votes, salts }
+}
+
+impl<const VOTES: usize, F: PrimeField + Absorb> ConstraintSynthesizer<F>
+    for TallyCircuit<VOTES, F>
+{
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.vote_commitments.len() != VOTES
+            || self.votes.len() != VOTES
+            || self.salts.len() != VOTES
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let vote_commitments = self
+            .vote_commitments
+            .iter()
+            .map(|commitment| FpVar::new_input(ns!(cs, "vote_commitment"), || Ok(*commitment)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let total = FpVar::new_input(ns!(cs, "total"), || Ok(self.total))?;
+
+        let mut sum = FpVar::zero();
+        for ((commitment, vote), salt) in vote_commitments.iter().zip(&self.votes).zip(&self.salts)
+        {
+            // Private inputs
+            let vote = FpVar::new_witness(ns!(cs, "vote"), || Ok(*vote))?;
+            let salt = FpVar::new_witness(ns!(cs, "salt"), || Ok(*salt))?;
+
+            let _ns = ns!(cs, "vote_commitment == Poseidon(vote, salt)");
+            poseidon::hash_gadget(cs.clone(), &[vote.clone(), salt])?.enforce_equal(commitment)?;
+            sum += vote;
+        }
+
+        let _ns = ns!(cs, "total == sum(votes)");
+        sum.enforce_equal(&total)?;
+
+        Ok(())
+    }
+}
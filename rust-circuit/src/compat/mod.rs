@@ -0,0 +1,93 @@
+use crate::circuit::Circuit;
+use crate::keys;
+use serde::Serialize;
+
+/// Version of this crate, which client bundles are checked against.
+pub const LIBRARY_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Machine-readable compatibility verdict returned by [`check_compat`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Verdict {
+    pub compatible: bool,
+    pub library_version: String,
+    pub circuit_version: String,
+    pub pk_fingerprint: String,
+    /// Steps the client must take before its proofs will verify; empty when compatible.
+    pub actions: Vec<Action>,
+}
+
+/// Upgrade step required to reach a compatible deployment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "action", rename_all = "camelCase")]
+pub enum Action {
+    /// The client bundle is from an incompatible release line.
+    #[serde(rename_all = "camelCase")]
+    UpgradeClient { required: String, found: String },
+    /// The client was built for a different circuit version.
+    #[serde(rename_all = "camelCase")]
+    UpgradeCircuit { required: String, found: String },
+    /// The loaded proving key was not generated for this circuit.
+    #[serde(rename_all = "camelCase")]
+    ReplaceProvingKey { expected: String, found: String },
+}
+
+/// Checks whether a client can produce proofs that this library version verifies.
+///
+/// # Errors
+/// Returns error if `client_version` is not a `major.minor.patch` version.
+pub fn check_compat(
+    client_version: &str,
+    circuit_version: &str,
+    pk_fingerprint: &str,
+) -> anyhow::Result<Verdict> {
+    let mut actions = Vec::new();
+
+    if release_line(client_version)? != release_line(LIBRARY_VERSION)? {
+        actions.push(Action::UpgradeClient {
+            required: LIBRARY_VERSION.to_string(),
+            found: client_version.to_string(),
+        });
+    }
+
+    if circuit_version != Circuit::VERSION {
+        actions.push(Action::UpgradeCircuit {
+            required: Circuit::VERSION.to_string(),
+            found: circuit_version.to_string(),
+        });
+    }
+
+    let expected = keys::expected_proving_key_fingerprint();
+    let found = pk_fingerprint
+        .trim()
+        .trim_start_matches("0x")
+        .to_lowercase();
+    if found != expected {
+        actions.push(Action::ReplaceProvingKey { expected, found });
+    }
+
+    Ok(Verdict {
+        compatible: actions.is_empty(),
+        library_version: LIBRARY_VERSION.to_string(),
+        circuit_version: Circuit::VERSION.to_string(),
+        pk_fingerprint: keys::expected_proving_key_fingerprint(),
+        actions,
+    })
+}
+
+/// Semver compatibility class: the major version, or `0.minor` before 1.0.
+fn release_line(version: &str) -> anyhow::Result<(u64, u64)> {
+    let parts = version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| anyhow::anyhow!("Invalid version '{}': {}", version, e))?;
+
+    match parts[..] {
+        [0, minor, _] => Ok((0, minor)),
+        [major, _, _] => Ok((major, 0)),
+        _ => anyhow::bail!("Invalid version '{}': expected major.minor.patch", version),
+    }
+}
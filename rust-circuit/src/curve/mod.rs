@@ -0,0 +1,65 @@
+pub mod point;
+
+use ark_bn254::Bn254;
+use ark_ec::pairing::Pairing;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// Pairing-friendly curves the prover can target.
+///
+/// BN254 is the default and the curve Sui's `groth16` module verifies on.
+/// BLS12-381 requires the `bls12-381` cargo feature.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Curve {
+    #[default]
+    #[serde(rename = "bn254")]
+    Bn254,
+    #[serde(rename = "bls12-381")]
+    Bls12_381,
+}
+
+impl Curve {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Bn254 => "bn254",
+            Self::Bls12_381 => "bls12-381",
+        }
+    }
+
+    /// Returns error if support for this curve was not compiled in.
+    pub fn ensure_enabled(&self) -> anyhow::Result<()> {
+        match self {
+            Self::Bn254 => Ok(()),
+            Self::Bls12_381 if cfg!(feature = "bls12-381") => Ok(()),
+            Self::Bls12_381 => {
+                anyhow::bail!("Curve bls12-381 requires the `bls12-381` cargo feature")
+            }
+        }
+    }
+}
+
+impl FromStr for Curve {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "bn254" => Ok(Self::Bn254),
+            "bls12-381" => Ok(Self::Bls12_381),
+            _ => anyhow::bail!("Unknown curve '{}' (expected bn254 or bls12-381)", s),
+        }
+    }
+}
+
+/// Pairing engines with a [`Curve`] identifier.
+pub trait SupportedCurve: Pairing {
+    const CURVE: Curve;
+}
+
+impl SupportedCurve for Bn254 {
+    const CURVE: Curve = Curve::Bn254;
+}
+
+#[cfg(feature = "bls12-381")]
+impl SupportedCurve for ark_bls12_381::Bls12_381 {
+    const CURVE: Curve = Curve::Bls12_381;
+}
@@ -0,0 +1,146 @@
+use ark_ec::pairing::Pairing;
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_groth16::{Proof, VerifyingKey};
+use ark_serialize::{Compress, Validate};
+use ark_std::Zero;
+use std::fmt;
+
+/// Why a serialized curve point was rejected.
+///
+/// Carried as the source of the `anyhow` errors returned by the verify
+/// paths, so callers can `downcast_ref::<PointError>()` and branch on
+/// [`PointError::code`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PointError {
+    /// Wrong length, bad flags, or an x-coordinate with no point on the curve.
+    Malformed { name: String, reason: String },
+    /// On the curve but outside the prime-order subgroup (a low-order point).
+    NotInSubgroup { name: String },
+    /// The point at infinity, which no honest proof or key contains.
+    Identity { name: String },
+}
+
+impl PointError {
+    /// Stable identifier for programmatic handling.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Malformed { .. } => "E_POINT_MALFORMED",
+            Self::NotInSubgroup { .. } => "E_POINT_NOT_IN_SUBGROUP",
+            Self::Identity { .. } => "E_POINT_IDENTITY",
+        }
+    }
+}
+
+impl fmt::Display for PointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Malformed { name, reason } => {
+                write!(f, "{} is not a valid point: {}", name, reason)?
+            }
+            Self::NotInSubgroup { name } => {
+                write!(f, "{} is not in the prime-order subgroup", name)?
+            }
+            Self::Identity { name } => write!(f, "{} is the point at infinity", name)?,
+        }
+        write!(f, " ({})", self.code())
+    }
+}
+
+impl std::error::Error for PointError {}
+
+/// Deserializes a compressed point, rejecting points outside the
+/// prime-order subgroup and the identity.
+///
+/// # Errors
+/// Returns a [`PointError`] naming `name` if the point is rejected.
+pub fn deserialize_compressed<G: AffineRepr>(bytes: &[u8], name: &str) -> Result<G, PointError> {
+    deserialize_with(bytes, Compress::Yes, name)
+}
+
+/// Like [`deserialize_compressed`], but reads the point in either encoding.
+///
+/// # Errors
+/// Returns a [`PointError`] naming `name` if the point is rejected.
+pub fn deserialize_with<G: AffineRepr>(
+    bytes: &[u8],
+    compress: Compress,
+    name: &str,
+) -> Result<G, PointError> {
+    let point = G::deserialize_with_mode(bytes, compress, Validate::No).map_err(|e| {
+        PointError::Malformed {
+            name: name.to_string(),
+            reason: e.to_string(),
+        }
+    })?;
+
+    check(&point, name)?;
+    Ok(point)
+}
+
+/// Checks that `point` is on the curve, in the prime-order subgroup and not
+/// the identity.
+///
+/// # Errors
+/// Returns a [`PointError`] naming `name` if any check fails.
+pub fn check<G: AffineRepr>(point: &G, name: &str) -> Result<(), PointError> {
+    if point.is_zero() {
+        return Err(PointError::Identity {
+            name: name.to_string(),
+        });
+    }
+    if let Err(e) = point.check() {
+        // Uncompressed points may be off the curve; those do not survive a
+        // round trip through their x-coordinate
+        if is_on_curve(point) && !point.mul_bigint(G::ScalarField::MODULUS).is_zero() {
+            return Err(PointError::NotInSubgroup {
+                name: name.to_string(),
+            });
+        }
+        return Err(PointError::Malformed {
+            name: name.to_string(),
+            reason: e.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether `point` is on the curve, found by recomputing it from its
+/// x-coordinate, since [`AffineRepr`] has no direct check.
+fn is_on_curve<G: AffineRepr>(point: &G) -> bool {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).is_ok()
+        && G::deserialize_with_mode(&bytes[..], Compress::Yes, Validate::No)
+            .is_ok_and(|decompressed| decompressed == *point)
+}
+
+/// Applies [`check`] to every proof point.
+///
+/// # Errors
+/// Returns a [`PointError`] for the first rejected point.
+pub fn check_proof<E: Pairing>(proof: &Proof<E>) -> Result<(), PointError> {
+    check(&proof.a, "proof.a")?;
+    check(&proof.b, "proof.b")?;
+    check(&proof.c, "proof.c")
+}
+
+/// Applies [`check`] to the fixed verifying key points.
+///
+/// `gamma_abc_g1` is only checked for subgroup membership: an entry is the
+/// identity when the circuit ignores the corresponding public input.
+///
+/// # Errors
+/// Returns a [`PointError`] for the first rejected point.
+pub fn check_verifying_key<E: Pairing>(vk: &VerifyingKey<E>) -> Result<(), PointError> {
+    check(&vk.alpha_g1, "vk.alpha_g1")?;
+    check(&vk.beta_g2, "vk.beta_g2")?;
+    check(&vk.gamma_g2, "vk.gamma_g2")?;
+    check(&vk.delta_g2, "vk.delta_g2")?;
+    for (i, point) in vk.gamma_abc_g1.iter().enumerate() {
+        match check(point, &format!("vk.gamma_abc_g1[{}]", i)) {
+            Err(PointError::Identity { .. }) => {}
+            result => result?,
+        }
+    }
+    Ok(())
+}
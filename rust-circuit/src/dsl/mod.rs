@@ -0,0 +1,490 @@
+use crate::circuit::{metadata, zeroize_on_drop};
+use crate::field;
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{
+    fields::{fp::FpVar, FieldVar},
+    prelude::{AllocVar, EqGadget},
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use num_bigint::BigUint;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A circuit defined without Rust: declared variables and equations over
+/// them, e.g. `"c == a*b + d^2"`, read from JSON or TOML.
+///
+/// Expressions combine variables and integer constants (decimal or
+/// `0x`-prefixed hex) with `+`, `-`, `*`, parentheses and `^` by a constant
+/// exponent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitSpec {
+    pub name: String,
+    /// Public inputs, in the order proofs commit to them.
+    #[serde(default)]
+    pub public: Vec<String>,
+    #[serde(default)]
+    pub private: Vec<String>,
+    /// Equations `lhs == rhs`.
+    pub constraints: Vec<String>,
+}
+
+impl CircuitSpec {
+    /// # Errors
+    /// Returns error if `json` is not a valid spec.
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).map_err(|e| anyhow::anyhow!("Invalid circuit spec: {}", e))
+    }
+
+    /// # Errors
+    /// Returns error if `toml` is not a valid spec.
+    pub fn from_toml(toml: &str) -> anyhow::Result<Self> {
+        toml::from_str(toml).map_err(|e| anyhow::anyhow!("Invalid circuit spec: {}", e))
+    }
+
+    /// Parses the constraints, checking that every variable is declared once.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - a variable is declared twice or is not an identifier
+    /// - a constraint does not parse or uses an undeclared variable
+    pub fn compile(&self) -> anyhow::Result<Vec<Equation>> {
+        let mut declared = Vec::new();
+        for name in self.public.iter().chain(&self.private) {
+            if !is_identifier(name) {
+                anyhow::bail!("Invalid variable name: {:?}", name);
+            }
+            if declared.contains(&name) {
+                anyhow::bail!("Variable {} is declared twice", name);
+            }
+            declared.push(name);
+        }
+
+        self.constraints
+            .iter()
+            .map(|source| {
+                let equation = Equation::parse(source)
+                    .map_err(|e| anyhow::anyhow!("{} in {:?}", e, source))?;
+                let mut variables = Vec::new();
+                equation.lhs.variables(&mut variables);
+                equation.rhs.variables(&mut variables);
+                if let Some(name) = variables.iter().find(|name| !declared.contains(name)) {
+                    anyhow::bail!("Undeclared variable {} in {:?}", name, source);
+                }
+                Ok(equation)
+            })
+            .collect()
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// An arithmetic expression over named variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Constant(BigUint),
+    Variable(String),
+    Neg(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Pow(Box<Expr>, u64),
+}
+
+impl Expr {
+    /// # Errors
+    /// Returns error if `source` is not a valid expression.
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let mut parser = Parser::new(source)?;
+        let expr = parser.expr()?;
+        parser.expect_end()?;
+        Ok(expr)
+    }
+
+    /// Appends the names of the variables used, with repeats.
+    pub fn variables<'a>(&'a self, out: &mut Vec<&'a String>) {
+        match self {
+            Self::Constant(_) => {}
+            Self::Variable(name) => out.push(name),
+            Self::Neg(x) | Self::Pow(x, _) => x.variables(out),
+            Self::Add(a, b) | Self::Sub(a, b) | Self::Mul(a, b) => {
+                a.variables(out);
+                b.variables(out);
+            }
+        }
+    }
+
+    /// Evaluates the expression natively.
+    ///
+    /// # Errors
+    /// Returns error if a variable has no value.
+    pub fn evaluate<F: PrimeField>(&self, values: &HashMap<&str, F>) -> anyhow::Result<F> {
+        Ok(match self {
+            Self::Constant(c) => F::from(c.clone()),
+            Self::Variable(name) => *values
+                .get(name.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Missing value for {}", name))?,
+            Self::Neg(x) => -x.evaluate(values)?,
+            Self::Add(a, b) => a.evaluate(values)? + b.evaluate(values)?,
+            Self::Sub(a, b) => a.evaluate(values)? - b.evaluate(values)?,
+            Self::Mul(a, b) => a.evaluate(values)? * b.evaluate(values)?,
+            Self::Pow(x, exponent) => x.evaluate(values)?.pow([*exponent]),
+        })
+    }
+
+    /// In-circuit counterpart of [`Expr::evaluate`]; each product and each
+    /// squaring or multiplication of a power costs one constraint.
+    pub fn synthesize<F: PrimeField>(
+        &self,
+        variables: &HashMap<&str, FpVar<F>>,
+    ) -> r1cs::Result<FpVar<F>> {
+        Ok(match self {
+            Self::Constant(c) => FpVar::constant(F::from(c.clone())),
+            Self::Variable(name) => variables
+                .get(name.as_str())
+                .cloned()
+                .ok_or(r1cs::SynthesisError::AssignmentMissing)?,
+            Self::Neg(x) => x.synthesize(variables)?.negate()?,
+            Self::Add(a, b) => a.synthesize(variables)? + b.synthesize(variables)?,
+            Self::Sub(a, b) => a.synthesize(variables)? - b.synthesize(variables)?,
+            Self::Mul(a, b) => a.synthesize(variables)? * b.synthesize(variables)?,
+            Self::Pow(x, exponent) => x.synthesize(variables)?.pow_by_constant([*exponent])?,
+        })
+    }
+}
+
+/// A constraint `lhs == rhs`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub lhs: Expr,
+    pub rhs: Expr,
+}
+
+impl Equation {
+    /// # Errors
+    /// Returns error if `source` is not two expressions joined by `==`.
+    pub fn parse(source: &str) -> anyhow::Result<Self> {
+        let mut parser = Parser::new(source)?;
+        let lhs = parser.expr()?;
+        if parser.next() != Some(Token::Equals) {
+            anyhow::bail!("Expected ==");
+        }
+        let rhs = parser.expr()?;
+        parser.expect_end()?;
+        Ok(Self { lhs, rhs })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Number(BigUint),
+    Ident(String),
+    Plus,
+    Minus,
+    Star,
+    Caret,
+    LParen,
+    RParen,
+    Equals,
+}
+
+/// Recursive-descent parser; `^` binds tightest, then unary `-`, `*`, and
+/// `+`/`-`.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> anyhow::Result<Self> {
+        let mut tokens = Vec::new();
+        let mut chars = source.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            if c.is_whitespace() {
+                chars.next();
+                continue;
+            }
+            let word = |chars: &mut std::iter::Peekable<std::str::CharIndices>| {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                &source[start..end]
+            };
+            tokens.push(match c {
+                '0'..='9' => {
+                    let literal = word(&mut chars);
+                    let number = match literal.strip_prefix("0x") {
+                        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+                        None => BigUint::parse_bytes(literal.as_bytes(), 10),
+                    };
+                    Token::Number(
+                        number.ok_or_else(|| anyhow::anyhow!("Invalid number {:?}", literal))?,
+                    )
+                }
+                c if c.is_ascii_alphabetic() || c == '_' => Token::Ident(word(&mut chars).into()),
+                '=' => {
+                    chars.next();
+                    if chars.next().map(|(_, c)| c) != Some('=') {
+                        anyhow::bail!("Expected == at offset {}", start);
+                    }
+                    Token::Equals
+                }
+                _ => {
+                    chars.next();
+                    match c {
+                        '+' => Token::Plus,
+                        '-' => Token::Minus,
+                        '*' => Token::Star,
+                        '^' => Token::Caret,
+                        '(' => Token::LParen,
+                        ')' => Token::RParen,
+                        _ => anyhow::bail!("Unexpected {:?} at offset {}", c, start),
+                    }
+                }
+            });
+        }
+        Ok(Self {
+            tokens,
+            position: 0,
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn expect_end(&self) -> anyhow::Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(token) => anyhow::bail!("Unexpected {:?}", token),
+        }
+    }
+
+    fn expr(&mut self) -> anyhow::Result<Expr> {
+        let mut expr = self.term()?;
+        loop {
+            expr = match self.peek() {
+                Some(Token::Plus) => {
+                    self.next();
+                    Expr::Add(Box::new(expr), Box::new(self.term()?))
+                }
+                Some(Token::Minus) => {
+                    self.next();
+                    Expr::Sub(Box::new(expr), Box::new(self.term()?))
+                }
+                _ => return Ok(expr),
+            };
+        }
+    }
+
+    fn term(&mut self) -> anyhow::Result<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::Star) {
+            self.next();
+            expr = Expr::Mul(Box::new(expr), Box::new(self.unary()?));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> anyhow::Result<Expr> {
+        if self.peek() == Some(&Token::Minus) {
+            self.next();
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> anyhow::Result<Expr> {
+        let base = self.atom()?;
+        if self.peek() != Some(&Token::Caret) {
+            return Ok(base);
+        }
+        self.next();
+        match self.next() {
+            Some(Token::Number(exponent)) => {
+                let exponent = u64::try_from(exponent)
+                    .map_err(|_| anyhow::anyhow!("Exponent does not fit in a u64"))?;
+                Ok(Expr::Pow(Box::new(base), exponent))
+            }
+            token => anyhow::bail!("Expected a constant exponent, got {:?}", token),
+        }
+    }
+
+    fn atom(&mut self) -> anyhow::Result<Expr> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(Expr::Constant(n)),
+            Some(Token::Ident(name)) => Ok(Expr::Variable(name)),
+            Some(Token::LParen) => {
+                let expr = self.expr()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    token => anyhow::bail!("Expected ), got {:?}", token),
+                }
+            }
+            token => anyhow::bail!("Expected a number, variable or (, got {:?}", token),
+        }
+    }
+}
+
+/// Parses an assignment given as a JSON object of decimal or `0x`-hex
+/// strings, e.g. `{"a": "3", "b": "0x10"}`.
+///
+/// # Errors
+/// Returns error if `json` is not such an object.
+pub fn parse_assignment<F: PrimeField>(json: &str) -> anyhow::Result<HashMap<String, F>> {
+    let values: HashMap<String, String> =
+        serde_json::from_str(json).map_err(|e| anyhow::anyhow!("Invalid assignment: {}", e))?;
+    values
+        .into_iter()
+        .map(|(name, value)| Ok((name, field::parse_auto(&value)?)))
+        .collect()
+}
+
+/// The circuit compiled from a [`CircuitSpec`].
+#[derive(Debug, Clone)]
+pub struct DslCircuit<F: PrimeField = Fr> {
+    pub spec: CircuitSpec,
+    equations: Vec<Equation>,
+
+    // Public inputs
+    /// Values of `spec.public`, in order.
+    pub public: Vec<F>,
+
+    // Private inputs
+    /// Values of `spec.private`, in order.
+    pub private: Vec<F>,
+}
+
+metadata! {
+    impl DslCircuit {
+        name: "dsl",
+        version: "1.0.0",
+    }
+}
+
+impl<F: PrimeField> DslCircuit<F> {
+    /// Creates an empty circuit for the setup phase.
+    ///
+    /// # Errors
+    /// Returns error if the spec does not compile.
+    pub fn empty(spec: CircuitSpec) -> anyhow::Result<Self> {
+        Ok(Self {
+            equations: spec.compile()?,
+            public: vec![F::ZERO; spec.public.len()],
+            private: vec![F::ZERO; spec.private.len()],
+            spec,
+        })
+    }
+
+    /// Creates a circuit with every declared variable taken from `assignment`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - the spec does not compile
+    /// - a variable is missing from `assignment`, or it has an undeclared one
+    /// - a constraint does not hold
+    pub fn new(spec: CircuitSpec, assignment: &HashMap<String, F>) -> anyhow::Result<Self> {
+        let equations = spec.compile()?;
+        if let Some(name) = assignment
+            .keys()
+            .find(|name| !spec.public.contains(name) && !spec.private.contains(name))
+        {
+            anyhow::bail!("Assignment has undeclared variable {}", name);
+        }
+        let values = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| {
+                    assignment
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| anyhow::anyhow!("Missing value for {}", name))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+
+        let circuit = Self {
+            equations,
+            public: values(&spec.public)?,
+            private: values(&spec.private)?,
+            spec,
+        };
+        let values = circuit.named_values();
+        for (equation, source) in circuit.equations.iter().zip(&circuit.spec.constraints) {
+            if equation.lhs.evaluate(&values)? != equation.rhs.evaluate(&values)? {
+                anyhow::bail!("Constraint does not hold: {}", source);
+            }
+        }
+        Ok(circuit)
+    }
+
+    pub fn get_public_inputs(&self) -> Vec<F> {
+        self.public.clone()
+    }
+
+    fn named_values(&self) -> HashMap<&str, F> {
+        self.spec
+            .public
+            .iter()
+            .zip(&self.public)
+            .chain(self.spec.private.iter().zip(&self.private))
+            .map(|(name, value)| (name.as_str(), *value))
+            .collect()
+    }
+}
+
+zeroize_on_drop! {
+    impl[F: PrimeField] DslCircuit<F> { private }
+}
+
+impl<F: PrimeField> ConstraintSynthesizer<F> for DslCircuit<F> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<F>) -> r1cs::Result<()> {
+        if self.public.len() != self.spec.public.len()
+            || self.private.len() != self.spec.private.len()
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let mut variables = HashMap::new();
+        for (name, value) in self.spec.public.iter().zip(&self.public) {
+            let var = FpVar::new_input(ns!(cs, "public"), || Ok(*value))?;
+            variables.insert(name.as_str(), var);
+        }
+
+        // Private inputs
+        for (name, value) in self.spec.private.iter().zip(&self.private) {
+            let var = FpVar::new_witness(ns!(cs, "private"), || Ok(*value))?;
+            variables.insert(name.as_str(), var);
+        }
+
+        for equation in &self.equations {
+            let _ns = ns!(cs, "constraint");
+            equation
+                .lhs
+                .synthesize(&variables)?
+                .enforce_equal(&equation.rhs.synthesize(&variables)?)?;
+        }
+
+        Ok(())
+    }
+}
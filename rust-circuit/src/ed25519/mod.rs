@@ -0,0 +1,157 @@
+use ark_ec::{
+    models::CurveConfig,
+    twisted_edwards::{self, MontCurveConfig, TECurveConfig},
+};
+use ark_ff::{
+    fields::{Fp256, MontBackend, MontConfig},
+    AdditiveGroup, BigInteger, Field, MontFp, PrimeField,
+};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use sha2::{Digest, Sha512};
+
+#[derive(MontConfig)]
+#[modulus = "57896044618658097711785492504343953926634992332820282019728792003956564819949"]
+#[generator = "2"]
+pub struct FqConfig;
+
+/// Base field of Curve25519, `2^255 - 19`.
+pub type Fq = Fp256<MontBackend<FqConfig, 4>>;
+
+#[derive(MontConfig)]
+#[modulus = "7237005577332262213973186563042994240857116359379907606001950938285454250989"]
+#[generator = "2"]
+pub struct FrConfig;
+
+/// Scalar field of the prime-order subgroup: secret scalars and `S`.
+pub type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+/// edwards25519, `-x^2 + y^2 = 1 + d x^2 y^2`, the curve of Sui (and Solana)
+/// account keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Config;
+
+pub type Affine = twisted_edwards::Affine<Config>;
+pub type Projective = twisted_edwards::Projective<Config>;
+
+/// Length of an encoded public key.
+pub const PUBLIC_KEY_LEN: usize = 32;
+/// Length of an encoded signature, `R || S`.
+pub const SIGNATURE_LEN: usize = 64;
+
+impl CurveConfig for Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[8];
+    const COFACTOR_INV: Fr =
+        MontFp!("2713877091499598330239944961141122840321418634767465352250731601857045344121");
+}
+
+impl TECurveConfig for Config {
+    const COEFF_A: Fq = MontFp!("-1");
+    const COEFF_D: Fq =
+        MontFp!("37095705934669439343138083508754565189542113879843219016388785533085940283555");
+    const GENERATOR: Affine = Affine::new_unchecked(
+        MontFp!("15112221349535400772501151409588531511454012693041857206046113283949847762202"),
+        MontFp!("46316835694926478169428394003475163141307993866256225615783033603165251855960"),
+    );
+
+    type MontCurveConfig = Config;
+
+    fn mul_by_a(elem: Fq) -> Fq {
+        -elem
+    }
+}
+
+impl MontCurveConfig for Config {
+    const COEFF_A: Fq = MontFp!("486662");
+    const COEFF_B: Fq = MontFp!("-486664");
+
+    type TECurveConfig = Config;
+}
+
+/// Encodes a point as in RFC 8032: `y` little-endian, with the top bit
+/// holding the parity of `x`.
+pub fn compress(point: &Affine) -> [u8; PUBLIC_KEY_LEN] {
+    let mut bytes = [0u8; PUBLIC_KEY_LEN];
+    bytes.copy_from_slice(&point.y.into_bigint().to_bytes_le());
+    if point.x.into_bigint().is_odd() {
+        bytes[PUBLIC_KEY_LEN - 1] |= 0x80;
+    }
+    bytes
+}
+
+/// Decodes a point encoded by [`compress`], rejecting points outside the
+/// prime-order subgroup.
+///
+/// Small-order and mixed-order points decode on the curve but are not valid
+/// keys or `R` values under `verify_strict`, so they are errors here too.
+///
+/// # Errors
+/// Returns error if `y` is not canonical, no point has this encoding, or the
+/// point is the identity or has a torsion component.
+pub fn decompress(bytes: &[u8; PUBLIC_KEY_LEN]) -> anyhow::Result<Affine> {
+    let x_odd = bytes[PUBLIC_KEY_LEN - 1] & 0x80 != 0;
+    let mut y_bytes = *bytes;
+    y_bytes[PUBLIC_KEY_LEN - 1] &= 0x7f;
+
+    let y = Fq::from_le_bytes_mod_order(&y_bytes);
+    if y.into_bigint().to_bytes_le() != y_bytes {
+        anyhow::bail!("Point encoding is not canonical");
+    }
+
+    let y2 = y.square();
+    let x2 = (y2 - Fq::ONE)
+        * (Config::COEFF_D * y2 + Fq::ONE)
+            .inverse()
+            .ok_or_else(|| anyhow::anyhow!("Invalid point encoding"))?;
+    let mut x = x2
+        .sqrt()
+        .ok_or_else(|| anyhow::anyhow!("Point is not on edwards25519"))?;
+    if x == Fq::ZERO && x_odd {
+        anyhow::bail!("Invalid point encoding");
+    }
+    if x.into_bigint().is_odd() != x_odd {
+        x = -x;
+    }
+
+    let point = Affine::new_unchecked(x, y);
+    if point.is_zero() || !point.is_in_correct_subgroup_assuming_on_curve() {
+        anyhow::bail!("Point is not in the prime-order subgroup");
+    }
+    Ok(point)
+}
+
+/// The public key of the 32-byte secret `seed`, encoded.
+pub fn public_key(seed: &[u8; 32]) -> [u8; PUBLIC_KEY_LEN] {
+    SigningKey::from_bytes(seed).verifying_key().to_bytes()
+}
+
+/// Signs `message` with the 32-byte secret `seed`, as in RFC 8032.
+pub fn sign(seed: &[u8; 32], message: &[u8]) -> [u8; SIGNATURE_LEN] {
+    SigningKey::from_bytes(seed).sign(message).to_bytes()
+}
+
+/// Verifies an Ed25519 signature with `ed25519-dalek`'s `verify_strict`:
+/// `[S]B == R + [k]A` with `k = SHA-512(R || A || message)`, rejecting
+/// non-canonical `S` and small-order `A` or `R`.
+pub fn verify(
+    public_key: &[u8; PUBLIC_KEY_LEN],
+    message: &[u8],
+    signature: &[u8; SIGNATURE_LEN],
+) -> bool {
+    VerifyingKey::from_bytes(public_key).is_ok_and(|key| {
+        key.verify_strict(message, &Signature::from_bytes(signature))
+            .is_ok()
+    })
+}
+
+/// The challenge `k = SHA-512(R || A || message)`, reduced modulo the group order.
+pub fn challenge(big_r: &[u8; 32], public_key: &[u8; PUBLIC_KEY_LEN], message: &[u8]) -> Fr {
+    let digest = Sha512::new()
+        .chain_update(big_r)
+        .chain_update(public_key)
+        .chain_update(message)
+        .finalize();
+    Fr::from_le_bytes_mod_order(&digest)
+}
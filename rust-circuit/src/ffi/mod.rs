@@ -0,0 +1,112 @@
+use crate::api;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, UnwindSafe};
+use std::ptr;
+
+/// Generates a proof for a JSON `ProofInput`, as the wasm `prove`.
+///
+/// Returns 0 and sets `*proof_json` on success. Returns -1 and sets `*error`
+/// on failure. Strings written to the out-pointers must be released with
+/// [`vortex_free`].
+///
+/// # Safety
+/// `input_json` and `proving_key_hex` must be NUL-terminated strings;
+/// `proof_json` and `error` must be valid pointers or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn vortex_prove(
+    input_json: *const c_char,
+    proving_key_hex: *const c_char,
+    proof_json: *mut *mut c_char,
+    error: *mut *mut c_char,
+) -> i32 {
+    let result = call(|| {
+        let input_json = read_str(input_json, "input_json")?;
+        let proving_key_hex = read_str(proving_key_hex, "proving_key_hex")?;
+        api::prove(input_json, proving_key_hex, |_| {})
+    });
+
+    match result {
+        Ok(output) => {
+            write_str(proof_json, output);
+            0
+        }
+        Err(e) => {
+            write_error(error, e);
+            -1
+        }
+    }
+}
+
+/// Verifies a JSON `ProofOutput` against a hex-encoded verifying key.
+///
+/// Returns 1 if the proof is valid, 0 if it is not, and -1 with `*error`
+/// set if the inputs cannot be parsed.
+///
+/// # Safety
+/// `proof_json` and `verifying_key_hex` must be NUL-terminated strings;
+/// `error` must be a valid pointer or NULL.
+#[no_mangle]
+pub unsafe extern "C" fn vortex_verify(
+    proof_json: *const c_char,
+    verifying_key_hex: *const c_char,
+    error: *mut *mut c_char,
+) -> i32 {
+    let result = call(|| {
+        let proof_json = read_str(proof_json, "proof_json")?;
+        let verifying_key_hex = read_str(verifying_key_hex, "verifying_key_hex")?;
+        api::verify(proof_json, verifying_key_hex)
+    });
+
+    match result {
+        Ok(is_valid) => i32::from(is_valid),
+        Err(e) => {
+            write_error(error, e);
+            -1
+        }
+    }
+}
+
+/// Releases a string returned by this library. NULL is ignored.
+///
+/// # Safety
+/// `s` must be NULL or a pointer written by a `vortex_*` function that has
+/// not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn vortex_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Runs `f`, turning a panic into an error so it never unwinds into C.
+fn call<T>(f: impl FnOnce() -> anyhow::Result<T> + UnwindSafe) -> anyhow::Result<T> {
+    panic::catch_unwind(f).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(anyhow::anyhow!("Prover panicked: {}", message))
+    })
+}
+
+unsafe fn read_str<'a>(s: *const c_char, name: &str) -> anyhow::Result<&'a str> {
+    if s.is_null() {
+        anyhow::bail!("{} must not be NULL", name);
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|e| anyhow::anyhow!("{} is not valid UTF-8: {}", name, e))
+}
+
+unsafe fn write_str(out: *mut *mut c_char, s: String) {
+    if out.is_null() {
+        return;
+    }
+    // Our outputs are JSON or error messages and never contain NUL bytes
+    *out = CString::new(s).map_or(ptr::null_mut(), CString::into_raw);
+}
+
+unsafe fn write_error(error: *mut *mut c_char, e: anyhow::Error) {
+    write_str(error, e.to_string());
+}
@@ -0,0 +1,117 @@
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+use num_traits::Num;
+use std::str::FromStr;
+
+/// Textual encodings of a scalar field element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    /// Base-10 integer, e.g. `"30"`.
+    Decimal,
+    /// Big-endian `0x`-prefixed hex integer, e.g. `"0x1e"`.
+    Hex,
+    /// Hex of the little-endian bytes produced by `serialize_compressed`
+    /// (32 bytes for BN254 and BLS12-381),
+    /// the layout Sui's `groth16::public_proof_inputs_from_bytes` expects.
+    LeBytes,
+}
+
+impl FromStr for Encoding {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "decimal" => Ok(Self::Decimal),
+            "hex" => Ok(Self::Hex),
+            "le-bytes" => Ok(Self::LeBytes),
+            _ => anyhow::bail!(
+                "Unknown field encoding '{}' (expected decimal, hex or le-bytes)",
+                s
+            ),
+        }
+    }
+}
+
+/// Parses `s` as a field element in the given encoding.
+///
+/// Decimal and hex integers are reduced modulo the field order; little-endian
+/// bytes must have the field's serialized size and be canonical.
+pub fn parse<F: PrimeField>(s: &str, encoding: Encoding) -> anyhow::Result<F> {
+    let s = s.trim();
+
+    match encoding {
+        Encoding::Decimal | Encoding::Hex => Ok(F::from(parse_integer(s, encoding)?)),
+        Encoding::LeBytes => {
+            let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))
+                .map_err(|e| anyhow::anyhow!("Failed to decode bytes '{}': {}", s, e))?;
+            let size = F::ZERO.compressed_size();
+            if bytes.len() != size {
+                anyhow::bail!("Expected {} little-endian bytes, got {}", size, bytes.len());
+            }
+            F::deserialize_compressed(&bytes[..])
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize field element: {}", e))
+        }
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hex integer, detecting the encoding from the prefix.
+pub fn parse_auto<F: PrimeField>(s: &str) -> anyhow::Result<F> {
+    parse(s, detect(s))
+}
+
+/// Like [`parse_auto`], but rejects integers that are not below the field
+/// order instead of reducing them, so every element has a single spelling.
+///
+/// Use this for public inputs being verified: the on-chain verifiers reject
+/// non-canonical inputs, and accepting `c + p` for `c` would let one value,
+/// such as a nullifier, be presented in several forms.
+pub fn parse_canonical<F: PrimeField>(s: &str) -> anyhow::Result<F> {
+    let s = s.trim();
+    let value = parse_integer(s, detect(s))?;
+    if value >= F::MODULUS.into() {
+        anyhow::bail!("Value '{}' is not below the field order", s);
+    }
+    Ok(F::from(value))
+}
+
+fn detect(s: &str) -> Encoding {
+    let s = s.trim();
+    if s.starts_with("0x") || s.starts_with("0X") {
+        Encoding::Hex
+    } else {
+        Encoding::Decimal
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hex integer without reducing it.
+fn parse_integer(s: &str, encoding: Encoding) -> anyhow::Result<BigUint> {
+    if encoding == Encoding::Hex {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .ok_or_else(|| anyhow::anyhow!("Hex value '{}' must start with 0x", s))?;
+        BigUint::from_str_radix(digits, 16)
+            .map_err(|e| anyhow::anyhow!("Failed to parse hex '{}': {}", s, e))
+    } else {
+        BigUint::from_str(s).map_err(|e| anyhow::anyhow!("Failed to parse decimal '{}': {}", s, e))
+    }
+}
+
+/// Formats `value` in the given encoding.
+pub fn format<F: PrimeField>(value: &F, encoding: Encoding) -> String {
+    match encoding {
+        Encoding::Decimal => value.to_string(),
+        Encoding::Hex => {
+            let digits = hex::encode(value.into_bigint().to_bytes_be());
+            let digits = digits.trim_start_matches('0');
+            format!("0x{}", if digits.is_empty() { "0" } else { digits })
+        }
+        Encoding::LeBytes => {
+            let mut bytes = Vec::new();
+            value
+                .serialize_compressed(&mut bytes)
+                .expect("serializing into a Vec cannot fail");
+            hex::encode(bytes)
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::babyjubjub::{Affine, Config, Fq};
+use ark_ec::AffineRepr;
+use ark_r1cs_std::{
+    boolean::Boolean, fields::fp::FpVar, groups::curves::twisted_edwards::AffineVar, prelude::*,
+};
+use ark_relations::r1cs;
+
+/// A Baby Jubjub point, with native BN254 coordinates.
+pub type PointVar = AffineVar<Config, FpVar<Fq>>;
+
+/// `sum(scalar_i * base_i)` for constant bases and little-endian scalar bits,
+/// costing about 3 constraints per bit.
+///
+/// Scalars taken from `FpVar::to_bits_le` act modulo the subgroup order, as
+/// [`crate::babyjubjub::to_scalar`] does.
+pub fn fixed_base_msm(terms: &[(Affine, &[Boolean<Fq>])]) -> r1cs::Result<PointVar> {
+    let mut point = PointVar::zero();
+    for (base, bits) in terms {
+        // Bit `i` selects `2^i` times the base
+        let multiples = std::iter::successors(Some(base.into_group()), |p| Some(p + p))
+            .take(bits.len())
+            .collect::<Vec<_>>();
+        point.precomputed_base_scalar_mul_le(bits.iter().zip(&multiples))?;
+    }
+    Ok(point)
+}
+
+/// `scalar * point` for a variable point and little-endian scalar bits, by
+/// double-and-add.
+pub fn scalar_mul(point: &PointVar, bits: &[Boolean<Fq>]) -> r1cs::Result<PointVar> {
+    point.scalar_mul_le(bits.iter())
+}
@@ -0,0 +1,73 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*, uint8::UInt8};
+use ark_relations::r1cs;
+
+const URL_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Decodes unpadded base64url (RFC 4648 §5), as used by JWT segments,
+/// enforcing that every character is in the alphabet.
+///
+/// A trailing group of 2 or 3 characters decodes to 1 or 2 bytes; its unused
+/// low bits are ignored. Costs about 20 constraints per character.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if `chars.len() % 4 == 1`, which no encoding produces.
+pub fn decode_url<F: PrimeField>(chars: &[UInt8<F>]) -> r1cs::Result<Vec<UInt8<F>>> {
+    if chars.len() % 4 == 1 {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    // Sextets are concatenated most significant bit first
+    let mut bits = Vec::with_capacity(chars.len() * 6);
+    for char in chars {
+        let sextet = sextet(char)?;
+        bits.extend(sextet.into_iter().rev());
+    }
+
+    Ok(bits
+        .chunks_exact(8)
+        .map(|byte| UInt8::from_bits_le(&byte.iter().rev().cloned().collect::<Vec<_>>()))
+        .collect())
+}
+
+/// The little-endian bits of the value of `char` in the base64url alphabet.
+fn sextet<F: PrimeField>(char: &UInt8<F>) -> r1cs::Result<Vec<Boolean<F>>> {
+    let cs = char.cs();
+    // An invalid character gets the value 0, which then fails the check below
+    let value = char
+        .value()
+        .map(|c| URL_ALPHABET.iter().position(|&a| a == c).unwrap_or(0));
+    let bits = (0..6)
+        .map(|i| {
+            let bit = || {
+                value
+                    .map(|v| (v >> i) & 1 == 1)
+                    .map_err(|_| r1cs::SynthesisError::AssignmentMissing)
+            };
+            if char.is_constant() {
+                Boolean::new_constant(cs.clone(), bit()?)
+            } else {
+                Boolean::new_witness(cs.clone(), bit)
+            }
+        })
+        .collect::<r1cs::Result<Vec<_>>>()?;
+
+    // The alphabet is four runs of consecutive characters, so the character is
+    // the value plus an offset that depends on which run the value falls in:
+    // 'A' for [0, 26), 'a' - 26 for [26, 52), '0' - 52 for [52, 62), then '-', '_'
+    let (b1, b2, b3, b4, b5) = (&bits[1], &bits[2], &bits[3], &bits[4], &bits[5]);
+    let from_26 = b5 | &(&(b4 & b3) & &(b2 | b1));
+    let from_52 = &(b5 & b4) & &(b3 | b2);
+    let from_62 = Boolean::kary_and(&bits[1..])?;
+    let is_63 = &from_62 & &bits[0];
+    let is_62 = &from_62 & &!&bits[0];
+
+    let offset = FpVar::constant(F::from(b'A')) + FpVar::from(from_26) * F::from(6u8)
+        - FpVar::from(from_52) * F::from(75u8)
+        - FpVar::from(is_62) * F::from(13u8)
+        + FpVar::from(is_63) * F::from(36u8);
+    (Boolean::le_bits_to_fp(&bits)? + offset)
+        .enforce_equal(&Boolean::le_bits_to_fp(&char.to_bits_le()?)?)?;
+
+    Ok(bits)
+}
@@ -0,0 +1,164 @@
+use crate::gadgets::{comparison, select};
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*, uint8::UInt8};
+use ark_relations::r1cs;
+
+/// Bytes that always fit in a field element: 31 for BN254 and BLS12-381.
+pub fn capacity<F: PrimeField>() -> usize {
+    (F::MODULUS_BIT_SIZE as usize - 1) / 8
+}
+
+/// The value of a byte as a field element, at no cost.
+pub fn to_fp<F: PrimeField>(byte: &UInt8<F>) -> r1cs::Result<FpVar<F>> {
+    Boolean::le_bits_to_fp(&byte.to_bits_le()?)
+}
+
+/// Packs `bytes` as a big-endian integer, as `F::from_be_bytes_mod_order` does.
+///
+/// # Panics
+/// Panics if `bytes` is longer than [`capacity`], since the packing could wrap.
+pub fn pack_be<F: PrimeField>(bytes: &[UInt8<F>]) -> r1cs::Result<FpVar<F>> {
+    assert_fits::<F>(bytes.len());
+    bytes.iter().try_fold(FpVar::zero(), |packed, byte| {
+        Ok(packed * F::from(256u16) + to_fp(byte)?)
+    })
+}
+
+/// Packs `bytes` as a little-endian integer, as `F::from_le_bytes_mod_order` does.
+///
+/// # Panics
+/// Panics if `bytes` is longer than [`capacity`].
+pub fn pack_le<F: PrimeField>(bytes: &[UInt8<F>]) -> r1cs::Result<FpVar<F>> {
+    pack_be(&reverse(bytes))
+}
+
+/// Packs `bytes` into big-endian chunks of [`capacity`] bytes, the last one
+/// possibly shorter.
+pub fn pack_chunks_be<F: PrimeField>(bytes: &[UInt8<F>]) -> r1cs::Result<Vec<FpVar<F>>> {
+    bytes.chunks(capacity::<F>()).map(pack_be).collect()
+}
+
+/// Returns the `len` big-endian bytes of `x`, enforcing `x < 256^len`.
+///
+/// # Panics
+/// Panics if `len` exceeds [`capacity`].
+pub fn unpack_be<F: PrimeField>(x: &FpVar<F>, len: usize) -> r1cs::Result<Vec<UInt8<F>>> {
+    Ok(reverse(&unpack_le(x, len)?))
+}
+
+/// Returns the `len` little-endian bytes of `x`, enforcing `x < 256^len`.
+///
+/// # Panics
+/// Panics if `len` exceeds [`capacity`].
+pub fn unpack_le<F: PrimeField>(x: &FpVar<F>, len: usize) -> r1cs::Result<Vec<UInt8<F>>> {
+    assert_fits::<F>(len);
+    let bits = comparison::enforce_bit_length(x, 8 * len)?;
+    Ok(bits.chunks(8).map(UInt8::from_bits_le).collect())
+}
+
+/// Reverses the byte order, converting between big- and little-endian.
+pub fn reverse<F: PrimeField>(bytes: &[UInt8<F>]) -> Vec<UInt8<F>> {
+    bytes.iter().rev().cloned().collect()
+}
+
+/// Reverses the byte order within each `width`-byte word, e.g. to read the
+/// big-endian 32-bit words of SHA-256 as little-endian ones.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if `bytes` is not a whole number of words.
+pub fn swap_endianness<F: PrimeField>(
+    bytes: &[UInt8<F>],
+    width: usize,
+) -> r1cs::Result<Vec<UInt8<F>>> {
+    if width == 0 || !bytes.len().is_multiple_of(width) {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+    Ok(bytes.chunks(width).flat_map(reverse).collect())
+}
+
+/// Returns the `len` bytes of `bytes` starting at the private `offset`,
+/// enforcing `offset + len <= bytes.len()`.
+///
+/// Selects the window with a one-hot vector over the possible offsets,
+/// costing one constraint per offset and output byte.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if `len` exceeds `bytes.len()`.
+pub fn slice_at<F: PrimeField>(
+    bytes: &[UInt8<F>],
+    offset: &FpVar<F>,
+    len: usize,
+) -> r1cs::Result<Vec<UInt8<F>>> {
+    if len > bytes.len() {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    let cs = bytes.cs().or(offset.cs());
+    let selectors = select::one_hot(offset, bytes.len() - len + 1)?;
+
+    let values = bytes.iter().map(to_fp).collect::<r1cs::Result<Vec<_>>>()?;
+    (0..len)
+        .map(|j| {
+            let selected = select::select_one_hot(&selectors, &values[j..j + selectors.len()])?;
+            let byte = UInt8::new_witness(cs.clone(), || {
+                selected.value().map(|v| v.into_bigint().as_ref()[0] as u8)
+            })?;
+            to_fp(&byte)?.enforce_equal(&selected)?;
+            Ok(byte)
+        })
+        .collect()
+}
+
+/// Returns `n` flags, the first `len` of them set, enforcing `len <= n`.
+pub fn prefix_mask<F: PrimeField>(len: &FpVar<F>, n: usize) -> r1cs::Result<Vec<Boolean<F>>> {
+    let cs = len.cs();
+    let len_value = len.value();
+    let mask = (0..n)
+        .map(|i| {
+            Boolean::new_witness(cs.clone(), || {
+                let len = len_value?;
+                Ok(F::from(i as u64) < len)
+            })
+        })
+        .collect::<r1cs::Result<Vec<_>>>()?;
+
+    // Once a flag is clear all later ones are, and `len` of them are set
+    let mut count = FpVar::zero();
+    for (i, flag) in mask.iter().enumerate() {
+        if i > 0 {
+            mask[i - 1].conditional_enforce_equal(&Boolean::TRUE, flag)?;
+        }
+        count += FpVar::from(flag.clone());
+    }
+    count.enforce_equal(len)?;
+
+    Ok(mask)
+}
+
+/// Enforces that the first `len` bytes of `a` and `b` agree, as for two
+/// length-prefixed strings padded to a common maximum; the padding may differ.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if `a` and `b` have different lengths.
+pub fn enforce_equal_prefix<F: PrimeField>(
+    a: &[UInt8<F>],
+    b: &[UInt8<F>],
+    len: &FpVar<F>,
+) -> r1cs::Result<()> {
+    if a.len() != b.len() {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    for ((a, b), flag) in a.iter().zip(b).zip(prefix_mask(len, a.len())?) {
+        to_fp(a)?.conditional_enforce_equal(&to_fp(b)?, &flag)?;
+    }
+    Ok(())
+}
+
+fn assert_fits<F: PrimeField>(len: usize) {
+    assert!(
+        len <= capacity::<F>(),
+        "packing is limited to {} bytes",
+        capacity::<F>()
+    );
+}
@@ -0,0 +1,87 @@
+use crate::{babyjubjub, gadgets, poseidon};
+use ark_bn254::Fr;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ec::AffineRepr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+
+/// A hiding commitment to one field element, computed identically natively
+/// and in-circuit.
+///
+/// A commitment is a fixed number of field elements (one for a hash, two for
+/// the coordinates of a curve point), all of which become public inputs.
+pub trait CommitmentScheme<F: PrimeField> {
+    /// Scheme identifier, e.g. for circuit names.
+    const NAME: &'static str;
+    /// Field elements in a commitment.
+    const OUTPUT_LEN: usize;
+
+    /// Commits to `value` with `blinding`.
+    ///
+    /// # Errors
+    /// Returns error if the commitment cannot be computed.
+    fn commit(value: F, blinding: F) -> anyhow::Result<Vec<F>>;
+
+    /// In-circuit counterpart of [`CommitmentScheme::commit`].
+    fn commit_gadget(
+        cs: ConstraintSystemRef<F>,
+        value: &FpVar<F>,
+        blinding: &FpVar<F>,
+    ) -> r1cs::Result<Vec<FpVar<F>>>;
+}
+
+/// `Poseidon(value, blinding)`, the commitment used throughout the example circuits.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoseidonCommitment;
+
+impl<F: PrimeField + Absorb> CommitmentScheme<F> for PoseidonCommitment {
+    const NAME: &'static str = "poseidon";
+    const OUTPUT_LEN: usize = 1;
+
+    fn commit(value: F, blinding: F) -> anyhow::Result<Vec<F>> {
+        Ok(vec![poseidon::hash(&[value, blinding])?])
+    }
+
+    fn commit_gadget(
+        cs: ConstraintSystemRef<F>,
+        value: &FpVar<F>,
+        blinding: &FpVar<F>,
+    ) -> r1cs::Result<Vec<FpVar<F>>> {
+        Ok(vec![poseidon::hash_gadget(
+            cs,
+            &[value.clone(), blinding.clone()],
+        )?])
+    }
+}
+
+/// Pedersen commitment `value * G + blinding * H` over Baby Jubjub, the curve
+/// embedded in BN254; see [`babyjubjub::commit`]. The commitment is the
+/// point's `(x, y)`.
+///
+/// Additively homomorphic, unlike [`PoseidonCommitment`], but at about 3,300
+/// constraints against 240.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PedersenCommitment;
+
+impl CommitmentScheme<Fr> for PedersenCommitment {
+    const NAME: &'static str = "pedersen";
+    const OUTPUT_LEN: usize = 2;
+
+    fn commit(value: Fr, blinding: Fr) -> anyhow::Result<Vec<Fr>> {
+        let point = babyjubjub::commit(value, blinding);
+        Ok(vec![point.x, point.y])
+    }
+
+    fn commit_gadget(
+        _cs: ConstraintSystemRef<Fr>,
+        value: &FpVar<Fr>,
+        blinding: &FpVar<Fr>,
+    ) -> r1cs::Result<Vec<FpVar<Fr>>> {
+        let point = gadgets::babyjubjub::fixed_base_msm(&[
+            (babyjubjub::Affine::generator(), &value.to_bits_le()?),
+            (babyjubjub::blinding_generator(), &blinding.to_bits_le()?),
+        ])?;
+        Ok(vec![point.x, point.y])
+    }
+}
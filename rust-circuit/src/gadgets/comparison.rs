@@ -0,0 +1,103 @@
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+
+/// Enforces `0 <= x < 2^bits` and returns the little-endian bits of `x`.
+///
+/// Costs `bits + 1` constraints, far fewer than a full `to_bits_le`.
+///
+/// # Panics
+/// Panics if `bits` leaves no headroom below the modulus, since sums and
+/// differences of checked values could then wrap.
+pub fn enforce_bit_length<F: PrimeField>(
+    x: &FpVar<F>,
+    bits: usize,
+) -> r1cs::Result<Vec<Boolean<F>>> {
+    assert!(
+        bits + 2 < F::MODULUS_BIT_SIZE as usize,
+        "range checks are limited to {} bits",
+        F::MODULUS_BIT_SIZE - 3
+    );
+
+    let cs = x.cs();
+    let value = x.value().map(|v| v.into_bigint());
+    let bits = (0..bits)
+        .map(|i| {
+            let bit = || {
+                value
+                    .map(|v| v.get_bit(i))
+                    .map_err(|_| r1cs::SynthesisError::AssignmentMissing)
+            };
+            match x {
+                FpVar::Constant(_) => Boolean::new_constant(cs.clone(), bit()?),
+                FpVar::Var(_) => Boolean::new_witness(cs.clone(), bit),
+            }
+        })
+        .collect::<r1cs::Result<Vec<_>>>()?;
+
+    Boolean::le_bits_to_fp(&bits)?.enforce_equal(x)?;
+
+    Ok(bits)
+}
+
+/// Enforces `a <= b` for values already known to be below `2^bits`.
+pub fn enforce_less_or_equal<F: PrimeField>(
+    a: &FpVar<F>,
+    b: &FpVar<F>,
+    bits: usize,
+) -> r1cs::Result<()> {
+    // b - a wraps to a value near the modulus when a > b
+    enforce_bit_length(&(b - a), bits)?;
+    Ok(())
+}
+
+/// Enforces `a < b` for values already known to be below `2^bits`.
+pub fn enforce_less_than<F: PrimeField>(
+    a: &FpVar<F>,
+    b: &FpVar<F>,
+    bits: usize,
+) -> r1cs::Result<()> {
+    enforce_less_or_equal(&(a + F::ONE), b, bits + 1)
+}
+
+/// Returns whether `a <= b`, for values already known to be below `2^bits`.
+pub fn is_less_or_equal<F: PrimeField>(
+    a: &FpVar<F>,
+    b: &FpVar<F>,
+    bits: usize,
+) -> r1cs::Result<Boolean<F>> {
+    // 2^bits + b - a lies in [1, 2^(bits + 1)); its top bit is set iff a <= b
+    let offset = F::from(2u64).pow([bits as u64]);
+    let shifted = enforce_bit_length(&(b - a + offset), bits + 1)?;
+    Ok(shifted[bits].clone())
+}
+
+/// Returns whether `x` is zero, as circom's `IsZero`.
+///
+/// Costs 2 constraints, via [`EqGadget::is_eq`]: a witnessed `neq = 1 - out`
+/// and inverse `inv` with `x * inv = neq` and `x * (1 - neq) = 0`.
+pub fn is_zero<F: PrimeField>(x: &FpVar<F>) -> r1cs::Result<Boolean<F>> {
+    x.is_eq(&FpVar::zero())
+}
+
+/// Returns whether `a == b`, as circom's `IsEqual`, at the cost of [`is_zero`].
+pub fn is_equal<F: PrimeField>(a: &FpVar<F>, b: &FpVar<F>) -> r1cs::Result<Boolean<F>> {
+    a.is_eq(b)
+}
+
+/// Enforces `x != 0` with a single constraint, by witnessing its inverse.
+///
+/// A zero witness leaves the circuit unsatisfied rather than failing
+/// synthesis, as a prover could supply it. A constant `x` adds no constraint:
+/// a zero constant returns [`SynthesisError::Unsatisfiable`](r1cs::SynthesisError::Unsatisfiable),
+/// since no assignment could satisfy the circuit.
+pub fn enforce_nonzero<F: PrimeField>(x: &FpVar<F>) -> r1cs::Result<()> {
+    if let FpVar::Constant(value) = x {
+        if value.is_zero() {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+        return Ok(());
+    }
+    let inverse = FpVar::new_witness(x.cs(), || Ok(x.value()?.inverse().unwrap_or_default()))?;
+    x.mul_equals(&inverse, &FpVar::one())
+}
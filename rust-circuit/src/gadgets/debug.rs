@@ -0,0 +1,89 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::R1CSVar;
+use ark_relations::r1cs::ConstraintTrace;
+use std::cell::RefCell;
+use std::fmt;
+
+thread_local! {
+    static TRACE: RefCell<Option<Vec<TraceEntry>>> = const { RefCell::new(None) };
+}
+
+/// A value recorded by [`debug_print`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceEntry {
+    /// `ns!` path of the call, e.g. `merkle/level 3`; empty unless
+    /// namespaces are traced (as inside `witness::traced`).
+    pub namespace: String,
+    pub label: String,
+    /// `Debug` output of the variable's value.
+    pub value: String,
+}
+
+/// `namespace/label = value`.
+impl fmt::Display for TraceEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.namespace.is_empty() {
+            write!(f, "{}/", self.namespace)?;
+        }
+        write!(f, "{} = {}", self.label, self.value)
+    }
+}
+
+/// Records the value of `var` under `label` while a [`capture`] is running
+/// on this thread, so intermediate values can be inspected when a circuit
+/// is not satisfied.
+///
+/// Adds no constraints or variables. Outside [`capture`], as when proving,
+/// and in setup mode, where there are no values, it does nothing.
+pub fn debug_print<F, V>(label: &str, var: &V)
+where
+    F: PrimeField,
+    V: R1CSVar<F>,
+    V::Value: fmt::Debug,
+{
+    TRACE.with(|trace| {
+        let mut trace = trace.borrow_mut();
+        let Some(entries) = trace.as_mut() else {
+            return;
+        };
+        let Ok(value) = var.value() else {
+            return;
+        };
+
+        let namespace = ConstraintTrace::capture()
+            .map(|trace| {
+                let steps: Vec<&str> = trace.path().iter().map(|step| step.name).collect();
+                steps.join("/")
+            })
+            .unwrap_or_default();
+        entries.push(TraceEntry {
+            namespace,
+            label: label.to_string(),
+            value: format!("{:?}", value),
+        });
+    });
+}
+
+/// Runs `f`, typically constraint generation, and returns the values it
+/// passed to [`debug_print`] in call order.
+///
+/// Captures nest: an inner capture records only its own calls.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<TraceEntry>) {
+    // Restores the enclosing capture even if `f` panics
+    struct Restore(Option<Vec<TraceEntry>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let outer = self.0.take();
+            TRACE.with(|trace| *trace.borrow_mut() = outer);
+        }
+    }
+
+    let outer = TRACE.with(|trace| trace.borrow_mut().replace(Vec::new()));
+    let restore = Restore(outer);
+    let result = f();
+    let entries = TRACE.with(|trace| trace.borrow_mut().take().unwrap_or_default());
+    drop(restore);
+
+    (result, entries)
+}
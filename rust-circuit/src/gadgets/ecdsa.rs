@@ -0,0 +1,250 @@
+use crate::secp256k1::{self, Affine, Fq, Fr};
+use ark_ec::{short_weierstrass::SWCurveConfig, AffineRepr, CurveGroup};
+use ark_ff::{AdditiveGroup, Field, PrimeField};
+use ark_r1cs_std::{
+    boolean::Boolean,
+    fields::{emulated_fp::EmulatedFpVar, FieldVar},
+    prelude::*,
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSystemRef, SynthesisError},
+};
+
+/// A secp256k1 coordinate, emulated in the circuit's field `F`.
+pub type FqVar<F> = EmulatedFpVar<Fq, F>;
+/// A secp256k1 scalar, emulated in the circuit's field `F`.
+pub type ScalarVar<F> = EmulatedFpVar<Fr, F>;
+
+/// An affine secp256k1 point other than the point at infinity.
+///
+/// Additions use the incomplete affine formulas; [`PointVar::add`] enforces
+/// that the operands have distinct x-coordinates, so an exceptional case
+/// makes the circuit unsatisfiable rather than unsound.
+#[derive(Debug, Clone)]
+pub struct PointVar<F: PrimeField> {
+    pub x: FqVar<F>,
+    pub y: FqVar<F>,
+}
+
+impl<F: PrimeField> PointVar<F> {
+    /// # Panics
+    /// Panics if `point` is the point at infinity.
+    pub fn constant(point: Affine) -> Self {
+        let (x, y) = point.xy().expect("constant point must be finite");
+        Self {
+            x: FqVar::constant(x),
+            y: FqVar::constant(y),
+        }
+    }
+
+    /// Allocates a witness point and enforces that it is on the curve.
+    pub fn new_witness(
+        cs: ConstraintSystemRef<F>,
+        point: impl FnOnce() -> r1cs::Result<Affine>,
+    ) -> r1cs::Result<Self> {
+        let point = point().and_then(|point| point.xy().ok_or(SynthesisError::Unsatisfiable));
+        let x = FqVar::new_witness(ns!(cs, "x"), || point.map(|(x, _)| x))?;
+        let y = FqVar::new_witness(ns!(cs, "y"), || point.map(|(_, y)| y))?;
+
+        let point = Self { x, y };
+        point.enforce_on_curve()?;
+        Ok(point)
+    }
+
+    /// Enforces `y^2 = x^3 + 7`.
+    pub fn enforce_on_curve(&self) -> r1cs::Result<()> {
+        let rhs = self.x.square()? * &self.x + secp256k1::Config::COEFF_B;
+        self.y.square()?.enforce_equal(&rhs)
+    }
+
+    /// The point's value if both coordinates are constants.
+    fn as_constant(&self) -> Option<Affine> {
+        if self.x.is_constant() && self.y.is_constant() {
+            Some(Affine::new_unchecked(
+                self.x.value().ok()?,
+                self.y.value().ok()?,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// `self + other`, enforcing that the points have distinct x-coordinates.
+    ///
+    /// The slope is only determined when they do, so `dx` is proven
+    /// invertible; `self == other` or `self == -other` is unsatisfiable.
+    pub fn add(&self, other: &Self) -> r1cs::Result<Self> {
+        if let (Some(a), Some(b)) = (self.as_constant(), other.as_constant()) {
+            return Ok(Self::constant((a + b).into_affine()));
+        }
+
+        // lambda = (y2 - y1) / (x2 - x1), checked by one multiplication
+        let dx = &other.x - &self.x;
+        dx.enforce_not_equal(&FqVar::zero())?;
+        let lambda = FqVar::new_witness(self.x.cs().or(other.x.cs()), || {
+            let dy = other.y.value()? - self.y.value()?;
+            Ok(dy * dx.value()?.inverse().unwrap_or_default())
+        })?;
+        lambda.mul_equals(&dx, &(&other.y - &self.y))?;
+
+        self.complete_with(&lambda, &other.x)
+    }
+
+    /// `2 * self`.
+    pub fn double(&self) -> r1cs::Result<Self> {
+        if let Some(point) = self.as_constant() {
+            return Ok(Self::constant((point + point).into_affine()));
+        }
+
+        // lambda = 3x^2 / 2y; y is never zero as secp256k1 has prime order
+        let x_squared = self.x.square()?;
+        let lambda = FqVar::new_witness(self.x.cs(), || {
+            let x_squared = x_squared.value()?;
+            let two_y = self.y.value()?.double();
+            Ok((x_squared.double() + x_squared) * two_y.inverse().unwrap_or_default())
+        })?;
+        lambda.mul_equals(&self.y.double()?, &(x_squared.double()? + &x_squared))?;
+
+        self.complete_with(&lambda, &self.x)
+    }
+
+    /// The sum given its slope: `x3 = lambda^2 - x1 - x2`, `y3 = lambda (x1 - x3) - y1`.
+    ///
+    /// The coordinates are witnessed and checked by multiplication, so they
+    /// stay reduced across long chains of additions.
+    fn complete_with(&self, lambda: &FqVar<F>, other_x: &FqVar<F>) -> r1cs::Result<Self> {
+        let cs = lambda.cs();
+        let x = FqVar::new_witness(ns!(cs, "x3"), || {
+            Ok(lambda.value()?.square() - self.x.value()? - other_x.value()?)
+        })?;
+        lambda.mul_equals(lambda, &(&x + &self.x + other_x))?;
+
+        let y = FqVar::new_witness(ns!(cs, "y3"), || {
+            Ok(lambda.value()? * (self.x.value()? - x.value()?) - self.y.value()?)
+        })?;
+        lambda.mul_equals(&(&self.x - &x), &(&y + &self.y))?;
+
+        Ok(Self { x, y })
+    }
+}
+
+impl<F: PrimeField> CondSelectGadget<F> for PointVar<F> {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> r1cs::Result<Self> {
+        Ok(Self {
+            x: cond.select(&true_value.x, &false_value.x)?,
+            y: cond.select(&true_value.y, &false_value.y)?,
+        })
+    }
+}
+
+/// `u1 * G + u2 * q` by Shamir's trick over 2-bit windows of the
+/// little-endian bits of both scalars.
+///
+/// The accumulator starts at a fixed offset point, subtracted at the end, so
+/// it does not pass through infinity for honest inputs. `q` is chosen by the
+/// prover, so every addition is checked: inputs that reach an exceptional
+/// case make the circuit unsatisfiable.
+pub fn double_scalar_mul<F: PrimeField>(
+    u1_bits: &[Boolean<F>],
+    u2_bits: &[Boolean<F>],
+    q: &PointVar<F>,
+) -> r1cs::Result<PointVar<F>> {
+    if u1_bits.len() != u2_bits.len() {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let table = window_table(q)?;
+    let pad = |bits: &[Boolean<F>]| {
+        let mut bits = bits.to_vec();
+        bits.resize(bits.len().next_multiple_of(2), Boolean::FALSE);
+        bits
+    };
+    let (u1_bits, u2_bits) = (pad(u1_bits), pad(u2_bits));
+
+    let offset = offset_point();
+    let mut acc = PointVar::constant(offset);
+    for (u1, u2) in u1_bits.chunks(2).zip(u2_bits.chunks(2)).rev() {
+        acc = acc.double()?.double()?;
+
+        // Entry 0 is a placeholder: the sum is discarded when all bits are zero
+        let index = [u1[0].clone(), u1[1].clone(), u2[0].clone(), u2[1].clone()];
+        let sum = acc.add(&lookup(&index, &table)?)?;
+        acc = Boolean::kary_or(&index)?.select(&sum, &acc)?;
+    }
+
+    let correction = -(offset * Fr::from(2u64).pow([u1_bits.len() as u64])).into_affine();
+    acc.add(&PointVar::constant(correction))
+}
+
+/// `a * G + b * q` at index `a + 4 b` for `a, b < 4`, with `G` at index 0.
+fn window_table<F: PrimeField>(q: &PointVar<F>) -> r1cs::Result<Vec<PointVar<F>>> {
+    let g = Affine::generator();
+    let g_multiples = [g, (g + g).into_affine(), (g + g + g).into_affine()].map(PointVar::constant);
+    let q2 = q.double()?;
+    let q_multiples = [q.clone(), q2.clone(), q2.add(q)?];
+
+    let mut table = vec![g_multiples[0].clone()];
+    table.extend(g_multiples.iter().cloned());
+    for q_multiple in &q_multiples {
+        table.push(q_multiple.clone());
+        for g_multiple in &g_multiples {
+            table.push(g_multiple.add(q_multiple)?);
+        }
+    }
+    Ok(table)
+}
+
+/// The entry of `table` at the little-endian `index`.
+fn lookup<F: PrimeField>(index: &[Boolean<F>], table: &[PointVar<F>]) -> r1cs::Result<PointVar<F>> {
+    let mut entries = table.to_vec();
+    for bit in index {
+        entries = entries
+            .chunks(2)
+            .map(|pair| bit.select(&pair[1], &pair[0]))
+            .collect::<r1cs::Result<_>>()?;
+    }
+    Ok(entries.remove(0))
+}
+
+/// Enforces that `(r, s)` is a valid ECDSA signature by `public_key` on the
+/// message scalar `z` (see [`secp256k1::message_scalar`]).
+///
+/// Rather than inverting `s` in the circuit, `u1 = z / s` and `u2 = r / s`
+/// are witnessed and checked by multiplication. The recovered x-coordinate
+/// must equal `r` exactly; valid signatures where it exceeds the group
+/// order (probability about 2^-128) are rejected.
+pub fn enforce_valid_signature<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    public_key: &PointVar<F>,
+    z: &ScalarVar<F>,
+    r: &ScalarVar<F>,
+    s: &ScalarVar<F>,
+) -> r1cs::Result<()> {
+    r.enforce_not_equal(&ScalarVar::zero())?;
+
+    let s_inv = || -> r1cs::Result<Fr> { Ok(s.value()?.inverse().unwrap_or_default()) };
+    let u1 = ScalarVar::new_witness(ns!(cs, "u1"), || Ok(z.value()? * s_inv()?))?;
+    let u2 = ScalarVar::new_witness(ns!(cs, "u2"), || Ok(r.value()? * s_inv()?))?;
+    u1.mul_equals(s, z)?;
+    u2.mul_equals(s, r)?;
+
+    let point = double_scalar_mul(&u1.to_bits_le()?, &u2.to_bits_le()?, public_key)?;
+
+    let _ns = ns!(cs, "R.x == r");
+    for (x_bit, r_bit) in point.x.to_bits_le()?.iter().zip(&r.to_bits_le()?) {
+        x_bit.enforce_equal(r_bit)?;
+    }
+    Ok(())
+}
+
+/// The first point with x-coordinate at least 2, found by try-and-increment.
+fn offset_point() -> Affine {
+    (2u64..)
+        .find_map(|x| Affine::get_point_from_x_unchecked(Fq::from(x), false))
+        .expect("half of all x-coordinates are on the curve")
+}
@@ -0,0 +1,266 @@
+use crate::ed25519::{self, Affine, Fq, Fr};
+use crate::gadgets::sha512;
+use ark_ec::{twisted_edwards::TECurveConfig, AffineRepr, CurveGroup};
+use ark_ff::{AdditiveGroup, Field, PrimeField};
+use ark_r1cs_std::{
+    boolean::Boolean,
+    fields::{emulated_fp::EmulatedFpVar, FieldVar},
+    prelude::*,
+    uint8::UInt8,
+};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSystemRef, SynthesisError},
+};
+
+/// An edwards25519 coordinate, emulated in the circuit's field `F`.
+pub type FqVar<F> = EmulatedFpVar<Fq, F>;
+/// An edwards25519 scalar, emulated in the circuit's field `F`.
+pub type ScalarVar<F> = EmulatedFpVar<Fr, F>;
+
+/// An affine edwards25519 point.
+///
+/// Additions use the complete twisted Edwards law: `d` is not a square, so
+/// it holds for every pair of curve points, including doublings and the
+/// identity `(0, 1)`, and needs no exceptional cases.
+#[derive(Debug, Clone)]
+pub struct PointVar<F: PrimeField> {
+    pub x: FqVar<F>,
+    pub y: FqVar<F>,
+}
+
+impl<F: PrimeField> PointVar<F> {
+    pub fn constant(point: Affine) -> Self {
+        Self {
+            x: FqVar::constant(point.x),
+            y: FqVar::constant(point.y),
+        }
+    }
+
+    /// Allocates a witness point and enforces that it is on the curve.
+    ///
+    /// Only membership of the curve is enforced, not of the prime-order
+    /// subgroup.
+    pub fn new_witness(
+        cs: ConstraintSystemRef<F>,
+        point: impl FnOnce() -> r1cs::Result<Affine>,
+    ) -> r1cs::Result<Self> {
+        let point = point();
+        let x = FqVar::new_witness(ns!(cs, "x"), || point.map(|point| point.x))?;
+        let y = FqVar::new_witness(ns!(cs, "y"), || point.map(|point| point.y))?;
+
+        let point = Self { x, y };
+        point.enforce_on_curve()?;
+        Ok(point)
+    }
+
+    /// Enforces `-x^2 + y^2 = 1 + d x^2 y^2`.
+    pub fn enforce_on_curve(&self) -> r1cs::Result<()> {
+        let x_squared = self.x.square()?;
+        let y_squared = self.y.square()?;
+        let rhs = (&x_squared * &y_squared) * ed25519::Config::COEFF_D + Fq::ONE;
+        (y_squared - x_squared).enforce_equal(&rhs)
+    }
+
+    /// The point's value if both coordinates are constants.
+    fn as_constant(&self) -> Option<Affine> {
+        if self.x.is_constant() && self.y.is_constant() {
+            Some(Affine::new_unchecked(
+                self.x.value().ok()?,
+                self.y.value().ok()?,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// `self + other`:
+    /// `x3 = (x1 y2 + y1 x2) / (1 + d t)`, `y3 = (y1 y2 + x1 x2) / (1 - d t)`
+    /// with `t = x1 x2 y1 y2`.
+    ///
+    /// The coordinates are witnessed and checked by multiplication, so they
+    /// stay reduced across long chains of additions.
+    pub fn add(&self, other: &Self) -> r1cs::Result<Self> {
+        match (self.as_constant(), other.as_constant()) {
+            (Some(a), Some(b)) => return Ok(Self::constant((a + b).into_affine())),
+            (Some(a), _) if a.is_zero() => return Ok(other.clone()),
+            (_, Some(b)) if b.is_zero() => return Ok(self.clone()),
+            _ => {}
+        }
+
+        let xx = &self.x * &other.x;
+        let yy = &self.y * &other.y;
+        let cross = (&self.x + &self.y) * (&other.x + &other.y) - &xx - &yy;
+        let dt = (&xx * &yy) * ed25519::Config::COEFF_D;
+
+        let cs = self.x.cs().or(other.x.cs());
+        let x_denominator = &dt + Fq::ONE;
+        let x = FqVar::new_witness(ns!(cs, "x3"), || {
+            Ok(cross.value()? * x_denominator.value()?.inverse().unwrap_or_default())
+        })?;
+        x.mul_equals(&x_denominator, &cross)?;
+
+        let y_numerator = &yy + &xx;
+        let y_denominator = FqVar::one() - &dt;
+        let y = FqVar::new_witness(ns!(cs, "y3"), || {
+            Ok(y_numerator.value()? * y_denominator.value()?.inverse().unwrap_or_default())
+        })?;
+        y.mul_equals(&y_denominator, &y_numerator)?;
+
+        Ok(Self { x, y })
+    }
+
+    /// `2 * self`.
+    pub fn double(&self) -> r1cs::Result<Self> {
+        self.add(self)
+    }
+
+    /// `-self = (-x, y)`.
+    pub fn negate(&self) -> r1cs::Result<Self> {
+        Ok(Self {
+            x: self.x.negate()?,
+            y: self.y.clone(),
+        })
+    }
+
+    /// The RFC 8032 encoding, as [`ed25519::compress`]: `y` little-endian,
+    /// with the top bit holding the parity of `x`.
+    pub fn to_bytes(&self) -> r1cs::Result<Vec<UInt8<F>>> {
+        // Both decompositions are canonical, so the encoding is unique
+        let mut bits = self.y.to_bits_le()?;
+        bits.truncate(Fq::MODULUS_BIT_SIZE as usize);
+        bits.push(self.x.to_bits_le()?[0].clone());
+        Ok(bits.chunks(8).map(UInt8::from_bits_le).collect())
+    }
+}
+
+impl<F: PrimeField> CondSelectGadget<F> for PointVar<F> {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> r1cs::Result<Self> {
+        Ok(Self {
+            x: cond.select(&true_value.x, &false_value.x)?,
+            y: cond.select(&true_value.y, &false_value.y)?,
+        })
+    }
+}
+
+/// `u1 * B + u2 * q` by Shamir's trick over 2-bit windows of the
+/// little-endian bits of both scalars, where `B` is the base point.
+///
+/// The addition law is complete, so the accumulator may start at the
+/// identity and any `q` on the curve is handled.
+pub fn double_scalar_mul<F: PrimeField>(
+    u1_bits: &[Boolean<F>],
+    u2_bits: &[Boolean<F>],
+    q: &PointVar<F>,
+) -> r1cs::Result<PointVar<F>> {
+    if u1_bits.len() != u2_bits.len() {
+        return Err(SynthesisError::Unsatisfiable);
+    }
+
+    let table = window_table(q)?;
+    let pad = |bits: &[Boolean<F>]| {
+        let mut bits = bits.to_vec();
+        bits.resize(bits.len().next_multiple_of(2), Boolean::FALSE);
+        bits
+    };
+    let (u1_bits, u2_bits) = (pad(u1_bits), pad(u2_bits));
+
+    let mut acc = PointVar::constant(Affine::zero());
+    for (u1, u2) in u1_bits.chunks(2).zip(u2_bits.chunks(2)).rev() {
+        acc = acc.double()?.double()?;
+        let index = [u1[0].clone(), u1[1].clone(), u2[0].clone(), u2[1].clone()];
+        acc = acc.add(&lookup(&index, &table)?)?;
+    }
+    Ok(acc)
+}
+
+/// `a * B + b * q` at index `a + 4 b` for `a, b < 4`.
+fn window_table<F: PrimeField>(q: &PointVar<F>) -> r1cs::Result<Vec<PointVar<F>>> {
+    let b = Affine::generator();
+    let b_multiples = [
+        Affine::zero(),
+        b,
+        (b + b).into_affine(),
+        (b + b + b).into_affine(),
+    ]
+    .map(PointVar::constant);
+    let q2 = q.double()?;
+    let q_multiples = [
+        PointVar::constant(Affine::zero()),
+        q.clone(),
+        q2.clone(),
+        q2.add(q)?,
+    ];
+
+    let mut table = Vec::with_capacity(16);
+    for q_multiple in &q_multiples {
+        for b_multiple in &b_multiples {
+            table.push(b_multiple.add(q_multiple)?);
+        }
+    }
+    Ok(table)
+}
+
+/// The entry of `table` at the little-endian `index`.
+fn lookup<F: PrimeField>(index: &[Boolean<F>], table: &[PointVar<F>]) -> r1cs::Result<PointVar<F>> {
+    let mut entries = table.to_vec();
+    for bit in index {
+        entries = entries
+            .chunks(2)
+            .map(|pair| bit.select(&pair[1], &pair[0]))
+            .collect::<r1cs::Result<_>>()?;
+    }
+    Ok(entries.remove(0))
+}
+
+/// The little-endian 512-bit `digest`, reduced modulo the group order as
+/// [`ed25519::challenge`] does.
+pub fn digest_scalar<F: PrimeField>(digest: &[UInt8<F>]) -> r1cs::Result<ScalarVar<F>> {
+    let mut scalar = ScalarVar::zero();
+    let mut power = Fr::ONE;
+    for byte in digest {
+        for bit in byte.to_bits_le()? {
+            scalar += bit.select(&ScalarVar::constant(power), &ScalarVar::zero())?;
+            power.double_in_place();
+        }
+    }
+    Ok(scalar)
+}
+
+/// Enforces that `(r, s)` is a valid Ed25519 signature by `public_key` on
+/// `message`: `[s]B == R + [k]A` with `k = SHA-512(R || A || message)`, as
+/// [`ed25519::verify`] checks.
+///
+/// `s` must be canonical. The key is assumed to be in the prime-order
+/// subgroup; `R` then is too, as `R = [s]B - [k]A`. With `message` of up to
+/// 47 bytes the hash is one SHA-512 block.
+pub fn enforce_valid_signature<F: PrimeField>(
+    cs: ConstraintSystemRef<F>,
+    public_key: &PointVar<F>,
+    message: &[UInt8<F>],
+    r: &PointVar<F>,
+    s: &ScalarVar<F>,
+) -> r1cs::Result<()> {
+    let k = {
+        let _ns = ns!(cs, "k = SHA-512(R || A || message)");
+        let mut input = r.to_bytes()?;
+        input.extend(public_key.to_bytes()?);
+        input.extend_from_slice(message);
+        digest_scalar(&sha512::sha512(&input)?)?
+    };
+
+    let scalar_bits = |scalar: &ScalarVar<F>| -> r1cs::Result<Vec<Boolean<F>>> {
+        let mut bits = scalar.to_bits_le()?;
+        bits.truncate(Fr::MODULUS_BIT_SIZE as usize);
+        Ok(bits)
+    };
+    let point = double_scalar_mul(&scalar_bits(s)?, &scalar_bits(&k)?, &public_key.negate()?)?;
+
+    let _ns = ns!(cs, "[S]B - [k]A == R");
+    point.x.enforce_equal(&r.x)?;
+    point.y.enforce_equal(&r.y)
+}
@@ -0,0 +1,40 @@
+use crate::babyjubjub::{Affine, Fq};
+use crate::gadgets::babyjubjub::{self, PointVar};
+use crate::gadgets::comparison;
+use crate::poseidon;
+use ark_ec::AffineRepr;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+
+/// Enforces that `(r8, s)` is a valid EdDSA-Poseidon signature by
+/// `public_key` on `message`, as [`crate::babyjubjub::verify`] checks; `s` is
+/// given by its little-endian bits.
+///
+/// Like circomlib's `EdDSAPoseidonVerifier`, the key must not be the
+/// identity; it is assumed to be in the prime-order subgroup.
+pub fn enforce_valid_signature(
+    public_key: &PointVar,
+    message: &FpVar<Fq>,
+    r8: &PointVar,
+    s: &[Boolean<Fq>],
+) -> r1cs::Result<()> {
+    comparison::enforce_nonzero(&public_key.x)?;
+
+    let challenge = poseidon::circom_hash_gadget(
+        message.cs(),
+        &[
+            r8.x.clone(),
+            r8.y.clone(),
+            public_key.x.clone(),
+            public_key.y.clone(),
+            message.clone(),
+        ],
+    )?;
+    let challenge_times_key = babyjubjub::scalar_mul(public_key, &challenge.to_bits_le()?)?
+        .double()?
+        .double()?
+        .double()?;
+
+    babyjubjub::fixed_base_msm(&[(Affine::generator(), s)])?
+        .enforce_equal(&(challenge_times_key + r8))
+}
@@ -0,0 +1,33 @@
+use crate::babyjubjub::{Affine, Fq};
+use crate::gadgets::babyjubjub::{self, PointVar};
+use ark_ec::AffineRepr;
+use ark_r1cs_std::{boolean::Boolean, prelude::*};
+use ark_relations::r1cs;
+
+/// In-circuit counterpart of [`crate::babyjubjub::encrypt`], from the
+/// little-endian bits of the message and randomness; returns `(c1, c2)`.
+///
+/// Taking bits lets a range-checked message reuse its decomposition.
+pub fn encrypt(
+    public_key: &PointVar,
+    message: &[Boolean<Fq>],
+    randomness: &[Boolean<Fq>],
+) -> r1cs::Result<(PointVar, PointVar)> {
+    let c1 = babyjubjub::fixed_base_msm(&[(Affine::generator(), randomness)])?;
+    let c2 = babyjubjub::fixed_base_msm(&[(Affine::generator(), message)])?
+        + babyjubjub::scalar_mul(public_key, randomness)?;
+    Ok((c1, c2))
+}
+
+/// Enforces that `(c1, c2)` encrypts `message` under `public_key`.
+pub fn enforce_encryption(
+    public_key: &PointVar,
+    c1: &PointVar,
+    c2: &PointVar,
+    message: &[Boolean<Fq>],
+    randomness: &[Boolean<Fq>],
+) -> r1cs::Result<()> {
+    let (expected_c1, expected_c2) = encrypt(public_key, message, randomness)?;
+    c1.enforce_equal(&expected_c1)?;
+    c2.enforce_equal(&expected_c2)
+}
@@ -0,0 +1,70 @@
+use crate::gadgets::comparison;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+
+/// Enforces that `x` is one of the public `table` values, e.g. an allowed
+/// denomination or currency code.
+///
+/// The product of `x - t` over the table vanishes exactly when `x` is in it,
+/// which costs one constraint per entry after the first; keep tables small.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if `table` is empty, or if `x` is a constant
+/// missing from it.
+pub fn enforce_in_table<F: PrimeField>(x: &FpVar<F>, table: &[F]) -> r1cs::Result<()> {
+    let (last, rest) = table
+        .split_last()
+        .ok_or(r1cs::SynthesisError::Unsatisfiable)?;
+    if let FpVar::Constant(value) = x {
+        if !table.contains(value) {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+        return Ok(());
+    }
+    match rest {
+        [] => x.enforce_equal(&FpVar::constant(*last)),
+        _ => differences(x, rest).mul_equals(&(x - *last), &FpVar::zero()),
+    }
+}
+
+/// Returns whether `x` is one of the public `table` values, at the cost of
+/// [`enforce_in_table`] plus 2 constraints.
+pub fn is_in_table<F: PrimeField>(x: &FpVar<F>, table: &[F]) -> r1cs::Result<Boolean<F>> {
+    if table.is_empty() {
+        return Ok(Boolean::FALSE);
+    }
+    comparison::is_zero(&differences(x, table))
+}
+
+/// Enforces that `x` is one of the `set` values, which unlike a table may be
+/// allocated, e.g. a public allow-list chosen per proof. Costs one
+/// constraint per entry after the first.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if `set` is empty, or if every value is a
+/// constant and `x` is missing from `set`.
+pub fn enforce_in_set<F: PrimeField>(x: &FpVar<F>, set: &[FpVar<F>]) -> r1cs::Result<()> {
+    let (last, rest) = set
+        .split_last()
+        .ok_or(r1cs::SynthesisError::Unsatisfiable)?;
+    let product = rest
+        .iter()
+        .fold(FpVar::one(), |product, entry| product * (x - entry));
+    match (product, x - last) {
+        (FpVar::Constant(product), FpVar::Constant(last)) => {
+            if !(product * last).is_zero() {
+                return Err(r1cs::SynthesisError::Unsatisfiable);
+            }
+            Ok(())
+        }
+        (product, last) => product.mul_equals(&last, &FpVar::zero()),
+    }
+}
+
+/// The product of `x - t` over `table`.
+fn differences<F: PrimeField>(x: &FpVar<F>, table: &[F]) -> FpVar<F> {
+    table
+        .iter()
+        .fold(FpVar::one(), |product, entry| product * (x - *entry))
+}
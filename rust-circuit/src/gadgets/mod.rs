@@ -0,0 +1,17 @@
+pub mod babyjubjub;
+pub mod base64;
+pub mod bytes;
+pub mod commitment;
+pub mod comparison;
+pub mod debug;
+pub mod ecdsa;
+pub mod ed25519;
+pub mod eddsa;
+pub mod elgamal;
+pub mod lookup;
+pub mod regex;
+pub mod select;
+pub mod sha512;
+pub mod shuffle;
+pub mod signed;
+pub mod uint;
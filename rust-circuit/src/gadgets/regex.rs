@@ -0,0 +1,197 @@
+use crate::gadgets::comparison;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*, uint8::UInt8};
+use ark_relations::r1cs;
+use regex_automata::{
+    dfa::{dense, Automaton, StartKind},
+    nfa::thompson,
+    util::{start, syntax},
+    Anchored, MatchKind,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+/// A deterministic automaton over bytes deciding whether a whole input matches
+/// a regular expression, as walked by [`Dfa::matches`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dfa {
+    /// `transitions[state][byte]`, with `None` for the dead state. State 0 is
+    /// the start state.
+    transitions: Vec<Vec<Option<usize>>>,
+    accepting: Vec<bool>,
+}
+
+impl Dfa {
+    /// Compiles `pattern`, which must match the whole input.
+    ///
+    /// Patterns are over bytes rather than UTF-8: Unicode classes are off, so
+    /// `\w` is ASCII, and `\xFF` matches the byte `0xFF`.
+    ///
+    /// # Errors
+    /// Returns error if `pattern` is invalid or its DFA is too large.
+    pub fn new(pattern: &str) -> anyhow::Result<Self> {
+        let dfa = dense::Builder::new()
+            .configure(
+                dense::Config::new()
+                    .match_kind(MatchKind::All)
+                    .start_kind(StartKind::Anchored),
+            )
+            .syntax(syntax::Config::new().unicode(false).utf8(false))
+            .thompson(thompson::Config::new().utf8(false))
+            .build(&format!(r"(?:{})\z", pattern))
+            .map_err(|e| anyhow::anyhow!("Failed to compile regex '{}': {}", pattern, e))?;
+        let start = dfa
+            .start_state(&start::Config::new().anchored(Anchored::Yes))
+            .map_err(|e| anyhow::anyhow!("Failed to compile regex '{}': {}", pattern, e))?;
+
+        // Number the live states breadth-first, so that state ids follow the
+        // order rows are pushed
+        let mut ids = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        let mut transitions = Vec::new();
+        let mut accepting = Vec::new();
+        while let Some(id) = queue.pop_front() {
+            // Matches are reported one step late, so the end of input decides
+            accepting.push(dfa.is_match_state(dfa.next_eoi_state(id)));
+            let row = (0..=u8::MAX)
+                .map(|byte| {
+                    let next = dfa.next_state(id, byte);
+                    if dfa.is_dead_state(next) {
+                        return None;
+                    }
+                    let len = ids.len();
+                    Some(*ids.entry(next).or_insert_with(|| {
+                        queue.push_back(next);
+                        len
+                    }))
+                })
+                .collect();
+            transitions.push(row);
+        }
+
+        Ok(Self {
+            transitions,
+            accepting,
+        })
+    }
+
+    /// Compiles the language of inputs containing `substring`.
+    ///
+    /// # Errors
+    /// Returns error if the DFA is too large.
+    pub fn contains(substring: &[u8]) -> anyhow::Result<Self> {
+        let literal = substring
+            .iter()
+            .map(|byte| format!(r"\x{:02x}", byte))
+            .collect::<String>();
+        Self::new(&format!("(?s:.*{}.*)", literal))
+    }
+
+    pub fn num_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Runs the DFA natively.
+    pub fn is_match(&self, input: &[u8]) -> bool {
+        input
+            .iter()
+            .try_fold(0, |state, &byte| self.transitions[state][byte as usize])
+            .is_some_and(|state| self.accepting[state])
+    }
+
+    /// Returns whether `input` matches.
+    ///
+    /// Walks a one-hot vector of states, which is all zero once the input has
+    /// left the DFA. Each byte costs 2 constraints per byte value the DFA
+    /// tells apart plus one per pair of states joined by a transition.
+    pub fn matches<F: PrimeField>(&self, input: &[UInt8<F>]) -> r1cs::Result<Boolean<F>> {
+        let edges = self.edges();
+        let tested = edges
+            .values()
+            .flat_map(|set| set.bytes.iter().copied())
+            .collect::<BTreeSet<_>>();
+
+        let mut states = (0..self.num_states())
+            .map(|state| FpVar::constant(F::from(state == 0)))
+            .collect::<Vec<_>>();
+        for byte in input {
+            let value = Boolean::le_bits_to_fp(&byte.to_bits_le()?)?;
+            let is = tested
+                .iter()
+                .map(|&b| {
+                    Ok((
+                        b,
+                        FpVar::from(comparison::is_equal(&value, &FpVar::constant(F::from(b)))?),
+                    ))
+                })
+                .collect::<r1cs::Result<HashMap<_, _>>>()?;
+
+            let mut next = vec![FpVar::zero(); self.num_states()];
+            for ((from, to), set) in &edges {
+                let mut taken = set
+                    .bytes
+                    .iter()
+                    .fold(FpVar::zero(), |taken, b| taken + &is[b]);
+                if set.complement {
+                    taken = FpVar::one() - taken;
+                }
+                next[*to] += &states[*from] * taken;
+            }
+            states = next;
+        }
+
+        let accept = states
+            .iter()
+            .zip(&self.accepting)
+            .filter(|(_, accepting)| **accepting)
+            .fold(FpVar::zero(), |accept, (state, _)| accept + state);
+        match &accept {
+            FpVar::Constant(value) => Ok(Boolean::constant(value.is_one())),
+            FpVar::Var(_) => {
+                let matched = Boolean::new_witness(accept.cs(), || Ok(accept.value()?.is_one()))?;
+                FpVar::from(matched.clone()).enforce_equal(&accept)?;
+                Ok(matched)
+            }
+        }
+    }
+
+    /// Enforces that `input` matches.
+    pub fn enforce_match<F: PrimeField>(&self, input: &[UInt8<F>]) -> r1cs::Result<()> {
+        self.matches(input)?.enforce_equal(&Boolean::TRUE)
+    }
+
+    /// The bytes leading from each state to each other, stored as the smaller
+    /// of the set and its complement.
+    fn edges(&self) -> BTreeMap<(usize, usize), ByteSet> {
+        let mut edges = BTreeMap::<_, Vec<u8>>::new();
+        for (from, row) in self.transitions.iter().enumerate() {
+            for (byte, to) in row.iter().enumerate() {
+                if let Some(to) = to {
+                    edges.entry((from, *to)).or_default().push(byte as u8);
+                }
+            }
+        }
+
+        edges
+            .into_iter()
+            .map(|(edge, bytes)| {
+                let set = if bytes.len() <= 128 {
+                    ByteSet {
+                        bytes,
+                        complement: false,
+                    }
+                } else {
+                    ByteSet {
+                        bytes: (0..=u8::MAX).filter(|b| !bytes.contains(b)).collect(),
+                        complement: true,
+                    }
+                };
+                (edge, set)
+            })
+            .collect()
+    }
+}
+
+struct ByteSet {
+    bytes: Vec<u8>,
+    complement: bool,
+}
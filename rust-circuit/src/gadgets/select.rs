@@ -0,0 +1,73 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+
+/// Returns `(b, a)` if `swap` is set and `(a, b)` otherwise, e.g. to order a
+/// Merkle node and its sibling by the path direction.
+pub fn conditionally_swap<F: PrimeField, T: CondSelectGadget<F>>(
+    swap: &Boolean<F>,
+    a: &T,
+    b: &T,
+) -> r1cs::Result<(T, T)> {
+    Ok((swap.select(b, a)?, swap.select(a, b)?))
+}
+
+/// Returns `n` flags of which only the one at `index` is set, enforcing
+/// `index < n`.
+pub fn one_hot<F: PrimeField>(index: &FpVar<F>, n: usize) -> r1cs::Result<Vec<Boolean<F>>> {
+    if let FpVar::Constant(index) = index {
+        if (0..n).all(|i| *index != F::from(i as u64)) {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+        return Ok((0..n)
+            .map(|i| Boolean::constant(*index == F::from(i as u64)))
+            .collect());
+    }
+
+    let cs = index.cs();
+    let index_value = index.value();
+    let flags = (0..n)
+        .map(|i| Boolean::new_witness(cs.clone(), || Ok(index_value? == F::from(i as u64))))
+        .collect::<r1cs::Result<Vec<_>>>()?;
+
+    let mut count = FpVar::zero();
+    let mut position = FpVar::zero();
+    for (i, flag) in flags.iter().enumerate() {
+        count += FpVar::from(flag.clone());
+        position += FpVar::from(flag.clone()) * F::from(i as u64);
+    }
+    count.enforce_equal(&FpVar::one())?;
+    position.enforce_equal(index)?;
+
+    Ok(flags)
+}
+
+/// Returns the element of `array` whose flag is set in `one_hot`, costing one
+/// constraint per element; reuse the flags to select from several arrays.
+///
+/// # Errors
+/// Returns `Unsatisfiable` if the lengths differ.
+pub fn select_one_hot<F: PrimeField>(
+    one_hot: &[Boolean<F>],
+    array: &[FpVar<F>],
+) -> r1cs::Result<FpVar<F>> {
+    if one_hot.len() != array.len() {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    let mut selected = FpVar::zero();
+    for (flag, element) in one_hot.iter().zip(array) {
+        selected += element * FpVar::from(flag.clone());
+    }
+    Ok(selected)
+}
+
+/// Returns `array[index]` for a private `index`, enforcing `index < array.len()`.
+///
+/// A linear mux over a [`one_hot`] vector, costing about twice the array length.
+pub fn select_at_index<F: PrimeField>(
+    array: &[FpVar<F>],
+    index: &FpVar<F>,
+) -> r1cs::Result<FpVar<F>> {
+    select_one_hot(&one_hot(index, array.len())?, array)
+}
@@ -0,0 +1,66 @@
+use crate::babyjubjub::{self, Fq};
+use crate::gadgets::babyjubjub::{fixed_base_msm, PointVar};
+use crate::gadgets::select;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+
+/// Returns the permutation matrix whose row `i` is the one-hot vector of
+/// `indices[i]`, enforcing that the indices are a permutation of
+/// `0..indices.len()`.
+///
+/// Costs about `n^2` constraints for `n` indices.
+pub fn permutation_matrix<F: PrimeField>(
+    indices: &[FpVar<F>],
+) -> r1cs::Result<Vec<Vec<Boolean<F>>>> {
+    let n = indices.len();
+    let rows = indices
+        .iter()
+        .map(|index| select::one_hot(index, n))
+        .collect::<r1cs::Result<Vec<_>>>()?;
+
+    // Each row has one flag set; one per column too makes the map a bijection
+    for j in 0..n {
+        rows.iter()
+            .fold(FpVar::zero(), |count, row| {
+                count + FpVar::from(row[j].clone())
+            })
+            .enforce_equal(&FpVar::one())?;
+    }
+    Ok(rows)
+}
+
+/// Enforces `outputs[i] = inputs[permutation[i]] + randomness[i] * H`: the
+/// outputs are the Pedersen commitments of the inputs, reordered and
+/// re-randomized as by [`babyjubjub::rerandomize`].
+///
+/// # Errors
+/// Returns `Unsatisfiable` if the lengths differ.
+pub fn enforce_shuffle(
+    inputs: &[PointVar],
+    outputs: &[PointVar],
+    permutation: &[FpVar<Fq>],
+    randomness: &[FpVar<Fq>],
+) -> r1cs::Result<()> {
+    let n = inputs.len();
+    if outputs.len() != n || permutation.len() != n || randomness.len() != n {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    let xs = inputs.iter().map(|p| p.x.clone()).collect::<Vec<_>>();
+    let ys = inputs.iter().map(|p| p.y.clone()).collect::<Vec<_>>();
+    let blinding_generator = babyjubjub::blinding_generator();
+    for ((row, output), randomness) in permutation_matrix(permutation)?
+        .iter()
+        .zip(outputs)
+        .zip(randomness)
+    {
+        let source = PointVar::new(
+            select::select_one_hot(row, &xs)?,
+            select::select_one_hot(row, &ys)?,
+        );
+        let shift = fixed_base_msm(&[(blinding_generator, &randomness.to_bits_le()?)])?;
+        (source + shift).enforce_equal(output)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use crate::gadgets::comparison;
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{self, ConstraintSystemRef, Namespace};
+use std::borrow::Borrow;
+
+/// Encodes a signed value in the field, negative values as `p - |value|`.
+pub fn to_field<F: PrimeField>(value: i128) -> F {
+    if value < 0 {
+        -F::from(value.unsigned_abs())
+    } else {
+        F::from(value as u128)
+    }
+}
+
+/// Decodes a value encoded by [`to_field`], or `None` if it does not fit in an `i128`.
+pub fn from_field<F: PrimeField>(value: F) -> Option<i128> {
+    let negative = value.into_bigint() > F::MODULUS_MINUS_ONE_DIV_TWO;
+    let magnitude = if negative { -value } else { value }.into_bigint();
+    if magnitude.num_bits() > 127 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&magnitude.to_bytes_le()[..16]);
+    let magnitude = u128::from_le_bytes(bytes) as i128;
+    Some(if negative { -magnitude } else { magnitude })
+}
+
+/// A signed integer in `[-2^(N-1), 2^(N-1))`, held in its [`to_field`]
+/// encoding so that it adds and multiplies as a field element.
+///
+/// Every operation range-checks its result, which costs `N + 1` constraints,
+/// so a result that over- or underflows leaves the circuit unsatisfied.
+#[derive(Debug, Clone)]
+pub struct IntVar<const N: usize, F: PrimeField> {
+    value: FpVar<F>,
+}
+
+pub type Int32Var<F> = IntVar<32, F>;
+pub type Int64Var<F> = IntVar<64, F>;
+
+impl<const N: usize, F: PrimeField> IntVar<N, F> {
+    /// Interprets `x` as a signed integer, enforcing `-2^(N-1) <= x < 2^(N-1)`.
+    ///
+    /// # Panics
+    /// Panics if `N` is 0 or leaves no headroom below the modulus.
+    pub fn from_fp(x: &FpVar<F>) -> r1cs::Result<Self> {
+        Self::offset_bits(x)?;
+        Ok(Self { value: x.clone() })
+    }
+
+    /// The field encoding of the value, for use in further constraints.
+    pub fn to_fp(&self) -> &FpVar<F> {
+        &self.value
+    }
+
+    /// Returns the value as an unsigned field element, enforcing that it is
+    /// not negative.
+    pub fn to_unsigned(&self) -> r1cs::Result<FpVar<F>> {
+        comparison::enforce_bit_length(&self.value, N - 1)?;
+        Ok(self.value.clone())
+    }
+
+    pub fn is_negative(&self) -> r1cs::Result<Boolean<F>> {
+        // x + 2^(N-1) has its top bit clear iff x is negative
+        Ok(!&Self::offset_bits(&self.value)?[N - 1])
+    }
+
+    /// Returns `self + other`, enforcing that it stays in range.
+    pub fn add(&self, other: &Self) -> r1cs::Result<Self> {
+        Self::from_fp(&(&self.value + &other.value))
+    }
+
+    /// Returns `self - other`, enforcing that it stays in range.
+    pub fn sub(&self, other: &Self) -> r1cs::Result<Self> {
+        Self::from_fp(&(&self.value - &other.value))
+    }
+
+    /// Returns `self * other`, enforcing that it stays in range.
+    ///
+    /// # Panics
+    /// Panics if products of `N`-bit values could wrap around the modulus.
+    pub fn mul(&self, other: &Self) -> r1cs::Result<Self> {
+        assert!(
+            2 * N + 2 < F::MODULUS_BIT_SIZE as usize,
+            "products are limited to {} bits",
+            F::MODULUS_BIT_SIZE - 3
+        );
+        Self::from_fp(&(&self.value * &other.value))
+    }
+
+    /// Returns `-self`, enforcing that it stays in range, i.e. `self != -2^(N-1)`.
+    pub fn neg(&self) -> r1cs::Result<Self> {
+        Self::from_fp(&self.value.negate()?)
+    }
+
+    pub fn is_lt(&self, other: &Self) -> r1cs::Result<Boolean<F>> {
+        comparison::is_less_or_equal(&(self.offset() + F::ONE), &other.offset(), N + 1)
+    }
+
+    pub fn is_le(&self, other: &Self) -> r1cs::Result<Boolean<F>> {
+        comparison::is_less_or_equal(&self.offset(), &other.offset(), N)
+    }
+
+    pub fn enforce_lt(&self, other: &Self) -> r1cs::Result<()> {
+        comparison::enforce_less_than(&self.offset(), &other.offset(), N)
+    }
+
+    pub fn enforce_le(&self, other: &Self) -> r1cs::Result<()> {
+        comparison::enforce_less_or_equal(&self.offset(), &other.offset(), N)
+    }
+
+    /// `self + 2^(N-1)`, which lies in `[0, 2^N)` and preserves order.
+    fn offset(&self) -> FpVar<F> {
+        &self.value + Self::half()
+    }
+
+    fn offset_bits(x: &FpVar<F>) -> r1cs::Result<Vec<Boolean<F>>> {
+        assert!(N > 0, "signed integers need at least one bit");
+        comparison::enforce_bit_length(&(x + Self::half()), N)
+    }
+
+    fn half() -> F {
+        F::from(2u64).pow([N as u64 - 1])
+    }
+}
+
+impl<const N: usize, F: PrimeField> R1CSVar<F> for IntVar<N, F> {
+    type Value = i128;
+
+    fn cs(&self) -> ConstraintSystemRef<F> {
+        self.value.cs()
+    }
+
+    fn value(&self) -> r1cs::Result<i128> {
+        from_field(self.value.value()?).ok_or(r1cs::SynthesisError::Unsatisfiable)
+    }
+}
+
+impl<const N: usize, F: PrimeField> AllocVar<i128, F> for IntVar<N, F> {
+    /// Allocates the value and range-checks it.
+    fn new_variable<T: Borrow<i128>>(
+        cs: impl Into<Namespace<F>>,
+        f: impl FnOnce() -> r1cs::Result<T>,
+        mode: AllocationMode,
+    ) -> r1cs::Result<Self> {
+        let value = FpVar::new_variable(cs, || f().map(|v| to_field::<F>(*v.borrow())), mode)?;
+        Self::from_fp(&value)
+    }
+}
+
+impl<const N: usize, F: PrimeField> EqGadget<F> for IntVar<N, F> {
+    fn is_eq(&self, other: &Self) -> r1cs::Result<Boolean<F>> {
+        self.value.is_eq(&other.value)
+    }
+}
+
+impl<const N: usize, F: PrimeField> CondSelectGadget<F> for IntVar<N, F> {
+    fn conditionally_select(
+        cond: &Boolean<F>,
+        true_value: &Self,
+        false_value: &Self,
+    ) -> r1cs::Result<Self> {
+        Ok(Self {
+            value: FpVar::conditionally_select(cond, &true_value.value, &false_value.value)?,
+        })
+    }
+}
@@ -0,0 +1,106 @@
+use crate::gadgets::comparison;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar};
+use ark_relations::r1cs;
+
+// The fixed-width integers of `ark-r1cs-std`, as used by the SHA-256 gadget of
+// `ark-crypto-primitives`. They already provide wrapping and saturating
+// addition, comparisons, `rotate_left`/`rotate_right`, `<<`/`>>` and `^`/`&`/`|`/`!`.
+pub use ark_r1cs_std::uint::{PrimUInt, UInt};
+pub use ark_r1cs_std::{uint32::UInt32, uint64::UInt64};
+
+/// Converts `x` into an `N`-bit integer, enforcing `x < 2^N`.
+///
+/// Costs `N + 1` constraints, where `UInt::from_fp` decomposes the whole
+/// field element; use it to range-check amounts, e.g. as a [`UInt64`].
+pub fn from_fp<const N: usize, T: PrimUInt, F: PrimeField>(
+    x: &FpVar<F>,
+) -> r1cs::Result<UInt<N, T, F>> {
+    Ok(UInt::from_bits_le(&comparison::enforce_bit_length(x, N)?))
+}
+
+/// Returns `a + b mod 2^N` and the carry out.
+pub fn add_with_carry<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<(UInt<N, T, F>, Boolean<F>)> {
+    let bits = comparison::enforce_bit_length(&(a.to_fp()? + b.to_fp()?), N + 1)?;
+    Ok((UInt::from_bits_le(&bits[..N]), bits[N].clone()))
+}
+
+/// Returns `a + b`, enforcing that it does not overflow.
+pub fn checked_add<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<UInt<N, T, F>> {
+    from_fp(&(a.to_fp()? + b.to_fp()?))
+}
+
+/// Returns `a - b mod 2^N` and whether it borrowed, i.e. `a < b`.
+pub fn sub_with_borrow<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<(UInt<N, T, F>, Boolean<F>)> {
+    // 2^N + a - b lies in (0, 2^(N + 1)); its top bit is clear iff a < b
+    let offset = F::from(2u64).pow([N as u64]);
+    let bits = comparison::enforce_bit_length(&(a.to_fp()? - b.to_fp()? + offset), N + 1)?;
+    Ok((UInt::from_bits_le(&bits[..N]), !&bits[N]))
+}
+
+/// Returns `a - b mod 2^N`.
+pub fn wrapping_sub<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<UInt<N, T, F>> {
+    Ok(sub_with_borrow(a, b)?.0)
+}
+
+/// Returns `a - b`, enforcing `a >= b`.
+pub fn checked_sub<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<UInt<N, T, F>> {
+    from_fp(&(a.to_fp()? - b.to_fp()?))
+}
+
+/// Returns the low and high halves of the full product `a * b`.
+///
+/// # Panics
+/// Panics if `2N` leaves no headroom below the modulus, e.g. for 128 bits.
+pub fn widening_mul<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<(UInt<N, T, F>, UInt<N, T, F>)> {
+    let bits = comparison::enforce_bit_length(&(a.to_fp()? * b.to_fp()?), 2 * N)?;
+    Ok((
+        UInt::from_bits_le(&bits[..N]),
+        UInt::from_bits_le(&bits[N..]),
+    ))
+}
+
+/// Returns `a * b mod 2^N`.
+///
+/// # Panics
+/// Panics as [`widening_mul`].
+pub fn wrapping_mul<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<UInt<N, T, F>> {
+    Ok(widening_mul(a, b)?.0)
+}
+
+/// Returns `a * b`, enforcing that it does not overflow.
+///
+/// # Panics
+/// Panics as [`widening_mul`].
+pub fn checked_mul<const N: usize, T: PrimUInt, F: PrimeField>(
+    a: &UInt<N, T, F>,
+    b: &UInt<N, T, F>,
+) -> r1cs::Result<UInt<N, T, F>> {
+    assert!(
+        2 * N + 2 < F::MODULUS_BIT_SIZE as usize,
+        "products are limited to {} bits",
+        F::MODULUS_BIT_SIZE - 3
+    );
+    from_fp(&(a.to_fp()? * b.to_fp()?))
+}
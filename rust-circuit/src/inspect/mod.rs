@@ -0,0 +1,275 @@
+use crate::witness;
+use ark_ff::{BigInteger, PrimeField};
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisMode,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+/// Size of a synthesized circuit.
+#[derive(Debug, Clone)]
+pub struct CircuitStats {
+    pub num_constraints: usize,
+    pub num_witness_variables: usize,
+    /// Public inputs, excluding the constant `1`.
+    pub num_public_inputs: usize,
+    /// `ns!` path of every constraint, in synthesis order.
+    pub constraint_names: Vec<String>,
+}
+
+/// Number of constraints under one `ns!` path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NamespaceCost {
+    pub path: String,
+    /// Number of path segments, starting at 1 for top-level namespaces.
+    pub depth: usize,
+    pub num_constraints: usize,
+}
+
+impl CircuitStats {
+    /// Constraint counts per namespace down to `max_depth` levels, parents
+    /// before their children and siblings in synthesis order.
+    pub fn namespaces(&self, max_depth: usize) -> Vec<NamespaceCost> {
+        let mut costs: Vec<NamespaceCost> = Vec::new();
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        let mut sort_keys: Vec<Vec<usize>> = Vec::new();
+
+        for name in &self.constraint_names {
+            let segments: Vec<&str> = name.split('/').filter(|s| !s.is_empty()).collect();
+            let mut key = Vec::new();
+            for depth in 1..=segments.len().min(max_depth) {
+                let path = segments[..depth].join("/");
+                let position = match positions.get(&path) {
+                    Some(position) => *position,
+                    None => {
+                        let position = costs.len();
+                        positions.insert(path.clone(), position);
+                        costs.push(NamespaceCost {
+                            path,
+                            depth,
+                            num_constraints: 0,
+                        });
+                        // Sort by the first appearance of each ancestor, then of the path itself
+                        sort_keys.push([key.as_slice(), &[position]].concat());
+                        position
+                    }
+                };
+                key.push(position);
+                costs[position].num_constraints += 1;
+            }
+        }
+
+        let mut order: Vec<usize> = (0..costs.len()).collect();
+        order.sort_by(|a, b| sort_keys[*a].cmp(&sort_keys[*b]));
+        order.into_iter().map(|i| costs[i].clone()).collect()
+    }
+}
+
+/// What determines whether two builds of a circuit can share keys, as
+/// written by `inspect --json` and compared by `inspect --diff`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub name: String,
+    pub version: String,
+    pub num_constraints: usize,
+    pub num_witness_variables: usize,
+    pub num_public_inputs: usize,
+    /// Empty for circuits whose public inputs are not named.
+    pub public_input_names: Vec<String>,
+    /// Hex-encoded [`digest`].
+    pub cs_digest: String,
+    /// Constraint counts per top-level namespace.
+    pub namespaces: Vec<NamespaceCost>,
+}
+
+impl Summary {
+    pub fn new(
+        name: &str,
+        version: &str,
+        stats: &CircuitStats,
+        public_input_names: &[&str],
+        digest: [u8; 32],
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            num_constraints: stats.num_constraints,
+            num_witness_variables: stats.num_witness_variables,
+            num_public_inputs: stats.num_public_inputs,
+            public_input_names: public_input_names.iter().map(|s| s.to_string()).collect(),
+            cs_digest: hex::encode(digest),
+            namespaces: stats.namespaces(1),
+        }
+    }
+
+    /// Keys generated for `self` do not work for `after`.
+    pub fn requires_setup(&self, after: &Summary) -> bool {
+        self.cs_digest != after.cs_digest
+    }
+
+    /// One line per difference from `self` to `after`; empty if they match.
+    pub fn diff(&self, after: &Summary) -> Vec<String> {
+        let mut changes = Vec::new();
+
+        for (field, before, after) in [
+            ("name", &self.name, &after.name),
+            ("version", &self.version, &after.version),
+        ] {
+            if before != after {
+                changes.push(format!("{}: {} -> {}", field, before, after));
+            }
+        }
+        for (field, before, after) in [
+            ("constraints", self.num_constraints, after.num_constraints),
+            (
+                "witness variables",
+                self.num_witness_variables,
+                after.num_witness_variables,
+            ),
+            (
+                "public inputs",
+                self.num_public_inputs,
+                after.num_public_inputs,
+            ),
+        ] {
+            if before != after {
+                changes.push(format!(
+                    "{}: {} -> {} ({:+})",
+                    field,
+                    before,
+                    after,
+                    after as i64 - before as i64
+                ));
+            }
+        }
+        // Unnamed inputs cannot be compared beyond their number
+        if !self.public_input_names.is_empty()
+            && !after.public_input_names.is_empty()
+            && self.public_input_names != after.public_input_names
+        {
+            changes.push(format!(
+                "public input layout: [{}] -> [{}]",
+                self.public_input_names.join(", "),
+                after.public_input_names.join(", ")
+            ));
+        }
+
+        let count = |namespaces: &[NamespaceCost], path: &str| {
+            namespaces
+                .iter()
+                .find(|namespace| namespace.path == path)
+                .map_or(0, |namespace| namespace.num_constraints)
+        };
+        let mut paths: Vec<&str> = after.namespaces.iter().map(|n| n.path.as_str()).collect();
+        for namespace in &self.namespaces {
+            if !paths.contains(&namespace.path.as_str()) {
+                paths.push(&namespace.path);
+            }
+        }
+        for path in paths {
+            let (before, after) = (
+                count(&self.namespaces, path),
+                count(&after.namespaces, path),
+            );
+            if before != after {
+                changes.push(format!(
+                    "namespace {}: {} -> {} ({:+})",
+                    path,
+                    before,
+                    after,
+                    after as i64 - before as i64
+                ));
+            }
+        }
+
+        if self.requires_setup(after) {
+            changes.push(format!(
+                "constraint system digest: {} -> {}",
+                self.cs_digest, after.cs_digest
+            ));
+        }
+        changes
+    }
+}
+
+/// Synthesizes `circuit` in setup mode and reports its size.
+///
+/// No assignment is needed, so an empty circuit (as used for key generation)
+/// can be inspected.
+///
+/// # Errors
+/// Returns error if constraint generation fails.
+pub fn inspect<F, C>(circuit: C) -> anyhow::Result<CircuitStats>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+{
+    witness::traced(|| {
+        let cs = ConstraintSystem::<F>::new_ref();
+        cs.set_mode(SynthesisMode::Setup);
+        cs.set_optimization_goal(OptimizationGoal::Constraints);
+
+        circuit
+            .generate_constraints(cs.clone())
+            .map_err(|e| anyhow::anyhow!("Failed to generate constraints: {}", e))?;
+        cs.finalize();
+
+        Ok(CircuitStats {
+            num_constraints: cs.num_constraints(),
+            num_witness_variables: cs.num_witness_variables(),
+            num_public_inputs: cs.num_instance_variables() - 1,
+            constraint_names: cs.constraint_names().unwrap_or_default(),
+        })
+    })
+}
+
+/// SHA-256 of the R1CS matrices of `circuit`, synthesized as for key
+/// generation, so any change to the constraints or the public input layout
+/// changes it.
+///
+/// Hashes the instance, witness and constraint counts as little-endian
+/// `u64`s, then each row of `A`, `B` and `C` as its length followed by
+/// `(coefficient, variable)` pairs, coefficients as little-endian bytes.
+///
+/// # Errors
+/// Returns error if constraint generation fails.
+pub fn digest<F, C>(circuit: C) -> anyhow::Result<[u8; 32]>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+{
+    let cs = ConstraintSystem::<F>::new_ref();
+    cs.set_mode(SynthesisMode::Setup);
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+
+    circuit
+        .generate_constraints(cs.clone())
+        .map_err(|e| anyhow::anyhow!("Failed to generate constraints: {}", e))?;
+    cs.finalize();
+    let matrices = cs
+        .to_matrices()
+        .ok_or_else(|| anyhow::anyhow!("Constraint system has no matrices"))?;
+
+    let mut hasher = Sha256::new();
+    for count in [
+        matrices.num_instance_variables,
+        matrices.num_witness_variables,
+        matrices.num_constraints,
+    ] {
+        hasher.update((count as u64).to_le_bytes());
+    }
+    for matrix in [&matrices.a, &matrices.b, &matrices.c] {
+        for row in matrix {
+            hasher.update((row.len() as u64).to_le_bytes());
+            for (coefficient, variable) in row {
+                hasher.update(coefficient.into_bigint().to_bytes_le());
+                hasher.update((*variable as u64).to_le_bytes());
+            }
+        }
+    }
+
+    Ok(hasher.finalize().into())
+}
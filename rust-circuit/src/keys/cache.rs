@@ -0,0 +1,116 @@
+use super::header::{self, Expected};
+use ark_ec::pairing::Pairing;
+use ark_groth16::ProvingKey;
+use sha2::{Digest, Sha256};
+use std::any::{Any, TypeId};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Proving keys kept by default. Each one holds its full deserialized size
+/// (tens of MB for larger circuits), so this is kept small.
+pub const DEFAULT_CAPACITY: usize = 4;
+
+struct Entry {
+    curve: TypeId,
+    expected: Expected,
+    /// SHA-256 of the key bytes as given, header included.
+    digest: [u8; 32],
+    key: Arc<dyn Any + Send + Sync>,
+}
+
+struct Cache {
+    capacity: usize,
+    /// Most recently used last.
+    entries: Vec<Entry>,
+}
+
+static CACHE: Mutex<Cache> = Mutex::new(Cache {
+    capacity: DEFAULT_CAPACITY,
+    entries: Vec::new(),
+});
+
+fn lock() -> MutexGuard<'static, Cache> {
+    // The cache holds no invariant a panicking thread could break
+    CACHE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Sets the number of proving keys kept, evicting the least recently used
+/// ones beyond it; 0 disables caching.
+pub fn set_capacity(capacity: usize) {
+    let mut cache = lock();
+    cache.capacity = capacity;
+    let excess = cache.entries.len().saturating_sub(capacity);
+    cache.entries.drain(..excess);
+}
+
+/// Number of proving keys currently kept.
+pub fn len() -> usize {
+    lock().entries.len()
+}
+
+/// Drops every cached proving key; handles already given out stay valid.
+pub fn clear() {
+    lock().entries.clear();
+}
+
+/// Deserializes a (possibly headered) proving key like [`header::decode`],
+/// or returns the key already deserialized from the same bytes.
+///
+/// # Errors
+/// Returns error if the header does not match `expected` or the key cannot
+/// be deserialized.
+pub fn decode<E: Pairing>(bytes: &[u8], expected: &Expected) -> anyhow::Result<Arc<ProvingKey<E>>> {
+    get_or_decode(Sha256::digest(bytes).into(), expected, || {
+        header::decode(bytes, expected)
+    })
+}
+
+/// Returns the cached proving key for key bytes with SHA-256 `digest`
+/// loaded against `expected`, or runs `decode` and caches its result.
+///
+/// The lock is not held while decoding, so a key requested on two threads
+/// at once may be deserialized twice.
+///
+/// # Errors
+/// Returns error if `decode` fails.
+pub fn get_or_decode<E, D>(
+    digest: [u8; 32],
+    expected: &Expected,
+    decode: D,
+) -> anyhow::Result<Arc<ProvingKey<E>>>
+where
+    E: Pairing,
+    D: FnOnce() -> anyhow::Result<ProvingKey<E>>,
+{
+    let curve = TypeId::of::<E>();
+    let matches = |entry: &Entry| {
+        entry.curve == curve && entry.expected == *expected && entry.digest == digest
+    };
+
+    {
+        let mut cache = lock();
+        if let Some(position) = cache.entries.iter().position(matches) {
+            let entry = cache.entries.remove(position);
+            let key = Arc::clone(&entry.key);
+            cache.entries.push(entry);
+            return Ok(key
+                .downcast::<ProvingKey<E>>()
+                .expect("entries are keyed by curve"));
+        }
+    }
+
+    let key = Arc::new(decode()?);
+
+    let mut cache = lock();
+    if cache.capacity > 0 && !cache.entries.iter().any(matches) {
+        if cache.entries.len() == cache.capacity {
+            cache.entries.remove(0);
+        }
+        cache.entries.push(Entry {
+            curve,
+            expected: *expected,
+            digest,
+            key: Arc::clone(&key) as Arc<dyn Any + Send + Sync>,
+        });
+    }
+    Ok(key)
+}
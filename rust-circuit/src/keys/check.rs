@@ -0,0 +1,55 @@
+use crate::circuit::Circuit;
+use crate::prover;
+use ark_ec::pairing::Pairing;
+use ark_groth16::{ProvingKey, VerifyingKey};
+use rand_core::{CryptoRng, RngCore};
+
+/// Checks that `vk` belongs to `pk`: it must equal `pk.vk`, and a test proof
+/// generated with `pk` must verify under `vk`.
+///
+/// `non_trivial` selects the circuit `pk` was generated for; the test proof
+/// factors 15 as 3 · 5, which satisfies either variant.
+///
+/// # Errors
+/// Returns error naming the mismatched vk fields, or if the test proof
+/// cannot be generated or does not verify.
+pub fn check_pair<E, R>(
+    pk: &ProvingKey<E>,
+    vk: &VerifyingKey<E>,
+    non_trivial: bool,
+    rng: &mut R,
+) -> anyhow::Result<()>
+where
+    E: Pairing,
+    R: RngCore + CryptoRng,
+{
+    let mismatched = [
+        ("alpha_g1", pk.vk.alpha_g1 == vk.alpha_g1),
+        ("beta_g2", pk.vk.beta_g2 == vk.beta_g2),
+        ("gamma_g2", pk.vk.gamma_g2 == vk.gamma_g2),
+        ("delta_g2", pk.vk.delta_g2 == vk.delta_g2),
+        ("gamma_abc_g1", pk.vk.gamma_abc_g1 == vk.gamma_abc_g1),
+    ]
+    .into_iter()
+    .filter(|(_, equal)| !equal)
+    .map(|(name, _)| name)
+    .collect::<Vec<_>>();
+    if !mismatched.is_empty() {
+        anyhow::bail!(
+            "Verifying key does not match the proving key ({} differ)",
+            mismatched.join(", ")
+        );
+    }
+
+    let circuit =
+        Circuit::<E::ScalarField>::new(15u64.into(), 3u64.into(), 5u64.into(), non_trivial)?;
+    let bundle = prover::prove(circuit, pk, rng)
+        .map_err(|e| anyhow::anyhow!("Failed to generate test proof: {}", e))?;
+    if !prover::verify(&bundle, vk)? {
+        anyhow::bail!(
+            "Test proof generated with the proving key does not verify under the verifying key"
+        );
+    }
+
+    Ok(())
+}
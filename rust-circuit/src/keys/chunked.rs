@@ -0,0 +1,101 @@
+use super::header::{HEADER_LEN, MAGIC};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::io::{self, Read};
+
+/// Key bytes received in pieces, e.g. from a network stream, read back without
+/// first concatenating them.
+///
+/// Reading drops each chunk once it is consumed, so deserializing a key with
+/// [`super::header::decode_reader`] frees the raw bytes as the key is built.
+#[derive(Debug, Default)]
+pub struct ChunkedBytes {
+    chunks: VecDeque<Vec<u8>>,
+    /// Read position within the front chunk.
+    offset: usize,
+    len: usize,
+}
+
+impl ChunkedBytes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: Vec<u8>) {
+        if !chunk.is_empty() {
+            self.len += chunk.len();
+            self.chunks.push_back(chunk);
+        }
+    }
+
+    /// Number of bytes not yet read.
+    pub fn len(&self) -> usize {
+        self.len - self.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Same as [`super::fingerprint`] of the unread bytes with their header stripped.
+    pub fn fingerprint(&self) -> String {
+        let prefix: Vec<u8> = self.bytes().take(HEADER_LEN).collect();
+        let skip = if prefix.len() == HEADER_LEN && prefix.starts_with(MAGIC) {
+            HEADER_LEN
+        } else {
+            0
+        };
+
+        let mut hasher = Sha256::new();
+        let mut remaining = skip;
+        for chunk in self.slices() {
+            let start = remaining.min(chunk.len());
+            remaining -= start;
+            hasher.update(&chunk[start..]);
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    /// SHA-256 of the unread bytes, header included, as used by [`super::cache`].
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for chunk in self.slices() {
+            hasher.update(chunk);
+        }
+        hasher.finalize().into()
+    }
+
+    fn slices(&self) -> impl Iterator<Item = &[u8]> {
+        self.chunks.iter().enumerate().map(|(i, chunk)| {
+            if i == 0 {
+                &chunk[self.offset..]
+            } else {
+                &chunk[..]
+            }
+        })
+    }
+
+    fn bytes(&self) -> impl Iterator<Item = u8> + '_ {
+        self.slices().flat_map(|chunk| chunk.iter().copied())
+    }
+}
+
+impl Read for ChunkedBytes {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(front) = self.chunks.front() else {
+            return Ok(0);
+        };
+
+        let n = buf.len().min(front.len() - self.offset);
+        buf[..n].copy_from_slice(&front[self.offset..self.offset + n]);
+        self.offset += n;
+
+        if self.offset == front.len() {
+            self.len -= front.len();
+            self.offset = 0;
+            self.chunks.pop_front();
+        }
+
+        Ok(n)
+    }
+}
@@ -0,0 +1,105 @@
+use super::header::{self, KeyHeader, HEADER_LEN};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Identifies the constraint system behind a proof or key: the
+/// [`header::circuit_id`] written into key file headers, hex-encoded in JSON.
+///
+/// Echoed in proofs, so a proof checked against a key for another version of
+/// the circuit is rejected instead of failing to verify.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CircuitId(pub [u8; 8]);
+
+impl CircuitId {
+    /// The id of the circuit compiled into this build.
+    pub fn for_circuit(non_trivial: bool) -> Self {
+        Self(header::circuit_id(non_trivial))
+    }
+
+    /// The id in the header of a hex-encoded key, if it has one.
+    ///
+    /// Only the header is decoded; malformed hex yields `None` and is left to
+    /// the key's deserialization to report.
+    pub fn from_key_hex(key_hex: &str) -> Option<Self> {
+        let bytes = hex::decode(key_hex.get(..2 * HEADER_LEN)?).ok()?;
+        let header = KeyHeader::parse(&bytes).ok()??;
+        Some(Self(header.circuit_id))
+    }
+
+    /// Checks that a proof with this id can be verified with a key for
+    /// `expected`, or, for a key without a header, with either option of the
+    /// circuit compiled into this build.
+    ///
+    /// # Errors
+    /// Returns [`CircuitMismatchError`] if it cannot.
+    pub fn check(&self, expected: Option<CircuitId>) -> Result<(), CircuitMismatchError> {
+        let matches = match expected {
+            Some(expected) => *self == expected,
+            None => [false, true]
+                .into_iter()
+                .any(|non_trivial| *self == Self::for_circuit(non_trivial)),
+        };
+        if matches {
+            Ok(())
+        } else {
+            Err(CircuitMismatchError {
+                found: *self,
+                expected: expected.unwrap_or_else(|| Self::for_circuit(false)),
+            })
+        }
+    }
+}
+
+/// The circuit label if the id is one of this build's, else its hex.
+impl fmt::Display for CircuitId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&header::describe(&self.0))
+    }
+}
+
+impl Serialize for CircuitId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(self.0))
+    }
+}
+
+impl<'de> Deserialize<'de> for CircuitId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex_id = String::deserialize(deserializer)?;
+        let mut id = [0u8; 8];
+        hex::decode_to_slice(&hex_id, &mut id)
+            .map_err(|e| serde::de::Error::custom(format!("Invalid circuit id: {}", e)))?;
+        Ok(Self(id))
+    }
+}
+
+/// A proof was made for a different circuit than its verifying key.
+///
+/// Returned (inside the `anyhow::Error`) by the verify functions, so callers
+/// can `downcast_ref::<CircuitMismatchError>()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitMismatchError {
+    pub found: CircuitId,
+    pub expected: CircuitId,
+}
+
+impl CircuitMismatchError {
+    /// Stable identifier for programmatic handling.
+    pub fn code(&self) -> &'static str {
+        "E_CIRCUIT_MISMATCH"
+    }
+}
+
+impl fmt::Display for CircuitMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Proof is for {}, expected {} ({})",
+            self.found,
+            self.expected,
+            self.code()
+        )
+    }
+}
+
+impl std::error::Error for CircuitMismatchError {}
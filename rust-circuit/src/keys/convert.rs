@@ -0,0 +1,171 @@
+use super::header::{self, Expected, KeyHeader};
+use crate::circom::{snarkjs, zkey};
+use crate::curve::SupportedCurve;
+use ark_groth16::{ProvingKey, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use base64::Engine;
+use std::str::FromStr;
+
+/// Which half of a Groth16 key pair is being converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyKind {
+    Proving,
+    Verifying,
+}
+
+impl FromStr for KeyKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "pk" | "proving" => Ok(Self::Proving),
+            "vk" | "verifying" => Ok(Self::Verifying),
+            _ => anyhow::bail!("Unknown key kind '{}' (expected pk or vk)", s),
+        }
+    }
+}
+
+/// On-disk key formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyFormat {
+    /// Compressed arkworks bytes, as in `keys/*.bin`. A key header is
+    /// validated when reading but not written.
+    Bin,
+    /// Uncompressed arkworks bytes; larger but faster to load.
+    BinUncompressed,
+    /// Hex of the compressed bytes, as in `keys/*.hex`.
+    Hex,
+    /// Standard base64 of the compressed bytes.
+    Base64,
+    /// snarkjs `verification_key.json` (verifying keys only).
+    Snarkjs,
+    /// snarkjs Groth16 `.zkey` (input only); see [`zkey::read_zkey`].
+    Zkey,
+}
+
+impl FromStr for KeyFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "bin" => Ok(Self::Bin),
+            "bin-uncompressed" => Ok(Self::BinUncompressed),
+            "hex" => Ok(Self::Hex),
+            "base64" => Ok(Self::Base64),
+            "snarkjs" => Ok(Self::Snarkjs),
+            "zkey" => Ok(Self::Zkey),
+            _ => anyhow::bail!(
+                "Unknown key format '{}' (expected bin, bin-uncompressed, hex, base64, snarkjs or zkey)",
+                s
+            ),
+        }
+    }
+}
+
+/// Re-encodes a serialized key from one format to another.
+///
+/// # Errors
+/// Returns error if `data` is not a valid key of `kind` in format `from`, or
+/// if `kind` cannot be represented in one of the formats.
+pub fn convert<E: SupportedCurve>(
+    data: &[u8],
+    kind: KeyKind,
+    from: KeyFormat,
+    to: KeyFormat,
+) -> anyhow::Result<Vec<u8>> {
+    if to == KeyFormat::Zkey {
+        anyhow::bail!("Writing .zkey files is not supported");
+    }
+
+    let expected = Expected {
+        kind,
+        curve: E::CURVE,
+        non_trivial: None,
+    };
+
+    match kind {
+        KeyKind::Proving => {
+            if from == KeyFormat::Snarkjs || to == KeyFormat::Snarkjs {
+                anyhow::bail!("snarkjs verification_key.json only holds verifying keys");
+            }
+            let pk: ProvingKey<E> = match from {
+                KeyFormat::Zkey => zkey::read_zkey(data)?,
+                _ => decode(data, from, &expected)?,
+            };
+            encode(&pk, to)
+        }
+        KeyKind::Verifying => {
+            let vk: VerifyingKey<E> = match from {
+                KeyFormat::Snarkjs => {
+                    let json = serde_json::from_slice(data)
+                        .map_err(|e| anyhow::anyhow!("Failed to parse snarkjs JSON: {}", e))?;
+                    snarkjs::verifying_key_from_json(&json)?
+                }
+                KeyFormat::Zkey => zkey::read_zkey::<E>(data)?.vk,
+                _ => decode(data, from, &expected)?,
+            };
+
+            match to {
+                KeyFormat::Snarkjs => {
+                    serde_json::to_vec_pretty(&snarkjs::verifying_key_to_json(&vk))
+                        .map_err(|e| anyhow::anyhow!("Failed to serialize snarkjs JSON: {}", e))
+                }
+                _ => encode(&vk, to),
+            }
+        }
+    }
+}
+
+fn decode<T: CanonicalDeserialize>(
+    data: &[u8],
+    format: KeyFormat,
+    expected: &Expected,
+) -> anyhow::Result<T> {
+    let bytes = match format {
+        KeyFormat::Bin | KeyFormat::BinUncompressed => data.to_vec(),
+        KeyFormat::Hex => hex::decode(text(data)?)
+            .map_err(|e| anyhow::anyhow!("Failed to decode key hex: {}", e))?,
+        KeyFormat::Base64 => base64::engine::general_purpose::STANDARD
+            .decode(text(data)?)
+            .map_err(|e| anyhow::anyhow!("Failed to decode key base64: {}", e))?,
+        KeyFormat::Snarkjs | KeyFormat::Zkey => {
+            unreachable!("snarkjs keys are handled by the caller")
+        }
+    };
+    if KeyHeader::parse(&bytes)?.is_some() {
+        return header::decode(&bytes, expected);
+    }
+
+    let key = match format {
+        KeyFormat::BinUncompressed => T::deserialize_uncompressed(&bytes[..]),
+        _ => T::deserialize_compressed(&bytes[..]),
+    };
+    key.map_err(|e| anyhow::anyhow!("Failed to deserialize key: {}", e))
+}
+
+fn encode<T: CanonicalSerialize>(key: &T, format: KeyFormat) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match format {
+        KeyFormat::BinUncompressed => key.serialize_uncompressed(&mut bytes),
+        _ => key.serialize_compressed(&mut bytes),
+    }
+    .map_err(|e| anyhow::anyhow!("Failed to serialize key: {}", e))?;
+
+    Ok(match format {
+        KeyFormat::Bin | KeyFormat::BinUncompressed => bytes,
+        KeyFormat::Hex => hex::encode(bytes).into_bytes(),
+        KeyFormat::Base64 => base64::engine::general_purpose::STANDARD
+            .encode(bytes)
+            .into_bytes(),
+        KeyFormat::Snarkjs | KeyFormat::Zkey => {
+            unreachable!("snarkjs keys are handled by the caller")
+        }
+    })
+}
+
+/// Text formats may end with a newline when edited by hand.
+fn text(data: &[u8]) -> anyhow::Result<&str> {
+    std::str::from_utf8(data)
+        .map(str::trim)
+        .map_err(|e| anyhow::anyhow!("Key is not valid text: {}", e))
+}
@@ -0,0 +1,249 @@
+use super::convert::KeyKind;
+use crate::circuit::Circuit;
+use crate::curve::Curve;
+use crate::inspect;
+use ark_bn254::Fr;
+use ark_serialize::{CanonicalDeserialize, SerializationError};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::sync::OnceLock;
+
+/// First bytes of every headered key file.
+pub const MAGIC: &[u8; 4] = b"VTXK";
+/// Bumped whenever the header layout changes.
+pub const FORMAT_VERSION: u8 = 1;
+/// Header size in bytes.
+pub const HEADER_LEN: usize = 16;
+
+const COMPRESSED_FLAG: u8 = 1;
+
+/// Header prefixed to `.bin` key files so that loading the wrong file fails
+/// with a clear message instead of a deserialization error.
+///
+/// Layout: magic (4) | format version (1) | kind (1) | curve (1) | flags (1) | circuit id (8).
+/// Hex key files stay headerless, since Sui and the JS bindings consume the
+/// raw arkworks bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyHeader {
+    pub kind: KeyKind,
+    pub curve: Curve,
+    pub circuit_id: [u8; 8],
+    pub compressed: bool,
+}
+
+/// Identifies the constraint system a key was generated for: the first 8
+/// bytes of the SHA-256 of the circuit name, version and options, and of the
+/// digest of its R1CS matrices (see [`inspect::digest`]).
+///
+/// The matrices are those synthesized over BN254; the constraints are the
+/// same on every curve. Computed once per option.
+pub fn circuit_id(non_trivial: bool) -> [u8; 8] {
+    static IDS: OnceLock<[[u8; 8]; 2]> = OnceLock::new();
+    IDS.get_or_init(|| {
+        [false, true].map(|non_trivial| {
+            let circuit = if non_trivial {
+                Circuit::<Fr>::empty_non_trivial()
+            } else {
+                Circuit::<Fr>::empty()
+            };
+            let cs_digest = inspect::digest(circuit)
+                .expect("the multiplication circuit synthesizes without values");
+            circuit_id_of(&circuit_label(non_trivial), &cs_digest)
+        })
+    })[usize::from(non_trivial)]
+}
+
+/// The circuit id of a circuit `label` with the R1CS digest `cs_digest`.
+///
+/// A circuit whose constraints change without a version bump gets a new
+/// id, so keys for the old constraints no longer load.
+pub fn circuit_id_of(label: &str, cs_digest: &[u8; 32]) -> [u8; 8] {
+    let digest = Sha256::new()
+        .chain_update(label)
+        .chain_update(cs_digest)
+        .finalize();
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&digest[..8]);
+    id
+}
+
+/// The label of `id` if it is one of this build's circuit ids, else
+/// `unknown circuit <hex>`.
+pub fn describe(id: &[u8; 8]) -> String {
+    [false, true]
+        .into_iter()
+        .find(|non_trivial| *id == circuit_id(*non_trivial))
+        .map(circuit_label)
+        .unwrap_or_else(|| format!("unknown circuit {}", hex::encode(id)))
+}
+
+/// `name@version`, with `+non-trivial` for that option.
+pub fn circuit_label(non_trivial: bool) -> String {
+    let options = if non_trivial { "+non-trivial" } else { "" };
+    format!("{}@{}{}", Circuit::NAME, Circuit::VERSION, options)
+}
+
+/// What a loader knows about the key it expects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Expected {
+    pub kind: KeyKind,
+    pub curve: Curve,
+    /// Circuit option the key must match; `None` accepts either.
+    pub non_trivial: Option<bool>,
+}
+
+impl KeyHeader {
+    pub fn to_bytes(&self) -> [u8; HEADER_LEN] {
+        let mut bytes = [0u8; HEADER_LEN];
+        bytes[..4].copy_from_slice(MAGIC);
+        bytes[4] = FORMAT_VERSION;
+        bytes[5] = match self.kind {
+            KeyKind::Proving => 0,
+            KeyKind::Verifying => 1,
+        };
+        bytes[6] = match self.curve {
+            Curve::Bn254 => 0,
+            Curve::Bls12_381 => 1,
+        };
+        bytes[7] = if self.compressed { COMPRESSED_FLAG } else { 0 };
+        bytes[8..].copy_from_slice(&self.circuit_id);
+        bytes
+    }
+
+    /// Parses the header at the start of `bytes`, or returns `None` if there is none.
+    ///
+    /// # Errors
+    /// Returns error if the header is truncated or from a newer format version.
+    pub fn parse(bytes: &[u8]) -> anyhow::Result<Option<Self>> {
+        if !bytes.starts_with(MAGIC) {
+            return Ok(None);
+        }
+        if bytes.len() < HEADER_LEN {
+            anyhow::bail!("Key file header is truncated");
+        }
+        if bytes[4] != FORMAT_VERSION {
+            anyhow::bail!(
+                "Key file format version {} is not supported (expected {})",
+                bytes[4],
+                FORMAT_VERSION
+            );
+        }
+
+        let kind = match bytes[5] {
+            0 => KeyKind::Proving,
+            1 => KeyKind::Verifying,
+            kind => anyhow::bail!("Key file has unknown key kind {}", kind),
+        };
+        let curve = match bytes[6] {
+            0 => Curve::Bn254,
+            1 => Curve::Bls12_381,
+            curve => anyhow::bail!("Key file has unknown curve id {}", curve),
+        };
+
+        let mut circuit_id = [0u8; 8];
+        circuit_id.copy_from_slice(&bytes[8..HEADER_LEN]);
+
+        Ok(Some(Self {
+            kind,
+            curve,
+            circuit_id,
+            compressed: bytes[7] & COMPRESSED_FLAG != 0,
+        }))
+    }
+
+    fn check(&self, expected: &Expected) -> anyhow::Result<()> {
+        if self.kind != expected.kind {
+            anyhow::bail!(
+                "Key file holds a {} key, expected a {} key",
+                kind_name(self.kind),
+                kind_name(expected.kind)
+            );
+        }
+        if self.curve != expected.curve {
+            anyhow::bail!(
+                "Key file is for curve {}, expected {}",
+                self.curve.name(),
+                expected.curve.name()
+            );
+        }
+        if let Some(non_trivial) = expected.non_trivial {
+            if self.circuit_id != circuit_id(non_trivial) {
+                anyhow::bail!(
+                    "Key file was generated for {}, expected {}; regenerate the keys with keygen",
+                    describe(&self.circuit_id),
+                    circuit_label(non_trivial)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The serialized key without its header, if it has one.
+pub fn strip(bytes: &[u8]) -> &[u8] {
+    if bytes.starts_with(MAGIC) && bytes.len() >= HEADER_LEN {
+        &bytes[HEADER_LEN..]
+    } else {
+        bytes
+    }
+}
+
+/// Deserializes a key, validating its [`KeyHeader`] against `expected`.
+///
+/// Headerless bytes are read as a compressed arkworks key, so hex files and
+/// keys from earlier releases keep loading.
+///
+/// # Errors
+/// Returns error if the header does not match `expected` or the key cannot
+/// be deserialized.
+pub fn decode<T: CanonicalDeserialize>(bytes: &[u8], expected: &Expected) -> anyhow::Result<T> {
+    decode_reader(bytes, expected)
+}
+
+/// Like [`decode`], but reads the key from `reader`, so it never has to be
+/// held in one contiguous buffer.
+///
+/// # Errors
+/// Returns error if reading fails, the header does not match `expected` or
+/// the key cannot be deserialized.
+pub fn decode_reader<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    expected: &Expected,
+) -> anyhow::Result<T> {
+    let _span = tracing::debug_span!(
+        "deserialize_key",
+        kind = kind_name(expected.kind),
+        curve = expected.curve.name()
+    )
+    .entered();
+
+    let mut prefix = Vec::with_capacity(HEADER_LEN);
+    (&mut reader)
+        .take(HEADER_LEN as u64)
+        .read_to_end(&mut prefix)
+        .map_err(|e| anyhow::anyhow!("Failed to read {} key: {}", kind_name(expected.kind), e))?;
+
+    let Some(header) = KeyHeader::parse(&prefix)? else {
+        return T::deserialize_compressed(prefix.as_slice().chain(reader))
+            .map_err(|e| deserialize_error(expected.kind, e));
+    };
+    header.check(expected)?;
+
+    if header.compressed {
+        T::deserialize_compressed(reader)
+    } else {
+        T::deserialize_uncompressed(reader)
+    }
+    .map_err(|e| deserialize_error(expected.kind, e))
+}
+
+fn deserialize_error(kind: KeyKind, e: SerializationError) -> anyhow::Error {
+    anyhow::anyhow!("Failed to deserialize {} key: {}", kind_name(kind), e)
+}
+
+fn kind_name(kind: KeyKind) -> &'static str {
+    match kind {
+        KeyKind::Proving => "proving",
+        KeyKind::Verifying => "verifying",
+    }
+}
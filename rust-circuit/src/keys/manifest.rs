@@ -0,0 +1,192 @@
+use super::circuit_id::CircuitId;
+use crate::circuit::Circuit;
+use crate::curve::{Curve, SupportedCurve};
+use crate::inspect;
+#[cfg(feature = "bls12-381")]
+use ark_bls12_381::Bls12_381;
+use ark_bn254::{Bn254, Fr};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Description of a key directory, written by keygen as `manifest.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    pub circuit: String,
+    pub circuit_version: String,
+    #[serde(default)]
+    pub non_trivial: bool,
+    pub curve: Curve,
+    pub num_constraints: usize,
+    /// Absent in manifests from older versions, which are not checked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circuit_id: Option<CircuitId>,
+    /// Hex-encoded [`Circuit::cs_digest`] on `curve`, to detect constraint
+    /// changes made without a version bump; absent in older manifests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cs_digest: Option<String>,
+    pub files: Vec<FileEntry>,
+}
+
+/// Size and SHA-256 digest of one key file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileEntry {
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl FileEntry {
+    pub fn new(name: &str, contents: &[u8]) -> Self {
+        Self {
+            name: name.to_string(),
+            size: contents.len() as u64,
+            sha256: super::fingerprint(contents),
+        }
+    }
+}
+
+impl Manifest {
+    pub const FILE_NAME: &'static str = "manifest.json";
+
+    /// Manifest for keys of this crate's circuit; `files` is left empty.
+    ///
+    /// # Errors
+    /// Returns error if the circuit cannot be synthesized.
+    pub fn for_circuit(curve: Curve, non_trivial: bool) -> anyhow::Result<Self> {
+        Ok(Self {
+            circuit: Circuit::NAME.to_string(),
+            circuit_version: Circuit::VERSION.to_string(),
+            non_trivial,
+            curve,
+            num_constraints: num_constraints(non_trivial)?,
+            circuit_id: Some(CircuitId::for_circuit(non_trivial)),
+            cs_digest: Some(cs_digest(curve, non_trivial)?),
+            files: Vec::new(),
+        })
+    }
+
+    /// Reads `manifest.json` from the key directory `dir`.
+    ///
+    /// # Errors
+    /// Returns error if the file cannot be read or parsed.
+    pub fn read(dir: &Path) -> anyhow::Result<Self> {
+        let path = dir.join(Self::FILE_NAME);
+        let json = fs::read_to_string(&path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&json)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    /// Checks every listed file in `dir` against its size and digest, and the
+    /// manifest against the circuit compiled into this build.
+    ///
+    /// # Errors
+    /// Returns error listing every mismatch found.
+    pub fn verify(&self, dir: &Path) -> anyhow::Result<()> {
+        let mut problems = Vec::new();
+
+        if self.circuit != Circuit::NAME {
+            problems.push(format!(
+                "keys are for circuit '{}', this build proves '{}'",
+                self.circuit,
+                Circuit::NAME
+            ));
+        } else {
+            if self.circuit_version != Circuit::VERSION {
+                problems.push(format!(
+                    "keys are for circuit version {}, this build is {}",
+                    self.circuit_version,
+                    Circuit::VERSION
+                ));
+            }
+            let expected = num_constraints(self.non_trivial)?;
+            if self.num_constraints != expected {
+                problems.push(format!(
+                    "keys are for {} constraints, the circuit has {}",
+                    self.num_constraints, expected
+                ));
+            }
+            if let Some(circuit_id) = self.circuit_id {
+                let expected = CircuitId::for_circuit(self.non_trivial);
+                if circuit_id != expected {
+                    problems.push(format!(
+                        "keys are for {}, this build is {}",
+                        circuit_id, expected
+                    ));
+                }
+            }
+            if let Some(digest) = &self.cs_digest {
+                let expected = cs_digest(self.curve, self.non_trivial)?;
+                if *digest != expected {
+                    problems.push(format!(
+                        "keys are for constraint system {}, this build has {}",
+                        digest, expected
+                    ));
+                }
+            }
+        }
+
+        for file in &self.files {
+            let contents = match fs::read(dir.join(&file.name)) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    problems.push(format!("{}: {}", file.name, e));
+                    continue;
+                }
+            };
+            let actual = FileEntry::new(&file.name, &contents);
+            if actual.size != file.size {
+                problems.push(format!(
+                    "{}: expected {} bytes, found {}",
+                    file.name, file.size, actual.size
+                ));
+            } else if actual.sha256 != file.sha256 {
+                problems.push(format!("{}: SHA-256 digest does not match", file.name));
+            }
+        }
+
+        if !problems.is_empty() {
+            anyhow::bail!("Manifest check failed:\n  - {}", problems.join("\n  - "));
+        }
+        Ok(())
+    }
+}
+
+fn num_constraints(non_trivial: bool) -> anyhow::Result<usize> {
+    let circuit = if non_trivial {
+        Circuit::<Fr>::empty_non_trivial()
+    } else {
+        Circuit::<Fr>::empty()
+    };
+
+    Ok(inspect::inspect(circuit)?.num_constraints)
+}
+
+/// Hex-encoded [`Circuit::cs_digest`] of this build's circuit on `curve`.
+///
+/// # Errors
+/// Returns error if `curve` is not enabled or the circuit cannot be
+/// synthesized.
+pub fn cs_digest(curve: Curve, non_trivial: bool) -> anyhow::Result<String> {
+    curve.ensure_enabled()?;
+    match curve {
+        Curve::Bn254 => cs_digest_on::<Bn254>(non_trivial),
+        #[cfg(feature = "bls12-381")]
+        Curve::Bls12_381 => cs_digest_on::<Bls12_381>(non_trivial),
+        #[cfg(not(feature = "bls12-381"))]
+        Curve::Bls12_381 => unreachable!("checked by ensure_enabled"),
+    }
+}
+
+fn cs_digest_on<E: SupportedCurve>(non_trivial: bool) -> anyhow::Result<String> {
+    let circuit = if non_trivial {
+        Circuit::<E::ScalarField>::empty_non_trivial()
+    } else {
+        Circuit::<E::ScalarField>::empty()
+    };
+
+    Ok(hex::encode(circuit.cs_digest()?))
+}
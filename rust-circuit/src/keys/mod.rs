@@ -0,0 +1,39 @@
+pub mod cache;
+pub mod check;
+pub mod chunked;
+pub mod circuit_id;
+pub mod convert;
+pub mod header;
+pub mod manifest;
+
+use sha2::{Digest, Sha256};
+
+/// Compressed proving key shipped with this crate (see `keys/` and the keygen binary).
+pub const PROVING_KEY_BYTES: &[u8] = include_bytes!("../../keys/proving_key.bin");
+
+/// Hex-encoded SHA-256 digest of serialized key bytes.
+pub fn fingerprint(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Fingerprint of the proving key this build of the crate was generated with.
+///
+/// Taken over the key itself, without the file header, so it matches the
+/// fingerprint of the hex-encoded key clients load.
+pub fn expected_proving_key_fingerprint() -> String {
+    fingerprint(header::strip(PROVING_KEY_BYTES))
+}
+
+/// Checks that the key `bytes`, with or without a file header, have the
+/// fingerprint `expected` (hex, optionally `0x`-prefixed) and returns it.
+///
+/// # Errors
+/// Returns error if the fingerprints differ.
+pub fn check_fingerprint(bytes: &[u8], expected: &str) -> anyhow::Result<String> {
+    let expected = expected.trim_start_matches("0x").to_ascii_lowercase();
+    let actual = fingerprint(header::strip(bytes));
+    if actual != expected {
+        anyhow::bail!("Key has fingerprint {}, expected {}", actual, expected);
+    }
+    Ok(actual)
+}
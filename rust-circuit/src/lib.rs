@@ -1,4 +1,49 @@
+pub mod aggregation;
+pub mod api;
+pub mod audit;
+pub mod babyjubjub;
+pub mod circom;
 pub mod circuit;
+pub mod compat;
+pub mod curve;
+pub mod dsl;
+pub mod ed25519;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod field;
+pub mod gadgets;
+pub mod inspect;
+pub mod keys;
+pub mod logging;
+pub mod merkle;
+pub mod mimc;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+#[cfg(feature = "napi")]
+pub mod node;
+pub mod poseidon;
+pub mod prover;
+#[cfg(feature = "recursion")]
+pub mod recursion;
+pub mod rescue;
+pub mod secp256k1;
+pub mod setup;
+pub mod solidity;
+pub mod sui;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+pub mod witness;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(feature = "wasm-bindings")]
 pub mod wasm;
+
+// TLSF is smaller than the default dlmalloc, but only safe single-threaded
+#[cfg(all(feature = "small-wasm", target_arch = "wasm32"))]
+#[global_allocator]
+static ALLOCATOR: rlsf::SmallGlobalTlsf = rlsf::SmallGlobalTlsf::new();
+
+#[cfg(all(feature = "small-wasm", feature = "wasm-threads"))]
+compile_error!("The small-wasm allocator is single-threaded; build without wasm-threads");
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use tracing::level_filters::LevelFilter;
+use tracing::subscriber::Interest;
+use tracing::{Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Levels in increasing verbosity, indexed by [`MAX_LEVEL`].
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::OFF,
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
+/// Index into [`LEVELS`] of the most verbose level logged; off by default.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the most verbose level logged by every sink installed from this
+/// module: `off`, `error`, `warn`, `info`, `debug` or `trace`.
+///
+/// Takes effect immediately, also for a subscriber that is already installed.
+///
+/// # Errors
+/// Returns error if `level` is not one of the above.
+pub fn set_level(level: &str) -> anyhow::Result<()> {
+    let filter: LevelFilter = level.trim().to_ascii_lowercase().parse().map_err(|_| {
+        anyhow::anyhow!(
+            "Unknown log level '{}' (expected off, error, warn, info, debug or trace)",
+            level
+        )
+    })?;
+    let index = LEVELS
+        .iter()
+        .position(|known| *known == filter)
+        .expect("LEVELS lists every level filter");
+    MAX_LEVEL.store(index as u8, Ordering::Relaxed);
+    Ok(())
+}
+
+/// The level set with [`set_level`].
+pub fn level() -> LevelFilter {
+    LEVELS[MAX_LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// Passes spans and events up to [`level`], except the `ns!` spans of
+/// constraint generation (target `r1cs`), which number in the thousands.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LevelLayer;
+
+impl<S: Subscriber> Layer<S> for LevelLayer {
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        // Ask on every call, since the level can change at runtime
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        metadata.target() != "r1cs" && *metadata.level() <= level()
+    }
+}
+
+/// Logs spans and events up to `level` to stderr through
+/// `tracing-subscriber`, with the duration of each span as it closes.
+///
+/// # Errors
+/// Returns error if `level` is unknown or a global subscriber is already set.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn init(level: &str) -> anyhow::Result<()> {
+    use tracing_subscriber::fmt::{self, format::FmtSpan};
+    use tracing_subscriber::{layer::SubscriberExt, Registry};
+
+    set_level(level)?;
+    let subscriber = Registry::default().with(LevelLayer).with(
+        fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_span_events(FmtSpan::CLOSE),
+    );
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| anyhow::anyhow!("Failed to install log subscriber: {}", e))
+}
@@ -0,0 +1,120 @@
+use super::{from_decimal, hash_pair, to_decimal, zero_hashes};
+use crate::field::{self, Encoding};
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use serde::{Deserialize, Serialize};
+
+/// An append-only tree that keeps only its frontier: for each level, the
+/// last left child inserted there.
+///
+/// This is the `O(depth)` state an on-chain contract stores; the roots it
+/// produces match a [`super::MerkleTree`] over the same leaves. It cannot
+/// produce paths, for which the full tree is needed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    try_from = "IncrementalMerkleTreeRepr",
+    into = "IncrementalMerkleTreeRepr",
+    bound = "F: PrimeField + Absorb"
+)]
+pub struct IncrementalMerkleTree<F: PrimeField> {
+    next_index: u64,
+    filled_subtrees: Vec<F>,
+    root: F,
+    zeros: Vec<F>,
+}
+
+impl<F: PrimeField + Absorb> IncrementalMerkleTree<F> {
+    /// Creates an empty tree of `depth` levels.
+    ///
+    /// # Errors
+    /// Returns error if `depth` is 64 or more, or hashing fails.
+    pub fn new(depth: usize) -> anyhow::Result<Self> {
+        if depth >= u64::BITS as usize {
+            anyhow::bail!("Tree depth {} is too large", depth);
+        }
+
+        let zeros = zero_hashes(depth)?;
+        Ok(Self {
+            next_index: 0,
+            filled_subtrees: zeros[..depth].to_vec(),
+            root: zeros[depth],
+            zeros,
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.filled_subtrees.len()
+    }
+
+    /// Index the next leaf will be inserted at; also the number of leaves.
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    pub fn root(&self) -> F {
+        self.root
+    }
+
+    /// Appends `leaf`, returning its index.
+    ///
+    /// # Errors
+    /// Returns error if the tree is full or hashing fails.
+    pub fn insert(&mut self, leaf: F) -> anyhow::Result<u64> {
+        let index = self.next_index;
+        if index >> self.depth() != 0 {
+            anyhow::bail!("Tree of depth {} is full", self.depth());
+        }
+
+        let mut node = leaf;
+        for level in 0..self.depth() {
+            node = if (index >> level) & 1 == 0 {
+                self.filled_subtrees[level] = node;
+                hash_pair(node, self.zeros[level])?
+            } else {
+                hash_pair(self.filled_subtrees[level], node)?
+            };
+        }
+
+        self.root = node;
+        self.next_index += 1;
+        Ok(index)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IncrementalMerkleTreeRepr {
+    next_index: u64,
+    filled_subtrees: Vec<String>,
+    root: String,
+}
+
+impl<F: PrimeField> From<IncrementalMerkleTree<F>> for IncrementalMerkleTreeRepr {
+    fn from(tree: IncrementalMerkleTree<F>) -> Self {
+        Self {
+            next_index: tree.next_index,
+            filled_subtrees: to_decimal(&tree.filled_subtrees),
+            root: field::format(&tree.root, Encoding::Decimal),
+        }
+    }
+}
+
+impl<F: PrimeField + Absorb> TryFrom<IncrementalMerkleTreeRepr> for IncrementalMerkleTree<F> {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: IncrementalMerkleTreeRepr) -> anyhow::Result<Self> {
+        let mut tree = Self::new(repr.filled_subtrees.len())?;
+        if repr.next_index > 1 << tree.depth() {
+            anyhow::bail!(
+                "Next index {} is beyond a tree of depth {}",
+                repr.next_index,
+                tree.depth()
+            );
+        }
+
+        tree.next_index = repr.next_index;
+        tree.filled_subtrees = from_decimal(&repr.filled_subtrees)?;
+        tree.root = field::parse_auto(&repr.root)?;
+        Ok(tree)
+    }
+}
@@ -0,0 +1,368 @@
+use crate::field::{self, Encoding};
+use crate::gadgets::select;
+use crate::poseidon;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{boolean::Boolean, fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+mod incremental;
+pub mod sparse;
+
+pub use incremental::IncrementalMerkleTree;
+
+/// Depth used by the example circuits: room for about a million leaves.
+pub const DEFAULT_DEPTH: usize = 20;
+
+/// Poseidon hash of two children, the node hash of every tree in this module.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn hash_pair<F: PrimeField + Absorb>(left: F, right: F) -> anyhow::Result<F> {
+    poseidon::hash(&[left, right])
+}
+
+/// Roots of empty subtrees: `zeros[0]` is the empty leaf (zero) and
+/// `zeros[i + 1] = hash_pair(zeros[i], zeros[i])`.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn zero_hashes<F: PrimeField + Absorb>(depth: usize) -> anyhow::Result<Vec<F>> {
+    let mut zeros = vec![F::ZERO];
+    for level in 0..depth {
+        zeros.push(hash_pair(zeros[level], zeros[level])?);
+    }
+    Ok(zeros)
+}
+
+/// A fixed-depth binary Poseidon Merkle tree whose unused leaves are zero.
+///
+/// Only the populated part of each level is stored, so deep trees over a
+/// few leaves stay cheap to build. Serializes as its depth and leaves (as
+/// decimal strings); the inner nodes are rebuilt when deserializing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    try_from = "MerkleTreeRepr",
+    into = "MerkleTreeRepr",
+    bound = "F: PrimeField + Absorb"
+)]
+pub struct MerkleTree<F: PrimeField> {
+    /// `levels[0]` are the leaves, `levels[depth]` holds the root.
+    levels: Vec<Vec<F>>,
+    zeros: Vec<F>,
+}
+
+impl<F: PrimeField + Absorb> MerkleTree<F> {
+    /// Builds a tree of `depth` levels over `leaves`, padded with zero leaves.
+    ///
+    /// # Errors
+    /// Returns error if there are more than `2^depth` leaves or hashing fails.
+    pub fn new(depth: usize, leaves: Vec<F>) -> anyhow::Result<Self> {
+        if depth >= usize::BITS as usize || leaves.len() > 1 << depth {
+            anyhow::bail!(
+                "{} leaves do not fit in a tree of depth {}",
+                leaves.len(),
+                depth
+            );
+        }
+
+        let zeros = zero_hashes(depth)?;
+        let mut levels = vec![leaves];
+        for level in 0..depth {
+            let nodes = &levels[level];
+            let parents = nodes
+                .chunks(2)
+                .map(|pair| hash_pair(pair[0], pair.get(1).copied().unwrap_or(zeros[level])))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            levels.push(parents);
+        }
+
+        Ok(Self { levels, zeros })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    pub fn root(&self) -> F {
+        self.levels[self.depth()]
+            .first()
+            .copied()
+            .unwrap_or(self.zeros[self.depth()])
+    }
+
+    pub fn leaves(&self) -> &[F] {
+        &self.levels[0]
+    }
+
+    /// Appends `leaf` at the next free index, returning its path.
+    ///
+    /// # Errors
+    /// Returns error if the tree is full or hashing fails.
+    pub fn insert(&mut self, leaf: F) -> anyhow::Result<MerklePath<F>> {
+        let index = self.leaves().len();
+        if index >= 1 << self.depth() {
+            anyhow::bail!("Tree of depth {} is full", self.depth());
+        }
+
+        self.levels[0].push(leaf);
+        for level in 0..self.depth() {
+            let parent = index >> (level + 1);
+            let left = self.levels[level][parent * 2];
+            let right = self.levels[level]
+                .get(parent * 2 + 1)
+                .copied()
+                .unwrap_or(self.zeros[level]);
+            let node = hash_pair(left, right)?;
+
+            let parents = &mut self.levels[level + 1];
+            if parent < parents.len() {
+                parents[parent] = node;
+            } else {
+                parents.push(node);
+            }
+        }
+
+        self.path(index)
+    }
+
+    /// Appends `leaves` in order, returning the path of each one as inserted;
+    /// the witness for [`enforce_batch_insert`].
+    ///
+    /// # Errors
+    /// Returns error if the leaves do not fit or hashing fails.
+    pub fn insert_batch(&mut self, leaves: &[F]) -> anyhow::Result<Vec<MerklePath<F>>> {
+        if self.leaves().len() + leaves.len() > 1 << self.depth() {
+            anyhow::bail!(
+                "{} more leaves do not fit in a tree of depth {} with {} leaves",
+                leaves.len(),
+                self.depth(),
+                self.leaves().len()
+            );
+        }
+
+        leaves.iter().map(|leaf| self.insert(*leaf)).collect()
+    }
+
+    /// Index of the first leaf equal to `leaf`, e.g. to find one's own commitment.
+    pub fn position(&self, leaf: &F) -> Option<usize> {
+        self.leaves().iter().position(|candidate| candidate == leaf)
+    }
+
+    /// The authentication path of the leaf at `index`.
+    ///
+    /// # Errors
+    /// Returns error if `index` is not a populated leaf.
+    pub fn path(&self, index: usize) -> anyhow::Result<MerklePath<F>> {
+        if index >= self.leaves().len() {
+            anyhow::bail!(
+                "Leaf {} is out of range for a tree with {} leaves",
+                index,
+                self.leaves().len()
+            );
+        }
+
+        let siblings = (0..self.depth())
+            .map(|level| {
+                let sibling = (index >> level) ^ 1;
+                self.levels[level]
+                    .get(sibling)
+                    .copied()
+                    .unwrap_or(self.zeros[level])
+            })
+            .collect();
+
+        Ok(MerklePath {
+            siblings,
+            index: index as u64,
+        })
+    }
+}
+
+/// Siblings from the leaf up to the root, and the leaf position whose bits
+/// (least significant first) say whether each node is a right child.
+///
+/// Serializes as `{ "siblings": [...], "index": n }` with decimal siblings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(
+    try_from = "MerklePathRepr",
+    into = "MerklePathRepr",
+    bound = "F: PrimeField"
+)]
+pub struct MerklePath<F: PrimeField> {
+    pub siblings: Vec<F>,
+    pub index: u64,
+}
+
+impl<F: PrimeField + Absorb> MerklePath<F> {
+    /// A path of the right shape for the setup phase.
+    pub fn empty(depth: usize) -> Self {
+        Self {
+            siblings: vec![F::ZERO; depth],
+            index: 0,
+        }
+    }
+
+    /// Recomputes the root from `leaf`.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn root(&self, leaf: F) -> anyhow::Result<F> {
+        self.siblings
+            .iter()
+            .enumerate()
+            .try_fold(leaf, |node, (level, sibling)| {
+                if (self.index >> level) & 1 == 1 {
+                    hash_pair(*sibling, node)
+                } else {
+                    hash_pair(node, *sibling)
+                }
+            })
+    }
+}
+
+impl<F: PrimeField> Zeroize for MerklePath<F> {
+    fn zeroize(&mut self) {
+        self.siblings.zeroize();
+        self.index.zeroize();
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MerkleTreeRepr {
+    depth: usize,
+    leaves: Vec<String>,
+}
+
+impl<F: PrimeField> From<MerkleTree<F>> for MerkleTreeRepr {
+    fn from(tree: MerkleTree<F>) -> Self {
+        Self {
+            depth: tree.levels.len() - 1,
+            leaves: to_decimal(&tree.levels[0]),
+        }
+    }
+}
+
+impl<F: PrimeField + Absorb> TryFrom<MerkleTreeRepr> for MerkleTree<F> {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: MerkleTreeRepr) -> anyhow::Result<Self> {
+        Self::new(repr.depth, from_decimal(&repr.leaves)?)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct MerklePathRepr {
+    siblings: Vec<String>,
+    index: u64,
+}
+
+impl<F: PrimeField> From<MerklePath<F>> for MerklePathRepr {
+    fn from(path: MerklePath<F>) -> Self {
+        Self {
+            siblings: to_decimal(&path.siblings),
+            index: path.index,
+        }
+    }
+}
+
+impl<F: PrimeField> TryFrom<MerklePathRepr> for MerklePath<F> {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: MerklePathRepr) -> anyhow::Result<Self> {
+        Ok(Self {
+            siblings: from_decimal(&repr.siblings)?,
+            index: repr.index,
+        })
+    }
+}
+
+fn to_decimal<F: PrimeField>(values: &[F]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| field::format(value, Encoding::Decimal))
+        .collect()
+}
+
+fn from_decimal<F: PrimeField>(values: &[String]) -> anyhow::Result<Vec<F>> {
+    values
+        .iter()
+        .map(|value| field::parse_auto(value))
+        .collect()
+}
+
+/// Allocated [`MerklePath`]: sibling witnesses and the index bits.
+pub struct MerklePathVar<F: PrimeField> {
+    pub siblings: Vec<FpVar<F>>,
+    /// Little-endian bits of the leaf index; bit `i` is set when the node at
+    /// level `i` is a right child.
+    pub index_bits: Vec<Boolean<F>>,
+}
+
+impl<F: PrimeField + Absorb> MerklePathVar<F> {
+    /// Allocates `path` as witnesses.
+    pub fn new_witness(cs: ConstraintSystemRef<F>, path: &MerklePath<F>) -> r1cs::Result<Self> {
+        let siblings = path
+            .siblings
+            .iter()
+            .map(|sibling| FpVar::new_witness(cs.clone(), || Ok(*sibling)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+        let index_bits = (0..path.siblings.len())
+            .map(|level| Boolean::new_witness(cs.clone(), || Ok((path.index >> level) & 1 == 1)))
+            .collect::<r1cs::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            siblings,
+            index_bits,
+        })
+    }
+
+    /// Root of the tree containing `leaf` at this path.
+    pub fn root(&self, cs: ConstraintSystemRef<F>, leaf: &FpVar<F>) -> r1cs::Result<FpVar<F>> {
+        let mut node = leaf.clone();
+        for (sibling, is_right) in self.siblings.iter().zip(&self.index_bits) {
+            let (left, right) = select::conditionally_swap(is_right, &node, sibling)?;
+            node = poseidon::hash_gadget(cs.clone(), &[left, right])?;
+        }
+        Ok(node)
+    }
+
+    /// The leaf index as a field element.
+    pub fn index(&self) -> r1cs::Result<FpVar<F>> {
+        Boolean::le_bits_to_fp(&self.index_bits)
+    }
+}
+
+/// Enforces that appending `leaves` at consecutive indices from `start_index`
+/// turns the tree with root `old_root` into one with root `new_root`.
+///
+/// `paths` are the paths returned by [`MerkleTree::insert_batch`]: each slot
+/// must hold the empty (zero) leaf before its insertion, so existing leaves
+/// cannot be overwritten.
+pub fn enforce_batch_insert<F: PrimeField + Absorb>(
+    cs: ConstraintSystemRef<F>,
+    old_root: &FpVar<F>,
+    new_root: &FpVar<F>,
+    start_index: &FpVar<F>,
+    leaves: &[FpVar<F>],
+    paths: &[MerklePathVar<F>],
+) -> r1cs::Result<()> {
+    if leaves.len() != paths.len() {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    let mut root = old_root.clone();
+    let mut index = start_index.clone();
+    for (leaf, path) in leaves.iter().zip(paths) {
+        path.index()?.enforce_equal(&index)?;
+        path.root(cs.clone(), &FpVar::zero())?
+            .enforce_equal(&root)?;
+
+        root = path.root(cs.clone(), leaf)?;
+        index += F::ONE;
+    }
+
+    root.enforce_equal(new_root)
+}
@@ -0,0 +1,212 @@
+use super::{hash_pair, zero_hashes, MerklePath, MerklePathVar};
+use crate::poseidon;
+use ark_crypto_primitives::sponge::Absorb;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+use std::collections::HashMap;
+
+/// Depth used by the example circuits: keys are placed by their low 64 bits.
+pub const DEFAULT_DEPTH: usize = 64;
+
+/// The leaf stored for `key`: `Poseidon(key, value)`. Absent keys leave a
+/// zero leaf.
+///
+/// # Errors
+/// Returns error if hashing fails.
+pub fn leaf<F: PrimeField + Absorb>(key: F, value: F) -> anyhow::Result<F> {
+    poseidon::hash(&[key, value])
+}
+
+/// The slot of `key` in a tree of `depth` levels: its low `depth` bits.
+pub fn slot<F: PrimeField>(key: &F, depth: usize) -> u64 {
+    let low = key.into_bigint().as_ref()[0];
+    if depth >= u64::BITS as usize {
+        low
+    } else {
+        low & ((1 << depth) - 1)
+    }
+}
+
+/// A key-value Merkle tree with one slot per possible key, so that absent
+/// keys can be proven absent.
+///
+/// Keys are expected to be uniformly distributed (hashes, nullifiers), as
+/// only their low `depth` bits choose the slot; inserting a key whose slot
+/// holds another key fails rather than overwriting it.
+#[derive(Debug, Clone)]
+pub struct SparseMerkleTree<F: PrimeField> {
+    depth: usize,
+    /// Non-empty nodes by `(level, index)`; level 0 are the leaves.
+    nodes: HashMap<(usize, u64), F>,
+    entries: HashMap<u64, (F, F)>,
+    zeros: Vec<F>,
+}
+
+impl<F: PrimeField + Absorb> SparseMerkleTree<F> {
+    /// Creates an empty tree of `depth` levels.
+    ///
+    /// # Errors
+    /// Returns error if `depth` is 0 or above 64, or hashing fails.
+    pub fn new(depth: usize) -> anyhow::Result<Self> {
+        if depth == 0 || depth > u64::BITS as usize {
+            anyhow::bail!("Sparse tree depth must be between 1 and 64, got {}", depth);
+        }
+
+        Ok(Self {
+            depth,
+            nodes: HashMap::new(),
+            entries: HashMap::new(),
+            zeros: zero_hashes(depth)?,
+        })
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> F {
+        self.node(self.depth, 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The value stored for `key`, if any.
+    pub fn get(&self, key: &F) -> Option<F> {
+        self.entries
+            .get(&slot(key, self.depth))
+            .filter(|(stored, _)| stored == key)
+            .map(|(_, value)| *value)
+    }
+
+    /// Stores `value` for `key`, replacing any previous value.
+    ///
+    /// # Errors
+    /// Returns error if the slot of `key` holds a different key, or hashing fails.
+    pub fn insert(&mut self, key: F, value: F) -> anyhow::Result<()> {
+        let slot = slot(&key, self.depth);
+        self.ensure_slot_free(slot, &key)?;
+
+        self.entries.insert(slot, (key, value));
+        self.update(slot, leaf(key, value)?)
+    }
+
+    /// Removes `key`, returning its value if it was present.
+    ///
+    /// # Errors
+    /// Returns error if hashing fails.
+    pub fn remove(&mut self, key: &F) -> anyhow::Result<Option<F>> {
+        let Some(value) = self.get(key) else {
+            return Ok(None);
+        };
+
+        let slot = slot(key, self.depth);
+        self.entries.remove(&slot);
+        self.update(slot, F::ZERO)?;
+        Ok(Some(value))
+    }
+
+    /// The path of the slot of `key`, proving membership when `key` is
+    /// present and non-membership otherwise.
+    ///
+    /// # Errors
+    /// Returns error if the slot of `key` holds a different key, whose
+    /// presence proves nothing about `key`.
+    pub fn path(&self, key: &F) -> anyhow::Result<MerklePath<F>> {
+        let slot = slot(key, self.depth);
+        self.ensure_slot_free(slot, key)?;
+
+        let siblings = (0..self.depth)
+            .map(|level| self.node(level, (slot >> level) ^ 1))
+            .collect();
+        Ok(MerklePath {
+            siblings,
+            index: slot,
+        })
+    }
+
+    fn ensure_slot_free(&self, slot: u64, key: &F) -> anyhow::Result<()> {
+        match self.entries.get(&slot) {
+            Some((stored, _)) if stored != key => {
+                anyhow::bail!("Slot {} is already taken by another key", slot)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn node(&self, level: usize, index: u64) -> F {
+        self.nodes
+            .get(&(level, index))
+            .copied()
+            .unwrap_or(self.zeros[level])
+    }
+
+    /// Sets the leaf at `slot` and rehashes its ancestors, dropping nodes
+    /// that become empty.
+    fn update(&mut self, slot: u64, leaf: F) -> anyhow::Result<()> {
+        let mut node = leaf;
+        for level in 0..=self.depth {
+            let index = if level == u64::BITS as usize {
+                0
+            } else {
+                slot >> level
+            };
+            if node == self.zeros[level] {
+                self.nodes.remove(&(level, index));
+            } else {
+                self.nodes.insert((level, index), node);
+            }
+
+            if level < self.depth {
+                let sibling = self.node(level, index ^ 1);
+                node = if index & 1 == 0 {
+                    hash_pair(node, sibling)?
+                } else {
+                    hash_pair(sibling, node)?
+                };
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Enforces that `path` is the path of the slot of `key`.
+fn enforce_slot<F: PrimeField>(key: &FpVar<F>, path: &MerklePathVar<F>) -> r1cs::Result<()> {
+    let key_bits = key.to_bits_le()?;
+    for (index_bit, key_bit) in path.index_bits.iter().zip(&key_bits) {
+        index_bit.enforce_equal(key_bit)?;
+    }
+    Ok(())
+}
+
+/// Enforces that the tree with root `root` stores `value` for `key`.
+pub fn enforce_membership<F: PrimeField + Absorb>(
+    cs: ConstraintSystemRef<F>,
+    root: &FpVar<F>,
+    key: &FpVar<F>,
+    value: &FpVar<F>,
+    path: &MerklePathVar<F>,
+) -> r1cs::Result<()> {
+    enforce_slot(key, path)?;
+    let leaf = poseidon::hash_gadget(cs.clone(), &[key.clone(), value.clone()])?;
+    path.root(cs, &leaf)?.enforce_equal(root)
+}
+
+/// Enforces that the tree with root `root` has no entry for `key`: the leaf
+/// in its slot is empty.
+pub fn enforce_non_membership<F: PrimeField + Absorb>(
+    cs: ConstraintSystemRef<F>,
+    root: &FpVar<F>,
+    key: &FpVar<F>,
+    path: &MerklePathVar<F>,
+) -> r1cs::Result<()> {
+    enforce_slot(key, path)?;
+    path.root(cs, &FpVar::zero())?.enforce_equal(root)
+}
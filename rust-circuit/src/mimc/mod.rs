@@ -0,0 +1,114 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+use sha3::{Digest, Keccak256};
+
+/// Feistel rounds per permutation.
+pub const ROUNDS: usize = 220;
+/// S-box exponent.
+pub const ALPHA: u64 = 5;
+/// Seed the round constants are derived from.
+pub const SEED: &str = "mimcsponge";
+
+/// Round constants of circomlib's `MiMCSponge`: a Keccak-256 chain from
+/// [`SEED`], reduced into the field, with the first and last set to zero.
+pub fn round_constants<F: PrimeField>() -> Vec<F> {
+    let mut digest = Keccak256::digest(SEED.as_bytes());
+    let mut constants = vec![F::ZERO; ROUNDS];
+    for constant in &mut constants[1..ROUNDS - 1] {
+        digest = Keccak256::digest(digest);
+        *constant = F::from_be_bytes_mod_order(&digest);
+    }
+    constants
+}
+
+/// The MiMC-Feistel permutation of `(left, right)` under `key`, as circomlib's
+/// `MiMCFeistel`.
+pub fn permute<F: PrimeField>(constants: &[F], left: F, right: F, key: F) -> (F, F) {
+    let (mut left, mut right) = (left, right);
+    for (i, constant) in constants.iter().enumerate() {
+        let t = (left + key + constant).pow([ALPHA]);
+        if i < constants.len() - 1 {
+            (left, right) = (right + t, left);
+        } else {
+            right += t;
+        }
+    }
+    (left, right)
+}
+
+/// Absorbs `inputs` one per permutation and squeezes `outputs` elements, as
+/// circomlib's `MiMCSponge(inputs.len(), 220, outputs)`.
+pub fn sponge<F: PrimeField>(inputs: &[F], key: F, outputs: usize) -> Vec<F> {
+    let constants = round_constants::<F>();
+    let (mut left, mut right) = (F::ZERO, F::ZERO);
+    for input in inputs {
+        (left, right) = permute(&constants, left + input, right, key);
+    }
+
+    let mut squeezed = vec![left];
+    while squeezed.len() < outputs {
+        (left, right) = permute(&constants, left, right, key);
+        squeezed.push(left);
+    }
+    squeezed.truncate(outputs);
+    squeezed
+}
+
+/// Hashes `inputs` natively with a zero key, as the MiMC mixers deployed on
+/// Ethereum (e.g. Tornado Cash's `hashLeftRight`) do.
+pub fn hash<F: PrimeField>(inputs: &[F]) -> F {
+    sponge(inputs, F::ZERO, 1)[0]
+}
+
+/// In-circuit counterpart of [`permute`], costing 3 constraints per round.
+pub fn permute_gadget<F: PrimeField>(
+    constants: &[F],
+    left: &FpVar<F>,
+    right: &FpVar<F>,
+    key: &FpVar<F>,
+) -> r1cs::Result<(FpVar<F>, FpVar<F>)> {
+    let (mut left, mut right) = (left.clone(), right.clone());
+    for (i, constant) in constants.iter().enumerate() {
+        let t = &left + key + *constant;
+        let t = t.square()?.square()? * &t;
+        if i < constants.len() - 1 {
+            (left, right) = (right + t, left);
+        } else {
+            right += t;
+        }
+    }
+    Ok((left, right))
+}
+
+/// In-circuit counterpart of [`sponge`].
+pub fn sponge_gadget<F: PrimeField>(
+    inputs: &[FpVar<F>],
+    key: &FpVar<F>,
+    outputs: usize,
+) -> r1cs::Result<Vec<FpVar<F>>> {
+    let constants = round_constants::<F>();
+    let (mut left, mut right) = (FpVar::zero(), FpVar::zero());
+    for input in inputs {
+        (left, right) = permute_gadget(&constants, &(left + input), &right, key)?;
+    }
+
+    let mut squeezed = vec![left.clone()];
+    while squeezed.len() < outputs {
+        (left, right) = permute_gadget(&constants, &left, &right, key)?;
+        squeezed.push(left.clone());
+    }
+    squeezed.truncate(outputs);
+    Ok(squeezed)
+}
+
+/// In-circuit counterpart of [`hash`], producing the same digest.
+pub fn hash_gadget<F: PrimeField>(inputs: &[FpVar<F>]) -> r1cs::Result<FpVar<F>> {
+    Ok(sponge_gadget(inputs, &FpVar::zero(), 1)?.remove(0))
+}
+
+/// The field element a Keccak-256 digest reduces to, as Solidity's
+/// `uint256(keccak256(data)) % p`, e.g. for MiMC trees' empty leaves.
+pub fn keccak_to_field<F: PrimeField>(data: &[u8]) -> F {
+    F::from_be_bytes_mod_order(&Keccak256::digest(data))
+}
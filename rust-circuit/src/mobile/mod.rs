@@ -0,0 +1,80 @@
+use crate::api;
+use crate::curve::Curve;
+use crate::keys::{self, convert::KeyKind, header, header::Expected};
+use ark_bn254::Bn254;
+use std::fmt;
+use std::sync::Arc;
+
+/// Error surfaced to Swift and Kotlin as a thrown exception.
+#[derive(Debug, uniffi::Error)]
+pub enum VortexError {
+    Failed { message: String },
+}
+
+impl fmt::Display for VortexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed { message } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for VortexError {}
+
+impl From<anyhow::Error> for VortexError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::Failed {
+            message: e.to_string(),
+        }
+    }
+}
+
+/// A BN254 proving key deserialized once and reused across proofs.
+#[derive(uniffi::Object)]
+pub struct ProvingKey {
+    inner: Arc<ark_groth16::ProvingKey<Bn254>>,
+    fingerprint: String,
+}
+
+#[uniffi::export]
+impl ProvingKey {
+    /// Loads a compressed proving key, e.g. the contents of `proving_key.bin`;
+    /// its key header, if any, must name a BN254 proving key.
+    #[uniffi::constructor]
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Arc<Self>, VortexError> {
+        let inner = keys::cache::decode(
+            &bytes,
+            &Expected {
+                kind: KeyKind::Proving,
+                curve: Curve::Bn254,
+                non_trivial: None,
+            },
+        )?;
+
+        Ok(Arc::new(Self {
+            inner,
+            fingerprint: keys::fingerprint(header::strip(&bytes)),
+        }))
+    }
+
+    /// SHA-256 fingerprint of the key bytes, as used by `check_compat`.
+    pub fn fingerprint(&self) -> String {
+        self.fingerprint.clone()
+    }
+
+    /// Generates a proof for a JSON `ProofInput` and returns the JSON `ProofOutput`.
+    pub fn prove(&self, input_json: String) -> Result<String, VortexError> {
+        Ok(api::prove_with_key(&input_json, &self.inner, |_| {})?)
+    }
+}
+
+/// Generates a proof with a hex-encoded proving key; same JSON as the wasm `prove`.
+#[uniffi::export]
+pub fn prove(input_json: String, proving_key_hex: String) -> Result<String, VortexError> {
+    Ok(api::prove(&input_json, &proving_key_hex, |_| {})?)
+}
+
+#[uniffi::export]
+pub fn verify(proof_json: String, verifying_key_hex: String) -> Result<bool, VortexError> {
+    Ok(api::verify(&proof_json, &verifying_key_hex)?)
+}
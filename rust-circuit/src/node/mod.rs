@@ -0,0 +1,95 @@
+use crate::api;
+use crate::curve::Curve;
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Task};
+use napi_derive::napi;
+use std::str::FromStr;
+
+/// Hex-encoded compressed Groth16 keys.
+#[napi(object)]
+pub struct KeyPair {
+    pub proving_key_hex: String,
+    pub verifying_key_hex: String,
+}
+
+#[napi(object)]
+pub struct KeygenOptions {
+    /// `"bn254"` (default) or `"bls12-381"`
+    pub curve: Option<String>,
+    /// Generate keys for the non-trivial factorization circuit
+    pub non_trivial: Option<bool>,
+}
+
+/// Generates a proof on the libuv thread pool and resolves with the JSON
+/// `ProofOutput`, in the same format as the wasm `prove`.
+#[napi(ts_return_type = "Promise<string>")]
+pub fn prove(input_json: String, proving_key_hex: String) -> AsyncTask<ProveTask> {
+    AsyncTask::new(ProveTask {
+        input_json,
+        proving_key_hex,
+    })
+}
+
+#[napi]
+pub fn verify(proof_json: String, verifying_key_hex: String) -> napi::Result<bool> {
+    api::verify(&proof_json, &verifying_key_hex).map_err(to_napi_error)
+}
+
+/// Runs a single-party setup on the libuv thread pool. Development only: the
+/// caller learns the toxic waste.
+#[napi(ts_return_type = "Promise<KeyPair>")]
+pub fn keygen(options: Option<KeygenOptions>) -> napi::Result<AsyncTask<KeygenTask>> {
+    let (curve, non_trivial) = match options {
+        Some(options) => (options.curve, options.non_trivial.unwrap_or(false)),
+        None => (None, false),
+    };
+    let curve = match curve {
+        Some(curve) => Curve::from_str(&curve).map_err(to_napi_error)?,
+        None => Curve::default(),
+    };
+
+    Ok(AsyncTask::new(KeygenTask { curve, non_trivial }))
+}
+
+pub struct ProveTask {
+    input_json: String,
+    proving_key_hex: String,
+}
+
+impl Task for ProveTask {
+    type Output = String;
+    type JsValue = String;
+
+    fn compute(&mut self) -> napi::Result<String> {
+        api::prove(&self.input_json, &self.proving_key_hex, |_| {}).map_err(to_napi_error)
+    }
+
+    fn resolve(&mut self, _env: Env, output: String) -> napi::Result<String> {
+        Ok(output)
+    }
+}
+
+pub struct KeygenTask {
+    curve: Curve,
+    non_trivial: bool,
+}
+
+impl Task for KeygenTask {
+    type Output = api::KeyPair;
+    type JsValue = KeyPair;
+
+    fn compute(&mut self) -> napi::Result<api::KeyPair> {
+        api::keygen(self.curve, self.non_trivial).map_err(to_napi_error)
+    }
+
+    fn resolve(&mut self, _env: Env, keys: api::KeyPair) -> napi::Result<KeyPair> {
+        Ok(KeyPair {
+            proving_key_hex: keys.proving_key_hex,
+            verifying_key_hex: keys.verifying_key_hex,
+        })
+    }
+}
+
+fn to_napi_error(e: anyhow::Error) -> napi::Error {
+    napi::Error::from_reason(e.to_string())
+}
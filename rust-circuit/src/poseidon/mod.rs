@@ -0,0 +1,120 @@
+use ark_crypto_primitives::crh::poseidon::constraints::{CRHGadget, CRHParametersVar};
+use ark_crypto_primitives::crh::poseidon::CRH;
+use ark_crypto_primitives::crh::{CRHScheme, CRHSchemeGadget};
+use ark_crypto_primitives::sponge::constraints::CryptographicSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::constraints::PoseidonSpongeVar;
+use ark_crypto_primitives::sponge::poseidon::{
+    find_poseidon_ark_and_mds, PoseidonConfig, PoseidonSponge,
+};
+use ark_crypto_primitives::sponge::{Absorb, CryptographicSponge};
+use ark_ff::PrimeField;
+use ark_r1cs_std::fields::fp::FpVar;
+use ark_r1cs_std::prelude::AllocVar;
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+
+/// Sponge rate: field elements absorbed per permutation.
+pub const RATE: usize = 2;
+/// Sponge capacity.
+pub const CAPACITY: usize = 1;
+pub const FULL_ROUNDS: usize = 8;
+pub const PARTIAL_ROUNDS: usize = 57;
+/// Partial rounds of circomlib's `Poseidon(n)`, indexed by `n - 1`.
+pub const CIRCOM_PARTIAL_ROUNDS: [usize; 16] = [
+    56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68,
+];
+/// S-box exponent.
+pub const ALPHA: u64 = 5;
+
+/// Poseidon parameters shared by the native hash and the gadget.
+///
+/// Width 3 with x^5 S-boxes and 8 full / 57 partial rounds, the security
+/// parameters circomlib uses for two inputs. Round constants and the MDS
+/// matrix are generated with the Grain LFSR from the reference
+/// implementation, so digests do not match circomlib's `poseidon`; hash with
+/// [`hash`] (or the wasm `poseidon_hash`) instead of a JS library.
+pub fn config<F: PrimeField>() -> PoseidonConfig<F> {
+    let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+        F::MODULUS_BIT_SIZE as u64,
+        RATE,
+        FULL_ROUNDS as u64,
+        PARTIAL_ROUNDS as u64,
+        0,
+    );
+
+    PoseidonConfig::new(FULL_ROUNDS, PARTIAL_ROUNDS, ALPHA, mds, ark, RATE, CAPACITY)
+}
+
+/// Hashes `inputs` natively: absorbs every element, squeezes one.
+///
+/// # Errors
+/// Returns error if the sponge fails.
+pub fn hash<F: PrimeField + Absorb>(inputs: &[F]) -> anyhow::Result<F> {
+    CRH::<F>::evaluate(&config(), inputs)
+        .map_err(|e| anyhow::anyhow!("Failed to compute Poseidon hash: {}", e))
+}
+
+/// In-circuit counterpart of [`hash`], producing the same digest.
+pub fn hash_gadget<F: PrimeField + Absorb>(
+    cs: ConstraintSystemRef<F>,
+    inputs: &[FpVar<F>],
+) -> r1cs::Result<FpVar<F>> {
+    let params = CRHParametersVar::new_constant(cs, config::<F>())?;
+    CRHGadget::<F>::evaluate(&params, inputs)
+}
+
+/// Parameters of circomlib's `Poseidon(inputs)`: width `inputs + 1`, with the
+/// round constants and MDS matrix of [`config`]'s generator.
+///
+/// # Panics
+/// Panics if `inputs` is not between 1 and 16.
+pub fn circom_config<F: PrimeField>(inputs: usize) -> PoseidonConfig<F> {
+    let partial_rounds = CIRCOM_PARTIAL_ROUNDS[inputs - 1];
+    let (ark, mds) = find_poseidon_ark_and_mds::<F>(
+        F::MODULUS_BIT_SIZE as u64,
+        inputs,
+        FULL_ROUNDS as u64,
+        partial_rounds as u64,
+        0,
+    );
+
+    PoseidonConfig::new(FULL_ROUNDS, partial_rounds, ALPHA, mds, ark, inputs, 1)
+}
+
+/// Hashes `inputs` as circomlib's `Poseidon(inputs.len())` and circomlibjs'
+/// `poseidon` do over BN254, e.g. to match Semaphore or Tornado Cash Nova.
+///
+/// circomlib permutes `[0, inputs...]` once and outputs the capacity element,
+/// where [`hash`] outputs the first rate element, so digests differ.
+///
+/// # Errors
+/// Returns error if there are not between 1 and 16 inputs.
+pub fn circom_hash<F: PrimeField + Absorb>(inputs: &[F]) -> anyhow::Result<F> {
+    if !(1..=CIRCOM_PARTIAL_ROUNDS.len()).contains(&inputs.len()) {
+        anyhow::bail!(
+            "circomlib Poseidon takes 1 to {} inputs, got {}",
+            CIRCOM_PARTIAL_ROUNDS.len(),
+            inputs.len()
+        );
+    }
+
+    // Absorbing a full rate only permutes on squeeze
+    let mut sponge = PoseidonSponge::new(&circom_config(inputs.len()));
+    sponge.absorb(&inputs);
+    let _: Vec<F> = sponge.squeeze_field_elements(1);
+    Ok(sponge.state[0])
+}
+
+/// In-circuit counterpart of [`circom_hash`], at the cost of one permutation.
+pub fn circom_hash_gadget<F: PrimeField + Absorb>(
+    cs: ConstraintSystemRef<F>,
+    inputs: &[FpVar<F>],
+) -> r1cs::Result<FpVar<F>> {
+    if !(1..=CIRCOM_PARTIAL_ROUNDS.len()).contains(&inputs.len()) {
+        return Err(r1cs::SynthesisError::Unsatisfiable);
+    }
+
+    let mut sponge = PoseidonSpongeVar::new(cs, &circom_config(inputs.len()));
+    sponge.absorb(&inputs)?;
+    sponge.squeeze_field_elements(1)?;
+    Ok(sponge.state[0].clone())
+}
@@ -0,0 +1,258 @@
+use crate::audit::{self, AuditRecord};
+use crate::witness;
+use ark_bn254::Bn254;
+use ark_crypto_primitives::snark::SNARK;
+use ark_ec::pairing::Pairing;
+use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::{CanonicalSerialize, Compress};
+use rand_core::{CryptoRng, RngCore};
+use std::fmt;
+use std::future::Future;
+
+mod prepared;
+mod progress;
+
+pub use progress::{prove_with_checkpoints, prove_with_progress, ProvingPhase};
+pub(crate) use progress::{reporting, run_ready};
+
+/// A Groth16 proof together with the public inputs it was generated for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProofBundle<E: Pairing = Bn254> {
+    pub proof: Proof<E>,
+    /// Public inputs in the order allocated by `generate_constraints()`.
+    pub public_inputs: Vec<E::ScalarField>,
+}
+
+impl<E: Pairing> ProofBundle<E> {
+    /// Proof component A (compressed: 32 bytes on BN254).
+    pub fn proof_a_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        serialize(&self.proof.a, Compress::Yes, "proof.a")
+    }
+
+    /// Proof component B (compressed: 64 bytes on BN254).
+    pub fn proof_b_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        serialize(&self.proof.b, Compress::Yes, "proof.b")
+    }
+
+    /// Proof component C (compressed: 32 bytes on BN254).
+    pub fn proof_c_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        serialize(&self.proof.c, Compress::Yes, "proof.c")
+    }
+
+    /// Compressed proof points, as expected by `groth16::proof_points_from_bytes` in Sui Move.
+    pub fn proof_serialized(&self) -> anyhow::Result<Vec<u8>> {
+        self.proof_serialized_with(Compress::Yes)
+    }
+
+    /// Proof components A, B and C in the given encoding.
+    ///
+    /// Uncompressed points are twice the size but skip the square roots
+    /// needed to decompress them, which dominate deserialization on G2.
+    pub fn proof_points_with(&self, compress: Compress) -> anyhow::Result<[Vec<u8>; 3]> {
+        Ok([
+            serialize(&self.proof.a, compress, "proof.a")?,
+            serialize(&self.proof.b, compress, "proof.b")?,
+            serialize(&self.proof.c, compress, "proof.c")?,
+        ])
+    }
+
+    /// Proof points in the given encoding; only the compressed form is
+    /// accepted by `groth16::proof_points_from_bytes`.
+    pub fn proof_serialized_with(&self, compress: Compress) -> anyhow::Result<Vec<u8>> {
+        serialize(&self.proof, compress, "proof")
+    }
+
+    /// Concatenated public inputs, as expected by `groth16::public_proof_inputs_from_bytes`.
+    pub fn public_inputs_serialized(&self) -> anyhow::Result<Vec<u8>> {
+        let mut serialized = Vec::new();
+        for input in &self.public_inputs {
+            input
+                .serialize_compressed(&mut serialized)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize public input: {}", e))?;
+        }
+        Ok(serialized)
+    }
+}
+
+/// Generates a Groth16 proof for `circuit`.
+///
+/// The circuit is synthesized once up front so that unsatisfied constraints are
+/// reported as an error instead of producing a proof that will never verify.
+///
+/// # Errors
+/// Returns error if:
+/// - Constraints cannot be generated or are not satisfied
+/// - Groth16 proving fails
+pub fn prove<E, C, R>(circuit: C, pk: &ProvingKey<E>, rng: &mut R) -> anyhow::Result<ProofBundle<E>>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField> + Clone,
+    R: RngCore + CryptoRng,
+{
+    let _span = tracing::info_span!("prove").entered();
+
+    let public_inputs = witness::generate(circuit.clone())?.public_inputs().to_vec();
+
+    let proof = Groth16::<E>::prove(pk, circuit, rng)
+        .map_err(|e| anyhow::anyhow!("Failed to generate proof: {}", e))?;
+
+    Ok(ProofBundle {
+        proof,
+        public_inputs,
+    })
+}
+
+/// Generates a Groth16 proof whose blinding factors are derived from `session_key`
+/// and a fresh nonce, returning an [`AuditRecord`] committing to them.
+///
+/// `progress` is called as each proving phase starts, as in [`prove_with_progress`].
+///
+/// # Errors
+/// Returns error if:
+/// - Constraints cannot be generated or are not satisfied
+/// - Groth16 proving fails
+pub fn prove_audited<E, C, R, F>(
+    circuit: C,
+    pk: &ProvingKey<E>,
+    session_key: &[u8; 32],
+    rng: &mut R,
+    progress: F,
+) -> anyhow::Result<(ProofBundle<E>, AuditRecord)>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField> + Clone,
+    R: RngCore + CryptoRng,
+    F: FnMut(ProvingPhase),
+{
+    run_ready(prove_audited_with_checkpoints(
+        circuit,
+        pk,
+        session_key,
+        rng,
+        reporting(progress),
+    ))
+}
+
+/// Like [`prove_audited`], but awaits `checkpoint` as each phase starts, as in
+/// [`prove_with_checkpoints`].
+///
+/// # Errors
+/// Returns error if a checkpoint fails, or as [`prove_audited`].
+pub async fn prove_audited_with_checkpoints<E, C, R, F, Fut>(
+    circuit: C,
+    pk: &ProvingKey<E>,
+    session_key: &[u8; 32],
+    rng: &mut R,
+    checkpoint: F,
+) -> anyhow::Result<(ProofBundle<E>, AuditRecord)>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField> + Clone,
+    R: RngCore + CryptoRng,
+    F: FnMut(ProvingPhase) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let mut nonce = [0u8; 32];
+    rng.fill_bytes(&mut nonce);
+    let (r, s) = audit::derive_randomness(session_key, &nonce);
+
+    let bundle = progress::prove_staged(circuit, pk, r, s, checkpoint).await?;
+
+    let record = AuditRecord {
+        nonce,
+        commitment: audit::commit(&r, &s),
+    };
+
+    Ok((bundle, record))
+}
+
+/// Re-randomizes the proof in `bundle` so it cannot be linked to the prover's
+/// original output, keeping the public inputs.
+///
+/// The result verifies under `vk` exactly when the original does.
+pub fn rerandomize<E, R>(
+    bundle: &ProofBundle<E>,
+    vk: &VerifyingKey<E>,
+    rng: &mut R,
+) -> ProofBundle<E>
+where
+    E: Pairing,
+    R: RngCore + CryptoRng,
+{
+    ProofBundle {
+        proof: Groth16::<E>::rerandomize_proof(vk, &bundle.proof, rng),
+        public_inputs: bundle.public_inputs.clone(),
+    }
+}
+
+/// Public inputs supplied for verification do not match the verifying key.
+///
+/// Returned (inside the `anyhow::Error`) by [`verify`], so callers can
+/// `downcast_ref::<InputCountError>()` instead of matching on messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputCountError {
+    /// `vk.gamma_abc_g1.len() - 1`.
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl InputCountError {
+    /// Stable identifier for programmatic handling.
+    pub fn code(&self) -> &'static str {
+        "E_INPUT_COUNT"
+    }
+
+    /// Checks that `vk` takes exactly `actual` public inputs.
+    pub fn check<E: Pairing>(vk: &VerifyingKey<E>, actual: usize) -> Result<(), Self> {
+        let expected = vk.gamma_abc_g1.len().saturating_sub(1);
+        if actual == expected {
+            Ok(())
+        } else {
+            Err(Self { expected, actual })
+        }
+    }
+}
+
+impl fmt::Display for InputCountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Expected {} public inputs, got {} ({})",
+            self.expected,
+            self.actual,
+            self.code()
+        )
+    }
+}
+
+impl std::error::Error for InputCountError {}
+
+/// Verifies `bundle` against `vk`.
+///
+/// The prepared form of `vk` is cached per thread, so verifying repeatedly
+/// against the same key skips the pairing precomputation.
+///
+/// # Errors
+/// Returns [`InputCountError`] if `bundle` has the wrong number of public
+/// inputs for `vk`, or an error if the verifier cannot process them; an
+/// invalid proof yields `Ok(false)`.
+pub fn verify<E: Pairing>(bundle: &ProofBundle<E>, vk: &VerifyingKey<E>) -> anyhow::Result<bool> {
+    InputCountError::check(vk, bundle.public_inputs.len())?;
+
+    let pvk = prepared::prepared(vk)?;
+    Groth16::<E>::verify_proof(&pvk, &bundle.proof, &bundle.public_inputs)
+        .map_err(|e| anyhow::anyhow!("Verification failed: {}", e))
+}
+
+fn serialize<T: CanonicalSerialize>(
+    value: &T,
+    compress: Compress,
+    name: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    value
+        .serialize_with_mode(&mut bytes, compress)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize {}: {}", name, e))?;
+    Ok(bytes)
+}
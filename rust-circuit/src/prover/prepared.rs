@@ -0,0 +1,56 @@
+use ark_ec::pairing::Pairing;
+use ark_groth16::{PreparedVerifyingKey, VerifyingKey};
+use ark_serialize::CanonicalSerialize;
+use sha2::{Digest, Sha256};
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Number of prepared keys kept per thread; a relayer or browser session
+/// rarely verifies against more than a few keys.
+const CAPACITY: usize = 8;
+
+type Entry = (TypeId, [u8; 32], Rc<dyn Any>);
+
+thread_local! {
+    /// Prepared keys by curve and key digest, most recently used last.
+    static CACHE: RefCell<Vec<Entry>> = const { RefCell::new(Vec::new()) };
+}
+
+/// `prepare_verifying_key(vk)`, computed once per key and thread.
+///
+/// Keys are identified by the SHA-256 of their compressed encoding, which
+/// is much cheaper than the pairing `e(alpha, beta)` computed to prepare them.
+///
+/// # Errors
+/// Returns error if `vk` cannot be serialized.
+pub(crate) fn prepared<E: Pairing>(
+    vk: &VerifyingKey<E>,
+) -> anyhow::Result<Rc<PreparedVerifyingKey<E>>> {
+    let mut bytes = Vec::new();
+    vk.serialize_compressed(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize verifying key: {}", e))?;
+    let key = (TypeId::of::<E>(), <[u8; 32]>::from(Sha256::digest(&bytes)));
+
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let position = cache
+            .iter()
+            .position(|(curve, digest, _)| (*curve, *digest) == key);
+        if let Some(position) = position {
+            let entry = cache.remove(position);
+            let pvk = Rc::clone(&entry.2);
+            cache.push(entry);
+            return Ok(pvk
+                .downcast::<PreparedVerifyingKey<E>>()
+                .expect("entries are keyed by curve"));
+        }
+
+        let pvk = Rc::new(ark_groth16::prepare_verifying_key(vk));
+        if cache.len() == CAPACITY {
+            cache.remove(0);
+        }
+        cache.push((key.0, key.1, Rc::clone(&pvk) as Rc<dyn Any>));
+        Ok(pvk)
+    })
+}
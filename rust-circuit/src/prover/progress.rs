@@ -0,0 +1,234 @@
+use super::ProofBundle;
+use crate::witness;
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{AdditiveGroup, PrimeField, UniformRand};
+use ark_groth16::r1cs_to_qap::{LibsnarkReduction, R1CSToQAP};
+use ark_groth16::{Proof, ProvingKey};
+use ark_poly::GeneralEvaluationDomain;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use rand_core::{CryptoRng, RngCore};
+use std::future::{self, Future, Ready};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use zeroize::Zeroizing;
+
+/// Stage of proof generation reported to progress callbacks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProvingPhase {
+    /// Constraint synthesis and the R1CS-to-QAP witness map.
+    Witness,
+    /// Multi-scalar multiplication for proof component A.
+    AMsm,
+    /// Multi-scalar multiplications for proof component B (G1 and G2).
+    BMsm,
+    /// Multi-scalar multiplications for proof component C.
+    CMsm,
+    /// Encoding the proof for output.
+    Serialize,
+    /// Proof generation finished.
+    Done,
+}
+
+impl ProvingPhase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Witness => "witness",
+            Self::AMsm => "a-msm",
+            Self::BMsm => "b-msm",
+            Self::CMsm => "c-msm",
+            Self::Serialize => "serialize",
+            Self::Done => "done",
+        }
+    }
+
+    /// Approximate overall completion, in percent, when this phase starts.
+    pub fn percent(&self) -> u8 {
+        match self {
+            Self::Witness => 0,
+            Self::AMsm => 20,
+            Self::BMsm => 40,
+            Self::CMsm => 70,
+            Self::Serialize => 95,
+            Self::Done => 100,
+        }
+    }
+}
+
+/// Generates a Groth16 proof for `circuit`, calling `progress` as each phase starts.
+///
+/// Produces the same proofs as [`super::prove`]; the Groth16 prover is run
+/// stage by stage so that the callback can be invoked between the MSMs.
+/// The [`ProvingPhase::Serialize`] and [`ProvingPhase::Done`] phases are left
+/// to the caller, which owns the output encoding.
+///
+/// # Errors
+/// Returns error if:
+/// - Constraints cannot be generated or are not satisfied
+/// - The witness map cannot be computed
+pub fn prove_with_progress<E, C, R, F>(
+    circuit: C,
+    pk: &ProvingKey<E>,
+    rng: &mut R,
+    progress: F,
+) -> anyhow::Result<ProofBundle<E>>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField> + Clone,
+    R: RngCore + CryptoRng,
+    F: FnMut(ProvingPhase),
+{
+    run_ready(prove_with_checkpoints(
+        circuit,
+        pk,
+        rng,
+        reporting(progress),
+    ))
+}
+
+/// Like [`prove_with_progress`], but awaits `checkpoint` as each phase starts.
+///
+/// This lets async callers yield to their event loop between phases, and
+/// abandon the proof by returning an error from the checkpoint.
+///
+/// # Errors
+/// Returns error if a checkpoint fails, or as [`prove_with_progress`].
+pub async fn prove_with_checkpoints<E, C, R, F, Fut>(
+    circuit: C,
+    pk: &ProvingKey<E>,
+    rng: &mut R,
+    checkpoint: F,
+) -> anyhow::Result<ProofBundle<E>>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField> + Clone,
+    R: RngCore + CryptoRng,
+    F: FnMut(ProvingPhase) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    let r = E::ScalarField::rand(rng);
+    let s = E::ScalarField::rand(rng);
+
+    prove_staged(circuit, pk, r, s, checkpoint).await
+}
+
+/// Stage-by-stage Groth16 prover with explicit blinding factors `(r, s)`.
+#[tracing::instrument(name = "prove", level = "info", skip_all)]
+pub(super) async fn prove_staged<E, C, F, Fut>(
+    circuit: C,
+    pk: &ProvingKey<E>,
+    r: E::ScalarField,
+    s: E::ScalarField,
+    mut checkpoint: F,
+) -> anyhow::Result<ProofBundle<E>>
+where
+    E: Pairing,
+    C: ConstraintSynthesizer<E::ScalarField> + Clone,
+    F: FnMut(ProvingPhase) -> Fut,
+    Fut: Future<Output = anyhow::Result<()>>,
+{
+    // The witness and the blinding factors must not outlive the proof, even
+    // when a checkpoint abandons it
+    let (r, s) = (Zeroizing::new(r), Zeroizing::new(s));
+
+    checkpoint(ProvingPhase::Witness).await?;
+
+    let (witness, matrices) = witness::generate_with_matrices(circuit)?;
+    let full_assignment = Zeroizing::new([witness.instance.as_slice(), &witness.witness].concat());
+
+    let h = Zeroizing::new(tracing::debug_span!("witness_map").in_scope(|| {
+        LibsnarkReduction::witness_map_from_matrices::<
+            E::ScalarField,
+            GeneralEvaluationDomain<E::ScalarField>,
+        >(
+            &matrices,
+            matrices.num_instance_variables,
+            matrices.num_constraints,
+            &full_assignment,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to compute witness map: {}", e))
+    })?);
+
+    let public_inputs = full_assignment[1..matrices.num_instance_variables].to_vec();
+    let assignment = Zeroizing::new(
+        full_assignment[1..]
+            .iter()
+            .map(|x| x.into_bigint())
+            .collect::<Vec<_>>(),
+    );
+
+    checkpoint(ProvingPhase::AMsm).await?;
+    let g_a = tracing::debug_span!("msm", component = "a")
+        .in_scope(|| calculate_coeff(pk.delta_g1 * *r, &pk.a_query, pk.vk.alpha_g1, &assignment));
+
+    checkpoint(ProvingPhase::BMsm).await?;
+    let (g1_b, g2_b) = tracing::debug_span!("msm", component = "b").in_scope(|| {
+        let g1_b = if *r != E::ScalarField::ZERO {
+            calculate_coeff(pk.delta_g1 * *s, &pk.b_g1_query, pk.beta_g1, &assignment)
+        } else {
+            E::G1::ZERO
+        };
+        let g2_b = calculate_coeff(
+            pk.vk.delta_g2 * *s,
+            &pk.b_g2_query,
+            pk.vk.beta_g2,
+            &assignment,
+        );
+        (g1_b, g2_b)
+    });
+
+    checkpoint(ProvingPhase::CMsm).await?;
+    let g_c = tracing::debug_span!("msm", component = "c").in_scope(|| {
+        let h_bigint = Zeroizing::new(h.iter().map(|x| x.into_bigint()).collect::<Vec<_>>());
+        let h_acc = E::G1::msm_bigint(&pk.h_query, &h_bigint);
+        let aux = &assignment[public_inputs.len()..];
+        let l_aux_acc = E::G1::msm_bigint(&pk.l_query, aux);
+        g_a * *s + g1_b * *r - pk.delta_g1 * (*r * *s) + l_aux_acc + h_acc
+    });
+
+    Ok(ProofBundle {
+        proof: Proof {
+            a: g_a.into_affine(),
+            b: g2_b.into_affine(),
+            c: g_c.into_affine(),
+        },
+        public_inputs,
+    })
+}
+
+/// Adapts a synchronous progress callback into a checkpoint that never suspends.
+pub(crate) fn reporting<F>(mut progress: F) -> impl FnMut(ProvingPhase) -> Ready<anyhow::Result<()>>
+where
+    F: FnMut(ProvingPhase),
+{
+    move |phase| {
+        progress(phase);
+        future::ready(Ok(()))
+    }
+}
+
+/// Drives a future whose awaits all complete immediately, such as a proof
+/// whose checkpoints come from [`reporting`].
+pub(crate) fn run_ready<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    match future
+        .as_mut()
+        .poll(&mut Context::from_waker(Waker::noop()))
+    {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("synchronous checkpoints never suspend"),
+    }
+}
+
+/// `initial + query[0] + <query[1..], assignment> + vk_param`, as in ark-groth16.
+fn calculate_coeff<G>(
+    initial: G::Group,
+    query: &[G],
+    vk_param: G,
+    assignment: &[<G::ScalarField as PrimeField>::BigInt],
+) -> G::Group
+where
+    G: AffineRepr,
+    G::Group: VariableBaseMSM<MulBase = G>,
+{
+    initial + query[0] + G::Group::msm_bigint(&query[1..], assignment) + vk_param
+}
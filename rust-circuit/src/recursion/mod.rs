@@ -0,0 +1,126 @@
+use crate::circuit::metadata;
+use crate::prover::{self, InputCountError, ProofBundle};
+use ark_bls12_377::{constraints::PairingVar, Bls12_377};
+use ark_bw6_761::BW6_761;
+use ark_crypto_primitives::snark::constraints::{
+    BooleanInputVar, FromFieldElementsGadget, SNARKGadget,
+};
+use ark_ec::pairing::Pairing;
+use ark_groth16::constraints::{Groth16VerifierGadget, ProofVar, VerifyingKeyVar};
+use ark_groth16::{Proof, VerifyingKey};
+use ark_r1cs_std::prelude::{AllocVar, Boolean, EqGadget};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use ark_std::Zero;
+
+/// The curve the aggregated proofs are made on.
+pub type InnerCurve = Bls12_377;
+
+/// The curve the recursive proof is made on: its scalar field is the base
+/// field of [`InnerCurve`], so BLS12-377 pairings are native arithmetic here.
+pub type OuterCurve = BW6_761;
+
+type InnerFr = <InnerCurve as Pairing>::ScalarField;
+type OuterFr = <OuterCurve as Pairing>::ScalarField;
+type Verifier = Groth16VerifierGadget<InnerCurve, PairingVar>;
+
+/// Proves that `COUNT` Groth16 proofs on BLS12-377 verify against `vk`, so
+/// one BW6-761 proof attests to all of them.
+///
+/// `vk` is compiled into the constraints, so keys are generated per inner
+/// verifying key and proof count. Each inner proof costs about 33,000
+/// constraints.
+#[derive(Debug, Clone)]
+pub struct RecursiveCircuit<const COUNT: usize> {
+    /// Constant: fixed when the keys are generated.
+    pub vk: VerifyingKey<InnerCurve>,
+
+    // Private inputs; their public inputs are also this circuit's
+    pub proofs: Vec<ProofBundle<InnerCurve>>,
+}
+
+metadata! {
+    impl<const COUNT: usize> RecursiveCircuit<COUNT> {
+        name: "recursion",
+        version: "1.0.0",
+    }
+}
+
+impl<const COUNT: usize> RecursiveCircuit<COUNT> {
+    /// Creates an empty circuit for the setup phase.
+    pub fn empty(vk: VerifyingKey<InnerCurve>) -> Self {
+        let inputs = vk.gamma_abc_g1.len().saturating_sub(1);
+        let placeholder = ProofBundle {
+            proof: Proof::default(),
+            public_inputs: vec![InnerFr::zero(); inputs],
+        };
+        Self {
+            vk,
+            proofs: vec![placeholder; COUNT],
+        }
+    }
+
+    /// Creates a circuit aggregating `proofs`.
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - there are not `COUNT` proofs
+    /// - a proof has the wrong number of public inputs for `vk`
+    /// - a proof does not verify against `vk`
+    pub fn new(
+        vk: VerifyingKey<InnerCurve>,
+        proofs: Vec<ProofBundle<InnerCurve>>,
+    ) -> anyhow::Result<Self> {
+        if proofs.len() != COUNT {
+            anyhow::bail!("Expected {} proofs, got {}", COUNT, proofs.len());
+        }
+        for (i, bundle) in proofs.iter().enumerate() {
+            if !prover::verify(bundle, &vk)? {
+                anyhow::bail!("Proof {} does not verify", i);
+            }
+        }
+        Ok(Self { vk, proofs })
+    }
+
+    /// The public inputs of every inner proof, in order, packed into as few
+    /// BW6-761 scalars as fit.
+    pub fn get_public_inputs(&self) -> Vec<OuterFr> {
+        self.proofs
+            .iter()
+            .flat_map(|bundle| {
+                BooleanInputVar::<InnerFr, OuterFr>::repack_input(&bundle.public_inputs)
+            })
+            .collect()
+    }
+}
+
+impl<const COUNT: usize> ConstraintSynthesizer<OuterFr> for RecursiveCircuit<COUNT> {
+    fn generate_constraints(self, cs: ConstraintSystemRef<OuterFr>) -> r1cs::Result<()> {
+        if self.proofs.len() != COUNT
+            || self
+                .proofs
+                .iter()
+                .any(|bundle| InputCountError::check(&self.vk, bundle.public_inputs.len()).is_err())
+        {
+            return Err(r1cs::SynthesisError::Unsatisfiable);
+        }
+
+        let vk = VerifyingKeyVar::<InnerCurve, PairingVar>::new_constant(ns!(cs, "vk"), &self.vk)?;
+        let pvk = vk.prepare()?;
+
+        for bundle in &self.proofs {
+            let inputs =
+                BooleanInputVar::new_input(ns!(cs, "inputs"), || Ok(&bundle.public_inputs))?;
+
+            // Private inputs
+            let proof = ProofVar::new_witness(ns!(cs, "proof"), || Ok(&bundle.proof))?;
+
+            let _ns = ns!(cs, "Groth16.Verify(vk, inputs, proof)");
+            Verifier::verify_with_processed_vk(&pvk, &inputs, &proof)?
+                .enforce_equal(&Boolean::TRUE)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,276 @@
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs;
+use num_bigint::BigUint;
+use num_traits::One;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+
+/// Sponge rate: field elements absorbed per permutation.
+pub const RATE: usize = 2;
+/// Sponge capacity.
+pub const CAPACITY: usize = 1;
+/// Security level, in bits, the round count is chosen for.
+pub const SECURITY_LEVEL: usize = 128;
+
+/// Rescue-Prime parameters shared by the native hash and the gadget.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RescueConfig<F: PrimeField> {
+    pub width: usize,
+    pub capacity: usize,
+    pub rounds: usize,
+    /// S-box exponent, the smallest `alpha >= 3` invertible modulo `p - 1`.
+    pub alpha: u64,
+    /// Inverse S-box exponent, `alpha^-1 mod (p - 1)`, as little-endian limbs.
+    pub alpha_inv: Vec<u64>,
+    pub mds: Vec<Vec<F>>,
+    /// `2 * width` constants per round.
+    pub round_constants: Vec<F>,
+}
+
+impl<F: PrimeField> RescueConfig<F> {
+    /// Generates parameters as the Rescue-Prime reference implementation
+    /// does: rounds from the Gröbner basis bound plus a 50% margin, an MDS
+    /// matrix from a Vandermonde matrix over the field's generator, and round
+    /// constants from SHAKE256 of `Rescue-XLIX(p,m,c,s)`.
+    ///
+    /// # Errors
+    /// Returns error if `capacity` leaves no rate.
+    pub fn new(width: usize, capacity: usize, security_level: usize) -> anyhow::Result<Self> {
+        if capacity == 0 || capacity >= width {
+            anyhow::bail!(
+                "Capacity must be between 1 and {}, got {}",
+                width.saturating_sub(1),
+                capacity
+            );
+        }
+
+        let modulus: BigUint = F::MODULUS.into();
+        let order = &modulus - 1u32;
+        let alpha = (3u64..)
+            .find(|alpha| gcd(*alpha, &order) == 1)
+            .expect("some small exponent is coprime to p - 1");
+        let alpha_inv = BigUint::from(alpha)
+            .modinv(&order)
+            .expect("alpha is coprime to p - 1")
+            .to_u64_digits();
+        let rounds = rounds(width, capacity, security_level, alpha);
+
+        Ok(Self {
+            width,
+            capacity,
+            rounds,
+            alpha,
+            alpha_inv,
+            mds: mds(width),
+            round_constants: round_constants(&modulus, width, capacity, security_level, rounds),
+        })
+    }
+
+    /// The Rescue-XLIX permutation.
+    pub fn permute(&self, state: &mut [F]) {
+        for round in self.round_constants.chunks(2 * self.width) {
+            let (first, second) = round.split_at(self.width);
+            for x in state.iter_mut() {
+                *x = x.pow([self.alpha]);
+            }
+            self.mix(state, first);
+            for x in state.iter_mut() {
+                *x = x.pow(&self.alpha_inv);
+            }
+            self.mix(state, second);
+        }
+    }
+
+    /// In-circuit counterpart of [`RescueConfig::permute`].
+    ///
+    /// Each S-box and inverse S-box costs about `log2(alpha) + 1`
+    /// constraints, the inverse by checking a witnessed root.
+    pub fn permute_gadget(&self, state: &mut [FpVar<F>]) -> r1cs::Result<()> {
+        for round in self.round_constants.chunks(2 * self.width) {
+            let (first, second) = round.split_at(self.width);
+            for x in state.iter_mut() {
+                *x = x.pow_by_constant([self.alpha])?;
+            }
+            self.mix_gadget(state, first);
+            for x in state.iter_mut() {
+                *x = self.root_gadget(x)?;
+            }
+            self.mix_gadget(state, second);
+        }
+        Ok(())
+    }
+
+    /// `state = mds * state + constants`.
+    fn mix(&self, state: &mut [F], constants: &[F]) {
+        let mixed = self
+            .mds
+            .iter()
+            .zip(constants)
+            .map(|(row, constant)| {
+                row.iter()
+                    .zip(state.iter())
+                    .fold(*constant, |acc, (m, x)| acc + *m * x)
+            })
+            .collect::<Vec<_>>();
+        state.clone_from_slice(&mixed);
+    }
+
+    fn mix_gadget(&self, state: &mut [FpVar<F>], constants: &[F]) {
+        let mixed = self
+            .mds
+            .iter()
+            .zip(constants)
+            .map(|(row, constant)| {
+                row.iter()
+                    .zip(state.iter())
+                    .fold(FpVar::constant(*constant), |acc, (m, x)| acc + x * *m)
+            })
+            .collect::<Vec<_>>();
+        state.clone_from_slice(&mixed);
+    }
+
+    /// `x^(1/alpha)`, witnessed and checked by raising it back to `alpha`.
+    fn root_gadget(&self, x: &FpVar<F>) -> r1cs::Result<FpVar<F>> {
+        if let FpVar::Constant(x) = x {
+            return Ok(FpVar::constant(x.pow(&self.alpha_inv)));
+        }
+        let root = FpVar::new_witness(x.cs(), || Ok(x.value()?.pow(&self.alpha_inv)))?;
+        root.pow_by_constant([self.alpha - 1])?
+            .mul_equals(&root, x)?;
+        Ok(root)
+    }
+}
+
+/// Parameters used by [`hash`]: width 3, like [`crate::poseidon`], at 128-bit
+/// security. Digests are this crate's own; no other library is matched.
+pub fn config<F: PrimeField>() -> RescueConfig<F> {
+    RescueConfig::new(RATE + CAPACITY, CAPACITY, SECURITY_LEVEL)
+        .expect("default parameters are valid")
+}
+
+/// Hashes `inputs` natively: pads with a one and zeros to a whole number of
+/// blocks, absorbs them and squeezes one element.
+pub fn hash<F: PrimeField>(inputs: &[F]) -> F {
+    let config = config::<F>();
+    let mut state = vec![F::ZERO; config.width];
+    for block in pad(inputs.to_vec(), F::ONE, F::ZERO).chunks(RATE) {
+        for (x, input) in state.iter_mut().zip(block) {
+            *x += input;
+        }
+        config.permute(&mut state);
+    }
+    state[0]
+}
+
+/// In-circuit counterpart of [`hash`], producing the same digest.
+pub fn hash_gadget<F: PrimeField>(inputs: &[FpVar<F>]) -> r1cs::Result<FpVar<F>> {
+    let config = config::<F>();
+    let mut state = vec![FpVar::zero(); config.width];
+    for block in pad(inputs.to_vec(), FpVar::one(), FpVar::zero()).chunks(RATE) {
+        for (x, input) in state.iter_mut().zip(block) {
+            *x += input;
+        }
+        config.permute_gadget(&mut state)?;
+    }
+    Ok(state.swap_remove(0))
+}
+
+fn pad<T: Clone>(mut inputs: Vec<T>, one: T, zero: T) -> Vec<T> {
+    inputs.push(one);
+    while !inputs.len().is_multiple_of(RATE) {
+        inputs.push(zero.clone());
+    }
+    inputs
+}
+
+/// Rounds resisting Gröbner basis attacks, plus 50% and at least 8.
+fn rounds(width: usize, capacity: usize, security_level: usize, alpha: u64) -> usize {
+    let rate = width - capacity;
+    let target = BigUint::one() << security_level;
+    let bound = (1..25)
+        .find(|&n| {
+            let degree = (alpha as usize - 1) * width * (n - 1) / 2 + 2;
+            let variables = width * (n - 1) + rate;
+            let binomial = binomial(variables + degree, variables);
+            &binomial * &binomial > target
+        })
+        .unwrap_or(24);
+    (3 * bound.max(5)).div_ceil(2)
+}
+
+fn binomial(n: usize, k: usize) -> BigUint {
+    (0..k).fold(BigUint::one(), |acc, i| acc * (n - i) / (i + 1))
+}
+
+fn gcd(a: u64, b: &BigUint) -> u64 {
+    let (mut a, mut b) = (a, (b % a).try_into().unwrap_or(0u64));
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The transposed right half of the reduced echelon form of the `width x
+/// 2 * width` Vandermonde matrix `V[i][j] = g^(i * j)`.
+fn mds<F: PrimeField>(width: usize) -> Vec<Vec<F>> {
+    let g = F::GENERATOR;
+    let mut v = (0..width)
+        .map(|i| {
+            (0..2 * width)
+                .map(|j| g.pow([(i * j) as u64]))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // The left half is an invertible Vandermonde matrix, so every pivot is
+    // found in its own column
+    for col in 0..width {
+        let pivot = (col..width)
+            .find(|&row| v[row][col] != F::ZERO)
+            .expect("Vandermonde matrices are invertible");
+        v.swap(col, pivot);
+        let inverse = v[col][col].inverse().expect("pivots are non-zero");
+        for x in &mut v[col] {
+            *x *= inverse;
+        }
+        for row in 0..width {
+            if row != col {
+                let factor = v[row][col];
+                let pivot_row = v[col].clone();
+                for (x, p) in v[row].iter_mut().zip(pivot_row) {
+                    *x -= factor * p;
+                }
+            }
+        }
+    }
+
+    (0..width)
+        .map(|i| (0..width).map(|j| v[j][width + i]).collect())
+        .collect()
+}
+
+fn round_constants<F: PrimeField>(
+    modulus: &BigUint,
+    width: usize,
+    capacity: usize,
+    security_level: usize,
+    rounds: usize,
+) -> Vec<F> {
+    let bytes_per_int = (F::MODULUS_BIT_SIZE as usize).div_ceil(8) + 1;
+    let seed = format!(
+        "Rescue-XLIX({},{},{},{})",
+        modulus, width, capacity, security_level
+    );
+    let mut shake = Shake256::default();
+    shake.update(seed.as_bytes());
+    let mut bytes = vec![0u8; bytes_per_int * 2 * width * rounds];
+    shake.finalize_xof().read(&mut bytes);
+
+    bytes
+        .chunks(bytes_per_int)
+        .map(F::from_le_bytes_mod_order)
+        .collect()
+}
@@ -0,0 +1,122 @@
+use ark_ec::{
+    models::CurveConfig,
+    short_weierstrass::{self, SWCurveConfig},
+    AffineRepr, CurveGroup,
+};
+use ark_ff::{
+    fields::{Fp256, MontBackend, MontConfig},
+    AdditiveGroup, BigInteger, Field, MontFp, PrimeField, UniformRand, Zero,
+};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::Zeroize;
+
+#[derive(MontConfig)]
+#[modulus = "115792089237316195423570985008687907853269984665640564039457584007908834671663"]
+#[generator = "3"]
+pub struct FqConfig;
+
+/// Base field of secp256k1, over which curve coordinates are defined.
+pub type Fq = Fp256<MontBackend<FqConfig, 4>>;
+
+#[derive(MontConfig)]
+#[modulus = "115792089237316195423570985008687907852837564279074904382605163141518161494337"]
+#[generator = "7"]
+pub struct FrConfig;
+
+/// Scalar field of secp256k1: private keys and signature components.
+pub type Fr = Fp256<MontBackend<FrConfig, 4>>;
+
+/// secp256k1, `y^2 = x^3 + 7`, the curve of Ethereum and Bitcoin keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Config;
+
+pub type Affine = short_weierstrass::Affine<Config>;
+pub type Projective = short_weierstrass::Projective<Config>;
+
+impl CurveConfig for Config {
+    type BaseField = Fq;
+    type ScalarField = Fr;
+
+    const COFACTOR: &'static [u64] = &[1];
+    const COFACTOR_INV: Fr = Fr::ONE;
+}
+
+impl SWCurveConfig for Config {
+    const COEFF_A: Fq = Fq::ZERO;
+    const COEFF_B: Fq = MontFp!("7");
+    const GENERATOR: Affine = Affine::new_unchecked(
+        MontFp!("55066263022277343669578718895168534326250603453777594175500187360389116729240"),
+        MontFp!("32670510020758816978083085130507043184471273380659243275938904335757337482424"),
+    );
+
+    fn mul_by_a(_: Fq) -> Fq {
+        Fq::ZERO
+    }
+}
+
+/// An ECDSA signature `(r, s)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    pub r: Fr,
+    pub s: Fr,
+}
+
+impl Zeroize for Signature {
+    fn zeroize(&mut self) {
+        self.r.zeroize();
+        self.s.zeroize();
+    }
+}
+
+/// The scalar ECDSA signs for a 32-byte message digest (e.g. Keccak-256 for
+/// Ethereum, double SHA-256 for Bitcoin): the digest as a big-endian integer
+/// reduced modulo the group order.
+pub fn message_scalar(digest: &[u8; 32]) -> Fr {
+    Fr::from_be_bytes_mod_order(digest)
+}
+
+pub fn public_key(secret_key: &Fr) -> Affine {
+    (Affine::generator() * secret_key).into_affine()
+}
+
+/// Signs `digest` with a random nonce.
+///
+/// Meant for tests and fixtures; wallets should sign with RFC 6979 nonces.
+pub fn sign<R: RngCore + CryptoRng>(secret_key: &Fr, digest: &[u8; 32], rng: &mut R) -> Signature {
+    loop {
+        let k = Fr::rand(rng);
+        let Some(k_inv) = k.inverse() else {
+            continue;
+        };
+        let Some((x, _)) = (Affine::generator() * k).into_affine().xy() else {
+            continue;
+        };
+
+        let r = x_scalar(&x);
+        let s = k_inv * (message_scalar(digest) + r * secret_key);
+        if !r.is_zero() && !s.is_zero() {
+            return Signature { r, s };
+        }
+    }
+}
+
+/// Verifies an ECDSA signature natively.
+pub fn verify(public_key: &Affine, digest: &[u8; 32], signature: &Signature) -> bool {
+    let Some(s_inv) = signature.s.inverse() else {
+        return false;
+    };
+    if signature.r.is_zero() || public_key.is_zero() {
+        return false;
+    }
+
+    let u1 = message_scalar(digest) * s_inv;
+    let u2 = signature.r * s_inv;
+    let point = (Affine::generator() * u1 + *public_key * u2).into_affine();
+
+    point.xy().is_some_and(|(x, _)| x_scalar(&x) == signature.r)
+}
+
+/// The x-coordinate of a point reduced modulo the group order, as in `r`.
+fn x_scalar(x: &Fq) -> Fr {
+    Fr::from_le_bytes_mod_order(&x.into_bigint().to_bytes_le())
+}
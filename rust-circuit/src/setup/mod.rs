@@ -0,0 +1,331 @@
+use crate::circom::{self, ptau};
+use crate::curve::SupportedCurve;
+use crate::keys;
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{Field, UniformRand, Zero};
+use ark_groth16::{ProvingKey, VerifyingKey};
+use ark_poly::{EvaluationDomain, GeneralEvaluationDomain};
+use ark_relations::r1cs::ConstraintSynthesizer;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand_chacha::ChaCha20Rng;
+use rand_core::{CryptoRng, RngCore, SeedableRng};
+use sha2::{Digest, Sha256};
+
+/// Public record of one Groth16 phase-2 contribution.
+///
+/// The contributor multiplies delta by a secret `d` and divides the L and H
+/// queries by it. `s_delta = s·d` and `r_delta = r·d`, with `r` hashed from
+/// the transcript, prove knowledge of `d` without revealing it.
+#[derive(Debug, Clone, PartialEq, CanonicalSerialize, CanonicalDeserialize)]
+pub struct Contribution<E: Pairing> {
+    /// Transcript hash before this contribution.
+    pub previous_hash: [u8; 32],
+    /// `delta_g1` after this contribution.
+    pub delta_after: E::G1Affine,
+    pub s: E::G1Affine,
+    pub s_delta: E::G1Affine,
+    pub r_delta: E::G2Affine,
+}
+
+impl<E: Pairing> Contribution<E> {
+    /// Transcript hash after this contribution; the next contribution builds on it.
+    pub fn hash(&self) -> anyhow::Result<[u8; 32]> {
+        let mut bytes = Vec::new();
+        self.serialize_compressed(&mut bytes)
+            .map_err(|e| anyhow::anyhow!("Failed to serialize contribution: {}", e))?;
+
+        Ok(Sha256::digest(bytes).into())
+    }
+}
+
+/// Transcript hash a ceremony starts from: the fingerprint of the compressed
+/// initial proving key.
+///
+/// # Errors
+/// Returns error if the key cannot be serialized.
+pub fn initial_hash<E: Pairing>(pk: &ProvingKey<E>) -> anyhow::Result<[u8; 32]> {
+    let mut bytes = Vec::new();
+    pk.serialize_compressed(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize proving key: {}", e))?;
+
+    let mut hash = [0u8; 32];
+    hex::decode_to_slice(keys::fingerprint(&bytes), &mut hash)?;
+    Ok(hash)
+}
+
+/// Derives the initial phase-2 proving key for `circuit` from a phase-1
+/// `.ptau` file, as `snarkjs groth16 setup` does.
+///
+/// gamma and delta start out as the generators, so the key is only safe to
+/// use after at least one [`contribute`] round.
+///
+/// # Errors
+/// Returns error if constraint generation fails or the ptau file is invalid
+/// or too small for the circuit.
+pub fn from_ptau<E, C>(circuit: C, ptau_bytes: &[u8]) -> anyhow::Result<ProvingKey<E>>
+where
+    E: SupportedCurve,
+    C: ConstraintSynthesizer<E::ScalarField>,
+{
+    let matrices = circom::synthesize_matrices(circuit)?;
+    let num_instance = matrices.num_instance_variables;
+    let num_vars = num_instance + matrices.num_witness_variables;
+
+    // Same domain as the prover's witness map
+    let domain =
+        GeneralEvaluationDomain::<E::ScalarField>::new(matrices.num_constraints + num_instance)
+            .ok_or_else(|| anyhow::anyhow!("Circuit is too large for the scalar field"))?;
+    let n = domain.size();
+    let powers = ptau::read_ptau::<E>(ptau_bytes, n)?;
+
+    // [L_k(τ)] for the Lagrange polynomials of the domain, by inverse FFT of [τ^k]
+    let lagrange = |points: &[E::G1Affine]| {
+        domain.ifft(
+            &points[..n]
+                .iter()
+                .map(|p| p.into_group())
+                .collect::<Vec<_>>(),
+        )
+    };
+    let l_tau_g1 = lagrange(&powers.tau_g1);
+    let l_alpha_g1 = lagrange(&powers.alpha_tau_g1);
+    let l_beta_g1 = lagrange(&powers.beta_tau_g1);
+    let l_tau_g2 = domain.ifft(
+        &powers
+            .tau_g2
+            .iter()
+            .map(|p| p.into_group())
+            .collect::<Vec<_>>(),
+    );
+
+    // a_i(τ), b_i(τ) and β·a_i(τ) + α·b_i(τ) + c_i(τ) for every variable
+    let mut a = vec![E::G1::zero(); num_vars];
+    let mut b_g1 = vec![E::G1::zero(); num_vars];
+    let mut b_g2 = vec![E::G2::zero(); num_vars];
+    let mut abc = vec![E::G1::zero(); num_vars];
+    for (k, row) in matrices.a.iter().enumerate() {
+        for (coeff, var) in row {
+            a[*var] += l_tau_g1[k] * coeff;
+            abc[*var] += l_beta_g1[k] * coeff;
+        }
+    }
+    for (k, row) in matrices.b.iter().enumerate() {
+        for (coeff, var) in row {
+            b_g1[*var] += l_tau_g1[k] * coeff;
+            b_g2[*var] += l_tau_g2[k] * coeff;
+            abc[*var] += l_alpha_g1[k] * coeff;
+        }
+    }
+    for (k, row) in matrices.c.iter().enumerate() {
+        for (coeff, var) in row {
+            abc[*var] += l_tau_g1[k] * coeff;
+        }
+    }
+    // Instance variables also appear in one extra A row each, as in ark-groth16
+    for i in 0..num_instance {
+        let k = matrices.num_constraints + i;
+        a[i] += l_tau_g1[k];
+        abc[i] += l_beta_g1[k];
+    }
+
+    // τ^j·Z(τ) = τ^(n+j) - τ^j, as Z(x) = x^n - 1
+    let h = (0..n - 1)
+        .map(|j| powers.tau_g1[n + j].into_group() - powers.tau_g1[j])
+        .collect::<Vec<_>>();
+
+    let abc = E::G1::normalize_batch(&abc);
+    Ok(ProvingKey {
+        vk: VerifyingKey {
+            alpha_g1: powers.alpha_tau_g1[0],
+            beta_g2: powers.beta_g2,
+            gamma_g2: powers.tau_g2[0],
+            delta_g2: powers.tau_g2[0],
+            gamma_abc_g1: abc[..num_instance].to_vec(),
+        },
+        beta_g1: powers.beta_tau_g1[0],
+        delta_g1: powers.tau_g1[0],
+        a_query: E::G1::normalize_batch(&a),
+        b_g1_query: E::G1::normalize_batch(&b_g1),
+        b_g2_query: E::G2::normalize_batch(&b_g2),
+        h_query: E::G1::normalize_batch(&h),
+        l_query: abc[num_instance..].to_vec(),
+    })
+}
+
+/// Adds a contribution to `pk` in place, randomizing delta with a secret
+/// drawn from `rng` and discarded on return.
+///
+/// `previous_hash` is [`initial_hash`] for the first contribution and the
+/// previous [`Contribution::hash`] afterwards.
+pub fn contribute<E, R>(
+    pk: &mut ProvingKey<E>,
+    previous_hash: [u8; 32],
+    rng: &mut R,
+) -> Contribution<E>
+where
+    E: Pairing,
+    R: RngCore + CryptoRng,
+{
+    let d = loop {
+        let d = E::ScalarField::rand(rng);
+        if !d.is_zero() {
+            break d;
+        }
+    };
+    let d_inv = d.inverse().expect("d is non-zero");
+
+    pk.delta_g1 = (pk.delta_g1 * d).into_affine();
+    pk.vk.delta_g2 = (pk.vk.delta_g2 * d).into_affine();
+    pk.l_query = scale(&pk.l_query, d_inv);
+    pk.h_query = scale(&pk.h_query, d_inv);
+
+    let s = E::G1::rand(rng).into_affine();
+    let s_delta = (s * d).into_affine();
+    let r = hash_to_g2::<E>(&previous_hash, &s, &s_delta);
+
+    Contribution {
+        previous_hash,
+        delta_after: pk.delta_g1,
+        s,
+        s_delta,
+        r_delta: (r * d).into_affine(),
+    }
+}
+
+/// Returns the contribution `after_transcript` adds to `before_transcript`,
+/// checking that it extends it by exactly one contribution made on top of
+/// `before`, the key the old transcript ends with.
+///
+/// Pass the result to [`verify_contribution`] to check the keys themselves.
+///
+/// # Errors
+/// Returns error if the new transcript is empty, does not extend the old
+/// one, or its last contribution builds on another transcript hash.
+pub fn next_contribution<'a, E: Pairing>(
+    before: &ProvingKey<E>,
+    before_transcript: &[Contribution<E>],
+    after_transcript: &'a [Contribution<E>],
+) -> anyhow::Result<&'a Contribution<E>> {
+    let Some((contribution, history)) = after_transcript.split_last() else {
+        anyhow::bail!("Transcript has no contributions");
+    };
+    if history != before_transcript {
+        anyhow::bail!("Transcript does not extend the previous one");
+    }
+    let previous_hash = match history.last() {
+        Some(previous) => previous.hash()?,
+        None => initial_hash(before)?,
+    };
+    if contribution.previous_hash != previous_hash {
+        anyhow::bail!("Contribution was not made on top of the previous key");
+    }
+
+    Ok(contribution)
+}
+
+/// Checks that `after` is `before` with `contribution` applied.
+///
+/// Returns `false` if the keys differ in anything but delta and the L/H
+/// queries, or if the proof of knowledge or the rescaling does not check out.
+/// `rng` only drives the batched L/H check.
+pub fn verify_contribution<E, R>(
+    before: &ProvingKey<E>,
+    after: &ProvingKey<E>,
+    contribution: &Contribution<E>,
+    rng: &mut R,
+) -> bool
+where
+    E: Pairing,
+    R: RngCore,
+{
+    let unchanged = before.vk.alpha_g1 == after.vk.alpha_g1
+        && before.vk.beta_g2 == after.vk.beta_g2
+        && before.vk.gamma_g2 == after.vk.gamma_g2
+        && before.vk.gamma_abc_g1 == after.vk.gamma_abc_g1
+        && before.beta_g1 == after.beta_g1
+        && before.a_query == after.a_query
+        && before.b_g1_query == after.b_g1_query
+        && before.b_g2_query == after.b_g2_query
+        && before.l_query.len() == after.l_query.len()
+        && before.h_query.len() == after.h_query.len();
+    if !unchanged || contribution.delta_after != after.delta_g1 {
+        return false;
+    }
+    if contribution.s.is_zero() || after.delta_g1.is_zero() {
+        return false;
+    }
+
+    // Proof of knowledge of d: s -> s·d and r -> r·d use the same factor...
+    let r = hash_to_g2::<E>(
+        &contribution.previous_hash,
+        &contribution.s,
+        &contribution.s_delta,
+    );
+    if !same_ratio::<E>(
+        (contribution.s, contribution.s_delta),
+        (r, contribution.r_delta),
+    ) {
+        return false;
+    }
+
+    // ...and so do delta in G1 and in G2
+    if !same_ratio::<E>((before.delta_g1, after.delta_g1), (r, contribution.r_delta))
+        || !same_ratio::<E>(
+            (before.delta_g1, after.delta_g1),
+            (before.vk.delta_g2, after.vk.delta_g2),
+        )
+    {
+        return false;
+    }
+
+    // L and H are divided by d; check a random linear combination of each
+    for (old, new) in [
+        (&before.l_query, &after.l_query),
+        (&before.h_query, &after.h_query),
+    ] {
+        let rho = (0..old.len())
+            .map(|_| E::ScalarField::rand(rng))
+            .collect::<Vec<_>>();
+        let old = E::G1::msm_unchecked(old, &rho).into_affine();
+        let new = E::G1::msm_unchecked(new, &rho).into_affine();
+        if !same_ratio::<E>((new, old), (before.vk.delta_g2, after.vk.delta_g2)) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// `e(g1.0, g2.1) == e(g1.1, g2.0)`, i.e. both pairs differ by the same factor.
+fn same_ratio<E: Pairing>(g1: (E::G1Affine, E::G1Affine), g2: (E::G2Affine, E::G2Affine)) -> bool {
+    E::pairing(g1.0, g2.1) == E::pairing(g1.1, g2.0)
+}
+
+/// Derives the G2 point the proof of knowledge is checked against, so that
+/// its discrete logarithm is unknown to the contributor.
+fn hash_to_g2<E: Pairing>(
+    previous_hash: &[u8; 32],
+    s: &E::G1Affine,
+    s_delta: &E::G1Affine,
+) -> E::G2Affine {
+    let mut bytes = Vec::new();
+    (*s, *s_delta)
+        .serialize_compressed(&mut bytes)
+        .expect("serializing into a Vec cannot fail");
+    let seed: [u8; 32] = Sha256::new()
+        .chain_update(previous_hash)
+        .chain_update(bytes)
+        .finalize()
+        .into();
+
+    E::G2::rand(&mut ChaCha20Rng::from_seed(seed)).into_affine()
+}
+
+fn scale<G: AffineRepr>(points: &[G], factor: G::ScalarField) -> Vec<G> {
+    let scaled = points
+        .iter()
+        .map(|point| *point * factor)
+        .collect::<Vec<_>>();
+    G::Group::normalize_batch(&scaled)
+}
@@ -0,0 +1,179 @@
+use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use ark_groth16::{Proof, VerifyingKey};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Arguments to `verifyProof` in the contract from [`verifier_contract`], as
+/// 32-byte `0x`-hex words accepted by ethers, viem and `cast`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Calldata {
+    /// `[a.x, a.y, b.x.c1, b.x.c0, b.y.c1, b.y.c0, c.x, c.y]`; the EVM pairing
+    /// precompile takes G2 coordinates with the imaginary part first.
+    pub proof: [String; 8],
+    /// Public inputs in the order allocated by `generate_constraints()`.
+    pub inputs: Vec<String>,
+}
+
+/// Encodes a BN254 proof and its public inputs in the `uint256[8]` /
+/// `uint256[]` layout expected by `verifyProof`.
+pub fn encode_calldata(proof: &Proof<Bn254>, inputs: &[Fr]) -> Calldata {
+    let [ax, ay] = g1_words(&proof.a);
+    let [[bx1, bx0], [by1, by0]] = g2_words(&proof.b);
+    let [cx, cy] = g1_words(&proof.c);
+
+    Calldata {
+        proof: [ax, ay, bx1, bx0, by1, by0, cx, cy].map(|word| word_hex(&word)),
+        inputs: inputs
+            .iter()
+            .map(|input| word_hex(&input.into_bigint().to_bytes_be()))
+            .collect(),
+    }
+}
+
+/// Renders a Solidity Groth16 verifier for `vk`.
+///
+/// The contract checks `e(-A, B) · e(α, β) · e(vk_x, γ) · e(C, δ) = 1` with
+/// the EIP-196/197 precompiles, so it only supports BN254.
+pub fn verifier_contract(vk: &VerifyingKey<Bn254>) -> String {
+    let mut constants = String::new();
+    push_g1(&mut constants, "ALPHA", &vk.alpha_g1);
+    push_g2(&mut constants, "BETA", &vk.beta_g2);
+    push_g2(&mut constants, "GAMMA", &vk.gamma_g2);
+    push_g2(&mut constants, "DELTA", &vk.delta_g2);
+    for (i, point) in vk.gamma_abc_g1.iter().enumerate() {
+        push_g1(&mut constants, &format!("IC{}", i), point);
+    }
+
+    let mut accumulate = String::new();
+    for i in 1..vk.gamma_abc_g1.len() {
+        writeln!(
+            accumulate,
+            "        vkX = ecAdd(vkX, ecMul([IC{i}_X, IC{i}_Y], input[{}]));",
+            i - 1
+        )
+        .expect("writing to a String cannot fail");
+    }
+
+    CONTRACT_TEMPLATE
+        .replace("{{CONSTANTS}}", constants.trim_end())
+        .replace("{{NUM_INPUTS}}", &(vk.gamma_abc_g1.len() - 1).to_string())
+        .replace("{{ACCUMULATE}}", accumulate.trim_end())
+}
+
+const CONTRACT_TEMPLATE: &str = r#"// SPDX-License-Identifier: MIT
+// Generated by the vortex `export solidity` command. Do not edit.
+pragma solidity ^0.8.20;
+
+contract Verifier {
+    // Base field and scalar field moduli of BN254
+    uint256 constant Q = 21888242871839275222246405745257275088696311157297823662689037894645226208583;
+    uint256 constant R = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+{{CONSTANTS}}
+
+    uint256 constant NUM_INPUTS = {{NUM_INPUTS}};
+
+    /// proof = [a.x, a.y, b.x.c1, b.x.c0, b.y.c1, b.y.c0, c.x, c.y]
+    function verifyProof(uint256[8] calldata proof, uint256[] calldata input) public view returns (bool) {
+        require(input.length == NUM_INPUTS, "Verifier: wrong number of inputs");
+        for (uint256 i = 0; i < input.length; i++) {
+            require(input[i] < R, "Verifier: input not in scalar field");
+        }
+
+        uint256[2] memory vkX = [IC0_X, IC0_Y];
+{{ACCUMULATE}}
+
+        uint256[24] memory pairing = [
+            proof[0], (Q - proof[1] % Q) % Q, proof[2], proof[3], proof[4], proof[5],
+            ALPHA_X, ALPHA_Y, BETA_X1, BETA_X0, BETA_Y1, BETA_Y0,
+            vkX[0], vkX[1], GAMMA_X1, GAMMA_X0, GAMMA_Y1, GAMMA_Y0,
+            proof[6], proof[7], DELTA_X1, DELTA_X0, DELTA_Y1, DELTA_Y0
+        ];
+        uint256[1] memory out;
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x08, pairing, 0x300, out, 0x20)
+        }
+        return ok && out[0] == 1;
+    }
+
+    function ecAdd(uint256[2] memory p, uint256[2] memory q) internal view returns (uint256[2] memory r) {
+        uint256[4] memory data = [p[0], p[1], q[0], q[1]];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x06, data, 0x80, r, 0x40)
+        }
+        require(ok, "Verifier: ecAdd failed");
+    }
+
+    function ecMul(uint256[2] memory p, uint256 s) internal view returns (uint256[2] memory r) {
+        uint256[3] memory data = [p[0], p[1], s];
+        bool ok;
+        assembly {
+            ok := staticcall(gas(), 0x07, data, 0x60, r, 0x40)
+        }
+        require(ok, "Verifier: ecMul failed");
+    }
+}
+"#;
+
+/// Affine coordinates as big-endian words; the identity is `(0, 0)` on the EVM.
+fn g1_words(point: &G1Affine) -> [Vec<u8>; 2] {
+    match point.xy() {
+        Some((x, y)) => [fq_bytes(&x), fq_bytes(&y)],
+        None => [vec![0], vec![0]],
+    }
+}
+
+/// `[[x.c1, x.c0], [y.c1, y.c0]]`, the coordinate order of the pairing precompile.
+fn g2_words(point: &G2Affine) -> [[Vec<u8>; 2]; 2] {
+    match point.xy() {
+        Some((x, y)) => [
+            [fq_bytes(&x.c1), fq_bytes(&x.c0)],
+            [fq_bytes(&y.c1), fq_bytes(&y.c0)],
+        ],
+        None => [[vec![0], vec![0]], [vec![0], vec![0]]],
+    }
+}
+
+fn fq_bytes(value: &Fq) -> Vec<u8> {
+    value.into_bigint().to_bytes_be()
+}
+
+/// A big-endian integer as a left-padded 32-byte hex word.
+fn word_hex(be_bytes: &[u8]) -> String {
+    let mut word = [0u8; 32];
+    word[32 - be_bytes.len()..].copy_from_slice(be_bytes);
+    format!("0x{}", hex::encode(word))
+}
+
+fn word_decimal(be_bytes: &[u8]) -> String {
+    num_bigint::BigUint::from_bytes_be(be_bytes).to_string()
+}
+
+fn push_g1(out: &mut String, name: &str, point: &G1Affine) {
+    let [x, y] = g1_words(point);
+    push_constant(out, &format!("{}_X", name), &x);
+    push_constant(out, &format!("{}_Y", name), &y);
+}
+
+fn push_g2(out: &mut String, name: &str, point: &G2Affine) {
+    let [[x1, x0], [y1, y0]] = g2_words(point);
+    push_constant(out, &format!("{}_X1", name), &x1);
+    push_constant(out, &format!("{}_X0", name), &x0);
+    push_constant(out, &format!("{}_Y1", name), &y1);
+    push_constant(out, &format!("{}_Y0", name), &y0);
+}
+
+fn push_constant(out: &mut String, name: &str, be_bytes: &[u8]) {
+    writeln!(
+        out,
+        "    uint256 constant {} = {};",
+        name,
+        word_decimal(be_bytes)
+    )
+    .expect("writing to a String cannot fail");
+}
@@ -0,0 +1,30 @@
+/// Pure `vector<u8>` arguments for `sui::groth16`, BCS-encoded so they can be
+/// passed to a programmable transaction as is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BcsProof {
+    /// Argument to `groth16::proof_points_from_bytes`.
+    pub proof_points_bytes: Vec<u8>,
+    /// Argument to `groth16::public_proof_inputs_from_bytes`.
+    pub public_inputs_bytes: Vec<u8>,
+}
+
+/// BCS encoding of a `vector<u8>`: the ULEB128 length followed by the bytes.
+pub fn encode_bytes(bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(bytes.len() + 5);
+    write_uleb128(&mut encoded, bytes.len());
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+/// Appends `value` as ULEB128, the encoding BCS uses for lengths and enum tags.
+pub fn write_uleb128(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
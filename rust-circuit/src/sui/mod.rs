@@ -0,0 +1,4 @@
+pub mod bcs;
+#[cfg(feature = "sui-tx")]
+pub mod tx;
+pub mod verifier;
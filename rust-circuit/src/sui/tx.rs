@@ -0,0 +1,106 @@
+use super::bcs::{self, BcsProof};
+use crate::api::ProofOutput;
+use std::str::FromStr;
+
+/// A `package::module::function` to call with a proof.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveCallTarget {
+    /// Package object ID, left-padded to 32 bytes.
+    pub package: [u8; 32],
+    pub module: String,
+    pub function: String,
+}
+
+impl FromStr for MoveCallTarget {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let [package, module, function] = s.split("::").collect::<Vec<_>>()[..] else {
+            anyhow::bail!("Move call target '{}' must be package::module::function", s);
+        };
+
+        let digits = package
+            .strip_prefix("0x")
+            .ok_or_else(|| anyhow::anyhow!("Package ID '{}' must start with 0x", package))?;
+        if digits.is_empty() || digits.len() > 64 {
+            anyhow::bail!("Package ID '{}' must be 1 to 32 bytes of hex", package);
+        }
+        let bytes = hex::decode(format!("{:0>64}", digits))
+            .map_err(|e| anyhow::anyhow!("Failed to decode package ID '{}': {}", package, e))?;
+        let mut package = [0u8; 32];
+        package.copy_from_slice(&bytes);
+
+        for name in [module, function] {
+            let mut chars = name.chars();
+            let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !valid {
+                anyhow::bail!("Invalid Move identifier '{}'", name);
+            }
+        }
+
+        Ok(Self {
+            package,
+            module: module.to_string(),
+            function: function.to_string(),
+        })
+    }
+}
+
+/// BCS-encoded pure arguments `(proof_points, public_inputs)`, in call order.
+///
+/// # Errors
+/// Returns error if the serialized hex fields of `output` are not valid hex.
+pub fn pure_arguments(output: &ProofOutput) -> anyhow::Result<Vec<Vec<u8>>> {
+    let BcsProof {
+        proof_points_bytes,
+        public_inputs_bytes,
+    } = output.to_bcs()?;
+
+    Ok(vec![proof_points_bytes, public_inputs_bytes])
+}
+
+/// BCS-encoded `TransactionKind::ProgrammableTransaction` with a single move
+/// call of `target(proof_points, public_inputs)`.
+///
+/// The bytes carry no sender, gas or expiration, so they can be loaded with
+/// `Transaction.fromKind` in the TypeScript SDK and signed there. `target`
+/// must take exactly the two byte vectors, like `assert_valid` in modules
+/// generated by `export sui-move`.
+///
+/// # Errors
+/// Returns error if the serialized hex fields of `output` are not valid hex.
+pub fn transaction_kind(output: &ProofOutput, target: &MoveCallTarget) -> anyhow::Result<Vec<u8>> {
+    let arguments = pure_arguments(output)?;
+
+    let mut bytes = Vec::new();
+    bcs::write_uleb128(&mut bytes, TRANSACTION_KIND_PROGRAMMABLE);
+
+    // inputs: Vec<CallArg>
+    bcs::write_uleb128(&mut bytes, arguments.len());
+    for argument in &arguments {
+        bcs::write_uleb128(&mut bytes, CALL_ARG_PURE);
+        bytes.extend(bcs::encode_bytes(argument));
+    }
+
+    // commands: Vec<Command>
+    bcs::write_uleb128(&mut bytes, 1);
+    bcs::write_uleb128(&mut bytes, COMMAND_MOVE_CALL);
+    bytes.extend_from_slice(&target.package);
+    bytes.extend(bcs::encode_bytes(target.module.as_bytes()));
+    bytes.extend(bcs::encode_bytes(target.function.as_bytes()));
+    bcs::write_uleb128(&mut bytes, 0); // type arguments
+    bcs::write_uleb128(&mut bytes, arguments.len());
+    for index in 0..arguments.len() as u16 {
+        bcs::write_uleb128(&mut bytes, ARGUMENT_INPUT);
+        bytes.extend(index.to_le_bytes());
+    }
+
+    Ok(bytes)
+}
+
+// Enum variant indices from `sui_types::transaction`
+const TRANSACTION_KIND_PROGRAMMABLE: usize = 0;
+const CALL_ARG_PURE: usize = 0;
+const COMMAND_MOVE_CALL: usize = 0;
+const ARGUMENT_INPUT: usize = 1;
@@ -0,0 +1,157 @@
+use crate::curve::{Curve, SupportedCurve};
+use ark_ec::pairing::Pairing;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_groth16::VerifyingKey;
+use ark_serialize::CanonicalSerialize;
+
+/// The four byte vectors `sui::groth16::pvk_from_bytes` builds a prepared
+/// verifying key from, so the chain does not redo the preparation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreparedVerifyingKeyBytes {
+    /// Compressed `gamma_abc_g1` points, concatenated.
+    pub vk_gamma_abc_g1: Vec<u8>,
+    /// Compressed `e(alpha_g1, beta_g2)`.
+    pub alpha_g1_beta_g2: Vec<u8>,
+    /// Compressed `-gamma_g2`.
+    pub gamma_g2_neg_pc: Vec<u8>,
+    /// Compressed `-delta_g2`.
+    pub delta_g2_neg_pc: Vec<u8>,
+}
+
+impl PreparedVerifyingKeyBytes {
+    /// Prepares `vk` in the layout of fastcrypto's `PreparedVerifyingKey::serialize_into_parts`.
+    ///
+    /// # Errors
+    /// Returns error if a component cannot be serialized.
+    pub fn new<E: Pairing>(vk: &VerifyingKey<E>) -> anyhow::Result<Self> {
+        let mut vk_gamma_abc_g1 = Vec::new();
+        for point in &vk.gamma_abc_g1 {
+            point
+                .serialize_compressed(&mut vk_gamma_abc_g1)
+                .map_err(|e| anyhow::anyhow!("Failed to serialize gamma_abc_g1: {}", e))?;
+        }
+
+        Ok(Self {
+            vk_gamma_abc_g1,
+            alpha_g1_beta_g2: serialize(
+                &E::pairing(vk.alpha_g1, vk.beta_g2).0,
+                "alpha_g1_beta_g2",
+            )?,
+            gamma_g2_neg_pc: serialize(
+                &(-vk.gamma_g2.into_group()).into_affine(),
+                "gamma_g2_neg_pc",
+            )?,
+            delta_g2_neg_pc: serialize(
+                &(-vk.delta_g2.into_group()).into_affine(),
+                "delta_g2_neg_pc",
+            )?,
+        })
+    }
+}
+
+/// Renders a Move module `address::module` that verifies proofs against `vk`
+/// with `sui::groth16`, embedding the prepared key bytes.
+///
+/// The module exposes `verify(proof_points, public_inputs): bool` and an
+/// `assert_valid` entry function that aborts with `EInvalidProof` instead,
+/// both taking the byte vectors found in `proofSerializedHex` and
+/// `publicInputsSerializedHex` of a proof.
+///
+/// # Errors
+/// Returns error if `address` is neither a named nor a `0x` address, `module`
+/// is not a valid Move identifier, or the key cannot be serialized.
+pub fn verifier_module<E: SupportedCurve>(
+    vk: &VerifyingKey<E>,
+    address: &str,
+    module: &str,
+) -> anyhow::Result<String> {
+    if !is_identifier(address) && !is_numeric_address(address) {
+        anyhow::bail!("Invalid Move address '{}'", address);
+    }
+    if !is_identifier(module) {
+        anyhow::bail!("Invalid Move module name '{}'", module);
+    }
+
+    let pvk = PreparedVerifyingKeyBytes::new(vk)?;
+    let curve = match E::CURVE {
+        Curve::Bn254 => "bn254",
+        Curve::Bls12_381 => "bls12381",
+    };
+
+    Ok(MODULE_TEMPLATE
+        .replace("{{ADDRESS}}", address)
+        .replace("{{MODULE}}", module)
+        .replace("{{CURVE}}", curve)
+        .replace("{{NUM_INPUTS}}", &(vk.gamma_abc_g1.len() - 1).to_string())
+        .replace("{{VK_GAMMA_ABC_G1}}", &hex::encode(&pvk.vk_gamma_abc_g1))
+        .replace("{{ALPHA_G1_BETA_G2}}", &hex::encode(&pvk.alpha_g1_beta_g2))
+        .replace("{{GAMMA_G2_NEG_PC}}", &hex::encode(&pvk.gamma_g2_neg_pc))
+        .replace("{{DELTA_G2_NEG_PC}}", &hex::encode(&pvk.delta_g2_neg_pc)))
+}
+
+const MODULE_TEMPLATE: &str = r#"// Generated by the vortex `export sui-move` command. Do not edit.
+module {{ADDRESS}}::{{MODULE}};
+
+use sui::groth16;
+
+// === Errors ===
+
+const EInvalidProof: u64 = 0;
+
+// === Constants ===
+
+/// Number of public inputs the circuit takes.
+const NUM_PUBLIC_INPUTS: u64 = {{NUM_INPUTS}};
+
+// === Public Functions ===
+
+/// Verifies compressed proof points against concatenated 32-byte public inputs.
+public fun verify(proof_points: vector<u8>, public_inputs: vector<u8>): bool {
+    if (public_inputs.length() != NUM_PUBLIC_INPUTS * 32) return false;
+
+    groth16::verify_groth16_proof(
+        &groth16::{{CURVE}}(),
+        &prepared_verifying_key(),
+        &groth16::public_proof_inputs_from_bytes(public_inputs),
+        &groth16::proof_points_from_bytes(proof_points),
+    )
+}
+
+entry fun assert_valid(proof_points: vector<u8>, public_inputs: vector<u8>) {
+    assert!(verify(proof_points, public_inputs), EInvalidProof);
+}
+
+// === Private Functions ===
+
+fun prepared_verifying_key(): groth16::PreparedVerifyingKey {
+    groth16::pvk_from_bytes(
+        x"{{VK_GAMMA_ABC_G1}}",
+        x"{{ALPHA_G1_BETA_G2}}",
+        x"{{GAMMA_G2_NEG_PC}}",
+        x"{{DELTA_G2_NEG_PC}}",
+    )
+}
+"#;
+
+/// Move identifiers: a letter or underscore followed by letters, digits or underscores.
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_numeric_address(address: &str) -> bool {
+    address.strip_prefix("0x").is_some_and(|digits| {
+        !digits.is_empty() && digits.len() <= 64 && digits.chars().all(|c| c.is_ascii_hexdigit())
+    })
+}
+
+fn serialize<T: CanonicalSerialize>(value: &T, name: &str) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    value
+        .serialize_compressed(&mut bytes)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize {}: {}", name, e))?;
+    Ok(bytes)
+}
@@ -0,0 +1,252 @@
+use crate::gadgets::debug::{self, TraceEntry};
+use crate::witness;
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{
+    ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem, OptimizationGoal,
+};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Value of one constraint `a * b = c` under a circuit's assignment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintEvaluation<F: PrimeField = Fr> {
+    /// Position in synthesis order.
+    pub index: usize,
+    /// `ns!` path of the constraint.
+    pub name: String,
+    pub a: F,
+    pub b: F,
+    pub c: F,
+}
+
+impl<F: PrimeField> ConstraintEvaluation<F> {
+    pub fn is_satisfied(&self) -> bool {
+        self.a * self.b == self.c
+    }
+}
+
+/// `#index name: a * b = c`, with `!=` if the constraint does not hold.
+impl<F: PrimeField> fmt::Display for ConstraintEvaluation<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let relation = if self.is_satisfied() { "=" } else { "!=" };
+        write!(
+            f,
+            "#{} {}: {} * {} {} {}",
+            self.index, self.name, self.a, self.b, relation, self.c
+        )
+    }
+}
+
+/// Synthesizes a circuit and evaluates every constraint against its
+/// assignment, without a setup or a Groth16 proof.
+///
+/// For circuit unit tests: a satisfied mock proof means a real proof with
+/// matching keys verifies, at a fraction of the cost.
+#[derive(Debug, Clone)]
+pub struct MockProver<F: PrimeField = Fr> {
+    matrices: ConstraintMatrices<F>,
+    names: Vec<String>,
+    /// Instance assignment, including the leading constant `1`.
+    instance: Vec<F>,
+    witness: Vec<F>,
+    evaluations: Vec<ConstraintEvaluation<F>>,
+    trace: Vec<TraceEntry>,
+}
+
+impl<F: PrimeField> MockProver<F> {
+    /// Synthesizes `circuit` with its assignment and evaluates its constraints.
+    ///
+    /// Unsatisfied constraints are not an error; check them with
+    /// [`MockProver::verify`] or [`MockProver::assert_satisfied`].
+    ///
+    /// # Errors
+    /// Returns error if constraint generation fails, e.g. because a value is
+    /// missing or a length check in the circuit rejects its inputs.
+    pub fn run<C: ConstraintSynthesizer<F>>(circuit: C) -> anyhow::Result<Self> {
+        witness::traced(|| Self::synthesize(circuit))
+    }
+
+    fn synthesize<C: ConstraintSynthesizer<F>>(circuit: C) -> anyhow::Result<Self> {
+        let cs = ConstraintSystem::<F>::new_ref();
+        cs.set_optimization_goal(OptimizationGoal::Constraints);
+
+        let (result, trace) = debug::capture(|| circuit.generate_constraints(cs.clone()));
+        result.map_err(|e| anyhow::anyhow!("Failed to generate constraints: {}", e))?;
+        cs.finalize();
+
+        let matrices = cs
+            .to_matrices()
+            .ok_or_else(|| anyhow::anyhow!("Constraint system has no matrices"))?;
+        let names = cs.constraint_names().unwrap_or_default();
+        let (instance, witness) = {
+            let inner = cs
+                .borrow()
+                .ok_or_else(|| anyhow::anyhow!("Constraint system has no assignment"))?;
+            (
+                inner.instance_assignment.clone(),
+                inner.witness_assignment.clone(),
+            )
+        };
+
+        Ok(Self::evaluate(matrices, names, instance, witness, trace))
+    }
+
+    /// Re-evaluates the constraints with `witness` in place of the private
+    /// assignment, e.g. to check that a tampered witness is rejected.
+    ///
+    /// The result has no trace, since the circuit is not synthesized again.
+    ///
+    /// # Errors
+    /// Returns error if `witness` has a different length than the circuit's.
+    pub fn with_witness(&self, witness: Vec<F>) -> anyhow::Result<Self> {
+        if witness.len() != self.witness.len() {
+            anyhow::bail!(
+                "Expected {} witness variables, got {}",
+                self.witness.len(),
+                witness.len()
+            );
+        }
+
+        Ok(Self::evaluate(
+            self.matrices.clone(),
+            self.names.clone(),
+            self.instance.clone(),
+            witness,
+            Vec::new(),
+        ))
+    }
+
+    fn evaluate(
+        matrices: ConstraintMatrices<F>,
+        names: Vec<String>,
+        instance: Vec<F>,
+        witness: Vec<F>,
+        trace: Vec<TraceEntry>,
+    ) -> Self {
+        let value = |index: usize| {
+            if index < instance.len() {
+                instance[index]
+            } else {
+                witness[index - instance.len()]
+            }
+        };
+        let eval = |row: &[(F, usize)]| {
+            row.iter()
+                .fold(F::ZERO, |acc, (coeff, index)| acc + *coeff * value(*index))
+        };
+
+        let rows = matrices.a.iter().zip(&matrices.b).zip(&matrices.c);
+        let evaluations = rows
+            .enumerate()
+            .map(|(index, ((a, b), c))| ConstraintEvaluation {
+                index,
+                name: names
+                    .get(index)
+                    .cloned()
+                    .unwrap_or_else(|| format!("#{}", index)),
+                a: eval(a),
+                b: eval(b),
+                c: eval(c),
+            })
+            .collect();
+
+        Self {
+            matrices,
+            names,
+            instance,
+            witness,
+            evaluations,
+            trace,
+        }
+    }
+
+    /// Public inputs in the order expected by the verifier (without the constant `1`).
+    pub fn public_inputs(&self) -> &[F] {
+        &self.instance[1..]
+    }
+
+    /// Private witness assignment.
+    pub fn witness(&self) -> &[F] {
+        &self.witness
+    }
+
+    pub fn num_constraints(&self) -> usize {
+        self.evaluations.len()
+    }
+
+    pub fn num_witness_variables(&self) -> usize {
+        self.witness.len()
+    }
+
+    /// Every constraint, in synthesis order.
+    pub fn evaluations(&self) -> &[ConstraintEvaluation<F>] {
+        &self.evaluations
+    }
+
+    /// Values recorded by [`debug::debug_print`] during synthesis, in call order.
+    pub fn trace(&self) -> &[TraceEntry] {
+        &self.trace
+    }
+
+    /// The constraints that do not hold, in synthesis order.
+    pub fn failures(&self) -> impl Iterator<Item = &ConstraintEvaluation<F>> {
+        self.evaluations.iter().filter(|e| !e.is_satisfied())
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Checks that every constraint holds.
+    ///
+    /// # Errors
+    /// Returns error listing every unsatisfied constraint, followed by the
+    /// [`trace`](Self::trace) if there is one.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let failures: Vec<String> = self.failures().map(ToString::to_string).collect();
+        if failures.is_empty() {
+            return Ok(());
+        }
+
+        let mut message = format!(
+            "{} of {} constraints are not satisfied:\n  {}",
+            failures.len(),
+            self.num_constraints(),
+            failures.join("\n  ")
+        );
+        if !self.trace.is_empty() {
+            let trace: Vec<String> = self.trace.iter().map(ToString::to_string).collect();
+            message += &format!("\nTrace:\n  {}", trace.join("\n  "));
+        }
+        Err(anyhow::anyhow!(message))
+    }
+
+    /// # Panics
+    /// Panics listing every unsatisfied constraint.
+    pub fn assert_satisfied(&self) {
+        if let Err(e) = self.verify() {
+            panic!("{}", e);
+        }
+    }
+
+    /// # Panics
+    /// Panics if every constraint holds, or if none of the unsatisfied
+    /// constraints has an `ns!` path containing `name`.
+    pub fn assert_unsatisfied_at(&self, name: &str) {
+        let failures: Vec<&ConstraintEvaluation<F>> = self.failures().collect();
+        assert!(
+            failures.iter().any(|e| e.name.contains(name)),
+            "Expected a constraint under '{}' to fail, failures: {:?}",
+            name,
+            failures.iter().map(|e| &e.name).collect::<Vec<_>>()
+        );
+    }
+}
+
+/// The private assignment is wiped when the mock prover is dropped.
+impl<F: PrimeField> Drop for MockProver<F> {
+    fn drop(&mut self) {
+        self.witness.zeroize();
+    }
+}
@@ -0,0 +1,6 @@
+mod mock;
+mod roundtrip;
+pub mod strategies;
+
+pub use mock::{ConstraintEvaluation, MockProver};
+pub use roundtrip::{roundtrip, roundtrip_with};
@@ -0,0 +1,103 @@
+use super::MockProver;
+use crate::{inspect, prover};
+use ark_bn254::{Bn254, Fr};
+use ark_crypto_primitives::snark::SNARK;
+use ark_groth16::Groth16;
+use ark_relations::r1cs::ConstraintSynthesizer;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::{Config, TestRunner};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use std::fmt::Debug;
+
+/// Property-tests a circuit end to end on BN254 with 16 cases; see
+/// [`roundtrip_with`].
+///
+/// # Panics
+/// Panics with the minimal failing circuit if any property does not hold.
+pub fn roundtrip<C, S>(circuit_strategy: S)
+where
+    C: ConstraintSynthesizer<Fr> + Clone + Debug,
+    S: Strategy<Value = C>,
+{
+    roundtrip_with(Config::with_cases(16), circuit_strategy)
+}
+
+/// Property-tests a circuit end to end on BN254.
+///
+/// `circuit_strategy` must generate satisfied instances of one constraint
+/// system; keys are set up once for the first instance drawn. For every
+/// instance it checks that:
+/// - every constraint holds under the [`MockProver`]
+/// - its [`inspect::digest`] matches the one the keys were set up for
+/// - a Groth16 proof of it verifies
+/// - the proof is rejected if any single public input is changed
+///
+/// Changing a witness variable is not checked: hints such as the inverse
+/// witnessed by `is_eq` are only constrained on one branch, so changing one
+/// can leave every constraint satisfied without affecting the statement.
+///
+/// Proofs use a ChaCha20 stream seeded by the runner, so failures reproduce.
+///
+/// # Panics
+/// Panics with the minimal failing circuit if any property does not hold.
+pub fn roundtrip_with<C, S>(config: Config, circuit_strategy: S)
+where
+    C: ConstraintSynthesizer<Fr> + Clone + Debug,
+    S: Strategy<Value = C>,
+{
+    let mut runner = TestRunner::new(config);
+
+    let setup_circuit = circuit_strategy
+        .new_tree(&mut runner)
+        .expect("circuit strategy rejected every case")
+        .current();
+    let digest = inspect::digest(setup_circuit.clone()).expect("failed to synthesize circuit");
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(
+        setup_circuit,
+        &mut ChaCha20Rng::from_seed([0u8; 32]),
+    )
+    .expect("failed to set up keys");
+    let pvk = Groth16::<Bn254>::process_vk(&pk.vk).expect("failed to prepare verifying key");
+
+    let cases = (circuit_strategy, any::<[u8; 32]>(), any::<Index>());
+    let result = runner.run(&cases, |(circuit, seed, input_index)| {
+        let mock =
+            MockProver::run(circuit.clone()).map_err(|e| TestCaseError::fail(e.to_string()))?;
+        mock.verify()
+            .map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert_eq!(
+            inspect::digest(circuit.clone()).map_err(|e| TestCaseError::fail(e.to_string()))?,
+            digest,
+            "strategy generated a different constraint system than the first case"
+        );
+
+        let bundle = prover::prove(circuit, &pk, &mut ChaCha20Rng::from_seed(seed))
+            .map_err(|e| TestCaseError::fail(e.to_string()))?;
+        prop_assert!(
+            Groth16::<Bn254>::verify_with_processed_vk(&pvk, &bundle.public_inputs, &bundle.proof)
+                .map_err(|e| TestCaseError::fail(e.to_string()))?,
+            "proof does not verify"
+        );
+
+        if !bundle.public_inputs.is_empty() {
+            let i = input_index.index(bundle.public_inputs.len());
+            let mut public_inputs = bundle.public_inputs.clone();
+            public_inputs[i] += Fr::from(1u64);
+            prop_assert!(
+                !Groth16::<Bn254>::verify_with_processed_vk(&pvk, &public_inputs, &bundle.proof)
+                    .map_err(|e| TestCaseError::fail(e.to_string()))?,
+                "proof verifies with public input {} changed",
+                i
+            );
+        }
+
+        Ok(())
+    });
+
+    if let Err(e) = result {
+        panic!("{}", e);
+    }
+}
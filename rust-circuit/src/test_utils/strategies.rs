@@ -0,0 +1,393 @@
+use crate::babyjubjub;
+use crate::circuit::age::AgeCircuit;
+use crate::circuit::airdrop::{self, AirdropCircuit, Eligible};
+use crate::circuit::auction::{AuctionCircuit, BidCircuit};
+use crate::circuit::batch_insert::BatchInsertCircuit;
+use crate::circuit::blocklist::{self, BlocklistCircuit};
+use crate::circuit::credential::{self, CredentialCircuit, Predicate};
+use crate::circuit::elgamal::ElGamalCircuit;
+use crate::circuit::hash_chain::HashChainCircuit;
+use crate::circuit::inference::InferenceCircuit;
+use crate::circuit::jwt::{JwtClaimCircuit, JwtLayout};
+use crate::circuit::opening::CommitmentOpeningCircuit;
+use crate::circuit::polynomial::PolynomialCircuit;
+use crate::circuit::semaphore::SemaphoreCircuit;
+use crate::circuit::shuffle::ShuffleCircuit;
+use crate::circuit::solvency::SolvencyCircuit;
+use crate::circuit::transfer::{self, Note, TransferCircuit};
+use crate::circuit::vote::{self, TallyCircuit, VoteCircuit};
+use crate::circuit::Circuit;
+use crate::merkle::{MerklePath, MerkleTree};
+use ark_bn254::Fr;
+use ark_ff::{AdditiveGroup, PrimeField};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use proptest::prelude::*;
+use proptest::sample::Index;
+/// Uniformly random BN254 scalars.
+pub fn field_element() -> impl Strategy<Value = Fr> {
+    any::<[u8; 32]>().prop_map(|bytes| Fr::from_le_bytes_mod_order(&bytes))
+}
+
+/// Satisfied instances of the multiplication circuit.
+pub fn multiplication(non_trivial: bool) -> impl Strategy<Value = Circuit> {
+    (field_element(), field_element()).prop_filter_map(
+        "factors must not be 0 or 1 in a non-trivial circuit",
+        move |(a, b)| Circuit::new(Circuit::compute_public(a, b), a, b, non_trivial).ok(),
+    )
+}
+
+/// Satisfied instances of a hash chain of `STEPS` steps.
+pub fn hash_chain<const STEPS: usize>() -> impl Strategy<Value = HashChainCircuit<STEPS>> {
+    field_element().prop_map(|input| {
+        HashChainCircuit::new(input).expect("hashing a field element cannot fail")
+    })
+}
+
+/// Satisfied instances of a polynomial evaluation of degree at most `DEGREE`,
+/// including polynomials of lower degree and the point `x = 0`.
+pub fn polynomial<const DEGREE: usize>() -> impl Strategy<Value = PolynomialCircuit<DEGREE>> {
+    (
+        prop::collection::vec(field_element(), 0..=DEGREE + 1),
+        prop_oneof![Just(Fr::ZERO), field_element()],
+        field_element(),
+    )
+        .prop_map(|(coefficients, x, salt): (Vec<Fr>, Fr, Fr)| {
+            PolynomialCircuit::new(&coefficients, x, salt)
+                .expect("at most DEGREE + 1 coefficients were generated")
+        })
+}
+
+/// A tree of `depth` levels holding `leaves`, and an index among them.
+fn tree_and_index(depth: usize, leaves: Vec<Fr>, index: Index) -> (MerkleTree<Fr>, usize) {
+    let index = index.index(leaves.len());
+    let tree = MerkleTree::new(depth, leaves).expect("the leaves fit in the tree");
+    (tree, index)
+}
+
+/// Satisfied instances of the age check, with years up to 4000.
+pub fn age() -> impl Strategy<Value = AgeCircuit> {
+    (0u64..2000, 0u64..=150, 0u64..1850, field_element()).prop_map(
+        |(birth_year, threshold, slack, salt)| {
+            AgeCircuit::new(birth_year + threshold + slack, threshold, birth_year, salt)
+                .expect("the birth year is at least threshold years back")
+        },
+    )
+}
+
+/// Satisfied instances of the solvency check, including `balance == minimum`.
+pub fn solvency() -> impl Strategy<Value = SolvencyCircuit> {
+    (any::<u64>(), any::<u64>(), field_element()).prop_map(|(a, b, salt)| {
+        SolvencyCircuit::new(a.min(b), a.max(b), salt).expect("the balance is above the minimum")
+    })
+}
+
+/// Satisfied claims on trees of `DEPTH` levels with up to 4 eligible addresses.
+pub fn airdrop<const DEPTH: usize>() -> impl Strategy<Value = AirdropCircuit<DEPTH>> {
+    (
+        prop::collection::vec((any::<[u8; 32]>(), field_element()), 1..=4),
+        any::<Index>(),
+        field_element(),
+        field_element(),
+    )
+        .prop_map(|(claimants, index, airdrop_id, recipient)| {
+            let eligible: Vec<Eligible<Fr>> = claimants
+                .iter()
+                .map(|(address, secret)| Eligible {
+                    address: *address,
+                    identity: airdrop::identity(*secret).expect("hashing cannot fail"),
+                })
+                .collect();
+            let index = index.index(claimants.len());
+            let tree = airdrop::build_tree(DEPTH, &eligible).expect("the list fits in the tree");
+            let (address, secret) = claimants[index];
+            AirdropCircuit::new(&tree, index, &address, secret, airdrop_id, recipient)
+                .expect("the claimant is on the list")
+        })
+}
+
+/// Satisfied votes from registries of `DEPTH` levels with up to 4 voters.
+pub fn vote<const DEPTH: usize>() -> impl Strategy<Value = VoteCircuit<DEPTH>> {
+    (
+        prop::collection::vec(field_element(), 1..=4),
+        any::<Index>(),
+        field_element(),
+        0u64..256,
+        any::<Index>(),
+        field_element(),
+    )
+        .prop_map(|(secrets, index, proposal_id, max_choice, vote, salt)| {
+            let identities = secrets
+                .iter()
+                .map(|secret| vote::voter_identity(*secret).expect("hashing cannot fail"))
+                .collect();
+            let (registry, index) = tree_and_index(DEPTH, identities, index);
+            let vote = vote.index(max_choice as usize + 1) as u64;
+            VoteCircuit::new(
+                &registry,
+                index,
+                secrets[index],
+                proposal_id,
+                max_choice,
+                vote,
+                salt,
+            )
+            .expect("the voter is registered and the vote in range")
+        })
+}
+
+/// Satisfied tallies of `VOTES` votes of up to 255.
+pub fn tally<const VOTES: usize>() -> impl Strategy<Value = TallyCircuit<VOTES>> {
+    prop::collection::vec((0u64..256, field_element()), VOTES)
+        .prop_map(|openings| TallyCircuit::new(&openings).expect("there are VOTES openings"))
+}
+
+/// Satisfied sealed bids, including bids on either bound.
+pub fn bid() -> impl Strategy<Value = BidCircuit> {
+    (any::<[u64; 3]>(), field_element()).prop_map(|(mut values, salt)| {
+        values.sort_unstable();
+        let [min_bid, bid, max_bid] = values;
+        BidCircuit::new(min_bid, max_bid, bid, salt).expect("the bid is within its bounds")
+    })
+}
+
+/// Satisfied auctions with `OTHERS` losing bids.
+pub fn auction<const OTHERS: usize>() -> impl Strategy<Value = AuctionCircuit<OTHERS>> {
+    (
+        1..=u64::MAX,
+        field_element(),
+        prop::collection::vec((any::<u64>(), field_element()), OTHERS),
+    )
+        .prop_map(|(winning_bid, winning_salt, others)| {
+            let others: Vec<(u64, Fr)> = others
+                .into_iter()
+                .map(|(bid, salt)| (bid % winning_bid, salt))
+                .collect();
+            AuctionCircuit::new(winning_bid, winning_salt, &others)
+                .expect("every other bid is below the winning bid")
+        })
+}
+
+/// Satisfied openings of Poseidon commitments.
+pub fn opening() -> impl Strategy<Value = CommitmentOpeningCircuit> {
+    (field_element(), field_element()).prop_map(|(value, blinding)| {
+        CommitmentOpeningCircuit::new(value, blinding).expect("committing cannot fail")
+    })
+}
+
+/// Satisfied transfers on trees of `DEPTH` levels: each input is a note in
+/// the tree or a zero-value note, and the public amount balances the values.
+pub fn transfer<const DEPTH: usize>() -> impl Strategy<Value = TransferCircuit<DEPTH>> {
+    let note = || (0u64..1 << 32, field_element(), field_element());
+    ([note(), note()], [note(), note()], field_element()).prop_map(
+        |(inputs, outputs, ext_data_hash)| {
+            let inputs = inputs.map(|(value, spending_key, blinding)| {
+                let owner = transfer::owner(spending_key).expect("hashing cannot fail");
+                (Note::new(value, owner, blinding), spending_key)
+            });
+            let leaves = inputs
+                .iter()
+                .map(|(note, _)| note.commitment().expect("hashing cannot fail"))
+                .collect();
+            let tree = MerkleTree::new(DEPTH, leaves).expect("two leaves fit in the tree");
+            let outputs = outputs.map(|(value, owner, blinding)| Note::new(value, owner, blinding));
+
+            let value = |note: &Note<Fr>| note.value.into_bigint().as_ref()[0] as i64;
+            let public_amount = outputs.iter().map(value).sum::<i64>()
+                - inputs.iter().map(|(note, _)| value(note)).sum::<i64>();
+            let [(note_0, key_0), (note_1, key_1)] = inputs;
+            TransferCircuit::new(
+                &tree,
+                [(0, note_0, key_0), (1, note_1, key_1)],
+                outputs,
+                public_amount,
+                ext_data_hash,
+            )
+            .expect("the inputs are in the tree and the values balance")
+        },
+    )
+}
+
+/// Satisfied batch inserts into trees of `DEPTH` levels that already hold
+/// up to 3 leaves.
+pub fn batch_insert<const DEPTH: usize, const BATCH_SIZE: usize>(
+) -> impl Strategy<Value = BatchInsertCircuit<DEPTH, BATCH_SIZE>> {
+    (
+        prop::collection::vec(field_element(), 0..=3),
+        prop::collection::vec(field_element(), BATCH_SIZE),
+    )
+        .prop_map(|(existing, leaves)| {
+            let mut tree = MerkleTree::new(DEPTH, existing).expect("the leaves fit in the tree");
+            BatchInsertCircuit::new(&mut tree, leaves).expect("the batch fits in the tree")
+        })
+}
+
+/// Satisfied non-membership proofs against blocklists of up to 4
+/// identifiers in trees of `DEPTH` levels.
+pub fn blocklist<const DEPTH: usize>() -> impl Strategy<Value = BlocklistCircuit<DEPTH>> {
+    (
+        prop::collection::vec(field_element(), 0..=4),
+        field_element(),
+        field_element(),
+    )
+        .prop_filter_map(
+            "the identifier's slot must not hold a blocklisted identifier",
+            |(blocklisted, identifier, salt)| {
+                let blocklist = blocklist::build_blocklist(DEPTH, &blocklisted).ok()?;
+                BlocklistCircuit::new(&blocklist, identifier, salt).ok()
+            },
+        )
+}
+
+/// Satisfied Semaphore signals from groups of `DEPTH` levels.
+pub fn semaphore<const DEPTH: usize>() -> impl Strategy<Value = SemaphoreCircuit<DEPTH>> {
+    (
+        field_element(),
+        field_element(),
+        prop::collection::vec(field_element(), DEPTH),
+        any::<u64>(),
+        field_element(),
+        field_element(),
+    )
+        .prop_map(
+            |(identity_nullifier, identity_trapdoor, siblings, index, signal, external)| {
+                let index = if DEPTH < 64 {
+                    index & ((1 << DEPTH) - 1)
+                } else {
+                    index
+                };
+                let path = MerklePath { siblings, index };
+                SemaphoreCircuit::new(
+                    identity_nullifier,
+                    identity_trapdoor,
+                    path,
+                    signal,
+                    external,
+                )
+                .expect("the path has DEPTH levels")
+            },
+        )
+}
+
+/// Satisfied disclosures of 4-attribute credentials, revealing a random
+/// subset and proving a bound on attribute 0 and membership of attribute 1
+/// in a set of 3.
+pub fn credential() -> impl Strategy<Value = CredentialCircuit> {
+    (
+        1u64..1 << 32,
+        any::<[u64; 3]>(),
+        any::<[bool; 4]>(),
+        prop::collection::vec(field_element(), 3),
+        any::<Index>(),
+        field_element(),
+    )
+        .prop_map(|(age, others, disclosed, mut set, member, secret)| {
+            let mut attributes = vec![Fr::from(age)];
+            attributes.extend(others.map(Fr::from));
+            let index = member.index(set.len());
+            set[index] = attributes[1];
+
+            let secret = babyjubjub::to_scalar(secret);
+            let signature = credential::issue(secret, &attributes).expect("4 attributes");
+            let predicates = vec![
+                Predicate::GreaterThan {
+                    attribute: 0,
+                    bound: Fr::from(age - 1),
+                },
+                Predicate::InSet { attribute: 1, set },
+            ];
+            CredentialCircuit::new(
+                babyjubjub::public_key(secret),
+                attributes,
+                signature,
+                disclosed.to_vec(),
+                predicates,
+            )
+            .expect("the signature verifies and the predicates hold")
+        })
+}
+
+/// Satisfied evaluations of a dense layer, with weights, biases and inputs
+/// below `2^20` in absolute value so no output overflows.
+pub fn inference<const INPUTS: usize, const OUTPUTS: usize>(
+) -> impl Strategy<Value = InferenceCircuit<INPUTS, OUTPUTS>> {
+    let value = || -(1i32 << 20)..1 << 20;
+    (
+        prop::collection::vec(prop::collection::vec(value(), INPUTS), OUTPUTS),
+        prop::collection::vec(value(), OUTPUTS),
+        prop::collection::vec(value(), INPUTS),
+        field_element(),
+    )
+        .prop_map(|(weights, biases, input, salt)| {
+            let weights = weights
+                .into_iter()
+                .map(|row| row.try_into().expect("INPUTS weights per row"))
+                .collect::<Vec<_>>()
+                .try_into()
+                .expect("OUTPUTS rows");
+            let biases = biases.try_into().expect("OUTPUTS biases");
+            let input = input.try_into().expect("INPUTS values");
+            InferenceCircuit::new(weights, biases, input, salt)
+                .expect("products of 20-bit values do not overflow")
+        })
+}
+
+/// Satisfied ElGamal encryptions of `u64` messages under random keys.
+pub fn elgamal() -> impl Strategy<Value = ElGamalCircuit> {
+    (
+        field_element(),
+        any::<u64>(),
+        field_element(),
+        field_element(),
+    )
+        .prop_filter_map(
+            "the secret key must not be zero",
+            |(secret, message, randomness, salt)| {
+                let public_key = babyjubjub::public_key(babyjubjub::to_scalar(secret));
+                ElGamalCircuit::new(public_key, message, randomness, salt).ok()
+            },
+        )
+}
+
+/// Satisfied shuffles of `SIZE` Pedersen commitments.
+pub fn shuffle<const SIZE: usize>() -> impl Strategy<Value = ShuffleCircuit<SIZE>> {
+    (
+        prop::collection::vec((field_element(), field_element()), SIZE),
+        Just((0..SIZE).collect::<Vec<_>>()).prop_shuffle(),
+        prop::collection::vec(field_element(), SIZE),
+    )
+        .prop_map(|(openings, permutation, randomness)| {
+            let inputs = openings
+                .iter()
+                .map(|(value, blinding)| babyjubjub::commit(*value, *blinding))
+                .collect();
+            ShuffleCircuit::new(inputs, permutation, randomness)
+                .expect("the permutation and randomness match the inputs")
+        })
+}
+
+/// The layout [`jwt`] generates tokens of.
+pub fn jwt_layout() -> JwtLayout {
+    JwtLayout::of(&jwt_token("0000000000", "00000000"), "sub").expect("the token has both claims")
+}
+
+fn jwt_token(sub: &str, nonce: &str) -> String {
+    let payload = format!(
+        r#"{{"iss":"https://issuer.example","sub":"{}","nonce":"{}"}}"#,
+        sub, nonce
+    );
+    format!(
+        "{}.{}.signature",
+        URL_SAFE_NO_PAD.encode(r#"{"alg":"RS256","typ":"JWT"}"#),
+        URL_SAFE_NO_PAD.encode(payload)
+    )
+}
+
+/// Satisfied claims from tokens of [`jwt_layout`], with random subjects and
+/// nonces.
+pub fn jwt() -> impl Strategy<Value = JwtClaimCircuit> {
+    ("[0-9a-z]{10}", "[0-9A-Za-z_-]{8}", field_element()).prop_map(|(sub, nonce, salt)| {
+        JwtClaimCircuit::new(&jwt_layout(), &jwt_token(&sub, &nonce), salt)
+            .expect("the token has the generated layout")
+    })
+}
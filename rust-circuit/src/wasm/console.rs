@@ -0,0 +1,140 @@
+use super::to_js_error;
+use crate::logging::{self, LevelLayer};
+use std::fmt::{self, Write};
+use std::sync::Once;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = info)]
+    fn console_info(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn console_log(message: &str);
+    #[wasm_bindgen(js_namespace = console, js_name = debug)]
+    fn console_debug(message: &str);
+}
+
+static INSTALL: Once = Once::new();
+
+/// Logs spans and events of the prover and verifier up to `level` (`off`,
+/// `error`, `warn`, `info`, `debug` or `trace`) to the browser console.
+/// Logging is off until this is called.
+#[wasm_bindgen]
+pub fn set_log_level(level: &str) -> Result<(), JsValue> {
+    logging::set_level(level).map_err(to_js_error)?;
+
+    INSTALL.call_once(|| {
+        let subscriber = Registry::default().with(LevelLayer).with(ConsoleLayer);
+        // Fails only if the embedding application installed its own subscriber
+        let _ = tracing::subscriber::set_global_default(subscriber);
+    });
+    Ok(())
+}
+
+/// Writes events to the `console` method matching their level, and the
+/// duration of each span as it closes.
+struct ConsoleLayer;
+
+/// Formatted fields and start time of a span, kept in its extensions.
+struct SpanTiming {
+    fields: String,
+    start: f64,
+}
+
+impl<S> Layer<S> for ConsoleLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(SpanTiming {
+            fields: fields.0,
+            start: js_sys::Date::now(),
+        });
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        if let Some(timing) = extensions.get_mut::<SpanTiming>() {
+            let mut fields = Fields(std::mem::take(&mut timing.fields));
+            values.record(&mut fields);
+            timing.fields = fields.0;
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut line = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                let _ = write!(line, "{}:", span.name());
+            }
+            line.push(' ');
+        }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        line.push_str(fields.0.trim_start());
+
+        match *event.metadata().level() {
+            Level::ERROR => console_error(&line),
+            Level::WARN => console_warn(&line),
+            Level::INFO => console_info(&line),
+            Level::DEBUG => console_log(&line),
+            Level::TRACE => console_debug(&line),
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        let Some(timing) = extensions.get::<SpanTiming>() else {
+            return;
+        };
+        console_log(&format!(
+            "{}{}: {:.1} ms",
+            span.name(),
+            timing.fields,
+            js_sys::Date::now() - timing.start
+        ));
+    }
+}
+
+/// ` key=value` pairs, with the message (if any) first and unlabelled.
+#[derive(Default)]
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!(" {:?}", value));
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!(" {}", value));
+        } else {
+            let _ = write!(self.0, " {}={}", field.name(), value);
+        }
+    }
+}
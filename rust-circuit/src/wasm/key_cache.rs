@@ -0,0 +1,225 @@
+use super::prover::progress_reporter;
+use super::to_js_error;
+use crate::api;
+use crate::curve::Curve;
+use crate::keys::{self, chunked::ChunkedBytes, convert::KeyKind, header, header::Expected};
+use ark_bn254::Bn254;
+use ark_groth16::ProvingKey;
+use js_sys::{Reflect, Uint8Array};
+use std::sync::Arc;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Cache, CacheStorage, ReadableStream, ReadableStreamDefaultReader, Response};
+
+/// Cache API bucket keys are stored in unless the caller names another one.
+const DEFAULT_CACHE_NAME: &str = "vortex-keys";
+
+#[wasm_bindgen]
+extern "C" {
+    // The global `fetch`, available on both windows and workers
+    #[wasm_bindgen(js_name = fetch)]
+    fn fetch_with_str(input: &str) -> js_sys::Promise;
+}
+
+/// A BN254 proving key deserialized once and reused across proofs.
+#[wasm_bindgen]
+pub struct ProvingKeyHandle {
+    inner: Arc<ProvingKey<Bn254>>,
+    fingerprint: String,
+}
+
+#[wasm_bindgen]
+impl ProvingKeyHandle {
+    /// SHA-256 fingerprint of the key bytes, without the file header.
+    #[wasm_bindgen(getter)]
+    pub fn fingerprint(&self) -> String {
+        self.fingerprint.clone()
+    }
+
+    /// Same as the top-level `prove`, without deserializing the key again.
+    pub fn prove(
+        &self,
+        input_json: &str,
+        on_progress: Option<js_sys::Function>,
+    ) -> Result<String, JsValue> {
+        api::prove_with_key(input_json, &self.inner, progress_reporter(&on_progress))
+            .map_err(to_js_error)
+    }
+}
+
+/// Sets how many deserialized proving keys `prove` and the loaders keep for
+/// reuse (default 4), evicting the least recently used; 0 disables caching.
+#[wasm_bindgen]
+pub fn set_proving_key_cache_capacity(capacity: usize) {
+    keys::cache::set_capacity(capacity);
+}
+
+/// Assembles a proving key from chunks, e.g. the `Uint8Array`s of a
+/// `ReadableStream`, so it is never held as one hex string or buffer.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct ProvingKeyLoader {
+    chunks: ChunkedBytes,
+}
+
+#[wasm_bindgen]
+impl ProvingKeyLoader {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the next chunk of the binary (not hex-encoded) key.
+    pub fn push_chunk(&mut self, chunk: Vec<u8>) {
+        self.chunks.push(chunk);
+    }
+
+    /// Deserializes the pushed chunks, releasing each one as it is consumed.
+    pub fn finish(mut self) -> Result<ProvingKeyHandle, JsValue> {
+        let fingerprint = self.chunks.fingerprint();
+        let expected = expected_proving_key();
+        let inner = keys::cache::get_or_decode(self.chunks.digest(), &expected, || {
+            header::decode_reader(&mut self.chunks, &expected)
+        })
+        .map_err(to_js_error)?;
+
+        Ok(ProvingKeyHandle { inner, fingerprint })
+    }
+}
+
+/// Reads a binary proving key from `stream`, e.g. `(await fetch(url)).body`,
+/// with [`ProvingKeyLoader`].
+#[wasm_bindgen]
+pub async fn load_proving_key_from_stream(
+    stream: ReadableStream,
+) -> Result<ProvingKeyHandle, JsValue> {
+    let mut loader = ProvingKeyLoader::new();
+    read_stream(&stream, |chunk| loader.push_chunk(chunk.to_vec())).await?;
+
+    loader.finish()
+}
+
+/// Downloads a binary proving key (e.g. `proving_key.bin`) from `url`, checks
+/// its fingerprint against `expected_sha256` and returns a reusable handle.
+///
+/// Verified keys are stored with the Cache API under `cache_name` (default
+/// `vortex-keys`), so later loads skip the download; a cached copy that no
+/// longer matches the fingerprint is evicted and fetched again. Where the
+/// Cache API is unavailable the key is always downloaded.
+#[wasm_bindgen]
+pub async fn load_proving_key_from_url(
+    url: String,
+    expected_sha256: String,
+    cache_name: Option<String>,
+) -> Result<ProvingKeyHandle, JsValue> {
+    let cache = open_cache(cache_name.as_deref().unwrap_or(DEFAULT_CACHE_NAME)).await?;
+
+    if let Some(cache) = &cache {
+        if let Some(bytes) = cached_bytes(cache, &url).await? {
+            if let Ok(fingerprint) = keys::check_fingerprint(&bytes, &expected_sha256) {
+                return handle_from_bytes(bytes, fingerprint);
+            }
+            JsFuture::from(cache.delete_with_str(&url)).await?;
+        }
+    }
+
+    let mut bytes = read_body(&fetch(&url).await?).await?;
+    let fingerprint = keys::check_fingerprint(&bytes, &expected_sha256)
+        .map_err(|e| JsValue::from_str(&format!("Proving key at {}: {}", url, e)))?;
+
+    if let Some(cache) = &cache {
+        let response = Response::new_with_opt_u8_array(Some(&mut bytes))?;
+        JsFuture::from(cache.put_with_str(&url, &response)).await?;
+    }
+
+    handle_from_bytes(bytes, fingerprint)
+}
+
+fn handle_from_bytes(bytes: Vec<u8>, fingerprint: String) -> Result<ProvingKeyHandle, JsValue> {
+    let inner = keys::cache::decode(&bytes, &expected_proving_key()).map_err(to_js_error)?;
+
+    Ok(ProvingKeyHandle { inner, fingerprint })
+}
+
+fn expected_proving_key() -> Expected {
+    Expected {
+        kind: KeyKind::Proving,
+        curve: Curve::Bn254,
+        non_trivial: None,
+    }
+}
+
+/// Opens the named cache, or `None` where `caches` is missing (insecure
+/// contexts, some embedded webviews).
+async fn open_cache(name: &str) -> Result<Option<Cache>, JsValue> {
+    let caches = Reflect::get(&js_sys::global(), &JsValue::from_str("caches"))?;
+    let Ok(caches) = caches.dyn_into::<CacheStorage>() else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        JsFuture::from(caches.open(name)).await?.unchecked_into(),
+    ))
+}
+
+async fn cached_bytes(cache: &Cache, url: &str) -> Result<Option<Vec<u8>>, JsValue> {
+    let cached = JsFuture::from(cache.match_with_str(url)).await?;
+    if cached.is_undefined() {
+        return Ok(None);
+    }
+
+    read_body(&cached.unchecked_into()).await.map(Some)
+}
+
+async fn fetch(url: &str) -> Result<Response, JsValue> {
+    let response: Response = JsFuture::from(fetch_with_str(url)).await?.unchecked_into();
+    if !response.ok() {
+        return Err(JsValue::from_str(&format!(
+            "Failed to fetch {}: HTTP {}",
+            url,
+            response.status()
+        )));
+    }
+
+    Ok(response)
+}
+
+/// Reads a response body chunk by chunk into a single buffer, sized from
+/// `Content-Length` when present, instead of materializing an `ArrayBuffer` copy.
+async fn read_body(response: &Response) -> Result<Vec<u8>, JsValue> {
+    let capacity = response
+        .headers()
+        .get("content-length")?
+        .and_then(|length| length.parse().ok())
+        .unwrap_or(0);
+    let mut bytes = Vec::with_capacity(capacity);
+
+    if let Some(body) = response.body() {
+        read_stream(&body, |chunk| {
+            let start = bytes.len();
+            bytes.resize(start + chunk.length() as usize, 0);
+            chunk.copy_to(&mut bytes[start..]);
+        })
+        .await?;
+    }
+
+    Ok(bytes)
+}
+
+/// Drains `stream`, passing each `Uint8Array` chunk to `on_chunk`.
+async fn read_stream(
+    stream: &ReadableStream,
+    mut on_chunk: impl FnMut(Uint8Array),
+) -> Result<(), JsValue> {
+    let reader: ReadableStreamDefaultReader = stream.get_reader().unchecked_into();
+
+    loop {
+        let chunk = JsFuture::from(reader.read()).await?;
+        if Reflect::get(&chunk, &JsValue::from_str("done"))?.is_truthy() {
+            return Ok(());
+        }
+
+        on_chunk(Reflect::get(&chunk, &JsValue::from_str("value"))?.unchecked_into());
+    }
+}
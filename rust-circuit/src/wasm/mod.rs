@@ -1,195 +1,230 @@
-use crate::circuit::Circuit;
-use ark_bn254::{Bn254, Fr};
-use ark_crypto_primitives::snark::SNARK;
-use ark_groth16::Groth16;
-use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
-use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use num_bigint::BigUint;
-use serde::{Deserialize, Serialize};
+use crate::api;
+use crate::circuit::sizes;
+use crate::compat;
+use crate::field::{self, Encoding};
+use crate::gadgets::commitment::{CommitmentScheme, PedersenCommitment};
+use crate::merkle::MerkleTree;
+use crate::poseidon;
+use ark_bn254::Fr;
 use std::str::FromStr;
 use wasm_bindgen::prelude::*;
 
+mod console;
+
+// The prover and verifier halves can be built as separate, smaller modules
+// with the `wasm-prover` and `wasm-verifier` features
+#[cfg(feature = "wasm-prover")]
+mod key_cache;
+#[cfg(feature = "wasm-prover")]
+mod prover;
+#[cfg(feature = "wasm-verifier")]
+mod verifier;
+
+pub use crate::api::{ProofInput, ProofMeta, ProofOutput};
+pub use console::set_log_level;
+#[cfg(feature = "wasm-prover")]
+pub use key_cache::{
+    load_proving_key_from_stream, load_proving_key_from_url, set_proving_key_cache_capacity,
+    ProvingKeyHandle, ProvingKeyLoader,
+};
+#[cfg(feature = "wasm-prover")]
+pub use prover::{
+    compute_expected_public, prove, prove_async, prove_bcs, prove_deterministic, prove_typed,
+    rerandomize_proof,
+};
+#[cfg(feature = "wasm-verifier")]
+pub use verifier::{verify, verify_serialized, verify_typed, verify_with_inputs};
+
+// Spawns the rayon worker pool; must be awaited from JS before proving
+#[cfg(all(feature = "wasm-threads", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
+
 // Set panic hook for better error messages in browser
+#[cfg(feature = "panic-hook")]
 #[wasm_bindgen(start)]
 pub fn main() {
     console_error_panic_hook::set_once();
 }
 
-/// Proof output structure that matches the expected format for Sui Move contracts
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ProofOutput {
-    /// Proof component A (compressed: 32 bytes)
-    pub proof_a: Vec<u8>,
-    /// Proof component B (compressed: 64 bytes)  
-    pub proof_b: Vec<u8>,
-    /// Proof component C (compressed: 32 bytes)
-    pub proof_c: Vec<u8>,
-    /// All public inputs in order expected by Move contract
-    pub public_inputs: Vec<String>,
-    pub proof_serialized_hex: String,
-    pub public_inputs_serialized_hex: String,
+// TypeScript declarations for the serde types taken and returned as objects
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+export type Curve = "bn254" | "bls12-381";
+
+export interface ProofInput {
+    c: string;
+    a: string;
+    b: string;
+    curve?: Curve;
+    nonTrivial?: boolean;
+    auditSessionKey?: string;
+    timings?: boolean;
+    uncompressed?: boolean;
+}
+
+export interface Timings {
+    keyLoadMs: number;
+    witnessMs: number;
+    proveMs: number;
+    serializeMs: number;
 }
 
-/// Input structure for proof generation
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ProofInput {
-    // Public inputs
-    pub c: String,
+export interface ProofMeta {
+    randomnessNonce?: string;
+    randomnessCommitment?: string;
+    timings?: Timings;
+}
 
-    // Private inputs
-    pub a: String,
-    pub b: String,
+export interface ProofOutput {
+    curve: Curve;
+    proofA: number[];
+    proofB: number[];
+    proofC: number[];
+    uncompressed?: boolean;
+    publicInputs: string[];
+    proofSerializedHex: string;
+    publicInputsSerializedHex: string;
+    circuitId?: string;
+    meta?: ProofMeta;
 }
+"#;
 
+/// Poseidon hash of decimal or 0x-hex BN254 field elements, with the same
+/// parameters as the circuit gadget; returns the digest as a decimal string.
 #[wasm_bindgen]
-pub fn prove(input_json: &str, proving_key_hex: &str) -> Result<String, JsValue> {
-    // Parse input
-    let input: ProofInput = serde_json::from_str(input_json)
-        .map_err(|e| JsValue::from_str(&format!("Failed to parse input JSON: {}", e)))?;
-
-    // Parse proving key
-    let pk = deserialize_proving_key(proving_key_hex)?;
-
-    // Convert input strings to field elements
-    let c = parse_field_element(&input.c)?;
-    let a = parse_field_element(&input.a)?;
-    let b = parse_field_element(&input.b)?;
-
-    let circuit = Circuit::new(c, a, b)
-        .map_err(|e| JsValue::from_str(&format!("Failed to create circuit: {}", e)))?;
-
-    // Generate proof using deterministic RNG for testing
-    // In production, you should use a secure RNG
-    use rand_chacha::ChaCha20Rng;
-    use rand_core::SeedableRng;
-
-    let mut rng = ChaCha20Rng::from_entropy();
-
-    let cs = ConstraintSystem::<Fr>::new_ref();
-    circuit
-        .clone()
-        .generate_constraints(cs.clone())
-        .expect("Failed to generate constraints");
-    if !cs.is_satisfied().expect("Failed to check constraints") {
-        panic!("Constraints are not satisfied");
-    }
-
-    // Extract public inputs from the circuit using the builder pattern method
-    // This ensures the order matches generate_constraints() automatically
-    let public_inputs_field = circuit.get_public_inputs();
-    let public_inputs_serialized = circuit
-        .get_public_inputs_serialized()
-        .map_err(|e| JsValue::from_str(&format!("Failed to serialize public inputs: {}", e)))?;
-
-    let proof = Groth16::<Bn254>::prove(&pk, circuit, &mut rng)
-        .map_err(|e| JsValue::from_str(&format!("Failed to generate proof: {}", e)))?;
-
-    // Serialize proof components (compressed format)
-    let mut proof_a_bytes = Vec::new();
-    proof
-        .a
-        .serialize_compressed(&mut proof_a_bytes)
-        .map_err(|e| JsValue::from_str(&format!("Failed to serialize proof.a: {}", e)))?;
-
-    let mut proof_b_bytes = Vec::new();
-    proof
-        .b
-        .serialize_compressed(&mut proof_b_bytes)
-        .map_err(|e| JsValue::from_str(&format!("Failed to serialize proof.b: {}", e)))?;
-
-    let mut proof_c_bytes = Vec::new();
-    proof
-        .c
-        .serialize_compressed(&mut proof_c_bytes)
-        .map_err(|e| JsValue::from_str(&format!("Failed to serialize proof.c: {}", e)))?;
-
-    // Serialize proof
-    let mut proof_serialized = Vec::new();
-    proof.serialize_compressed(&mut proof_serialized).unwrap();
-
-    // Convert public inputs to strings for JSON output
-    let public_inputs: Vec<String> = public_inputs_field
+pub fn poseidon_hash(inputs: Vec<String>) -> Result<String, JsValue> {
+    let inputs = inputs
         .iter()
-        .map(|input| input.to_string())
-        .collect();
+        .map(|input| field::parse_auto::<Fr>(input))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(to_js_error)?;
+    let digest = poseidon::hash(&inputs).map_err(to_js_error)?;
 
-    let output = ProofOutput {
-        proof_a: proof_a_bytes,
-        proof_b: proof_b_bytes,
-        proof_c: proof_c_bytes,
-        public_inputs,
-        proof_serialized_hex: hex::encode(proof_serialized),
-        public_inputs_serialized_hex: hex::encode(public_inputs_serialized),
-    };
+    Ok(field::format(&digest, Encoding::Decimal))
+}
+
+/// Pedersen commitment over Baby Jubjub to a decimal or 0x-hex BN254 field
+/// element, with the generators of the circuit gadget; returns the point's
+/// `[x, y]` as decimal strings.
+#[wasm_bindgen]
+pub fn commit(value: &str, blinding: &str) -> Result<Vec<String>, JsValue> {
+    let value = field::parse_auto::<Fr>(value).map_err(to_js_error)?;
+    let blinding = field::parse_auto::<Fr>(blinding).map_err(to_js_error)?;
+    let commitment = PedersenCommitment::commit(value, blinding).map_err(to_js_error)?;
 
-    serde_json::to_string(&output)
-        .map_err(|e| JsValue::from_str(&format!("Failed to serialize output: {}", e)))
+    Ok(commitment
+        .iter()
+        .map(|x| field::format(x, Encoding::Decimal))
+        .collect())
 }
 
+/// Root of a Poseidon Merkle tree of `depth` levels over decimal or 0x-hex
+/// leaves, padded with zero leaves; returns it as a decimal string.
 #[wasm_bindgen]
-pub fn verify(proof_json: &str, verifying_key_hex: &str) -> Result<String, JsValue> {
-    // Parse proof output
-    let proof_output: ProofOutput = serde_json::from_str(proof_json)
-        .map_err(|e| JsValue::from_str(&format!("Failed to parse proof JSON: {}", e)))?;
+pub fn merkle_root(leaves: Vec<String>, depth: usize) -> Result<String, JsValue> {
+    let tree = merkle_tree(&leaves, depth)?;
 
-    // Parse verifying key
-    let vk_bytes = hex::decode(verifying_key_hex)
-        .map_err(|e| JsValue::from_str(&format!("Failed to decode verifying key hex: {}", e)))?;
+    Ok(field::format(&tree.root(), Encoding::Decimal))
+}
 
-    let vk = ark_groth16::VerifyingKey::<Bn254>::deserialize_compressed(&vk_bytes[..])
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize verifying key: {}", e)))?;
+/// Returns the JSON path (`{ siblings, index }`) of the leaf at `index`, the
+/// witness of the membership circuits.
+#[wasm_bindgen]
+pub fn merkle_path(leaves: Vec<String>, depth: usize, index: usize) -> Result<String, JsValue> {
+    let path = merkle_tree(&leaves, depth)?
+        .path(index)
+        .map_err(to_js_error)?;
 
-    // Deserialize proof components
-    let proof_a = ark_bn254::G1Affine::deserialize_compressed(&proof_output.proof_a[..])
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize proof.a: {}", e)))?;
+    serde_json::to_string(&path)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize Merkle path: {}", e)))
+}
 
-    let proof_b = ark_bn254::G2Affine::deserialize_compressed(&proof_output.proof_b[..])
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize proof.b: {}", e)))?;
+fn merkle_tree(leaves: &[String], depth: usize) -> Result<MerkleTree<Fr>, JsValue> {
+    let leaves = leaves
+        .iter()
+        .map(|leaf| field::parse_auto::<Fr>(leaf))
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map_err(to_js_error)?;
+
+    MerkleTree::new(depth, leaves).map_err(to_js_error)
+}
 
-    let proof_c = ark_bn254::G1Affine::deserialize_compressed(&proof_output.proof_c[..])
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize proof.c: {}", e)))?;
+/// Returns JSON describing the circuit: name, version, constraint count,
+/// public input names and the fingerprint of the shipped proving key.
+#[wasm_bindgen]
+pub fn circuit_info(non_trivial: Option<bool>) -> Result<String, JsValue> {
+    let info = api::circuit_info(non_trivial.unwrap_or(false)).map_err(to_js_error)?;
 
-    let proof = ark_groth16::Proof {
-        a: proof_a,
-        b: proof_b,
-        c: proof_c,
-    };
+    serde_json::to_string(&info)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize circuit info: {}", e)))
+}
 
-    // Parse public inputs
-    let public_inputs: Result<Vec<Fr>, JsValue> = proof_output
-        .public_inputs
+/// Ids of the compiled-in sizes of the parameterized circuits, e.g.
+/// `semaphore/depth=20`.
+#[wasm_bindgen]
+pub fn sized_circuits() -> Vec<String> {
+    sizes::SIZED_CIRCUITS
         .iter()
-        .map(|s| parse_field_element(s))
-        .collect();
-    let public_inputs = public_inputs?;
+        .map(|circuit| circuit.id())
+        .collect()
+}
+
+/// Returns JSON describing the compiled-in circuit size `id`: name, version,
+/// parameters and constraint count.
+#[wasm_bindgen]
+pub fn sized_circuit_info(id: &str) -> Result<String, JsValue> {
+    let info = api::sized_circuit_info(id).map_err(to_js_error)?;
+
+    serde_json::to_string(&info)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize circuit info: {}", e)))
+}
 
-    // Verify proof
-    let pvk = ark_groth16::prepare_verifying_key(&vk);
-    let is_valid = Groth16::<Bn254>::verify_proof(&pvk, &proof, &public_inputs)
-        .map_err(|e| JsValue::from_str(&format!("Verification failed: {}", e)))?;
+/// Converts a decimal or 0x-hex field element into `encoding`
+/// (`"decimal"`, `"hex"` or `"le-bytes"`).
+#[wasm_bindgen]
+pub fn format_field(value: &str, encoding: &str) -> Result<String, JsValue> {
+    let encoding = Encoding::from_str(encoding).map_err(to_js_error)?;
+    let value = field::parse_auto::<Fr>(value).map_err(to_js_error)?;
 
-    Ok(is_valid.to_string())
+    Ok(field::format(&value, encoding))
 }
 
-fn deserialize_proving_key(
-    proving_key_hex: &str,
-) -> Result<ark_groth16::ProvingKey<Bn254>, JsValue> {
-    let pk_bytes = hex::decode(proving_key_hex)
-        .map_err(|e| JsValue::from_str(&format!("Failed to decode proving key hex: {}", e)))?;
+/// Parses `value` given in `encoding` and returns it as a decimal string,
+/// the format accepted by `prove`.
+#[wasm_bindgen]
+pub fn parse_field(value: &str, encoding: &str) -> Result<String, JsValue> {
+    let encoding = Encoding::from_str(encoding).map_err(to_js_error)?;
+    let value = field::parse::<Fr>(value, encoding).map_err(to_js_error)?;
 
-    let pk = ark_groth16::ProvingKey::<Bn254>::deserialize_compressed(&pk_bytes[..])
-        .map_err(|e| JsValue::from_str(&format!("Failed to deserialize proving key: {}", e)))?;
+    Ok(field::format(&value, Encoding::Decimal))
+}
 
-    Ok(pk)
+/// Returns a JSON compatibility verdict for a client bundle; see [`compat::check_compat`].
+#[wasm_bindgen]
+pub fn check_compat(
+    client_version: &str,
+    circuit_version: &str,
+    pk_fingerprint: &str,
+) -> Result<String, JsValue> {
+    let verdict = compat::check_compat(client_version, circuit_version, pk_fingerprint)
+        .map_err(to_js_error)?;
+
+    serde_json::to_string(&verdict)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize verdict: {}", e)))
 }
 
-fn parse_field_element(s: &str) -> Result<Fr, JsValue> {
-    // Handle both decimal and hex strings
-    let s = s.trim();
+/// Bytes of linear memory the module currently holds. Wasm memory only
+/// grows, so this is the peak footprint so far, including freed allocations.
+#[wasm_bindgen]
+pub fn memory_usage() -> f64 {
+    let memory: js_sys::WebAssembly::Memory = wasm_bindgen::memory().unchecked_into();
+    // A SharedArrayBuffer under wasm-threads, so read `byteLength` generically
+    js_sys::Reflect::get(&memory.buffer(), &JsValue::from_str("byteLength"))
+        .ok()
+        .and_then(|length| length.as_f64())
+        .unwrap_or(0.0)
+}
 
-    let big_uint = BigUint::from_str(s)
-        .map_err(|e| JsValue::from_str(&format!("Failed to parse decimal '{}': {}", s, e)))?;
-    Ok(Fr::from(big_uint))
+fn to_js_error(e: anyhow::Error) -> JsValue {
+    JsValue::from_str(&e.to_string())
 }
@@ -0,0 +1,162 @@
+use super::to_js_error;
+use crate::api::{self, ProofInput, ProofOutput};
+use crate::prover::ProvingPhase;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// Generates a proof. If `on_progress` is given it is called as
+/// `on_progress(phase, percent)` with phases `witness`, `a-msm`, `b-msm`,
+/// `c-msm`, `serialize` and finally `done` at 100%.
+#[wasm_bindgen]
+pub fn prove(
+    input_json: &str,
+    proving_key_hex: &str,
+    on_progress: Option<js_sys::Function>,
+) -> Result<String, JsValue> {
+    api::prove(input_json, proving_key_hex, progress_reporter(&on_progress)).map_err(to_js_error)
+}
+
+/// Like `prove`, but returns a Promise and yields to the event loop as each
+/// proving phase starts. If `signal` (an `AbortSignal`, or any object with an
+/// `aborted` flag) has been aborted by then, proving stops and the Promise
+/// rejects with `signal.reason`.
+#[wasm_bindgen]
+pub async fn prove_async(
+    input_json: String,
+    proving_key_hex: String,
+    on_progress: Option<js_sys::Function>,
+    signal: Option<js_sys::Object>,
+) -> Result<String, JsValue> {
+    let report = progress_reporter(&on_progress);
+    let checkpoint = |phase: ProvingPhase| {
+        report(phase);
+        let signal = signal.clone();
+        async move {
+            yield_to_event_loop().await;
+            match signal {
+                Some(signal) if is_aborted(&signal) => anyhow::bail!("Proving aborted"),
+                _ => Ok(()),
+            }
+        }
+    };
+
+    api::prove_async(&input_json, &proving_key_hex, checkpoint)
+        .await
+        .map_err(|e| match &signal {
+            Some(signal) if is_aborted(signal) => abort_reason(signal),
+            _ => to_js_error(e),
+        })
+}
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &js_sys::Function, timeout: i32);
+}
+
+/// Resolves on the next macrotask, so pending events such as an abort get to run.
+async fn yield_to_event_loop() {
+    let promise = js_sys::Promise::new(&mut |resolve, _| set_timeout(&resolve, 0));
+    // setTimeout never rejects
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
+
+fn is_aborted(signal: &JsValue) -> bool {
+    js_sys::Reflect::get(signal, &JsValue::from_str("aborted"))
+        .is_ok_and(|aborted| aborted.is_truthy())
+}
+
+fn abort_reason(signal: &JsValue) -> JsValue {
+    match js_sys::Reflect::get(signal, &JsValue::from_str("reason")) {
+        Ok(reason) if !reason.is_undefined() => reason,
+        _ => JsValue::from_str("Proving aborted"),
+    }
+}
+
+/// Like `prove`, but takes a `ProofInput` object and returns a `ProofOutput`
+/// object instead of JSON strings.
+#[wasm_bindgen(unchecked_return_type = "ProofOutput")]
+pub fn prove_typed(
+    #[wasm_bindgen(unchecked_param_type = "ProofInput")] input: JsValue,
+    proving_key_hex: &str,
+    on_progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
+    let input: ProofInput = serde_wasm_bindgen::from_value(input)?;
+    let output = api::prove_typed(&input, proving_key_hex, progress_reporter(&on_progress))
+        .map_err(to_js_error)?;
+
+    output
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .map_err(JsValue::from)
+}
+
+/// Forwards proving phases to an optional JS `on_progress(phase, percent)` callback.
+pub(super) fn progress_reporter(
+    on_progress: &Option<js_sys::Function>,
+) -> impl Fn(ProvingPhase) + Copy + '_ {
+    move |phase: ProvingPhase| {
+        if let Some(callback) = on_progress {
+            // Progress reporting is best effort; a throwing callback must not abort proving
+            let _ = callback.call2(
+                &JsValue::NULL,
+                &JsValue::from_str(phase.name()),
+                &JsValue::from(phase.percent()),
+            );
+        }
+    }
+}
+
+/// Like `prove`, but seeds the prover randomness with the hex-encoded 32-byte
+/// `seed_hex`, so the same input and seed always give the same proof. For
+/// reproducible test vectors only: the seed reveals the private inputs.
+#[wasm_bindgen]
+pub fn prove_deterministic(
+    input_json: &str,
+    proving_key_hex: &str,
+    seed_hex: &str,
+) -> Result<String, JsValue> {
+    api::prove_deterministic(input_json, proving_key_hex, seed_hex, |_| {}).map_err(to_js_error)
+}
+
+/// Like `prove`, but returns `{ proofPointsBytes, publicInputsBytes }` as
+/// BCS-encoded `Uint8Array`s, ready to pass as pure transaction arguments.
+#[wasm_bindgen]
+pub fn prove_bcs(
+    input_json: &str,
+    proving_key_hex: &str,
+    on_progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
+    let proof_json = prove(input_json, proving_key_hex, on_progress)?;
+    let proof_output: ProofOutput = serde_json::from_str(&proof_json)
+        .map_err(|e| JsValue::from_str(&format!("Failed to parse proof JSON: {}", e)))?;
+    let bcs = proof_output.to_bcs().map_err(to_js_error)?;
+
+    let result = js_sys::Object::new();
+    js_sys::Reflect::set(
+        &result,
+        &JsValue::from_str("proofPointsBytes"),
+        &js_sys::Uint8Array::from(&bcs.proof_points_bytes[..]),
+    )?;
+    js_sys::Reflect::set(
+        &result,
+        &JsValue::from_str("publicInputsBytes"),
+        &js_sys::Uint8Array::from(&bcs.public_inputs_bytes[..]),
+    )?;
+
+    Ok(result.into())
+}
+
+/// Re-randomizes a proof from `prove` so it cannot be linked to its prover;
+/// returns the new `ProofOutput` JSON with the same public inputs.
+#[wasm_bindgen]
+pub fn rerandomize_proof(proof_json: &str, verifying_key_hex: &str) -> Result<String, JsValue> {
+    api::rerandomize(proof_json, verifying_key_hex).map_err(to_js_error)
+}
+
+/// Evaluates the circuit on the private inputs of a `prove` input (`c` may
+/// be omitted) and returns `{"c": "<a * b>"}` as JSON, so the public input
+/// can be filled in or checked before proving.
+#[wasm_bindgen]
+pub fn compute_expected_public(input_json: &str) -> Result<String, JsValue> {
+    api::compute_expected_public(input_json).map_err(to_js_error)
+}
@@ -0,0 +1,86 @@
+use super::to_js_error;
+use crate::api::{self, ProofOutput, VerificationResult};
+use crate::curve::Curve;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Verifies a proof from `prove` and returns JSON `{"valid": bool, "reason"?: string}`,
+/// where `reason` is `pairing_mismatch`, `bad_point`, `input_count_mismatch` or
+/// `circuit_mismatch`.
+#[wasm_bindgen]
+pub fn verify(proof_json: &str, verifying_key_hex: &str) -> Result<String, JsValue> {
+    let result = api::verify_detailed(proof_json, verifying_key_hex).map_err(to_js_error)?;
+
+    result_to_json(&result)
+}
+
+fn result_to_json(result: &VerificationResult) -> Result<String, JsValue> {
+    serde_json::to_string(result)
+        .map_err(|e| JsValue::from_str(&format!("Failed to serialize verification result: {}", e)))
+}
+
+/// Verifies a hex-encoded proof (`proofSerializedHex`) against
+/// decimal or 0x-hex public inputs, for callers that only have the proof
+/// bytes and the claimed inputs rather than a full `ProofOutput`.
+///
+/// The proof may be compressed or, from `prove` with `uncompressed` set,
+/// uncompressed; the encoding is told apart by its length.
+///
+/// Returns the same JSON as `verify`. `curve` defaults to `"bn254"`.
+#[wasm_bindgen]
+pub fn verify_with_inputs(
+    proof_hex: &str,
+    public_inputs: Vec<String>,
+    verifying_key_hex: &str,
+    curve: Option<String>,
+) -> Result<String, JsValue> {
+    let curve = match curve {
+        Some(curve) => Curve::from_str(&curve).map_err(to_js_error)?,
+        None => Curve::default(),
+    };
+    let result = VerificationResult::from_outcome(api::verify_with_inputs(
+        curve,
+        proof_hex,
+        &public_inputs,
+        verifying_key_hex,
+    ))
+    .map_err(to_js_error)?;
+
+    result_to_json(&result)
+}
+
+/// Verifies the `proofSerializedHex` and `publicInputsSerializedHex` fields
+/// of a proof, the same bytes submitted on chain, and returns the same JSON as
+/// `verify`. `curve` defaults to `"bn254"`.
+#[wasm_bindgen]
+pub fn verify_serialized(
+    proof_serialized_hex: &str,
+    public_inputs_serialized_hex: &str,
+    verifying_key_hex: &str,
+    curve: Option<String>,
+) -> Result<String, JsValue> {
+    let curve = match curve {
+        Some(curve) => Curve::from_str(&curve).map_err(to_js_error)?,
+        None => Curve::default(),
+    };
+    let result = VerificationResult::from_outcome(api::verify_serialized(
+        curve,
+        proof_serialized_hex,
+        public_inputs_serialized_hex,
+        verifying_key_hex,
+    ))
+    .map_err(to_js_error)?;
+
+    result_to_json(&result)
+}
+
+/// Like `verify`, but takes a `ProofOutput` object and returns a boolean.
+#[wasm_bindgen]
+pub fn verify_typed(
+    #[wasm_bindgen(unchecked_param_type = "ProofOutput")] proof: JsValue,
+    verifying_key_hex: &str,
+) -> Result<bool, JsValue> {
+    let proof: ProofOutput = serde_wasm_bindgen::from_value(proof)?;
+
+    api::verify_typed(&proof, verifying_key_hex).map_err(to_js_error)
+}
@@ -0,0 +1,269 @@
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{
+    ConstraintLayer, ConstraintMatrices, ConstraintSynthesizer, ConstraintSystem,
+    ConstraintSystemRef, OptimizationGoal,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::{mpsc, Mutex};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+use tracing_subscriber::{layer::SubscriberExt, Registry};
+use zeroize::Zeroize;
+
+/// Full variable assignment of a synthesized circuit.
+#[derive(Debug, Clone)]
+pub struct Witness<F: PrimeField = Fr> {
+    /// Instance assignment, including the leading constant `1`.
+    pub instance: Vec<F>,
+    /// Private witness assignment.
+    pub witness: Vec<F>,
+}
+
+impl<F: PrimeField> Witness<F> {
+    /// Public inputs in the order expected by the verifier (without the constant `1`).
+    pub fn public_inputs(&self) -> &[F] {
+        &self.instance[1..]
+    }
+}
+
+/// The private assignment is wiped when the witness is dropped.
+impl<F: PrimeField> Drop for Witness<F> {
+    fn drop(&mut self) {
+        self.witness.zeroize();
+    }
+}
+
+/// Synthesizes `circuit` and returns its assignment.
+///
+/// If the constraints are not satisfied, `circuit` is synthesized again under
+/// [`traced`] so that the error names the first failing constraint.
+///
+/// # Errors
+/// Returns error if constraint generation fails or the constraints are not satisfied.
+pub fn generate<F, C>(circuit: C) -> anyhow::Result<Witness<F>>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F> + Clone,
+{
+    Ok(assign(circuit, false)?.0)
+}
+
+/// Like [`generate`], also returning the R1CS matrices of the circuit.
+///
+/// # Errors
+/// Returns error if constraint generation fails or the constraints are not satisfied.
+pub fn generate_with_matrices<F, C>(
+    circuit: C,
+) -> anyhow::Result<(Witness<F>, ConstraintMatrices<F>)>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F> + Clone,
+{
+    let (witness, matrices) = assign(circuit, true)?;
+    let matrices = matrices.ok_or_else(|| anyhow::anyhow!("Constraint system has no matrices"))?;
+    Ok((witness, matrices))
+}
+
+fn assign<F, C>(
+    circuit: C,
+    with_matrices: bool,
+) -> anyhow::Result<(Witness<F>, Option<ConstraintMatrices<F>>)>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F> + Clone,
+{
+    let span = tracing::debug_span!("synthesize", constraints = tracing::field::Empty);
+    let _entered = span.enter();
+
+    // Tracing records a path for every constraint, too slow for every proof:
+    // only an unsatisfied circuit is synthesized again under `traced`, to
+    // name the failing constraint
+    let cs = new_constraint_system();
+    if !synthesize_unchecked(circuit.clone(), cs.clone())? {
+        drop(cs);
+        return Err(traced(|| {
+            synthesize(circuit, new_constraint_system())
+                .err()
+                .unwrap_or_else(|| anyhow::anyhow!("Constraints are not satisfied"))
+        }));
+    }
+    span.record("constraints", cs.num_constraints());
+    let matrices = if with_matrices {
+        cs.to_matrices()
+    } else {
+        None
+    };
+
+    let mut cs = cs
+        .into_inner()
+        .ok_or_else(|| anyhow::anyhow!("Constraint system is still referenced"))?;
+    let witness = Witness {
+        instance: std::mem::take(&mut cs.instance_assignment),
+        witness: std::mem::take(&mut cs.witness_assignment),
+    };
+    Ok((witness, matrices))
+}
+
+fn new_constraint_system<F: PrimeField>() -> ConstraintSystemRef<F> {
+    let cs = ConstraintSystem::<F>::new_ref();
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    cs
+}
+
+/// Runs `f` with `ns!` namespaces traced by a subscriber of its own, so that
+/// constraint systems synthesized in `f` record the path of every constraint.
+///
+/// Those constraint systems must also be dropped in `f`: their traces hold
+/// spans of this subscriber, and `tracing-subscriber` panics when they are
+/// closed under another one, such as the one installed by [`crate::logging`].
+/// Spans and events created in `f` are not logged, and spans of the global
+/// subscriber must not be entered or left in it.
+pub fn traced<T>(f: impl FnOnce() -> T) -> T {
+    let subscriber = Registry::default().with(ConstraintLayer::default());
+    tracing::subscriber::with_default(subscriber, f)
+}
+
+/// Synthesizes `circuit` into `cs` and checks that every constraint holds.
+///
+/// Inside [`traced`], an unsatisfied constraint is reported with its `ns!`
+/// path and the values of the variables it involves.
+///
+/// # Errors
+/// Returns error if constraint generation fails or the constraints are not satisfied.
+pub fn synthesize<F, C>(circuit: C, cs: ConstraintSystemRef<F>) -> anyhow::Result<()>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+{
+    if synthesize_unchecked(circuit, cs.clone())? {
+        return Ok(());
+    }
+
+    Err(diagnose_unsatisfied(&cs))
+}
+
+/// Synthesizes `circuit` into `cs` and returns whether every constraint holds.
+fn synthesize_unchecked<F, C>(circuit: C, cs: ConstraintSystemRef<F>) -> anyhow::Result<bool>
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F>,
+{
+    circuit
+        .generate_constraints(cs.clone())
+        .map_err(|e| anyhow::anyhow!("Failed to generate constraints: {}", e))?;
+    cs.finalize();
+
+    cs.is_satisfied()
+        .map_err(|e| anyhow::anyhow!("Failed to check constraints: {}", e))
+}
+
+/// Builds an error describing the first unsatisfied constraint in `cs`.
+fn diagnose_unsatisfied<F: PrimeField>(cs: &ConstraintSystemRef<F>) -> anyhow::Error {
+    let (Some(matrices), Some(inner)) = (cs.to_matrices(), cs.borrow()) else {
+        return anyhow::anyhow!("Constraints are not satisfied");
+    };
+
+    let num_instance = inner.instance_assignment.len();
+    let value = |index: usize| {
+        if index < num_instance {
+            inner.instance_assignment[index]
+        } else {
+            inner.witness_assignment[index - num_instance]
+        }
+    };
+    let eval = |row: &[(F, usize)]| {
+        row.iter()
+            .fold(F::ZERO, |acc, (coeff, index)| acc + *coeff * value(*index))
+    };
+
+    let rows = matrices.a.iter().zip(&matrices.b).zip(&matrices.c);
+    for (i, ((a, b), c)) in rows.enumerate() {
+        let (a_val, b_val, c_val) = (eval(a), eval(b), eval(c));
+        if a_val * b_val == c_val {
+            continue;
+        }
+
+        let name = cs
+            .constraint_names()
+            .and_then(|names| names.get(i).cloned())
+            .unwrap_or_else(|| format!("#{}", i));
+
+        let mut indices = a
+            .iter()
+            .chain(b)
+            .chain(c)
+            .map(|(_, index)| *index)
+            .filter(|index| *index != 0)
+            .collect::<Vec<_>>();
+        indices.sort_unstable();
+        indices.dedup();
+
+        let involved = indices
+            .into_iter()
+            .map(|index| {
+                let label = if index < num_instance {
+                    format!("instance[{}]", index)
+                } else {
+                    format!("witness[{}]", index - num_instance)
+                };
+                format!("{} = {}", label, value(index))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        return anyhow::anyhow!(
+            "Constraint '{}' is not satisfied: {} * {} != {} (involved: {})",
+            name,
+            a_val,
+            b_val,
+            c_val,
+            involved
+        );
+    }
+
+    anyhow::anyhow!("Constraints are not satisfied")
+}
+
+/// Generates witnesses for a batch of circuits on `workers` threads.
+///
+/// Finished witnesses queue in a channel of `capacity` slots; once it is full,
+/// workers block holding their result until `sink` (running on the calling
+/// thread) has consumed earlier ones. At most `capacity + workers` finished
+/// witnesses are therefore held at any time, plus the one `sink` is handling,
+/// so memory stays bounded regardless of the batch size. Results are delivered as
+/// `(index, result)` pairs in completion order, where `index` is the position of
+/// the circuit in `circuits`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn generate_batch<F, C, I, S>(circuits: I, workers: usize, capacity: usize, mut sink: S)
+where
+    F: PrimeField,
+    C: ConstraintSynthesizer<F> + Clone + Send,
+    I: IntoIterator<Item = C>,
+    I::IntoIter: Send,
+    S: FnMut(usize, anyhow::Result<Witness<F>>),
+{
+    let queue = Mutex::new(circuits.into_iter().enumerate());
+    let (tx, rx) = mpsc::sync_channel(capacity);
+
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            let tx = tx.clone();
+            let queue = &queue;
+            scope.spawn(move || loop {
+                let next = queue.lock().map(|mut circuits| circuits.next());
+                let Ok(Some((index, circuit))) = next else {
+                    break;
+                };
+                if tx.send((index, generate(circuit))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        for (index, result) in rx {
+            sink(index, result);
+        }
+    });
+}
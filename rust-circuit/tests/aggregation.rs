@@ -0,0 +1,109 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, ProvingKey};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::aggregation::{self, AggregationSrs};
+use vortex::circuit::Circuit;
+use vortex::prover::{self, InputCountError, ProofBundle};
+
+fn setup(rng: &mut ChaCha20Rng) -> ProvingKey<Bn254> {
+    Groth16::<Bn254>::generate_random_parameters_with_reduction(Circuit::<Fr>::empty(), rng)
+        .unwrap()
+}
+
+/// Proofs of `3 * 5`, `7 * 11` and `13 * 17`.
+fn bundles(pk: &ProvingKey<Bn254>, rng: &mut ChaCha20Rng) -> Vec<ProofBundle> {
+    [(3u64, 5u64), (7, 11), (13, 17)]
+        .into_iter()
+        .map(|(a, b)| {
+            let circuit = Circuit::new(Fr::from(a * b), Fr::from(a), Fr::from(b), false).unwrap();
+            prover::prove(circuit, pk, rng).unwrap()
+        })
+        .collect()
+}
+
+fn public_inputs(bundles: &[ProofBundle]) -> Vec<Vec<Fr>> {
+    bundles
+        .iter()
+        .map(|bundle| bundle.public_inputs.clone())
+        .collect()
+}
+
+#[test]
+fn three_proofs_padded_to_four_verify() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = setup(&mut rng);
+    let bundles = bundles(&pk, &mut rng);
+    let srs = AggregationSrs::<Bn254>::setup(4, &mut rng).unwrap();
+
+    let proof = aggregation::aggregate(&srs, &bundles).unwrap();
+    let valid = aggregation::verify_aggregate(
+        &pk.vk,
+        &srs.verifier_key(),
+        &public_inputs(&bundles),
+        &proof,
+    );
+    assert!(valid.unwrap());
+}
+
+#[test]
+fn tampered_input_is_rejected() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = setup(&mut rng);
+    let bundles = bundles(&pk, &mut rng);
+    let srs = AggregationSrs::<Bn254>::setup(4, &mut rng).unwrap();
+
+    let proof = aggregation::aggregate(&srs, &bundles).unwrap();
+    let mut inputs = public_inputs(&bundles);
+    inputs[1][0] += Fr::from(1u64);
+    let valid = aggregation::verify_aggregate(&pk.vk, &srs.verifier_key(), &inputs, &proof);
+    assert!(!valid.unwrap());
+}
+
+#[test]
+fn tampered_proof_is_rejected() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = setup(&mut rng);
+    let mut bundles = bundles(&pk, &mut rng);
+    let srs = AggregationSrs::<Bn254>::setup(4, &mut rng).unwrap();
+
+    // Each proof is well-formed, but C no longer matches A and B
+    let c = bundles[0].proof.c;
+    bundles[0].proof.c = bundles[1].proof.c;
+    bundles[1].proof.c = c;
+
+    let proof = aggregation::aggregate(&srs, &bundles).unwrap();
+    let valid = aggregation::verify_aggregate(
+        &pk.vk,
+        &srs.verifier_key(),
+        &public_inputs(&bundles),
+        &proof,
+    );
+    assert!(!valid.unwrap());
+}
+
+#[test]
+fn srs_too_small_is_an_error() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = setup(&mut rng);
+    let bundles = bundles(&pk, &mut rng);
+    let srs = AggregationSrs::<Bn254>::setup(2, &mut rng).unwrap();
+
+    assert!(aggregation::aggregate(&srs, &bundles).is_err());
+}
+
+#[test]
+fn key_without_input_points_is_an_error() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = setup(&mut rng);
+    let bundles = bundles(&pk, &mut rng);
+    let srs = AggregationSrs::<Bn254>::setup(4, &mut rng).unwrap();
+    let proof = aggregation::aggregate(&srs, &bundles).unwrap();
+
+    let mut vk = pk.vk.clone();
+    vk.gamma_abc_g1.clear();
+    let error =
+        aggregation::verify_aggregate(&vk, &srs.verifier_key(), &public_inputs(&bundles), &proof)
+            .unwrap_err();
+    assert!(error.is::<InputCountError>());
+}
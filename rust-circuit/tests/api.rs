@@ -0,0 +1,540 @@
+use ark_bn254::Bn254;
+use ark_groth16::{Proof, ProvingKey, VerifyingKey};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, Compress, Validate};
+use serde_json::Value;
+use std::cell::RefCell;
+use std::fs;
+use std::future::Future;
+use std::path::Path;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use vortex::api::{
+    self, ExpectedPublic, ProofInput, ProofOutput, VerificationFailure, VerificationResult,
+};
+use vortex::curve::point::PointError;
+use vortex::curve::Curve;
+use vortex::keys;
+use vortex::keys::convert::KeyKind;
+use vortex::keys::header::{self, KeyHeader};
+use vortex::prover::{InputCountError, ProvingPhase};
+
+/// The BN254 test vectors, made with the keys in `keys/`.
+fn vectors() -> Value {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors/bn254.json");
+    serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// The first test vector proof, for `a = 5`, `b = 6`.
+fn proof() -> ProofOutput {
+    serde_json::from_value(vectors()["cases"][0]["proof"].clone()).unwrap()
+}
+
+fn vk_hex() -> String {
+    vectors()["verifyingKeyHex"].as_str().unwrap().to_string()
+}
+
+fn pk_hex() -> String {
+    vectors()["provingKeyHex"].as_str().unwrap().to_string()
+}
+
+/// The input of the first test vector.
+fn input() -> String {
+    vectors()["cases"][0]["input"].to_string()
+}
+
+#[test]
+fn verify_with_inputs_checks_the_supplied_inputs() {
+    let proof = proof();
+    let verify = |inputs: &[&str]| {
+        let inputs = inputs.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        api::verify_with_inputs(
+            Curve::Bn254,
+            &proof.proof_serialized_hex,
+            &inputs,
+            &vk_hex(),
+        )
+    };
+
+    assert!(verify(&["30"]).unwrap());
+    assert!(verify(&["0x1e"]).unwrap());
+    assert!(!verify(&["31"]).unwrap());
+
+    let error = verify(&["30", "1"]).unwrap_err();
+    let mismatch = error.downcast_ref::<InputCountError>().unwrap();
+    assert_eq!((mismatch.expected, mismatch.actual), (1, 2));
+
+    assert!(verify(&["thirty"]).is_err());
+}
+
+#[test]
+fn non_canonical_public_inputs_are_rejected() {
+    // 30 + r, the same field element spelled differently
+    let c_plus_r = "21888242871839275222246405745257275088548364400416034343698204186575808495647";
+    let proof = proof();
+
+    let inputs = [c_plus_r.to_string()];
+    let error = api::verify_with_inputs(
+        Curve::Bn254,
+        &proof.proof_serialized_hex,
+        &inputs,
+        &vk_hex(),
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(error.contains("not below the field order"), "{}", error);
+
+    let mut output = proof;
+    output.public_inputs = vec![c_plus_r.into()];
+    assert!(api::verify_typed(&output, &vk_hex()).is_err());
+    output.public_inputs = vec![format!("0x{:x}", 30)];
+    assert!(api::verify_typed(&output, &vk_hex()).unwrap());
+}
+
+#[test]
+fn verify_serialized_checks_the_compact_fields() {
+    let proof = proof();
+    let verify = |inputs_hex: &str| {
+        api::verify_serialized(
+            Curve::Bn254,
+            &proof.proof_serialized_hex,
+            inputs_hex,
+            &vk_hex(),
+        )
+    };
+
+    assert!(verify(&proof.public_inputs_serialized_hex).unwrap());
+    assert!(verify(&format!("0x{}", proof.public_inputs_serialized_hex)).unwrap());
+
+    // 31, little-endian
+    let other = format!("1f{}", "00".repeat(31));
+    assert!(!verify(&other).unwrap());
+
+    let error = verify(&"00".repeat(64)).unwrap_err();
+    assert!(error.is::<InputCountError>());
+
+    let error = verify(&"00".repeat(31)).unwrap_err().to_string();
+    assert!(error.contains("multiple of 32 bytes"), "{}", error);
+    assert!(verify("zz").is_err());
+}
+
+#[test]
+fn identity_proof_points_are_bad_points() {
+    let mut proof = proof();
+    proof.proof_a = vec![0; proof.proof_a.len()];
+    proof.proof_a[31] = 0x40; // infinity flag
+
+    let error = api::verify_typed(&proof, &vk_hex()).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<PointError>().unwrap().code(),
+        "E_POINT_IDENTITY"
+    );
+
+    let result = api::verify_detailed(&serde_json::to_string(&proof).unwrap(), &vk_hex());
+    assert_eq!(result.unwrap().reason, Some(VerificationFailure::BadPoint));
+}
+
+#[test]
+fn prove_deterministic_depends_only_on_the_seed() {
+    let prove = |seed: &str| api::prove_deterministic(&input(), &pk_hex(), seed, |_| {});
+    let seed = "11".repeat(32);
+
+    let proof = prove(&seed).unwrap();
+    assert_eq!(prove(&format!("0x{}", seed)).unwrap(), proof);
+    let other = prove(&"22".repeat(32)).unwrap();
+    assert_ne!(other, proof);
+    for proof in [proof, other] {
+        assert!(api::verify(&proof, &vk_hex()).unwrap());
+    }
+
+    let error = prove(&"11".repeat(31)).unwrap_err().to_string();
+    assert!(error.contains("32 bytes"), "{}", error);
+    assert!(prove("seed").is_err());
+}
+
+#[test]
+fn prove_draws_fresh_randomness() {
+    let first = api::prove(&input(), &pk_hex(), |_| {}).unwrap();
+    let second = api::prove(&input(), &pk_hex(), |_| {}).unwrap();
+    assert_ne!(first, second);
+    assert!(api::verify(&first, &vk_hex()).unwrap());
+    assert!(api::verify(&second, &vk_hex()).unwrap());
+}
+
+#[test]
+fn rerandomized_proofs_are_new_and_still_verify() {
+    let proof = proof();
+    let json = serde_json::to_string(&proof).unwrap();
+
+    let rerandomized: ProofOutput =
+        serde_json::from_str(&api::rerandomize(&json, &vk_hex()).unwrap()).unwrap();
+    assert_ne!(
+        rerandomized.proof_serialized_hex,
+        proof.proof_serialized_hex
+    );
+    assert_eq!(rerandomized.public_inputs, proof.public_inputs);
+    assert_eq!(rerandomized.circuit_id, proof.circuit_id);
+    assert!(api::verify_typed(&rerandomized, &vk_hex()).unwrap());
+
+    // An invalid proof stays invalid
+    let mut invalid = proof;
+    invalid.public_inputs = vec!["31".into()];
+    let json = serde_json::to_string(&invalid).unwrap();
+    let rerandomized = api::rerandomize(&json, &vk_hex()).unwrap();
+    assert!(!api::verify(&rerandomized, &vk_hex()).unwrap());
+
+    assert!(api::rerandomize("{}", &vk_hex()).is_err());
+}
+
+#[test]
+fn compute_expected_public_multiplies_the_factors() {
+    let expected = |json: &str| {
+        api::compute_expected_public(json)
+            .map(|json| serde_json::from_str::<ExpectedPublic>(&json).unwrap().c)
+    };
+
+    assert_eq!(expected(r#"{"a": "5", "b": "6"}"#).unwrap(), "30");
+    assert_eq!(
+        expected(r#"{"a": "0x10", "b": "2", "c": "1"}"#).unwrap(),
+        "32"
+    );
+    assert_eq!(
+        expected(r#"{"a": "1", "b": "7", "curve": "bn254"}"#).unwrap(),
+        "7"
+    );
+
+    // The checks proving applies
+    let error = expected(r#"{"a": "1", "b": "7", "nonTrivial": true}"#)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("must not be 0 or 1"), "{}", error);
+    assert!(expected(r#"{"a": "5"}"#).is_err());
+    assert!(expected(r#"{"a": "five", "b": "6"}"#).is_err());
+}
+
+#[test]
+fn typed_proving_round_trips_through_typed_verification() {
+    let input: ProofInput = serde_json::from_str(&input()).unwrap();
+    let proof = api::prove_typed(&input, &pk_hex(), |_| {}).unwrap();
+
+    assert_eq!(proof.public_inputs, ["30"]);
+    assert!(api::verify_typed(&proof, &vk_hex()).unwrap());
+
+    let wrong: ProofInput = serde_json::from_str(r#"{"a": "5", "b": "6", "c": "31"}"#).unwrap();
+    assert!(api::prove_typed(&wrong, &pk_hex(), |_| {}).is_err());
+}
+
+#[test]
+fn circuit_info_describes_the_shipped_keys() {
+    let info = api::circuit_info(false).unwrap();
+    assert_eq!(
+        (info.name.as_str(), info.non_trivial),
+        ("multiplication", false)
+    );
+    assert_eq!(info.num_constraints, 2);
+    assert_eq!(info.num_public_inputs, 1);
+    assert_eq!(info.public_input_names, ["c"]);
+
+    // The checked-in hex key is the one clients load
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("keys/proving_key.hex");
+    let pk_bytes = hex::decode(fs::read_to_string(path).unwrap().trim()).unwrap();
+    assert_eq!(
+        info.proving_key_fingerprint,
+        Some(keys::fingerprint(&pk_bytes))
+    );
+
+    // No key ships for the non-trivial variant
+    let info = api::circuit_info(true).unwrap();
+    assert_eq!(info.num_constraints, 6);
+    assert_eq!(info.proving_key_fingerprint, None);
+}
+
+/// Runs `future`, whose checkpoints never actually wait, to completion.
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn prove_async_passes_every_checkpoint() {
+    let phases = RefCell::new(Vec::new());
+    let proof = block_on(api::prove_async(&input(), &pk_hex(), |phase| {
+        phases.borrow_mut().push(phase);
+        async { Ok(()) }
+    }))
+    .unwrap();
+
+    assert!(api::verify(&proof, &vk_hex()).unwrap());
+    assert_eq!(phases.borrow().first(), Some(&ProvingPhase::Witness));
+    assert_eq!(phases.borrow().last(), Some(&ProvingPhase::Done));
+}
+
+#[test]
+fn failing_checkpoint_aborts_proving() {
+    let phases = RefCell::new(Vec::new());
+    let error = block_on(api::prove_async(&input(), &pk_hex(), |phase| {
+        phases.borrow_mut().push(phase);
+        async move {
+            match phase {
+                ProvingPhase::BMsm => anyhow::bail!("Proving aborted"),
+                _ => Ok(()),
+            }
+        }
+    }))
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "Proving aborted");
+    assert_eq!(phases.borrow().last(), Some(&ProvingPhase::BMsm));
+}
+
+#[test]
+fn verify_detailed_reports_why_a_proof_is_rejected() {
+    let verify = |proof: &ProofOutput| {
+        api::verify_detailed(&serde_json::to_string(proof).unwrap(), &vk_hex()).unwrap()
+    };
+
+    let valid = verify(&proof());
+    assert_eq!((valid.valid, valid.reason), (true, None));
+
+    let mut other_input = proof();
+    other_input.public_inputs = vec!["31".into()];
+    assert_eq!(
+        verify(&other_input).reason,
+        Some(VerificationFailure::PairingMismatch)
+    );
+
+    let mut extra_input = proof();
+    extra_input.public_inputs.push("1".into());
+    assert_eq!(
+        verify(&extra_input).reason,
+        Some(VerificationFailure::InputCountMismatch)
+    );
+
+    let mut truncated = proof();
+    truncated.proof_c.pop();
+    assert_eq!(
+        verify(&truncated).reason,
+        Some(VerificationFailure::BadPoint)
+    );
+
+    // Malformed data is an error rather than a rejection
+    assert!(api::verify_detailed("{}", &vk_hex()).is_err());
+    assert!(api::verify_detailed(&serde_json::to_string(&proof()).unwrap(), "00").is_err());
+}
+
+#[test]
+fn verification_result_serializes_reason_in_snake_case() {
+    let json = |result: &VerificationResult| serde_json::to_string(result).unwrap();
+    let proof = serde_json::to_string(&proof()).unwrap();
+
+    assert_eq!(
+        json(&api::verify_detailed(&proof, &vk_hex()).unwrap()),
+        r#"{"valid":true}"#
+    );
+    let rejected = VerificationResult {
+        valid: false,
+        reason: Some(VerificationFailure::InputCountMismatch),
+    };
+    assert_eq!(
+        json(&rejected),
+        r#"{"valid":false,"reason":"input_count_mismatch"}"#
+    );
+}
+
+#[test]
+fn wrong_input_count_is_a_dedicated_error() {
+    for inputs in [vec![], vec!["30".to_string(), "30".to_string()]] {
+        let mut proof = proof();
+        proof.public_inputs = inputs.clone();
+
+        let error = api::verify_typed(&proof, &vk_hex()).unwrap_err();
+        let mismatch = error.downcast_ref::<InputCountError>().unwrap();
+        assert_eq!(
+            *mismatch,
+            InputCountError {
+                expected: 1,
+                actual: inputs.len()
+            }
+        );
+        assert_eq!(mismatch.code(), "E_INPUT_COUNT");
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "Expected 1 public inputs, got {} (E_INPUT_COUNT)",
+                inputs.len()
+            )
+        );
+    }
+}
+
+/// The first test vector input, with `timings` set to `timings`.
+fn input_with_timings(timings: Value) -> String {
+    let mut input = vectors()["cases"][0]["input"].clone();
+    input["timings"] = timings;
+    input.to_string()
+}
+
+#[test]
+fn timings_are_reported_only_on_request() {
+    let prove = |input: &str| {
+        api::prove(input, &pk_hex(), |_| {})
+            .map(|json| serde_json::from_str::<ProofOutput>(&json).unwrap())
+    };
+
+    let proof = prove(&input_with_timings(Value::Bool(true))).unwrap();
+    let timings = proof
+        .meta
+        .as_ref()
+        .and_then(|meta| meta.timings.clone())
+        .unwrap();
+    for ms in [
+        timings.key_load_ms,
+        timings.witness_ms,
+        timings.prove_ms,
+        timings.serialize_ms,
+    ] {
+        assert!(ms.is_finite() && ms >= 0.0, "{:?}", timings);
+    }
+    assert!(api::verify_typed(&proof, &vk_hex()).unwrap());
+
+    let proof = prove(&input_with_timings(Value::Bool(false))).unwrap();
+    assert!(proof.meta.and_then(|meta| meta.timings).is_none());
+
+    assert!(prove(&input_with_timings(Value::from("yes"))).is_err());
+}
+
+#[test]
+fn timings_with_a_loaded_key_skip_key_loading() {
+    let pk_bytes = hex::decode(pk_hex()).unwrap();
+    let pk = ProvingKey::<Bn254>::deserialize_compressed(&pk_bytes[..]).unwrap();
+
+    let json = api::prove_with_key(&input_with_timings(Value::Bool(true)), &pk, |_| {}).unwrap();
+    let proof: ProofOutput = serde_json::from_str(&json).unwrap();
+    assert_eq!(proof.meta.unwrap().timings.unwrap().key_load_ms, 0.0);
+
+    let timings = serde_json::from_str::<Value>(&json).unwrap()["meta"]["timings"].clone();
+    let mut keys = timings.as_object().unwrap().keys().collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, ["keyLoadMs", "proveMs", "serializeMs", "witnessMs"]);
+}
+
+/// The first test vector, proved with its seed and `uncompressed` set.
+fn uncompressed_proof() -> ProofOutput {
+    let vectors = vectors();
+    let mut input = vectors["cases"][0]["input"].clone();
+    input["uncompressed"] = Value::Bool(true);
+    let seed = vectors["proofSeed"].as_str().unwrap();
+
+    let json = api::prove_deterministic(&input.to_string(), &pk_hex(), seed, |_| {}).unwrap();
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn uncompressed_proofs_hold_the_same_points() {
+    let compressed = proof();
+    let uncompressed = uncompressed_proof();
+    assert!(uncompressed.uncompressed);
+    assert_eq!(
+        [
+            uncompressed.proof_a.len(),
+            uncompressed.proof_b.len(),
+            uncompressed.proof_c.len()
+        ],
+        [64, 128, 64]
+    );
+    assert_eq!(uncompressed.public_inputs, compressed.public_inputs);
+
+    let decode = |hex: &str, compress| {
+        Proof::<Bn254>::deserialize_with_mode(
+            &hex::decode(hex).unwrap()[..],
+            compress,
+            Validate::Yes,
+        )
+        .unwrap()
+    };
+    assert_eq!(
+        decode(&uncompressed.proof_serialized_hex, Compress::No),
+        decode(&compressed.proof_serialized_hex, Compress::Yes)
+    );
+}
+
+#[test]
+fn uncompressed_proofs_verify_on_every_path() {
+    let uncompressed = uncompressed_proof();
+
+    assert!(api::verify_typed(&uncompressed, &vk_hex()).unwrap());
+    assert!(api::verify_with_inputs(
+        Curve::Bn254,
+        &uncompressed.proof_serialized_hex,
+        &uncompressed.public_inputs,
+        &vk_hex()
+    )
+    .unwrap());
+    assert!(api::verify_serialized(
+        Curve::Bn254,
+        &uncompressed.proof_serialized_hex,
+        &uncompressed.public_inputs_serialized_hex,
+        &vk_hex()
+    )
+    .unwrap());
+
+    // Compressed points read as uncompressed ones are malformed
+    let mut mislabelled = proof();
+    mislabelled.uncompressed = true;
+    let error = api::verify_typed(&mislabelled, &vk_hex()).unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<PointError>().unwrap().code(),
+        "E_POINT_MALFORMED"
+    );
+}
+
+#[test]
+fn proof_bytes_of_any_other_length_are_rejected() {
+    let proof = proof();
+    let verify = |proof_hex: &str| {
+        api::verify_serialized(
+            Curve::Bn254,
+            proof_hex,
+            &proof.public_inputs_serialized_hex,
+            &vk_hex(),
+        )
+    };
+
+    // 128 compressed bytes with trailing junk, and one byte short
+    let padded = format!("{}00", proof.proof_serialized_hex);
+    let error = verify(&padded).unwrap_err().to_string();
+    assert!(
+        error.contains("128 bytes compressed or 256 bytes uncompressed, got 129"),
+        "{}",
+        error
+    );
+    let truncated = &proof.proof_serialized_hex[..proof.proof_serialized_hex.len() - 2];
+    assert!(verify(truncated).is_err());
+
+    let uncompressed = uncompressed_proof();
+    assert!(verify(&format!("{}00", uncompressed.proof_serialized_hex)).is_err());
+}
+
+#[test]
+fn uncompressed_verifying_keys_load_by_their_header() {
+    let vk_bytes = hex::decode(vk_hex()).unwrap();
+    let vk = VerifyingKey::<Bn254>::deserialize_compressed(&vk_bytes[..]).unwrap();
+    let header = KeyHeader {
+        kind: KeyKind::Verifying,
+        curve: Curve::Bn254,
+        circuit_id: header::circuit_id(false),
+        compressed: false,
+    };
+    let mut uncompressed = header.to_bytes().to_vec();
+    vk.serialize_uncompressed(&mut uncompressed).unwrap();
+
+    assert!(api::verify_typed(&proof(), &hex::encode(&uncompressed)).unwrap());
+
+    // Without the header the bytes are taken to be compressed
+    let headerless = hex::encode(&uncompressed[header::HEADER_LEN..]);
+    assert!(api::verify_typed(&proof(), &headerless).is_err());
+}
@@ -0,0 +1,79 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use vortex::api::ProofOutput;
+use vortex::sui::bcs;
+
+/// ULEB128 lengths from the examples in the BCS specification, which Sui's
+/// SDKs implement.
+const ULEB128: [(usize, &str); 10] = [
+    (0x1, "01"),
+    (0x7f, "7f"),
+    (0x80, "8001"),
+    (0x3fff, "ff7f"),
+    (0x4000, "808001"),
+    (0x1f_ffff, "ffff7f"),
+    (0x20_0000, "80808001"),
+    (0xfff_ffff, "ffffff7f"),
+    (0x1000_0000, "8080808001"),
+    (0xffff_ffff, "ffffffff0f"),
+];
+
+/// The first BN254 test vector proof.
+fn proof() -> ProofOutput {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors/bn254.json");
+    let json: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    serde_json::from_value(json["cases"][0]["proof"].clone()).unwrap()
+}
+
+#[test]
+fn uleb128_matches_the_specification() {
+    for (value, encoding) in ULEB128 {
+        let mut bytes = Vec::new();
+        bcs::write_uleb128(&mut bytes, value);
+        assert_eq!(hex::encode(bytes), encoding, "{:#x}", value);
+    }
+}
+
+#[test]
+fn byte_vectors_are_length_prefixed() {
+    assert_eq!(bcs::encode_bytes(&[]), [0]);
+    assert_eq!(bcs::encode_bytes(&[1, 2, 3]), [3, 1, 2, 3]);
+    let long = bcs::encode_bytes(&[7; 200]);
+    assert_eq!(long[..2], [0xc8, 0x01]);
+    assert_eq!(long.len(), 202);
+}
+
+#[test]
+fn proof_encodes_as_the_groth16_byte_arguments() {
+    let proof = proof();
+    let encoded = proof.to_bcs().unwrap();
+
+    // 128 bytes of compressed points and one 32-byte input
+    let points = [
+        proof.proof_a.clone(),
+        proof.proof_b.clone(),
+        proof.proof_c.clone(),
+    ]
+    .concat();
+    assert_eq!(points.len(), 128);
+    assert_eq!(encoded.proof_points_bytes[..2], [0x80, 0x01]);
+    assert_eq!(encoded.proof_points_bytes[2..], points);
+    // The input c = 30 as a little-endian scalar
+    let mut input = [0u8; 32];
+    input[0] = 30;
+    assert_eq!(proof.public_inputs, ["30"]);
+    assert_eq!(encoded.public_inputs_bytes[0], 32);
+    assert_eq!(encoded.public_inputs_bytes[1..], input);
+}
+
+#[test]
+fn uncompressed_or_malformed_proof_is_rejected() {
+    let mut uncompressed = proof();
+    uncompressed.uncompressed = true;
+    assert!(uncompressed.to_bcs().is_err());
+
+    let mut malformed = proof();
+    malformed.proof_serialized_hex.push('z');
+    assert!(malformed.to_bcs().is_err());
+}
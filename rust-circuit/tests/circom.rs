@@ -0,0 +1,199 @@
+mod common;
+
+use ark_bn254::{Bn254, Fq, Fr, G1Projective};
+use ark_ec::{CurveGroup, PrimeGroup};
+use ark_ff::{BigInteger, PrimeField, UniformRand};
+use ark_groth16::ProvingKey;
+use ark_poly::{EvaluationDomain, Radix2EvaluationDomain};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::api;
+use vortex::circom::{self, zkey};
+use vortex::circuit::Circuit;
+use vortex::{prover, setup, witness};
+
+// Regenerate with `cargo run --bin export-r1cs fixtures/circom/multiplication.r1cs`
+const R1CS: &[u8] = include_bytes!("../fixtures/circom/multiplication.r1cs");
+// Regenerate with `cargo run --bin export-wtns fixtures/circom/multiplication_input.json
+// fixtures/circom/multiplication.wtns`
+const WTNS_INPUT: &str = include_str!("../fixtures/circom/multiplication_input.json");
+const WTNS: &[u8] = include_bytes!("../fixtures/circom/multiplication.wtns");
+// Written by `zkey_for` below for the seeded `toxic_waste`
+const ZKEY: &[u8] = include_bytes!("../fixtures/circom/multiplication.zkey");
+
+#[test]
+fn r1cs_matches_fixture() {
+    let mut bytes = Vec::new();
+    circom::write_r1cs(Circuit::<Fr>::empty(), &mut bytes).unwrap();
+    assert_eq!(bytes, R1CS);
+}
+
+#[test]
+fn r1cs_reads_back_the_written_constraints() {
+    let circuit = Circuit::new(Fr::from(15u64), Fr::from(3u64), Fr::from(5u64), true).unwrap();
+    let (_, matrices) = witness::generate_with_matrices(circuit.clone()).unwrap();
+
+    let mut bytes = Vec::new();
+    circom::write_r1cs(circuit, &mut bytes).unwrap();
+    assert_eq!(circom::read_r1cs::<Fr>(&bytes).unwrap(), matrices);
+
+    let fixture = circom::read_r1cs::<Fr>(R1CS).unwrap();
+    assert_eq!(fixture.num_instance_variables, 2);
+    assert_eq!(fixture.num_constraints, 2);
+}
+
+#[test]
+fn r1cs_for_other_field_is_rejected() {
+    assert!(circom::read_r1cs::<ark_bn254::Fq>(R1CS).is_err());
+    assert!(circom::read_r1cs::<Fr>(&R1CS[..R1CS.len() - 1]).is_err());
+}
+
+#[test]
+fn wtns_matches_fixture() {
+    assert_eq!(api::witness_file(WTNS_INPUT).unwrap(), WTNS);
+}
+
+#[test]
+fn wtns_reads_back_the_written_witness() {
+    let circuit = Circuit::new(Fr::from(15u64), Fr::from(3u64), Fr::from(5u64), false).unwrap();
+    let witness = witness::generate(circuit).unwrap();
+
+    let mut bytes = Vec::new();
+    circom::write_wtns(&witness, &mut bytes).unwrap();
+    let wires = circom::read_wtns::<Fr>(&bytes).unwrap();
+    assert_eq!(
+        wires,
+        [&witness.instance[..], &witness.witness[..]].concat()
+    );
+    assert_eq!(circom::read_wtns::<Fr>(WTNS).unwrap(), wires);
+}
+
+#[test]
+fn wtns_values_satisfy_the_r1cs_fixture() {
+    let matrices = circom::read_r1cs::<Fr>(R1CS).unwrap();
+    let wires = circom::read_wtns::<Fr>(WTNS).unwrap();
+    assert_eq!(
+        wires.len(),
+        matrices.num_instance_variables + matrices.num_witness_variables
+    );
+
+    let eval = |row: &[(Fr, usize)]| {
+        row.iter()
+            .map(|(coeff, wire)| *coeff * wires[*wire])
+            .sum::<Fr>()
+    };
+    for i in 0..matrices.num_constraints {
+        assert_eq!(
+            eval(&matrices.a[i]) * eval(&matrices.b[i]),
+            eval(&matrices.c[i])
+        );
+    }
+}
+
+#[test]
+fn wtns_for_other_field_is_rejected() {
+    assert!(circom::read_wtns::<ark_bn254::Fq>(WTNS).is_err());
+    assert!(circom::read_wtns::<Fr>(&WTNS[..WTNS.len() - 1]).is_err());
+}
+
+/// `(tau, alpha, beta)` of the zkey fixture.
+fn toxic_waste() -> (Fr, Fr, Fr) {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    (Fr::rand(&mut rng), Fr::rand(&mut rng), Fr::rand(&mut rng))
+}
+
+/// The initial key of the multiplication circuit for `toxic_waste`, with
+/// gamma and delta the generators.
+fn initial_key() -> ProvingKey<Bn254> {
+    let (tau, alpha, beta) = toxic_waste();
+    let ptau = common::ptau(3, tau, alpha, beta);
+    setup::from_ptau::<Bn254, _>(Circuit::<Fr>::empty(), &ptau).unwrap()
+}
+
+/// Writes `pk` as a snarkjs Groth16 zkey.
+///
+/// An arkworks key lacks the highest power in its H query, so the Lagrange
+/// points snarkjs stores are computed from `tau`; `pk` must have `delta = 1`.
+fn zkey_for(pk: &ProvingKey<Bn254>, tau: Fr) -> Vec<u8> {
+    let g1 = |scalar: Fr| common::g1_bytes((G1Projective::generator() * scalar).into_affine());
+    let points = |points: &[ark_bn254::G1Affine]| {
+        points
+            .iter()
+            .flat_map(|p| common::g1_bytes(*p))
+            .collect::<Vec<_>>()
+    };
+
+    let n = pk.h_query.len() + 1;
+    let num_public = pk.vk.gamma_abc_g1.len() - 1;
+    let mut header = 32u32.to_le_bytes().to_vec();
+    header.extend(Fq::MODULUS.to_bytes_le());
+    header.extend(32u32.to_le_bytes());
+    header.extend(Fr::MODULUS.to_bytes_le());
+    header.extend((pk.a_query.len() as u32).to_le_bytes());
+    header.extend((num_public as u32).to_le_bytes());
+    header.extend((n as u32).to_le_bytes());
+    header.extend(common::g1_bytes(pk.vk.alpha_g1));
+    header.extend(common::g1_bytes(pk.beta_g1));
+    header.extend(common::g2_bytes(pk.vk.beta_g2));
+    header.extend(common::g2_bytes(pk.vk.gamma_g2));
+    header.extend(common::g1_bytes(pk.delta_g1));
+    header.extend(common::g2_bytes(pk.vk.delta_g2));
+
+    // L_{2i+1}(τ) over the 2n-th roots of unity
+    let lagrange = Radix2EvaluationDomain::<Fr>::new(2 * n)
+        .unwrap()
+        .evaluate_all_lagrange_coefficients(tau);
+    let h = lagrange
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .flat_map(|l| g1(*l))
+        .collect();
+
+    common::container(
+        b"zkey",
+        1,
+        &[
+            (1, 1u32.to_le_bytes().to_vec()),
+            (2, header),
+            (3, points(&pk.vk.gamma_abc_g1)),
+            (5, points(&pk.a_query)),
+            (6, points(&pk.b_g1_query)),
+            (
+                7,
+                pk.b_g2_query
+                    .iter()
+                    .flat_map(|p| common::g2_bytes(*p))
+                    .collect(),
+            ),
+            (8, points(&pk.l_query)),
+            (9, h),
+        ],
+    )
+}
+
+#[test]
+fn zkey_matches_fixture() {
+    assert_eq!(zkey_for(&initial_key(), toxic_waste().0), ZKEY);
+}
+
+#[test]
+fn zkey_reads_back_the_written_key() {
+    let pk = initial_key();
+    assert_eq!(zkey::read_zkey::<Bn254>(ZKEY).unwrap(), pk);
+
+    // The key proves once delta is randomized
+    let mut rng = ChaCha20Rng::seed_from_u64(1);
+    let previous_hash = setup::initial_hash(&pk).unwrap();
+    let mut pk = pk;
+    setup::contribute(&mut pk, previous_hash, &mut rng);
+    let circuit = Circuit::new(Fr::from(15u64), Fr::from(3u64), Fr::from(5u64), false).unwrap();
+    let bundle = prover::prove(circuit, &pk, &mut rng).unwrap();
+    assert!(prover::verify(&bundle, &pk.vk).unwrap());
+}
+
+#[test]
+fn zkey_for_other_curve_or_truncated_is_rejected() {
+    assert!(zkey::read_zkey::<Bn254>(&ZKEY[..ZKEY.len() - 1]).is_err());
+    assert!(zkey::read_zkey::<Bn254>(R1CS).is_err());
+}
@@ -0,0 +1,85 @@
+use ark_bn254::Fr;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use vortex::api::{self, ProofOutput, VerificationFailure};
+use vortex::circuit::Circuit;
+use vortex::inspect;
+use vortex::keys::circuit_id::{CircuitId, CircuitMismatchError};
+use vortex::keys::header;
+
+fn read(path: &str) -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join(path)).unwrap()
+}
+
+/// The first BN254 test vector proof, made with the keys in `keys/`.
+fn proof() -> ProofOutput {
+    let json: Value = serde_json::from_slice(&read("testvectors/bn254.json")).unwrap();
+    serde_json::from_value(json["cases"][0]["proof"].clone()).unwrap()
+}
+
+/// `keys/verification_key.bin`, whose header names the circuit.
+fn headered_vk_hex() -> String {
+    hex::encode(read("keys/verification_key.bin"))
+}
+
+#[test]
+fn proof_matching_the_key_header_verifies() {
+    let proof = proof();
+    assert_eq!(proof.circuit_id, Some(CircuitId::for_circuit(false)));
+    assert!(api::verify_typed(&proof, &headered_vk_hex()).unwrap());
+}
+
+#[test]
+fn proof_for_another_circuit_is_rejected_by_the_key_header() {
+    let mut proof = proof();
+    proof.circuit_id = Some(CircuitId::for_circuit(true));
+
+    let error = api::verify_typed(&proof, &headered_vk_hex()).unwrap_err();
+    let mismatch = error.downcast_ref::<CircuitMismatchError>().unwrap();
+    assert_eq!(mismatch.expected, CircuitId::for_circuit(false));
+
+    let result = api::verify_detailed(&serde_json::to_string(&proof).unwrap(), &headered_vk_hex());
+    assert_eq!(
+        result.unwrap().reason,
+        Some(VerificationFailure::CircuitMismatch)
+    );
+}
+
+#[test]
+fn headerless_key_accepts_either_option_of_this_build() {
+    let vk_hex = String::from_utf8(read("keys/verification_key.hex")).unwrap();
+    let mut proof = proof();
+    proof.circuit_id = Some(CircuitId::for_circuit(true));
+    assert!(api::verify_typed(&proof, vk_hex.trim()).is_ok());
+
+    proof.circuit_id = Some(CircuitId([0xff; 8]));
+    let error = api::verify_typed(&proof, vk_hex.trim()).unwrap_err();
+    assert!(error.is::<CircuitMismatchError>());
+}
+
+#[test]
+fn circuit_id_is_hex_in_json() {
+    let id = CircuitId::for_circuit(false);
+    let json = serde_json::to_string(&id).unwrap();
+    assert_eq!(json, format!("\"{}\"", hex::encode(id.0)));
+    assert_eq!(serde_json::from_str::<CircuitId>(&json).unwrap(), id);
+}
+
+#[test]
+fn changed_constraint_system_changes_the_id() {
+    let label = header::circuit_label(false);
+    let cs_digest = inspect::digest(Circuit::<Fr>::empty()).unwrap();
+    assert_eq!(
+        header::circuit_id_of(&label, &cs_digest),
+        header::circuit_id(false)
+    );
+
+    // Same name and version, different constraints
+    let changed = inspect::digest(Circuit::<Fr>::empty_non_trivial()).unwrap();
+    assert_ne!(changed, cs_digest);
+    assert_ne!(
+        header::circuit_id_of(&label, &changed),
+        header::circuit_id(false)
+    );
+}
@@ -0,0 +1,43 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::age::AgeCircuit;
+
+fn circuit(birth_year: u64) -> AgeCircuit {
+    AgeCircuit::new(2024, 18, birth_year, Fr::from(42u64)).unwrap()
+}
+
+#[test]
+fn old_enough_is_satisfied() {
+    // Exactly the threshold, and well over it
+    assert!(is_satisfied(circuit(2006)));
+    assert!(is_satisfied(circuit(1950)));
+}
+
+#[test]
+fn too_young_is_rejected_and_not_satisfied() {
+    assert!(AgeCircuit::<Fr>::new(2024, 18, 2007, Fr::from(42u64)).is_err());
+
+    // The commitment to a later birth year, proved against an older one
+    let mut circuit = circuit(2006);
+    circuit.birth_year = Fr::from(2007u64);
+    circuit.commitment = AgeCircuit::commit(circuit.birth_year, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_commitment_is_not_satisfied() {
+    let mut circuit = circuit(2000);
+    circuit.commitment = AgeCircuit::commit(Fr::from(1990u64), circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn year_above_range_is_rejected_and_not_satisfied() {
+    assert!(AgeCircuit::<Fr>::new(1 << 16, 18, 1990, Fr::from(42u64)).is_err());
+
+    // A birth year that wraps below zero would pass an unchecked comparison
+    let mut circuit = circuit(2000);
+    circuit.birth_year = -Fr::from(1u64);
+    circuit.commitment = AgeCircuit::commit(circuit.birth_year, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
@@ -0,0 +1,83 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::airdrop::{self, AirdropCircuit, Eligible};
+use vortex::merkle::MerkleTree;
+
+type Airdrop2 = AirdropCircuit<2>;
+
+fn secret(i: u8) -> Fr {
+    Fr::from(1_000 + u64::from(i))
+}
+
+/// Addresses `[i; 32]` for `i` in `0..3`, each with the identity of `secret(i)`.
+fn tree() -> MerkleTree<Fr> {
+    let eligible: Vec<_> = (0..3)
+        .map(|i| Eligible {
+            address: [i; 32],
+            identity: airdrop::identity(secret(i)).unwrap(),
+        })
+        .collect();
+    airdrop::build_tree(2, &eligible).unwrap()
+}
+
+fn claim(airdrop_id: u64) -> Airdrop2 {
+    Airdrop2::new(
+        &tree(),
+        1,
+        &[1; 32],
+        secret(1),
+        Fr::from(airdrop_id),
+        Fr::from(99u64),
+    )
+    .unwrap()
+}
+
+#[test]
+fn eligible_claim_is_satisfied() {
+    assert!(is_satisfied(claim(7)));
+}
+
+#[test]
+fn nullifier_is_per_airdrop() {
+    assert_eq!(claim(7).nullifier, claim(7).nullifier);
+    assert_ne!(claim(7).nullifier, claim(8).nullifier);
+}
+
+#[test]
+fn other_secret_or_address_is_rejected() {
+    let tree = tree();
+    let new = |index, address: &[u8; 32], secret| {
+        Airdrop2::new(
+            &tree,
+            index,
+            address,
+            secret,
+            Fr::from(7u64),
+            Fr::from(99u64),
+        )
+    };
+    assert!(new(1, &[1; 32], secret(2)).is_err());
+    assert!(new(1, &[2; 32], secret(1)).is_err());
+    assert!(new(4, &[1; 32], secret(1)).is_err());
+}
+
+#[test]
+fn ineligible_address_is_not_satisfied() {
+    let mut circuit = claim(7);
+    circuit.address_hash = airdrop::address_hash(&[9; 32]).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn forged_nullifier_is_not_satisfied() {
+    let mut circuit = claim(7);
+    circuit.nullifier = claim(8).nullifier;
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_root_is_not_satisfied() {
+    let mut circuit = claim(7);
+    circuit.root += Fr::from(1u64);
+    assert!(!is_satisfied(circuit));
+}
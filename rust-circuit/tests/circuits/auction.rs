@@ -0,0 +1,78 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::auction::{self, AuctionCircuit, BidCircuit};
+
+type Auction2 = AuctionCircuit<2>;
+
+fn bid(bid: u64) -> BidCircuit {
+    BidCircuit::new(100, 1_000, bid, Fr::from(5u64)).unwrap()
+}
+
+/// A winning bid of 700 over sealed bids of 300 and 699.
+fn auction() -> Auction2 {
+    Auction2::new(
+        700,
+        Fr::from(1u64),
+        &[(300, Fr::from(2u64)), (699, Fr::from(3u64))],
+    )
+    .unwrap()
+}
+
+#[test]
+fn bid_within_bounds_is_satisfied() {
+    assert!(is_satisfied(bid(100)));
+    assert!(is_satisfied(bid(1_000)));
+}
+
+#[test]
+fn bid_outside_bounds_is_rejected_and_not_satisfied() {
+    assert!(BidCircuit::<Fr>::new(100, 1_000, 99, Fr::from(5u64)).is_err());
+    assert!(BidCircuit::<Fr>::new(100, 1_000, 1_001, Fr::from(5u64)).is_err());
+
+    for value in [99u64, 1_001] {
+        let mut circuit = bid(500);
+        circuit.bid = Fr::from(value);
+        circuit.commitment = auction::commit_bid(circuit.bid, circuit.salt).unwrap();
+        assert!(!is_satisfied(circuit));
+    }
+}
+
+#[test]
+fn bid_opening_other_commitment_is_not_satisfied() {
+    let mut circuit = bid(500);
+    circuit.commitment = auction::commit_bid(Fr::from(501u64), circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn highest_bid_wins() {
+    assert!(is_satisfied(auction()));
+}
+
+#[test]
+fn tie_or_higher_other_bid_is_rejected_and_not_satisfied() {
+    let others = |second| [(300, Fr::from(2u64)), (second, Fr::from(3u64))];
+    assert!(Auction2::new(700, Fr::from(1u64), &others(700)).is_err());
+    assert!(Auction2::new(700, Fr::from(1u64), &others(701)).is_err());
+
+    for second in [700u64, 701] {
+        let mut circuit = auction();
+        circuit.other_bids[1] = Fr::from(second);
+        circuit.other_commitments[1] =
+            auction::commit_bid(circuit.other_bids[1], circuit.other_salts[1]).unwrap();
+        assert!(!is_satisfied(circuit));
+    }
+}
+
+#[test]
+fn other_bid_not_opening_its_commitment_is_not_satisfied() {
+    let mut circuit = auction();
+    // Claims the sealed 699 was a lower bid
+    circuit.other_bids[1] = Fr::from(600u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn wrong_number_of_other_bids_is_rejected() {
+    assert!(Auction2::new(700, Fr::from(1u64), &[(300, Fr::from(2u64))]).is_err());
+}
@@ -0,0 +1,63 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::batch_insert::BatchInsertCircuit;
+use vortex::merkle::MerkleTree;
+
+type BatchInsert = BatchInsertCircuit<3, 2>;
+
+fn leaves(values: &[u64]) -> Vec<Fr> {
+    values.iter().copied().map(Fr::from).collect()
+}
+
+/// A tree of depth 3 holding 1, 2 and 3.
+fn tree() -> MerkleTree<Fr> {
+    MerkleTree::new(3, leaves(&[1, 2, 3])).unwrap()
+}
+
+#[test]
+fn append_is_satisfied_and_matches_the_tree() {
+    let mut tree = tree();
+    let circuit = BatchInsert::new(&mut tree, leaves(&[4, 5])).unwrap();
+
+    assert_eq!(circuit.start_index, Fr::from(3u64));
+    assert_eq!(
+        circuit.new_root,
+        MerkleTree::new(3, leaves(&[1, 2, 3, 4, 5])).unwrap().root()
+    );
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn insert_into_non_empty_slot_is_not_satisfied() {
+    // Claims 4 and 5 went into slots 1 and 2, overwriting 2 and 3
+    let tree = tree();
+    let overwritten = MerkleTree::new(3, leaves(&[1, 4, 5])).unwrap();
+    let circuit = BatchInsert {
+        old_root: tree.root(),
+        new_root: overwritten.root(),
+        start_index: Fr::from(1u64),
+        leaves: leaves(&[4, 5]),
+        paths: vec![tree.path(1).unwrap(), overwritten.path(2).unwrap()],
+    };
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_start_index_or_root_is_not_satisfied() {
+    let circuit = BatchInsert::new(&mut tree(), leaves(&[4, 5])).unwrap();
+
+    let mut shifted = circuit.clone();
+    shifted.start_index = Fr::from(4u64);
+    assert!(!is_satisfied(shifted));
+
+    let mut forged = circuit;
+    forged.new_root += Fr::from(1u64);
+    assert!(!is_satisfied(forged));
+}
+
+#[test]
+fn batch_that_does_not_fit_is_rejected() {
+    let mut full = MerkleTree::new(3, leaves(&[1, 2, 3, 4, 5, 6, 7])).unwrap();
+    assert!(BatchInsert::new(&mut full, leaves(&[8, 9])).is_err());
+    assert!(BatchInsert::new(&mut tree(), leaves(&[4])).is_err());
+}
@@ -0,0 +1,50 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::blocklist::{self, BlocklistCircuit};
+use vortex::merkle::sparse::SparseMerkleTree;
+
+type Blocklist8 = BlocklistCircuit<8>;
+
+fn blocklist() -> SparseMerkleTree<Fr> {
+    let blocked: Vec<Fr> = [11u64, 22, 33].into_iter().map(Fr::from).collect();
+    blocklist::build_blocklist(8, &blocked).unwrap()
+}
+
+#[test]
+fn identifier_not_on_blocklist_is_satisfied() {
+    let circuit = Blocklist8::new(&blocklist(), Fr::from(44u64), Fr::from(5u64)).unwrap();
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn blocklisted_identifier_is_rejected_and_not_satisfied() {
+    let blocklist = blocklist();
+    assert!(Blocklist8::new(&blocklist, Fr::from(22u64), Fr::from(5u64)).is_err());
+
+    // Commits to a blocklisted identifier, claiming the empty slot of another
+    let mut circuit = Blocklist8::new(&blocklist, Fr::from(44u64), Fr::from(5u64)).unwrap();
+    circuit.identifier = Fr::from(22u64);
+    circuit.commitment = Blocklist8::commit(circuit.identifier, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+
+    // ...or with the path of its own, occupied slot
+    let key = blocklist::key(Fr::from(22u64)).unwrap();
+    let mut circuit = Blocklist8::new(&blocklist, Fr::from(44u64), Fr::from(5u64)).unwrap();
+    circuit.identifier = Fr::from(22u64);
+    circuit.commitment = Blocklist8::commit(circuit.identifier, circuit.salt).unwrap();
+    circuit.path = blocklist.path(&key).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_commitment_is_not_satisfied() {
+    let mut circuit = Blocklist8::new(&blocklist(), Fr::from(44u64), Fr::from(5u64)).unwrap();
+    circuit.commitment = Blocklist8::commit(Fr::from(45u64), circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn blocklist_of_other_depth_is_rejected() {
+    let shallow = blocklist::build_blocklist(4, &[Fr::from(11u64)]).unwrap();
+    assert!(Blocklist8::new(&shallow, Fr::from(44u64), Fr::from(5u64)).is_err());
+}
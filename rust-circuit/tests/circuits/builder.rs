@@ -0,0 +1,153 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use vortex::circuit::builder::{CircuitBuilder, CompositeCircuit, Membership, Range, RANGE_BITS};
+use vortex::gadgets::comparison;
+use vortex::inspect;
+use vortex::merkle::{MerklePath, MerklePathVar, MerkleTree};
+
+/// Membership AND range over one leaf, written by hand.
+struct MemberInRange {
+    root: Fr,
+    min: Fr,
+    max: Fr,
+    leaf: Fr,
+    path: MerklePath<Fr>,
+}
+
+impl ConstraintSynthesizer<Fr> for MemberInRange {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> r1cs::Result<()> {
+        let root = FpVar::new_input(ns!(cs, "root"), || Ok(self.root))?;
+        let min = FpVar::new_input(ns!(cs, "min"), || Ok(self.min))?;
+        let max = FpVar::new_input(ns!(cs, "max"), || Ok(self.max))?;
+        let leaf = FpVar::new_witness(ns!(cs, "leaf"), || Ok(self.leaf))?;
+
+        let path = MerklePathVar::new_witness(ns!(cs, "path").cs(), &self.path)?;
+        path.root(cs.clone(), &leaf)?.enforce_equal(&root)?;
+
+        for x in [&min, &max, &leaf] {
+            comparison::enforce_bit_length(x, RANGE_BITS)?;
+        }
+        comparison::enforce_less_or_equal(&min, &leaf, RANGE_BITS)?;
+        comparison::enforce_less_or_equal(&leaf, &max, RANGE_BITS)
+    }
+}
+
+fn tree() -> MerkleTree<Fr> {
+    MerkleTree::new(4, [3u64, 25, 70].map(Fr::from).to_vec()).unwrap()
+}
+
+fn composite(tree: &MerkleTree<Fr>, index: usize, min: u64, max: u64) -> CompositeCircuit {
+    CircuitBuilder::new()
+        .witness("leaf", tree.leaves()[index])
+        .statement(
+            "member",
+            Membership {
+                leaf: "leaf".into(),
+                root: tree.root(),
+                path: tree.path(index).unwrap(),
+            },
+        )
+        .statement(
+            "range",
+            Range {
+                value: "leaf".into(),
+                min,
+                max,
+            },
+        )
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn composite_has_the_hand_written_matrices() {
+    let tree = tree();
+    let hand_written = MemberInRange {
+        root: tree.root(),
+        min: Fr::from(18u64),
+        max: Fr::from(65u64),
+        leaf: tree.leaves()[1],
+        path: tree.path(1).unwrap(),
+    };
+
+    assert_eq!(
+        inspect::digest(composite(&tree, 1, 18, 65)).unwrap(),
+        inspect::digest(hand_written).unwrap()
+    );
+}
+
+#[test]
+fn composite_public_inputs_follow_the_statements() {
+    let tree = tree();
+    let circuit = composite(&tree, 1, 18, 65);
+    assert_eq!(
+        circuit.public_input_names(),
+        ["member.root", "range.min", "range.max"]
+    );
+    assert_eq!(
+        circuit.get_public_inputs(),
+        [tree.root(), Fr::from(18u64), Fr::from(65u64)]
+    );
+}
+
+#[test]
+fn composite_is_satisfied_only_when_every_statement_holds() {
+    let tree = tree();
+    assert!(is_satisfied(composite(&tree, 1, 18, 65)));
+    // 70 is a member, but out of range
+    assert!(!is_satisfied(composite(&tree, 2, 18, 65)));
+
+    // 30 is in range, but the path of leaf 25 does not lead from it to the root
+    let not_member = CircuitBuilder::new()
+        .witness("leaf", Fr::from(30u64))
+        .statement(
+            "member",
+            Membership {
+                leaf: "leaf".into(),
+                root: tree.root(),
+                path: tree.path(1).unwrap(),
+            },
+        )
+        .statement(
+            "range",
+            Range {
+                value: "leaf".into(),
+                min: 18,
+                max: 65,
+            },
+        )
+        .build()
+        .unwrap();
+    assert!(!is_satisfied(not_member));
+}
+
+#[test]
+fn build_rejects_invalid_compositions() {
+    let range = || Range {
+        value: "leaf".into(),
+        min: 0,
+        max: 1,
+    };
+    assert!(CircuitBuilder::<Fr>::new().build().is_err());
+    assert!(CircuitBuilder::<Fr>::new()
+        .statement("range", range())
+        .build()
+        .is_err());
+    assert!(CircuitBuilder::<Fr>::new()
+        .witness("leaf", Fr::from(0u64))
+        .witness("leaf", Fr::from(1u64))
+        .statement("range", range())
+        .build()
+        .is_err());
+    assert!(CircuitBuilder::<Fr>::new()
+        .witness("leaf", Fr::from(0u64))
+        .statement("range", range())
+        .statement("range", range())
+        .build()
+        .is_err());
+}
@@ -0,0 +1,100 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::babyjubjub;
+use vortex::circuit::credential::{self, CredentialCircuit, Predicate};
+
+/// Age 30 and country code 44, proved over 18 and in `{1, 44, 49}`, with
+/// only the country disclosed.
+fn circuit() -> CredentialCircuit {
+    let secret = babyjubjub::to_scalar(Fr::from(1234u64));
+    let attributes = vec![Fr::from(30u64), Fr::from(44u64), Fr::from(99u64)];
+    let signature = credential::issue(secret, &attributes).unwrap();
+    CredentialCircuit::new(
+        babyjubjub::public_key(secret),
+        attributes,
+        signature,
+        vec![false, true, false],
+        predicates(18, 44),
+    )
+    .unwrap()
+}
+
+fn predicates(minimum_age: u64, country: u64) -> Vec<Predicate> {
+    vec![
+        Predicate::GreaterThan {
+            attribute: 0,
+            bound: Fr::from(minimum_age),
+        },
+        Predicate::InSet {
+            attribute: 1,
+            set: vec![Fr::from(1u64), Fr::from(country), Fr::from(49u64)],
+        },
+    ]
+}
+
+#[test]
+fn honest_credential_is_satisfied() {
+    let circuit = circuit();
+    assert_eq!(
+        circuit.revealed,
+        [Fr::from(0u64), Fr::from(44u64), Fr::from(0u64)]
+    );
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn other_issuer_is_rejected_and_not_satisfied() {
+    let circuit = circuit();
+    let other = babyjubjub::public_key(babyjubjub::to_scalar(Fr::from(5678u64)));
+    assert!(CredentialCircuit::new(
+        other,
+        circuit.attributes.clone(),
+        circuit.signature,
+        circuit.disclosed.clone(),
+        circuit.predicates.clone(),
+    )
+    .is_err());
+
+    let mut forged = circuit;
+    forged.issuer = other;
+    assert!(!is_satisfied(forged));
+}
+
+#[test]
+fn altered_attribute_is_not_satisfied() {
+    // An older age than was signed, which the predicate alone would accept
+    let mut circuit = circuit();
+    circuit.attributes[0] = Fr::from(40u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn misrevealed_attribute_is_not_satisfied() {
+    let mut other_value = circuit();
+    other_value.revealed[1] = Fr::from(49u64);
+    assert!(!is_satisfied(other_value));
+
+    // An undisclosed attribute must be revealed as 0
+    let mut undisclosed = circuit();
+    undisclosed.revealed[0] = Fr::from(30u64);
+    assert!(!is_satisfied(undisclosed));
+}
+
+#[test]
+fn failing_predicates_are_rejected_and_not_satisfied() {
+    let circuit = circuit();
+    for predicates in [predicates(30, 44), predicates(18, 45)] {
+        assert!(CredentialCircuit::new(
+            circuit.issuer,
+            circuit.attributes.clone(),
+            circuit.signature,
+            circuit.disclosed.clone(),
+            predicates.clone(),
+        )
+        .is_err());
+
+        let mut unsound = circuit.clone();
+        unsound.predicates = predicates;
+        assert!(!is_satisfied(unsound));
+    }
+}
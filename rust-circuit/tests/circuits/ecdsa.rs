@@ -0,0 +1,105 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{alloc::AllocVar, boolean::Boolean, R1CSVar};
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
+use ark_std::UniformRand;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::circuit::ecdsa::EcdsaCircuit;
+use vortex::gadgets::ecdsa::{self, PointVar};
+use vortex::secp256k1::{self, Affine, Fq};
+
+fn witness_bits(cs: &ConstraintSystemRef<Fr>, bits: &[bool]) -> Vec<Boolean<Fr>> {
+    bits.iter()
+        .map(|&bit| Boolean::new_witness(cs.clone(), || Ok(bit)).unwrap())
+        .collect()
+}
+
+/// The gadget's fixed offset point, recomputed.
+fn offset_point() -> Affine {
+    (2u64..)
+        .find_map(|x| Affine::get_point_from_x_unchecked(Fq::from(x), false))
+        .unwrap()
+}
+
+// The full circuit needs more than 6 GB to synthesize: run with
+// `cargo test --release --test circuits ecdsa -- --ignored`
+#[test]
+#[ignore = "needs more than 6 GB of memory"]
+fn honest_signature_is_satisfied() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let secret_key = secp256k1::Fr::rand(&mut rng);
+    let digest = [7u8; 32];
+    let signature = secp256k1::sign(&secret_key, &digest, &mut rng);
+
+    let circuit =
+        EcdsaCircuit::<Fr>::new(secp256k1::public_key(&secret_key), &digest, signature).unwrap();
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+#[ignore = "needs more than 6 GB of memory"]
+fn forged_signature_is_not_satisfied() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let secret_key = secp256k1::Fr::rand(&mut rng);
+    let signature = secp256k1::sign(&secret_key, &[7u8; 32], &mut rng);
+
+    // The same signature claimed for a different digest
+    let mut circuit =
+        EcdsaCircuit::<Fr>::new(secp256k1::public_key(&secret_key), &[7u8; 32], signature).unwrap();
+    circuit.digest_lo += Fr::from(1u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn double_scalar_mul_matches_native() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let q = secp256k1::public_key(&secp256k1::Fr::rand(&mut rng));
+    let (u1, u2) = (0xa5c3u64, 0x3e71u64);
+    let bits = |u: u64| secp256k1::Fr::from(u).into_bigint().to_bits_le()[..16].to_vec();
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let q_var = PointVar::new_witness(cs.clone(), || Ok(q)).unwrap();
+    let point = ecdsa::double_scalar_mul(
+        &witness_bits(&cs, &bits(u1)),
+        &witness_bits(&cs, &bits(u2)),
+        &q_var,
+    )
+    .unwrap();
+    assert!(cs.is_satisfied().unwrap());
+
+    let expected =
+        (Affine::generator() * secp256k1::Fr::from(u1) + q * secp256k1::Fr::from(u2)).into_affine();
+    assert_eq!(
+        (point.x.value().unwrap(), point.y.value().unwrap()),
+        expected.xy().unwrap()
+    );
+}
+
+// A prover-chosen key equal to the accumulator after the first window
+// (4 * offset) makes the addition hit the equal-point case, in which the
+// slope would otherwise be unconstrained.
+#[test]
+fn exceptional_addition_is_not_satisfied() {
+    let q = (offset_point() * secp256k1::Fr::from(4u64)).into_affine();
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let q = PointVar::new_witness(cs.clone(), || Ok(q)).unwrap();
+    ecdsa::double_scalar_mul(
+        &witness_bits(&cs, &[false, false]),
+        &witness_bits(&cs, &[true, false]),
+        &q,
+    )
+    .unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn adding_a_point_to_itself_is_not_satisfied() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let point = PointVar::new_witness(cs.clone(), || Ok(Affine::generator())).unwrap();
+    point.add(&point).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
@@ -0,0 +1,203 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use ark_ec::{AffineRepr, CurveGroup};
+use ark_ff::{BigInteger, PrimeField};
+use ark_r1cs_std::{alloc::AllocVar, boolean::Boolean, uint8::UInt8, R1CSVar};
+use ark_relations::r1cs::ConstraintSystem;
+use sha2::{Digest, Sha512};
+use vortex::circuit::ed25519::{self as circuit, Ed25519SignatureCircuit};
+use vortex::ed25519::{self, Affine};
+use vortex::gadgets::{ed25519 as gadget, ed25519::PointVar, sha512};
+
+/// RFC 8032, section 7.1, TEST 1 to 3: `(seed, public key, message, signature)`.
+const RFC8032: [(&str, &str, &str, &str); 3] = [
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "",
+        "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    ),
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        "72",
+        "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    ),
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        "af82",
+        "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+    ),
+];
+
+fn decode<const N: usize>(hex: &str) -> [u8; N] {
+    hex::decode(hex).unwrap().try_into().unwrap()
+}
+
+/// The order-2 point `(0, -1)`.
+fn torsion_point() -> Affine {
+    Affine::new_unchecked(ed25519::Fq::from(0u64), -ed25519::Fq::from(1u64))
+}
+
+#[test]
+fn rfc8032_vectors() {
+    for (seed, public_key, message, signature) in RFC8032 {
+        let (seed, public_key, signature) = (decode(seed), decode(public_key), decode(signature));
+        let message = hex::decode(message).unwrap();
+
+        assert_eq!(ed25519::public_key(&seed), public_key);
+        assert_eq!(ed25519::sign(&seed, &message), signature);
+        assert!(ed25519::verify(&public_key, &message, &signature));
+        assert!(ed25519::decompress(&public_key).is_ok());
+    }
+}
+
+#[test]
+fn rfc8032_signature_is_rejected_for_other_message() {
+    let (_, public_key, _, signature) = RFC8032[0];
+    assert!(!ed25519::verify(
+        &decode(public_key),
+        b"\x72",
+        &decode(signature)
+    ));
+}
+
+#[test]
+fn non_canonical_s_is_rejected() {
+    let (_, public_key, _, signature) = RFC8032[0];
+    let mut signature: [u8; 64] = decode(signature);
+
+    // S + L encodes the same scalar
+    let s = ed25519::Fr::from_le_bytes_mod_order(&signature[32..]).into_bigint();
+    let mut s_plus_l = s;
+    s_plus_l.add_with_carry(&ed25519::Fr::MODULUS);
+    signature[32..].copy_from_slice(&s_plus_l.to_bytes_le());
+    assert!(!ed25519::verify(&decode(public_key), b"", &signature));
+}
+
+#[test]
+fn small_and_mixed_order_points_are_rejected() {
+    let identity = ed25519::compress(&Affine::zero());
+    let small_order = ed25519::compress(&torsion_point());
+    let mixed_order = ed25519::compress(&(Affine::generator() + torsion_point()).into_affine());
+
+    for encoding in [identity, small_order, mixed_order] {
+        assert!(ed25519::decompress(&encoding).is_err());
+        assert!(circuit::build_tree::<Fr>(2, &[encoding]).is_err());
+    }
+    assert!(ed25519::decompress(&ed25519::compress(&Affine::generator())).is_ok());
+}
+
+#[test]
+fn sha512_gadget_matches_native() {
+    for len in [0usize, 3, 96, 200] {
+        let message: Vec<u8> = (0..len).map(|i| i as u8).collect();
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let bytes = UInt8::new_witness_vec(cs.clone(), &message).unwrap();
+        let digest = sha512::sha512(&bytes).unwrap().value().unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(digest, Sha512::digest(&message).to_vec());
+    }
+}
+
+#[test]
+fn point_encoding_and_challenge_match_native() {
+    let (_, public_key, _, signature) = RFC8032[1];
+    let (public_key, signature): ([u8; 32], [u8; 64]) = (decode(public_key), decode(signature));
+    let big_r: [u8; 32] = signature[..32].try_into().unwrap();
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let point = PointVar::new_witness(cs.clone(), || Ok(ed25519::decompress(&public_key).unwrap()))
+        .unwrap();
+    assert_eq!(point.to_bytes().unwrap().value().unwrap(), public_key);
+
+    let digest: Vec<u8> = Sha512::new()
+        .chain_update(big_r)
+        .chain_update(public_key)
+        .chain_update(b"\x72")
+        .finalize()
+        .to_vec();
+    let digest = UInt8::new_witness_vec(cs.clone(), &digest).unwrap();
+    let k = gadget::digest_scalar(&digest).unwrap();
+    assert_eq!(
+        k.value().unwrap(),
+        ed25519::challenge(&big_r, &public_key, b"\x72")
+    );
+    assert!(cs.is_satisfied().unwrap());
+}
+
+#[test]
+fn double_scalar_mul_matches_native() {
+    let q = (Affine::generator() * ed25519::Fr::from(0xbeefu64)).into_affine();
+    let (u1, u2) = (0xa5c3u64, 0x3e71u64);
+    let bits = |u: u64| ed25519::Fr::from(u).into_bigint().to_bits_le()[..16].to_vec();
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let witness_bits = |u: u64| -> Vec<Boolean<Fr>> {
+        bits(u)
+            .into_iter()
+            .map(|bit| Boolean::new_witness(cs.clone(), || Ok(bit)).unwrap())
+            .collect()
+    };
+    let q_var = PointVar::new_witness(cs.clone(), || Ok(q)).unwrap();
+    let point = gadget::double_scalar_mul(&witness_bits(u1), &witness_bits(u2), &q_var).unwrap();
+    assert!(cs.is_satisfied().unwrap());
+
+    let expected =
+        (Affine::generator() * ed25519::Fr::from(u1) + q * ed25519::Fr::from(u2)).into_affine();
+    assert_eq!(
+        (point.x.value().unwrap(), point.y.value().unwrap()),
+        (expected.x, expected.y)
+    );
+}
+
+#[test]
+fn point_off_the_curve_is_not_satisfied() {
+    let base = Affine::generator();
+    let off_curve = Affine::new_unchecked(base.x, base.y + ed25519::Fq::from(1u64));
+
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    PointVar::new_witness(cs.clone(), || Ok(off_curve)).unwrap();
+    assert!(!cs.is_satisfied().unwrap());
+}
+
+/// A registry of the RFC 8032 keys and a signature by the second over `message`.
+fn signed(message: &[u8; 32]) -> Ed25519SignatureCircuit<2> {
+    let keys: Vec<[u8; 32]> = RFC8032.iter().map(|(_, key, _, _)| decode(key)).collect();
+    let tree = circuit::build_tree(2, &keys).unwrap();
+    let signature = ed25519::sign(&decode(RFC8032[1].0), message);
+    Ed25519SignatureCircuit::new(&tree, 1, &keys[1], message, &signature).unwrap()
+}
+
+#[test]
+fn invalid_signature_or_key_is_rejected() {
+    let message = [7u8; 32];
+    let keys: Vec<[u8; 32]> = RFC8032.iter().map(|(_, key, _, _)| decode(key)).collect();
+    let tree = circuit::build_tree::<Fr>(2, &keys).unwrap();
+    let signature = ed25519::sign(&decode(RFC8032[1].0), &message);
+
+    // Signed by key 1, claimed for key 0 and for another message
+    assert!(Ed25519SignatureCircuit::<2>::new(&tree, 0, &keys[1], &message, &signature).is_err());
+    assert!(Ed25519SignatureCircuit::<2>::new(&tree, 0, &keys[0], &message, &signature).is_err());
+    assert!(Ed25519SignatureCircuit::<2>::new(&tree, 1, &keys[1], &[8u8; 32], &signature).is_err());
+}
+
+// The full circuit needs more memory than the default test environment:
+// run with `cargo test --release --test circuits ed25519 -- --ignored`
+#[test]
+#[ignore = "needs more than 6 GB of memory"]
+fn honest_signature_is_satisfied() {
+    let circuit = signed(&[7u8; 32]);
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+#[ignore = "needs more than 6 GB of memory"]
+fn signature_claimed_for_other_message_is_not_satisfied() {
+    let mut circuit = signed(&[7u8; 32]);
+    circuit.message_lo += Fr::from(1u64);
+    assert!(!is_satisfied(circuit));
+}
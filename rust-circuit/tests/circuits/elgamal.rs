@@ -0,0 +1,54 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::babyjubjub;
+use vortex::circuit::elgamal::ElGamalCircuit;
+
+fn secret() -> babyjubjub::Fr {
+    babyjubjub::Fr::from(123_456_789u64)
+}
+
+fn circuit(message: u64) -> ElGamalCircuit {
+    let public_key = babyjubjub::public_key(secret());
+    ElGamalCircuit::new(public_key, message, Fr::from(777u64), Fr::from(99u64)).unwrap()
+}
+
+#[test]
+fn encryption_is_satisfied_and_decrypts() {
+    let circuit = circuit(1_000);
+    assert_eq!(
+        babyjubjub::decrypt(secret(), &circuit.ciphertext, 1 << 16),
+        Some(1_000)
+    );
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn ciphertext_of_other_message_is_not_satisfied() {
+    let other = circuit(1_001);
+    let mut circuit = circuit(1_000);
+    circuit.ciphertext = other.ciphertext;
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_commitment_is_not_satisfied() {
+    let mut circuit = circuit(1_000);
+    circuit.commitment = ElGamalCircuit::commit(Fr::from(1_001u64), Fr::from(99u64)).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn message_above_64_bits_is_not_satisfied() {
+    let mut circuit = circuit(0);
+    let message = Fr::from(u64::MAX) + Fr::from(1u64);
+    circuit.message = message;
+    circuit.ciphertext = babyjubjub::encrypt(&circuit.public_key, message, circuit.randomness);
+    circuit.commitment = ElGamalCircuit::commit(message, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn public_key_outside_the_subgroup_is_rejected() {
+    let point = babyjubjub::Affine::new_unchecked(Fr::from(0u64), -Fr::from(1u64));
+    assert!(ElGamalCircuit::new(point, 1, Fr::from(1u64), Fr::from(1u64)).is_err());
+}
@@ -0,0 +1,69 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::inference::{InferenceCircuit, FRACTION_BITS};
+
+const ONE: i32 = 1 << FRACTION_BITS;
+
+/// `y0 = ReLU(x0 + 2 * x1 - 1)`, `y1 = ReLU(-x0 + 0.5)`.
+fn circuit(input: [i32; 2]) -> InferenceCircuit<2, 2> {
+    InferenceCircuit::new(
+        [[ONE, 2 * ONE], [-ONE, 0]],
+        [-ONE, ONE / 2],
+        input,
+        Fr::from(7u64),
+    )
+    .unwrap()
+}
+
+#[test]
+fn honest_inference_is_satisfied() {
+    let circuit = circuit([3 * ONE, ONE / 2]);
+    // 3 + 1 - 1, and -3 + 0.5 clamped to zero
+    assert_eq!(circuit.outputs, [3 * ONE, 0]);
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn products_are_floored() {
+    // 2^-16 * 2^-16 floors to zero rather than being rounded
+    let circuit = InferenceCircuit::<1, 1>::new([[1]], [0], [1], Fr::from(7u64)).unwrap();
+    assert_eq!(circuit.outputs, [0]);
+    assert!(is_satisfied(circuit));
+
+    // -2^-16 * 2^-16 floors to -2^-16, which the ReLU clamps
+    let circuit = InferenceCircuit::<1, 1>::new([[-1]], [1], [1], Fr::from(7u64)).unwrap();
+    assert_eq!(circuit.outputs, [0]);
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn other_output_is_not_satisfied() {
+    let mut off_by_one = circuit([3 * ONE, ONE / 2]);
+    off_by_one.outputs[0] += 1;
+    assert!(!is_satisfied(off_by_one));
+
+    // A negative output the ReLU should have clamped
+    let mut unclamped = circuit([3 * ONE, ONE / 2]);
+    unclamped.outputs[1] = -5 * ONE / 2;
+    assert!(!is_satisfied(unclamped));
+}
+
+#[test]
+fn other_input_is_not_satisfied() {
+    // An input that gives the same outputs, but not the committed one
+    let mut circuit = circuit([3 * ONE, ONE / 2]);
+    circuit.input = [4 * ONE, 0];
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn overflowing_output_is_rejected() {
+    let large = i32::MAX;
+    assert!(InferenceCircuit::<2, 1, Fr>::new(
+        [[large, large]],
+        [0],
+        [large, large],
+        Fr::from(7u64)
+    )
+    .is_err());
+}
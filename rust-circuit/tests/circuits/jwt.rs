@@ -0,0 +1,88 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use ark_r1cs_std::{uint8::UInt8, R1CSVar};
+use ark_relations::r1cs::ConstraintSystem;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use vortex::circuit::jwt::{JwtClaimCircuit, JwtLayout};
+use vortex::gadgets;
+
+const HEADER: &str = r#"{"alg":"RS256","typ":"JWT"}"#;
+const PAYLOAD: &str =
+    r#"{"iss":"https://issuer.example","sub":"1234567890","nonce":"n-0S6_WzA2Mj"}"#;
+
+fn token(payload: &str) -> String {
+    format!(
+        "{}.{}.signature",
+        URL_SAFE_NO_PAD.encode(HEADER),
+        URL_SAFE_NO_PAD.encode(payload)
+    )
+}
+
+fn circuit() -> JwtClaimCircuit<Fr> {
+    let token = token(PAYLOAD);
+    let layout = JwtLayout::of(&token, "sub").unwrap();
+    JwtClaimCircuit::new(&layout, &token, Fr::from(42u64)).unwrap()
+}
+
+#[test]
+fn token_with_claims_is_satisfied() {
+    assert!(is_satisfied(circuit()));
+}
+
+#[test]
+fn other_nonce_is_not_satisfied() {
+    let mut circuit = circuit();
+    circuit.nonce += Fr::from(1u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_claim_commitment_is_not_satisfied() {
+    let mut circuit = circuit();
+    circuit.salt = Fr::from(43u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn claim_offset_inside_another_claim_is_not_satisfied() {
+    let mut circuit = circuit();
+    circuit.claim_offset += 1;
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_token_of_same_layout_is_not_satisfied() {
+    let mut circuit = circuit();
+    // Same shape, different issuer: the digest no longer matches
+    let other = token(&PAYLOAD.replace("issuer", "ISSUER"));
+    circuit.signing_input = other.as_bytes()[..other.rfind('.').unwrap()].to_vec();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn base64url_decodes_like_the_base64_crate() {
+    for len in [0, 1, 2, 3, 10] {
+        let bytes = (0..len as u8)
+            .map(|i| i.wrapping_mul(97))
+            .collect::<Vec<_>>();
+        let encoded = URL_SAFE_NO_PAD.encode(&bytes);
+
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let chars = UInt8::new_witness_vec(cs.clone(), encoded.as_bytes()).unwrap();
+        let decoded = gadgets::base64::decode_url(&chars).unwrap();
+
+        assert_eq!(decoded.value().unwrap(), bytes);
+        assert!(cs.is_satisfied().unwrap());
+    }
+}
+
+#[test]
+fn base64url_rejects_characters_outside_the_alphabet() {
+    for char in [b'+', b'/', b'=', b'.'] {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let chars = UInt8::new_witness_vec(cs.clone(), &[b'A', char, b'A', b'A']).unwrap();
+        gadgets::base64::decode_url(&chars).unwrap();
+        assert!(!cs.is_satisfied().unwrap(), "{}", char as char);
+    }
+}
@@ -0,0 +1,30 @@
+mod age;
+mod airdrop;
+mod auction;
+mod batch_insert;
+mod blocklist;
+mod builder;
+mod credential;
+mod ecdsa;
+mod ed25519;
+mod elgamal;
+mod inference;
+mod jwt;
+mod multiplication;
+mod opening;
+mod pedersen;
+mod semaphore;
+mod shuffle;
+mod solvency;
+mod transfer;
+mod vote;
+
+use ark_ff::PrimeField;
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+
+/// Whether `circuit` synthesizes with its constraints satisfied. A witness
+/// that cannot be assigned, e.g. the inverse of zero, counts as unsatisfied.
+fn is_satisfied<F: PrimeField>(circuit: impl ConstraintSynthesizer<F>) -> bool {
+    let cs = ConstraintSystem::<F>::new_ref();
+    circuit.generate_constraints(cs.clone()).is_ok() && cs.is_satisfied().unwrap_or(false)
+}
@@ -0,0 +1,17 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::Circuit;
+use zeroize::Zeroize;
+
+#[test]
+fn zeroize_clears_the_factors_but_not_the_product() {
+    let mut circuit = Circuit::new(Fr::from(30u64), Fr::from(5u64), Fr::from(6u64), false).unwrap();
+    assert!(is_satisfied(circuit.clone()));
+
+    circuit.zeroize();
+    assert_eq!((circuit.a, circuit.b), (Fr::from(0u64), Fr::from(0u64)));
+    assert_eq!(circuit.c, Fr::from(30u64));
+
+    // The wiped factors no longer prove anything
+    assert!(!is_satisfied(circuit));
+}
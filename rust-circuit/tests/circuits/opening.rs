@@ -0,0 +1,36 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::opening::CommitmentOpeningCircuit;
+use vortex::poseidon;
+
+fn circuit() -> CommitmentOpeningCircuit {
+    CommitmentOpeningCircuit::new(Fr::from(42u64), Fr::from(123_456u64)).unwrap()
+}
+
+#[test]
+fn opening_is_satisfied() {
+    let circuit = circuit();
+    assert_eq!(
+        circuit.commitment,
+        [poseidon::hash(&[Fr::from(42u64), Fr::from(123_456u64)]).unwrap()]
+    );
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn wrong_value_or_blinding_is_not_satisfied() {
+    let mut circuit = circuit();
+    circuit.value = Fr::from(43u64);
+    assert!(!is_satisfied(circuit));
+
+    let mut circuit = self::circuit();
+    circuit.blinding += Fr::from(1u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn commitment_of_wrong_length_is_not_satisfied() {
+    let mut circuit = circuit();
+    circuit.commitment.push(Fr::from(0u64));
+    assert!(!is_satisfied(circuit));
+}
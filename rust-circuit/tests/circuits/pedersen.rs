@@ -0,0 +1,46 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use ark_ec::CurveGroup;
+use vortex::babyjubjub;
+use vortex::circuit::opening::CommitmentOpeningCircuit;
+use vortex::gadgets::commitment::PedersenCommitment;
+
+type OpeningCircuit = CommitmentOpeningCircuit<Fr, PedersenCommitment>;
+
+#[test]
+fn opening_is_satisfied() {
+    let circuit = OpeningCircuit::new(Fr::from(42u64), Fr::from(123_456u64)).unwrap();
+    let point = babyjubjub::commit(Fr::from(42u64), Fr::from(123_456u64));
+    assert_eq!(circuit.commitment, [point.x, point.y]);
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn wrong_opening_is_not_satisfied() {
+    let mut circuit = OpeningCircuit::new(Fr::from(42u64), Fr::from(123_456u64)).unwrap();
+    circuit.value = Fr::from(43u64);
+    assert!(!is_satisfied(circuit));
+
+    let mut circuit = OpeningCircuit::new(Fr::from(42u64), Fr::from(123_456u64)).unwrap();
+    circuit.blinding += Fr::from(1u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn commitments_add_up() {
+    let sum = babyjubjub::commit(Fr::from(3u64), Fr::from(10u64))
+        + babyjubjub::commit(Fr::from(4u64), Fr::from(20u64));
+    assert_eq!(
+        sum.into_affine(),
+        babyjubjub::commit(Fr::from(7u64), Fr::from(30u64))
+    );
+}
+
+#[test]
+fn rerandomized_commitment_opens_with_shifted_blinding() {
+    let commitment = babyjubjub::commit(Fr::from(5u64), Fr::from(10u64));
+    assert_eq!(
+        babyjubjub::rerandomize(&commitment, Fr::from(7u64)),
+        babyjubjub::commit(Fr::from(5u64), Fr::from(17u64))
+    );
+}
@@ -0,0 +1,101 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, PrimeField};
+use std::str::FromStr;
+use vortex::circuit::semaphore::{self, SemaphoreCircuit};
+use vortex::merkle::MerklePath;
+use vortex::poseidon;
+
+fn fr(decimal: &str) -> Fr {
+    Fr::from_str(decimal).unwrap()
+}
+
+fn hex_fr(hex: &str) -> Fr {
+    Fr::from_be_bytes_mod_order(&hex::decode(hex).unwrap())
+}
+
+#[test]
+fn hash_to_field_is_keccak_shifted_right_by_a_byte() {
+    // Keccak-256("") = c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+    assert_eq!(
+        semaphore::hash_to_field::<Fr>(b""),
+        hex_fr("c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a4")
+    );
+    // Semaphore's `hash(0)` over `toBeHex(0, 32)`; Keccak-256 of 32 zero
+    // bytes is 290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563
+    assert_eq!(
+        semaphore::hash_to_field::<Fr>(&[0u8; 32]),
+        hex_fr("290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e5")
+    );
+    assert!(
+        semaphore::hash_to_field::<Fr>(&[0xffu8; 32])
+            .into_bigint()
+            .num_bits()
+            <= 248
+    );
+}
+
+#[test]
+fn nullifier_hash_and_identity_commitment_match_semaphore_v3() {
+    // Poseidon(1, 2) in circomlibjs
+    let poseidon_1_2 =
+        fr("7853200120776062878684798364095072458815029376092732009249414926327459813530");
+
+    // Semaphore v3: Poseidon(external_nullifier, identity_nullifier)
+    assert_eq!(
+        semaphore::nullifier_hash(Fr::from(1u64), Fr::from(2u64)).unwrap(),
+        poseidon_1_2
+    );
+    // Semaphore v3: Poseidon(Poseidon(identity_nullifier, identity_trapdoor))
+    assert_eq!(
+        semaphore::identity_commitment(Fr::from(1u64), Fr::from(2u64)).unwrap(),
+        poseidon::circom_hash(&[poseidon_1_2]).unwrap()
+    );
+}
+
+#[test]
+fn group_root_hashes_pairs_with_circomlib_poseidon() {
+    // The empty subtree of depth 1 in a group with zero leaves:
+    // Poseidon(0, 0) in circomlibjs
+    let path = MerklePath {
+        siblings: vec![Fr::from(0u64)],
+        index: 0,
+    };
+    assert_eq!(
+        semaphore::group_root(&path, Fr::from(0u64)).unwrap(),
+        fr("14744269619966411208579211824598458697587494354926760081771325075741142829156")
+    );
+}
+
+fn circuit() -> SemaphoreCircuit<2> {
+    let path = MerklePath {
+        siblings: vec![Fr::from(11u64), Fr::from(12u64)],
+        index: 2,
+    };
+    let signal_hash = semaphore::hash_to_field(&[1u8; 32]);
+    let external_nullifier = semaphore::hash_to_field(&[2u8; 32]);
+    SemaphoreCircuit::new(
+        Fr::from(1u64),
+        Fr::from(2u64),
+        path,
+        signal_hash,
+        external_nullifier,
+    )
+    .unwrap()
+}
+
+#[test]
+fn group_member_signal_is_satisfied() {
+    assert!(is_satisfied(circuit()));
+}
+
+#[test]
+fn other_nullifier_hash_or_root_is_not_satisfied() {
+    let mut circuit_1 = circuit();
+    circuit_1.nullifier_hash += Fr::from(1u64);
+    assert!(!is_satisfied(circuit_1));
+
+    let mut circuit_2 = circuit();
+    circuit_2.path.index = 1;
+    assert!(!is_satisfied(circuit_2));
+}
@@ -0,0 +1,56 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::babyjubjub;
+use vortex::circuit::shuffle::ShuffleCircuit;
+
+type Shuffle3 = ShuffleCircuit<3>;
+
+/// Commitments to 10, 20 and 30 with blindings 1, 2 and 3.
+fn inputs() -> Vec<babyjubjub::Affine> {
+    (1..=3u64)
+        .map(|i| babyjubjub::commit(Fr::from(10 * i), Fr::from(i)))
+        .collect()
+}
+
+fn randomness() -> Vec<Fr> {
+    [100u64, 200, 300].into_iter().map(Fr::from).collect()
+}
+
+fn circuit() -> Shuffle3 {
+    Shuffle3::new(inputs(), vec![2, 0, 1], randomness()).unwrap()
+}
+
+#[test]
+fn shuffle_is_satisfied_and_keeps_values() {
+    let circuit = circuit();
+    // outputs[0] re-randomizes inputs[2], the commitment to 30
+    assert_eq!(
+        circuit.outputs[0],
+        babyjubjub::commit(Fr::from(30u64), Fr::from(103u64))
+    );
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn output_with_other_value_is_not_satisfied() {
+    let mut circuit = circuit();
+    circuit.outputs[0] = babyjubjub::commit(Fr::from(31u64), Fr::from(103u64));
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn duplicated_input_is_not_satisfied() {
+    let mut circuit = circuit();
+    // Outputs 0 and 1 both re-randomize inputs[2]; inputs[0] is dropped
+    circuit.permutation = vec![2, 2, 1];
+    circuit.outputs[1] = babyjubjub::rerandomize(&circuit.inputs[2], circuit.randomness[1]);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn invalid_arguments_are_rejected() {
+    assert!(Shuffle3::new(inputs(), vec![0, 0, 1], randomness()).is_err());
+    assert!(Shuffle3::new(inputs(), vec![0, 1, 3], randomness()).is_err());
+    assert!(Shuffle3::new(inputs()[..2].to_vec(), vec![0, 1], randomness()).is_err());
+    assert!(Shuffle3::new(inputs(), vec![0, 1, 2], randomness()[..2].to_vec()).is_err());
+}
@@ -0,0 +1,39 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::solvency::SolvencyCircuit;
+
+fn circuit(minimum: u64, balance: u64) -> SolvencyCircuit {
+    SolvencyCircuit::new(minimum, balance, Fr::from(7u64)).unwrap()
+}
+
+#[test]
+fn balance_at_or_above_minimum_is_satisfied() {
+    assert!(is_satisfied(circuit(1_000, 1_000)));
+    assert!(is_satisfied(circuit(1_000, u64::MAX)));
+    assert!(is_satisfied(circuit(0, 0)));
+}
+
+#[test]
+fn balance_below_minimum_is_rejected_and_not_satisfied() {
+    assert!(SolvencyCircuit::<Fr>::new(1_000, 999, Fr::from(7u64)).is_err());
+
+    let mut circuit = circuit(1_000, 1_000);
+    circuit.balance = Fr::from(999u64);
+    circuit.commitment = SolvencyCircuit::commit(circuit.balance, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn other_commitment_is_not_satisfied() {
+    let mut circuit = circuit(1_000, 5_000);
+    circuit.commitment = SolvencyCircuit::commit(Fr::from(6_000u64), circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn balance_above_64_bits_is_not_satisfied() {
+    let mut circuit = circuit(1_000, 1_000);
+    circuit.balance = Fr::from(u64::MAX) + Fr::from(1u64);
+    circuit.commitment = SolvencyCircuit::commit(circuit.balance, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
@@ -0,0 +1,126 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::transfer::{self, Note, TransferCircuit};
+use vortex::merkle::MerkleTree;
+
+type Transfer2 = TransferCircuit<2>;
+
+fn key(i: u64) -> Fr {
+    Fr::from(100 + i)
+}
+
+fn note(value: u64, i: u64) -> Note<Fr> {
+    Note::new(value, transfer::owner(key(i)).unwrap(), Fr::from(i))
+}
+
+/// Notes of 30 and 12 owned by keys 0 and 1 at leaves 0 and 1.
+fn tree() -> MerkleTree<Fr> {
+    let leaves = vec![
+        note(30, 0).commitment().unwrap(),
+        note(12, 1).commitment().unwrap(),
+    ];
+    MerkleTree::new(2, leaves).unwrap()
+}
+
+fn spend_both(outputs: [Note<Fr>; 2], public_amount: i64) -> anyhow::Result<Transfer2> {
+    Transfer2::new(
+        &tree(),
+        [(0, note(30, 0), key(0)), (1, note(12, 1), key(1))],
+        outputs,
+        public_amount,
+        Fr::from(77u64),
+    )
+}
+
+#[test]
+fn balanced_transfer_is_satisfied() {
+    let circuit = spend_both([note(40, 2), note(2, 3)], 0).unwrap();
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn withdrawal_is_satisfied() {
+    let circuit = spend_both(
+        [note(20, 2), Note::zero(Fr::from(0u64), Fr::from(0u64))],
+        -22,
+    )
+    .unwrap();
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn deposit_from_zero_notes_skips_membership() {
+    // Neither zero note is in the tree
+    let zero = |i| {
+        (
+            i,
+            Note::zero(transfer::owner(key(i)).unwrap(), Fr::from(9u64)),
+            key(i),
+        )
+    };
+    let circuit = Transfer2::new(
+        &tree(),
+        [zero(2), zero(3)],
+        [note(50, 2), note(0, 3)],
+        50,
+        Fr::from(77u64),
+    )
+    .unwrap();
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn unbalanced_transfer_is_rejected_and_not_satisfied() {
+    assert!(spend_both([note(40, 2), note(3, 3)], 0).is_err());
+
+    let mut circuit = spend_both([note(40, 2), note(2, 3)], 0).unwrap();
+    circuit.outputs[1] = note(3, 3);
+    circuit.output_commitments[1] = circuit.outputs[1].commitment().unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn note_spent_twice_is_not_satisfied() {
+    // Both inputs are the note at leaf 0, so both nullifiers are equal
+    let circuit = Transfer2::new(
+        &tree(),
+        [(0, note(30, 0), key(0)), (0, note(30, 0), key(0))],
+        [note(60, 2), note(0, 3)],
+        0,
+        Fr::from(77u64),
+    )
+    .unwrap();
+    assert_eq!(circuit.nullifiers[0], circuit.nullifiers[1]);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn note_outside_the_tree_is_rejected_and_not_satisfied() {
+    let outside = note(30, 5);
+    assert!(Transfer2::new(
+        &tree(),
+        [(0, outside, key(5)), (1, note(12, 1), key(1))],
+        [note(42, 2), note(0, 3)],
+        0,
+        Fr::from(77u64),
+    )
+    .is_err());
+
+    let mut circuit = spend_both([note(40, 2), note(2, 3)], 0).unwrap();
+    circuit.inputs[0].note = outside;
+    circuit.inputs[0].spending_key = key(5);
+    circuit.nullifiers[0] = transfer::nullifier(key(5), outside.commitment().unwrap(), 0).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn input_with_other_key_is_rejected() {
+    assert!(Transfer2::new(
+        &tree(),
+        [(0, note(30, 0), key(1)), (1, note(12, 1), key(1))],
+        [note(42, 2), note(0, 3)],
+        0,
+        Fr::from(77u64),
+    )
+    .is_err());
+}
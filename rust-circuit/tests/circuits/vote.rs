@@ -0,0 +1,129 @@
+use crate::is_satisfied;
+use ark_bn254::Fr;
+use vortex::circuit::vote::{self, TallyCircuit, VoteCircuit};
+use vortex::merkle::MerkleTree;
+
+type Vote2 = VoteCircuit<2>;
+type Tally3 = TallyCircuit<3>;
+
+fn secret(i: u64) -> Fr {
+    Fr::from(500 + i)
+}
+
+fn registry() -> MerkleTree<Fr> {
+    let leaves = (0..4)
+        .map(|i| vote::voter_identity(secret(i)).unwrap())
+        .collect();
+    MerkleTree::new(2, leaves).unwrap()
+}
+
+/// A vote by voter 2 on proposal 9 with choices `0..=3`.
+fn ballot(choice: u64) -> Vote2 {
+    Vote2::new(
+        &registry(),
+        2,
+        secret(2),
+        Fr::from(9u64),
+        3,
+        choice,
+        Fr::from(11u64),
+    )
+    .unwrap()
+}
+
+#[test]
+fn vote_within_choices_is_satisfied() {
+    assert!(is_satisfied(ballot(0)));
+    assert!(is_satisfied(ballot(3)));
+}
+
+#[test]
+fn vote_above_max_choice_is_rejected_and_not_satisfied() {
+    let new = |max_choice, choice| {
+        Vote2::new(
+            &registry(),
+            2,
+            secret(2),
+            Fr::from(9u64),
+            max_choice,
+            choice,
+            Fr::from(11u64),
+        )
+    };
+    assert!(new(3, 4).is_err());
+    assert!(new(1 << 8, 0).is_err());
+
+    let mut circuit = ballot(3);
+    circuit.vote = Fr::from(4u64);
+    circuit.vote_commitment = vote::commit_vote(circuit.vote, circuit.salt).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn unregistered_voter_is_rejected_and_not_satisfied() {
+    assert!(Vote2::new(
+        &registry(),
+        2,
+        secret(7),
+        Fr::from(9u64),
+        3,
+        1,
+        Fr::from(11u64)
+    )
+    .is_err());
+
+    let mut circuit = ballot(1);
+    circuit.secret = secret(7);
+    circuit.nullifier = vote::nullifier(circuit.secret, circuit.proposal_id).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn nullifier_for_other_proposal_is_not_satisfied() {
+    let mut circuit = ballot(1);
+    circuit.nullifier = vote::nullifier(secret(2), Fr::from(10u64)).unwrap();
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn tally_total_is_sum_of_votes() {
+    let tally = Tally3::new(&[
+        (1, Fr::from(1u64)),
+        (0, Fr::from(2u64)),
+        (1, Fr::from(3u64)),
+    ])
+    .unwrap();
+    assert_eq!(tally.total, Fr::from(2u64));
+    assert!(is_satisfied(tally));
+}
+
+#[test]
+fn tally_with_other_total_is_not_satisfied() {
+    let mut tally = Tally3::new(&[
+        (1, Fr::from(1u64)),
+        (0, Fr::from(2u64)),
+        (1, Fr::from(3u64)),
+    ])
+    .unwrap();
+    tally.total = Fr::from(3u64);
+    assert!(!is_satisfied(tally));
+}
+
+#[test]
+fn tally_opening_other_vote_is_not_satisfied() {
+    let mut tally = Tally3::new(&[
+        (1, Fr::from(1u64)),
+        (0, Fr::from(2u64)),
+        (1, Fr::from(3u64)),
+    ])
+    .unwrap();
+    // Opens the second commitment as a yes vote, keeping the total consistent
+    tally.votes[1] = Fr::from(1u64);
+    tally.total = Fr::from(3u64);
+    assert!(!is_satisfied(tally));
+}
+
+#[test]
+fn tally_with_wrong_vote_count_is_rejected() {
+    assert!(Tally3::new(&[(1, Fr::from(1u64))]).is_err());
+}
@@ -0,0 +1,60 @@
+use ark_bn254::{Fq, Fr, G1Affine, G1Projective, G2Affine, G2Projective};
+use ark_ec::{CurveGroup, PrimeGroup};
+use ark_ff::{BigInteger, Field, PrimeField};
+
+/// Little-endian Montgomery form, as snarkjs writes coordinates.
+fn montgomery(x: Fq) -> Vec<u8> {
+    (x * Fq::from(2u64).pow([256])).into_bigint().to_bytes_le()
+}
+
+pub fn g1_bytes(p: G1Affine) -> Vec<u8> {
+    [montgomery(p.x), montgomery(p.y)].concat()
+}
+
+pub fn g2_bytes(p: G2Affine) -> Vec<u8> {
+    [p.x.c0, p.x.c1, p.y.c0, p.y.c1]
+        .into_iter()
+        .flat_map(montgomery)
+        .collect()
+}
+
+/// A `.ptau` file with `2^power` powers of a known tau.
+pub fn ptau(power: u32, tau: Fr, alpha: Fr, beta: Fr) -> Vec<u8> {
+    let n = 1usize << power;
+    let powers = (0..2 * n - 1)
+        .scan(Fr::from(1u64), |acc, _| {
+            let current = *acc;
+            *acc *= tau;
+            Some(current)
+        })
+        .collect::<Vec<_>>();
+    let g1 = |scalar: Fr| g1_bytes((G1Projective::generator() * scalar).into_affine());
+    let g2 = |scalar: Fr| g2_bytes((G2Projective::generator() * scalar).into_affine());
+
+    let mut header = 32u32.to_le_bytes().to_vec();
+    header.extend(Fq::MODULUS.to_bytes_le());
+    header.extend(power.to_le_bytes());
+    let sections = [
+        (1u32, header),
+        (2, powers.iter().flat_map(|x| g1(*x)).collect()),
+        (3, powers[..n].iter().flat_map(|x| g2(*x)).collect()),
+        (4, powers[..n].iter().flat_map(|x| g1(alpha * x)).collect()),
+        (5, powers[..n].iter().flat_map(|x| g1(beta * x)).collect()),
+        (6, g2(beta)),
+    ];
+
+    container(b"ptau", 1, &sections)
+}
+
+/// An iden3 binary container: magic, version, then `(type, size, data)` sections.
+pub fn container(magic: &[u8; 4], version: u32, sections: &[(u32, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = magic.to_vec();
+    bytes.extend(version.to_le_bytes());
+    bytes.extend((sections.len() as u32).to_le_bytes());
+    for (section_type, data) in sections {
+        bytes.extend(section_type.to_le_bytes());
+        bytes.extend((data.len() as u64).to_le_bytes());
+        bytes.extend(data);
+    }
+    bytes
+}
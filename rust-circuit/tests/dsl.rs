@@ -0,0 +1,140 @@
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystem, ConstraintSystemRef},
+};
+use std::collections::HashMap;
+use vortex::circuit::Circuit;
+use vortex::dsl::{self, CircuitSpec, DslCircuit};
+use vortex::inspect;
+
+const MULTIPLICATION: &str = r#"{
+    "name": "multiplication",
+    "public": ["c"],
+    "private": ["a", "b"],
+    "constraints": ["c == a * b"]
+}"#;
+
+const SUM_OF_SQUARE: &str = r#"
+name = "sum-of-square"
+public = ["c"]
+private = ["a", "b", "d"]
+constraints = ["c == a*b + d^2"]
+"#;
+
+/// `c == a*b + d^2`, written by hand.
+struct SumOfSquare {
+    c: Fr,
+    a: Fr,
+    b: Fr,
+    d: Fr,
+}
+
+impl ConstraintSynthesizer<Fr> for SumOfSquare {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> r1cs::Result<()> {
+        let c = FpVar::new_input(ns!(cs, "c"), || Ok(self.c))?;
+        let a = FpVar::new_witness(ns!(cs, "a"), || Ok(self.a))?;
+        let b = FpVar::new_witness(ns!(cs, "b"), || Ok(self.b))?;
+        let d = FpVar::new_witness(ns!(cs, "d"), || Ok(self.d))?;
+        c.enforce_equal(&(a * b + d.square()?))
+    }
+}
+
+fn assignment(values: &[(&str, u64)]) -> HashMap<String, Fr> {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), Fr::from(*value)))
+        .collect()
+}
+
+fn is_satisfied(circuit: DslCircuit) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    circuit.generate_constraints(cs.clone()).is_ok() && cs.is_satisfied().unwrap()
+}
+
+#[test]
+fn dsl_multiplication_has_the_built_in_matrices() {
+    let spec = CircuitSpec::from_json(MULTIPLICATION).unwrap();
+    assert_eq!(
+        inspect::digest(DslCircuit::<Fr>::empty(spec).unwrap()).unwrap(),
+        Circuit::<Fr>::empty().cs_digest().unwrap()
+    );
+}
+
+#[test]
+fn dsl_expression_has_the_hand_written_matrices() {
+    let spec = CircuitSpec::from_toml(SUM_OF_SQUARE).unwrap();
+    let hand_written = SumOfSquare {
+        c: Fr::from(0u64),
+        a: Fr::from(0u64),
+        b: Fr::from(0u64),
+        d: Fr::from(0u64),
+    };
+    assert_eq!(
+        inspect::digest(DslCircuit::<Fr>::empty(spec).unwrap()).unwrap(),
+        inspect::digest(hand_written).unwrap()
+    );
+}
+
+#[test]
+fn satisfying_assignment_is_satisfied() {
+    let spec = CircuitSpec::from_toml(SUM_OF_SQUARE).unwrap();
+    let circuit = DslCircuit::new(
+        spec,
+        &assignment(&[("c", 31), ("a", 3), ("b", 5), ("d", 4)]),
+    )
+    .unwrap();
+    assert_eq!(circuit.get_public_inputs(), [Fr::from(31u64)]);
+    assert!(is_satisfied(circuit));
+}
+
+#[test]
+fn failing_assignment_is_rejected_and_not_satisfied() {
+    let spec = CircuitSpec::from_toml(SUM_OF_SQUARE).unwrap();
+    let wrong = assignment(&[("c", 32), ("a", 3), ("b", 5), ("d", 4)]);
+    assert!(DslCircuit::new(spec.clone(), &wrong).is_err());
+
+    let mut circuit = DslCircuit::new(
+        spec,
+        &assignment(&[("c", 31), ("a", 3), ("b", 5), ("d", 4)]),
+    )
+    .unwrap();
+    circuit.public[0] = Fr::from(32u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn assignment_must_match_the_declared_variables() {
+    let spec = CircuitSpec::from_json(MULTIPLICATION).unwrap();
+    assert!(DslCircuit::<Fr>::new(spec.clone(), &assignment(&[("c", 6), ("a", 2)])).is_err());
+    assert!(
+        DslCircuit::<Fr>::new(spec, &assignment(&[("c", 6), ("a", 2), ("b", 3), ("e", 1)]))
+            .is_err()
+    );
+}
+
+#[test]
+fn invalid_specs_do_not_compile() {
+    let spec = |public: &[&str], constraint: &str| CircuitSpec {
+        name: "invalid".into(),
+        public: public.iter().map(|name| name.to_string()).collect(),
+        private: vec!["a".into()],
+        constraints: vec![constraint.into()],
+    };
+    // Undeclared variable, duplicate declaration, bad name, no `==`, bad syntax
+    assert!(spec(&["c"], "c == a * b").compile().is_err());
+    assert!(spec(&["a"], "a == a").compile().is_err());
+    assert!(spec(&["1c"], "a == 1").compile().is_err());
+    assert!(spec(&["c"], "c + a").compile().is_err());
+    assert!(spec(&["c"], "c == (a *").compile().is_err());
+    assert!(spec(&["c"], "c == a^2 + 0x10").compile().is_ok());
+}
+
+#[test]
+fn assignments_parse_decimal_and_hex() {
+    let values = dsl::parse_assignment::<Fr>(r#"{"a": "3", "b": "0x10"}"#).unwrap();
+    assert_eq!(values["a"], Fr::from(3u64));
+    assert_eq!(values["b"], Fr::from(16u64));
+    assert!(dsl::parse_assignment::<Fr>(r#"{"a": 3}"#).is_err());
+}
@@ -0,0 +1,32 @@
+use ark_bn254::Bn254;
+use ark_groth16::VerifyingKey;
+use ark_serialize::CanonicalDeserialize;
+use vortex::solidity;
+use vortex::sui::verifier;
+
+// Generated from keys/verification_key.bin; after regenerating the keys, run
+// `cargo run --bin export solidity fixtures/export/Verifier.sol`
+const SOLIDITY_VERIFIER: &str = include_str!("../fixtures/export/Verifier.sol");
+// Likewise `cargo run --bin export sui-move fixtures/export/verifier.move`
+const MOVE_VERIFIER: &str = include_str!("../fixtures/export/verifier.move");
+
+fn verifying_key() -> VerifyingKey<Bn254> {
+    let hex = include_str!("../keys/verification_key.hex");
+    VerifyingKey::deserialize_compressed(&hex::decode(hex.trim()).unwrap()[..]).unwrap()
+}
+
+#[test]
+fn solidity_verifier_matches_snapshot() {
+    assert_eq!(
+        solidity::verifier_contract(&verifying_key()),
+        SOLIDITY_VERIFIER
+    );
+}
+
+#[test]
+fn move_verifier_matches_snapshot() {
+    assert_eq!(
+        verifier::verifier_module(&verifying_key(), "vortex", "verifier").unwrap(),
+        MOVE_VERIFIER
+    );
+}
@@ -0,0 +1,102 @@
+use crate::satisfied;
+use ark_bn254::Fr;
+use ark_ff::PrimeField;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*, uint8::UInt8};
+use ark_relations::r1cs::{ConstraintSystem, ConstraintSystemRef};
+use vortex::gadgets::bytes;
+
+fn witness_bytes(cs: &ConstraintSystemRef<Fr>, values: &[u8]) -> Vec<UInt8<Fr>> {
+    UInt8::new_witness_vec(cs.clone(), values).unwrap()
+}
+
+#[test]
+fn packing_matches_native() {
+    let values = [0x01, 0x02, 0x03, 0xff];
+    assert!(satisfied(|cs| {
+        let input = witness_bytes(&cs, &values);
+        assert_eq!(
+            bytes::pack_be(&input)?.value()?,
+            Fr::from_be_bytes_mod_order(&values)
+        );
+        assert_eq!(
+            bytes::pack_le(&input)?.value()?,
+            Fr::from_le_bytes_mod_order(&values)
+        );
+        Ok(())
+    }));
+}
+
+#[test]
+fn unpack_round_trips() {
+    assert!(satisfied(|cs| {
+        let x = FpVar::new_witness(cs, || Ok(Fr::from(0x0102_03ffu64)))?;
+        assert_eq!(bytes::unpack_be(&x, 4)?.value()?, [0x01, 0x02, 0x03, 0xff]);
+        assert_eq!(bytes::unpack_le(&x, 4)?.value()?, [0xff, 0x03, 0x02, 0x01]);
+        Ok(())
+    }));
+}
+
+#[test]
+fn unpack_of_too_large_value_is_not_satisfied() {
+    assert!(!satisfied(|cs| {
+        let x = FpVar::new_witness(cs, || Ok(Fr::from(1u64 << 32)))?;
+        bytes::unpack_be(&x, 4)?;
+        Ok(())
+    }));
+}
+
+#[test]
+fn swap_endianness_reverses_each_word() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let input = witness_bytes(&cs, &[1, 2, 3, 4, 5, 6, 7, 8]);
+    let swapped = bytes::swap_endianness(&input, 4).unwrap();
+    assert_eq!(swapped.value().unwrap(), [4, 3, 2, 1, 8, 7, 6, 5]);
+    assert!(bytes::swap_endianness(&input, 3).is_err());
+}
+
+#[test]
+fn slice_at_selects_the_window() {
+    assert!(satisfied(|cs| {
+        let input = witness_bytes(&cs, b"hello world");
+        let offset = FpVar::new_witness(cs, || Ok(Fr::from(6u64)))?;
+        assert_eq!(bytes::slice_at(&input, &offset, 5)?.value()?, b"world");
+        Ok(())
+    }));
+}
+
+#[test]
+fn slice_past_the_end_is_not_satisfied() {
+    assert!(!satisfied(|cs| {
+        let input = witness_bytes(&cs, b"hello world");
+        let offset = FpVar::new_witness(cs, || Ok(Fr::from(7u64)))?;
+        bytes::slice_at(&input, &offset, 5)?;
+        Ok(())
+    }));
+}
+
+#[test]
+fn equal_prefix_ignores_padding() {
+    assert!(satisfied(|cs| {
+        let a = witness_bytes(&cs, b"abc\0\0");
+        let b = witness_bytes(&cs, b"abcxy");
+        let len = FpVar::new_witness(cs, || Ok(Fr::from(3u64)))?;
+        bytes::enforce_equal_prefix(&a, &b, &len)
+    }));
+}
+
+#[test]
+fn different_prefix_is_not_satisfied() {
+    for (b, len) in [(&b"abd\0\0"[..], 3u64), (b"abcxy", 4), (b"abc\0\0", 6)] {
+        assert!(
+            !satisfied(|cs| {
+                let a = witness_bytes(&cs, b"abc\0\0");
+                let b = witness_bytes(&cs, b);
+                let len = FpVar::new_witness(cs, || Ok(Fr::from(len)))?;
+                bytes::enforce_equal_prefix(&a, &b, &len)
+            }),
+            "{:?} {}",
+            b,
+            len
+        );
+    }
+}
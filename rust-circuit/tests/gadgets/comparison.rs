@@ -0,0 +1,72 @@
+use crate::{satisfied, witness};
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::ConstraintSystem;
+use vortex::gadgets::comparison;
+
+#[test]
+fn is_zero_and_is_equal() {
+    for (a, b) in [(0u64, 0u64), (0, 1), (5, 5), (5, 6)] {
+        assert!(satisfied(|cs| {
+            let (x, y) = (witness(&cs, a)?, witness(&cs, b)?);
+            assert_eq!(comparison::is_zero(&x)?.value()?, a == 0);
+            assert_eq!(comparison::is_equal(&x, &y)?.value()?, a == b);
+            Ok(())
+        }));
+    }
+}
+
+#[test]
+fn enforce_nonzero_accepts_nonzero() {
+    assert!(satisfied(|cs| comparison::enforce_nonzero(&witness(
+        &cs, 7
+    )?)));
+    assert!(satisfied(|_| comparison::enforce_nonzero(
+        &FpVar::constant(Fr::from(7u64))
+    )));
+}
+
+#[test]
+fn enforce_nonzero_of_zero_is_not_satisfied() {
+    assert!(!satisfied(|cs| comparison::enforce_nonzero(&witness(
+        &cs, 0
+    )?)));
+    assert!(comparison::enforce_nonzero(&FpVar::<Fr>::zero()).is_err());
+}
+
+#[test]
+fn less_than_is_enforced() {
+    assert!(satisfied(|cs| comparison::enforce_less_than(
+        &witness(&cs, 3)?,
+        &witness(&cs, 4)?,
+        8
+    )));
+    assert!(!satisfied(|cs| comparison::enforce_less_than(
+        &witness(&cs, 4)?,
+        &witness(&cs, 4)?,
+        8
+    )));
+    assert!(!satisfied(|cs| comparison::enforce_less_or_equal(
+        &witness(&cs, 5)?,
+        &witness(&cs, 4)?,
+        8
+    )));
+}
+
+#[test]
+fn is_zero_costs_two_constraints() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let x = witness(&cs, 5).unwrap();
+    let before = cs.num_constraints();
+    let _ = comparison::is_zero(&x).unwrap();
+    assert_eq!(cs.num_constraints() - before, 2);
+}
+
+#[test]
+fn is_equal_costs_two_constraints() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let (a, b) = (witness(&cs, 5).unwrap(), witness(&cs, 7).unwrap());
+    let before = cs.num_constraints();
+    let _ = comparison::is_equal(&a, &b).unwrap();
+    assert_eq!(cs.num_constraints() - before, 2);
+}
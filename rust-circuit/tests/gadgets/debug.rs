@@ -0,0 +1,94 @@
+use crate::witness;
+use ark_bn254::Fr;
+use ark_r1cs_std::prelude::*;
+use ark_relations::{
+    ns,
+    r1cs::{ConstraintSystem, SynthesisMode},
+};
+use vortex::gadgets::debug::{self, TraceEntry};
+use vortex::witness::traced;
+
+#[test]
+fn capture_records_values_in_call_order() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let ((), trace) = debug::capture(|| {
+        let x = witness(&cs, 3).unwrap();
+        let square = x.square().unwrap();
+        debug::debug_print("x", &x);
+        debug::debug_print("square", &square);
+        debug::debug_print("is_nine", &square.is_eq(&witness(&cs, 9).unwrap()).unwrap());
+    });
+
+    let labels: Vec<&str> = trace.iter().map(|entry| entry.label.as_str()).collect();
+    assert_eq!(labels, ["x", "square", "is_nine"]);
+    assert_eq!(trace[1].to_string(), "square = 9");
+    assert_eq!(trace[2].value, "true");
+}
+
+#[test]
+fn debug_print_adds_no_constraints_or_variables() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let x = witness(&cs, 3).unwrap();
+    let before = (cs.num_constraints(), cs.num_witness_variables());
+    let ((), trace) = debug::capture(|| debug::debug_print("x", &x));
+
+    assert_eq!(trace.len(), 1);
+    assert_eq!((cs.num_constraints(), cs.num_witness_variables()), before);
+}
+
+#[test]
+fn nothing_is_recorded_outside_capture_or_in_setup() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let x = witness(&cs, 3).unwrap();
+    debug::debug_print("outside", &x);
+    let ((), trace) = debug::capture(|| ());
+    assert!(trace.is_empty());
+
+    // Setup mode has no values to record
+    let setup = ConstraintSystem::<Fr>::new_ref();
+    setup.set_mode(SynthesisMode::Setup);
+    let ((), trace) = debug::capture(|| {
+        let x = witness(&setup, 3).unwrap();
+        debug::debug_print("x", &x);
+    });
+    assert!(trace.is_empty());
+}
+
+#[test]
+fn inner_capture_records_only_its_own_calls() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let x = witness(&cs, 3).unwrap();
+    let (inner, outer) = debug::capture(|| {
+        debug::debug_print("outer", &x);
+        let ((), inner) = debug::capture(|| debug::debug_print("inner", &x));
+        debug::debug_print("after", &x);
+        inner
+    });
+
+    let labels = |trace: &[TraceEntry]| -> Vec<String> {
+        trace.iter().map(|entry| entry.label.clone()).collect()
+    };
+    assert_eq!(labels(&inner), ["inner"]);
+    assert_eq!(labels(&outer), ["outer", "after"]);
+}
+
+#[test]
+fn traced_entries_carry_their_namespace() {
+    let trace = traced(|| {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let x = witness(&cs, 3).unwrap();
+        let _ns = ns!(cs, "level 3");
+        debug::capture(|| debug::debug_print("x", &x)).1
+    });
+
+    assert!(
+        trace[0].namespace.ends_with("level 3"),
+        "{}",
+        trace[0].namespace
+    );
+    assert!(
+        trace[0].to_string().ends_with("level 3/x = 3"),
+        "{}",
+        trace[0]
+    );
+}
@@ -0,0 +1,69 @@
+use crate::{satisfied, witness};
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+use vortex::gadgets::lookup;
+
+fn table() -> Vec<Fr> {
+    [1u64, 5, 10, 25].into_iter().map(Fr::from).collect()
+}
+
+#[test]
+fn value_in_table_is_satisfied() {
+    for value in [1, 5, 10, 25] {
+        assert!(satisfied(|cs| lookup::enforce_in_table(
+            &witness(&cs, value)?,
+            &table()
+        )));
+    }
+    // A single-entry table is an equality
+    assert!(satisfied(|cs| lookup::enforce_in_table(
+        &witness(&cs, 5)?,
+        &[Fr::from(5u64)]
+    )));
+}
+
+#[test]
+fn value_missing_from_table_is_not_satisfied() {
+    for value in [0, 2, 26] {
+        assert!(!satisfied(|cs| lookup::enforce_in_table(
+            &witness(&cs, value)?,
+            &table()
+        )));
+    }
+    assert!(!satisfied(|cs| lookup::enforce_in_table(
+        &witness(&cs, 4)?,
+        &[Fr::from(5u64)]
+    )));
+    assert!(lookup::enforce_in_table(&FpVar::constant(Fr::from(2u64)), &table()).is_err());
+    assert!(lookup::enforce_in_table::<Fr>(&FpVar::zero(), &[]).is_err());
+}
+
+#[test]
+fn is_in_table_matches_membership() {
+    for value in 0..30u64 {
+        assert!(satisfied(|cs| {
+            let member = lookup::is_in_table(&witness(&cs, value)?, &table())?;
+            assert_eq!(member.value()?, table().contains(&Fr::from(value)));
+            Ok(())
+        }));
+    }
+}
+
+#[test]
+fn set_membership() {
+    let set = |cs: &ConstraintSystemRef<Fr>| {
+        [3u64, 7, 11]
+            .into_iter()
+            .map(|v| FpVar::new_input(cs.clone(), || Ok(Fr::from(v))))
+            .collect::<r1cs::Result<Vec<_>>>()
+    };
+    assert!(satisfied(|cs| lookup::enforce_in_set(
+        &witness(&cs, 7)?,
+        &set(&cs)?
+    )));
+    assert!(!satisfied(|cs| lookup::enforce_in_set(
+        &witness(&cs, 8)?,
+        &set(&cs)?
+    )));
+}
@@ -0,0 +1,22 @@
+mod bytes;
+mod comparison;
+mod debug;
+mod lookup;
+mod select;
+mod signed;
+mod uint;
+
+use ark_bn254::Fr;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
+use ark_relations::r1cs::{self, ConstraintSystem, ConstraintSystemRef};
+
+/// Whether the constraints added by `build` hold; a witness that cannot be
+/// assigned counts as unsatisfied.
+fn satisfied(build: impl FnOnce(ConstraintSystemRef<Fr>) -> r1cs::Result<()>) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    build(cs.clone()).is_ok() && cs.is_satisfied().unwrap_or(false)
+}
+
+fn witness(cs: &ConstraintSystemRef<Fr>, value: u64) -> r1cs::Result<FpVar<Fr>> {
+    FpVar::new_witness(cs.clone(), || Ok(Fr::from(value)))
+}
@@ -0,0 +1,64 @@
+use crate::satisfied;
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+use vortex::gadgets::select;
+
+fn array(cs: &ConstraintSystemRef<Fr>) -> r1cs::Result<Vec<FpVar<Fr>>> {
+    [10u64, 20, 30, 40]
+        .into_iter()
+        .map(|x| FpVar::new_witness(cs.clone(), || Ok(Fr::from(x))))
+        .collect()
+}
+
+#[test]
+fn select_at_index_returns_the_element() {
+    for i in 0..4u64 {
+        assert!(satisfied(|cs| {
+            let array = array(&cs)?;
+            let index = FpVar::new_witness(cs, || Ok(Fr::from(i)))?;
+            let selected = select::select_at_index(&array, &index)?;
+            assert_eq!(selected.value()?, Fr::from(10 * (i + 1)));
+            Ok(())
+        }));
+    }
+}
+
+#[test]
+fn index_out_of_range_is_not_satisfied() {
+    assert!(!satisfied(|cs| {
+        let array = array(&cs)?;
+        let index = FpVar::new_witness(cs, || Ok(Fr::from(4u64)))?;
+        select::select_at_index(&array, &index).map(drop)
+    }));
+    assert!(!satisfied(|cs| {
+        let array = array(&cs)?;
+        let index = FpVar::new_witness(cs, || Ok(-Fr::from(1u64)))?;
+        select::select_at_index(&array, &index).map(drop)
+    }));
+}
+
+#[test]
+fn constant_index_out_of_range_is_an_error() {
+    assert!(select::one_hot(&FpVar::constant(Fr::from(4u64)), 4).is_err());
+    let flags = select::one_hot(&FpVar::constant(Fr::from(2u64)), 4).unwrap();
+    assert_eq!(flags.value().unwrap(), [false, false, true, false]);
+}
+
+#[test]
+fn conditionally_swap_orders_the_pair() {
+    for swap in [false, true] {
+        assert!(satisfied(|cs| {
+            let a = FpVar::new_witness(cs.clone(), || Ok(Fr::from(1u64)))?;
+            let b = FpVar::new_witness(cs.clone(), || Ok(Fr::from(2u64)))?;
+            let flag = Boolean::new_witness(cs, || Ok(swap))?;
+            let (first, second) = select::conditionally_swap(&flag, &a, &b)?;
+            let expected = if swap { (2u64, 1u64) } else { (1, 2) };
+            assert_eq!(
+                (first.value()?, second.value()?),
+                (Fr::from(expected.0), Fr::from(expected.1))
+            );
+            Ok(())
+        }));
+    }
+}
@@ -0,0 +1,68 @@
+use crate::satisfied;
+use ark_bn254::Fr;
+use ark_r1cs_std::prelude::*;
+use ark_relations::r1cs::{self, ConstraintSystemRef};
+use vortex::gadgets::signed::{self, Int32Var};
+
+fn int(cs: &ConstraintSystemRef<Fr>, value: i128) -> r1cs::Result<Int32Var<Fr>> {
+    Int32Var::new_witness(cs.clone(), || Ok(value))
+}
+
+#[test]
+fn field_encoding_round_trips() {
+    for value in [0, 1, -1, i64::MIN as i128, i128::MAX, -i128::MAX] {
+        assert_eq!(
+            signed::from_field(signed::to_field::<Fr>(value)),
+            Some(value)
+        );
+    }
+}
+
+#[test]
+fn arithmetic_in_range_is_satisfied() {
+    for (a, b) in [
+        (7i128, -5i128),
+        (-7, 5),
+        (0, i32::MIN as i128),
+        (-46340, 46340),
+    ] {
+        assert!(satisfied(|cs| {
+            let (x, y) = (int(&cs, a)?, int(&cs, b)?);
+            assert_eq!(x.add(&y)?.value()?, a + b);
+            assert_eq!(x.mul(&y)?.value()?, a * b);
+            assert_eq!(x.is_negative()?.value()?, a < 0);
+            assert_eq!(x.is_lt(&y)?.value()?, a < b);
+            assert_eq!(x.is_le(&y)?.value()?, a <= b);
+            Ok(())
+        }));
+    }
+}
+
+#[test]
+fn out_of_range_values_are_not_satisfied() {
+    assert!(!satisfied(|cs| int(&cs, i32::MAX as i128 + 1).map(drop)));
+    assert!(!satisfied(|cs| int(&cs, i32::MIN as i128 - 1).map(drop)));
+}
+
+#[test]
+fn overflow_is_not_satisfied() {
+    assert!(!satisfied(|cs| int(&cs, i32::MAX as i128)?
+        .add(&int(&cs, 1)?)
+        .map(drop)));
+    assert!(!satisfied(|cs| int(&cs, i32::MIN as i128)?
+        .sub(&int(&cs, 1)?)
+        .map(drop)));
+    assert!(!satisfied(|cs| int(&cs, 1 << 16)?
+        .mul(&int(&cs, 1 << 15)?)
+        .map(drop)));
+    assert!(!satisfied(|cs| int(&cs, i32::MIN as i128)?.neg().map(drop)));
+}
+
+#[test]
+fn comparisons_are_enforced() {
+    assert!(satisfied(|cs| int(&cs, -3)?.enforce_lt(&int(&cs, 2)?)));
+    assert!(satisfied(|cs| int(&cs, 2)?.enforce_le(&int(&cs, 2)?)));
+    assert!(!satisfied(|cs| int(&cs, 2)?.enforce_lt(&int(&cs, 2)?)));
+    assert!(!satisfied(|cs| int(&cs, 2)?.enforce_le(&int(&cs, -3)?)));
+    assert!(!satisfied(|cs| int(&cs, -1)?.to_unsigned().map(drop)));
+}
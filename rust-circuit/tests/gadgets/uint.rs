@@ -0,0 +1,83 @@
+use crate::satisfied;
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::r1cs::ConstraintSystemRef;
+use vortex::gadgets::uint::{self, UInt32, UInt64};
+
+fn u32s(cs: &ConstraintSystemRef<Fr>, a: u32, b: u32) -> (UInt32<Fr>, UInt32<Fr>) {
+    (
+        UInt32::new_witness(cs.clone(), || Ok(a)).unwrap(),
+        UInt32::new_witness(cs.clone(), || Ok(b)).unwrap(),
+    )
+}
+
+#[test]
+fn arithmetic_matches_native() {
+    for (a, b) in [
+        (0u32, 0u32),
+        (7, 5),
+        (5, 7),
+        (u32::MAX, 1),
+        (u32::MAX, u32::MAX),
+    ] {
+        assert!(satisfied(|cs| {
+            let (x, y) = u32s(&cs, a, b);
+
+            let (sum, carry) = uint::add_with_carry(&x, &y)?;
+            assert_eq!((sum.value()?, carry.value()?), a.overflowing_add(b));
+            let (difference, borrow) = uint::sub_with_borrow(&x, &y)?;
+            assert_eq!((difference.value()?, borrow.value()?), a.overflowing_sub(b));
+            assert_eq!(uint::wrapping_sub(&x, &y)?.value()?, a.wrapping_sub(b));
+
+            let (low, high) = uint::widening_mul(&x, &y)?;
+            let product = a as u64 * b as u64;
+            assert_eq!(
+                (low.value()?, high.value()?),
+                (product as u32, (product >> 32) as u32)
+            );
+            assert_eq!(uint::wrapping_mul(&x, &y)?.value()?, a.wrapping_mul(b));
+            Ok(())
+        }));
+    }
+}
+
+#[test]
+fn checked_arithmetic_is_satisfied_without_overflow() {
+    assert!(satisfied(|cs| {
+        let (x, y) = u32s(&cs, 7, 5);
+        assert_eq!(uint::checked_add(&x, &y)?.value()?, 12);
+        assert_eq!(uint::checked_sub(&x, &y)?.value()?, 2);
+        assert_eq!(uint::checked_mul(&x, &y)?.value()?, 35);
+        Ok(())
+    }));
+}
+
+#[test]
+fn checked_overflow_is_not_satisfied() {
+    assert!(!satisfied(|cs| {
+        let (x, y) = u32s(&cs, u32::MAX, 1);
+        uint::checked_add(&x, &y).map(drop)
+    }));
+    assert!(!satisfied(|cs| {
+        let (x, y) = u32s(&cs, 5, 7);
+        uint::checked_sub(&x, &y).map(drop)
+    }));
+    assert!(!satisfied(|cs| {
+        let (x, y) = u32s(&cs, 1 << 16, 1 << 16);
+        uint::checked_mul(&x, &y).map(drop)
+    }));
+}
+
+#[test]
+fn from_fp_range_checks() {
+    assert!(satisfied(|cs| {
+        let x = FpVar::new_witness(cs, || Ok(Fr::from(u64::MAX)))?;
+        let x: UInt64<Fr> = uint::from_fp(&x)?;
+        assert_eq!(x.value()?, u64::MAX);
+        Ok(())
+    }));
+    assert!(!satisfied(|cs| {
+        let x = FpVar::new_witness(cs, || Ok(Fr::from(u64::MAX) + Fr::from(1u64)))?;
+        uint::from_fp::<64, u64, _>(&x).map(drop)
+    }));
+}
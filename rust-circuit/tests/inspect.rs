@@ -0,0 +1,71 @@
+use ark_bn254::Fr;
+use vortex::circuit::Circuit;
+use vortex::inspect::{self, Summary};
+
+fn summary(circuit: Circuit<Fr>, version: &str) -> Summary {
+    Summary::new(
+        Circuit::NAME,
+        version,
+        &inspect::inspect(circuit.clone()).unwrap(),
+        Circuit::PUBLIC_INPUT_NAMES,
+        circuit.cs_digest().unwrap(),
+    )
+}
+
+#[test]
+fn same_circuit_has_no_changes() {
+    let before = summary(Circuit::empty(), "1.0.0");
+    assert!(before.diff(&before.clone()).is_empty());
+    assert!(!before.requires_setup(&before));
+}
+
+#[test]
+fn changed_constraints_require_a_setup() {
+    let before = summary(Circuit::empty(), "1.0.0");
+    let after = summary(Circuit::empty_non_trivial(), "1.0.0");
+    assert!(before.requires_setup(&after));
+    assert_eq!(
+        before.diff(&after),
+        [
+            // Inverses of a, a - 1, b and b - 1, each a witness and a constraint
+            "constraints: 2 -> 6 (+4)".to_string(),
+            "witness variables: 3 -> 7 (+4)".to_string(),
+            "namespace circuit::non-trivial factors: 0 -> 4 (+4)".to_string(),
+            format!(
+                "constraint system digest: {} -> {}",
+                before.cs_digest, after.cs_digest
+            ),
+        ]
+    );
+}
+
+#[test]
+fn version_bump_alone_keeps_the_keys() {
+    let before = summary(Circuit::empty(), "1.0.0");
+    let after = summary(Circuit::empty(), "1.1.0");
+    assert_eq!(before.diff(&after), ["version: 1.0.0 -> 1.1.0"]);
+    assert!(!before.requires_setup(&after));
+}
+
+#[test]
+fn renamed_public_inputs_are_reported() {
+    let before = summary(Circuit::empty(), "1.0.0");
+    let mut after = before.clone();
+    after.public_input_names = vec!["product".into()];
+    assert_eq!(
+        before.diff(&after),
+        ["public input layout: [c] -> [product]"]
+    );
+
+    // Unnamed inputs are only compared by count
+    after.public_input_names.clear();
+    assert!(before.diff(&after).is_empty());
+}
+
+#[test]
+fn summary_json_round_trips() {
+    let summary = summary(Circuit::empty_non_trivial(), "1.0.0");
+    let json = serde_json::to_string(&summary).unwrap();
+    assert!(json.contains("\"csDigest\""));
+    assert_eq!(serde_json::from_str::<Summary>(&json).unwrap(), summary);
+}
@@ -0,0 +1,105 @@
+use ark_bn254::Bn254;
+use ark_groth16::ProvingKey;
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use vortex::curve::Curve;
+use vortex::keys::cache;
+use vortex::keys::convert::KeyKind;
+use vortex::keys::header::{self, Expected};
+
+/// The cache is process-wide, so tests take turns with it.
+fn exclusive() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    let guard = LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    cache::clear();
+    cache::set_capacity(cache::DEFAULT_CAPACITY);
+    guard
+}
+
+fn pk_bytes() -> Vec<u8> {
+    fs::read(Path::new(env!("CARGO_MANIFEST_DIR")).join("keys/proving_key.bin")).unwrap()
+}
+
+fn expected(non_trivial: Option<bool>) -> Expected {
+    Expected {
+        kind: KeyKind::Proving,
+        curve: Curve::Bn254,
+        non_trivial,
+    }
+}
+
+/// Looks up `digest`, counting in `decodes` whether it had to be decoded.
+fn get(digest: u8, decodes: &Cell<usize>) -> Arc<ProvingKey<Bn254>> {
+    cache::get_or_decode([digest; 32], &expected(None), || {
+        decodes.set(decodes.get() + 1);
+        header::decode(&pk_bytes(), &expected(None))
+    })
+    .unwrap()
+}
+
+#[test]
+fn same_bytes_are_decoded_once() {
+    let _guard = exclusive();
+    let bytes = pk_bytes();
+
+    let first = cache::decode::<Bn254>(&bytes, &expected(Some(false))).unwrap();
+    let second = cache::decode::<Bn254>(&bytes, &expected(Some(false))).unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+    assert_eq!(cache::len(), 1);
+
+    // Loaded under other expectations, the same bytes are checked again
+    let other = cache::decode::<Bn254>(&bytes, &expected(None)).unwrap();
+    assert!(!Arc::ptr_eq(&first, &other));
+    assert_eq!(cache::len(), 2);
+}
+
+#[test]
+fn least_recently_used_key_is_evicted() {
+    let _guard = exclusive();
+    cache::set_capacity(2);
+    let decodes = Cell::new(0);
+
+    get(1, &decodes);
+    get(2, &decodes);
+    get(1, &decodes);
+    get(3, &decodes);
+    assert_eq!((decodes.get(), cache::len()), (3, 2));
+
+    // 1 was used after 2, so 2 went
+    get(1, &decodes);
+    assert_eq!(decodes.get(), 3);
+    get(2, &decodes);
+    assert_eq!(decodes.get(), 4);
+
+    cache::set_capacity(1);
+    assert_eq!(cache::len(), 1);
+}
+
+#[test]
+fn zero_capacity_disables_caching() {
+    let _guard = exclusive();
+    cache::set_capacity(0);
+    let decodes = Cell::new(0);
+
+    get(1, &decodes);
+    get(1, &decodes);
+    assert_eq!((decodes.get(), cache::len()), (2, 0));
+}
+
+#[test]
+fn failed_decodes_are_not_cached() {
+    let _guard = exclusive();
+    let bytes = pk_bytes();
+
+    let truncated = &bytes[..bytes.len() - 1];
+    assert!(cache::decode::<Bn254>(truncated, &expected(None)).is_err());
+    let error = cache::decode::<Bn254>(&bytes, &expected(Some(true))).unwrap_err();
+    assert!(
+        error.to_string().contains("regenerate the keys"),
+        "{}",
+        error
+    );
+    assert_eq!(cache::len(), 0);
+}
@@ -0,0 +1,309 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, ProvingKey, VerifyingKey};
+use ark_serialize::CanonicalDeserialize;
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use vortex::circuit::Circuit;
+use vortex::curve::Curve;
+use vortex::keys::chunked::ChunkedBytes;
+use vortex::keys::convert::{self, KeyFormat, KeyKind};
+use vortex::keys::header::{self, Expected, KeyHeader};
+use vortex::keys::{self, check};
+
+fn read(name: &str) -> Vec<u8> {
+    fs::read(
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("keys")
+            .join(name),
+    )
+    .unwrap()
+}
+
+fn vk() -> VerifyingKey<Bn254> {
+    VerifyingKey::deserialize_compressed(header::strip(&read("verification_key.bin"))).unwrap()
+}
+
+fn pk() -> ProvingKey<Bn254> {
+    ProvingKey::deserialize_compressed(header::strip(&read("proving_key.bin"))).unwrap()
+}
+
+/// Converts `data` through each of `formats` in turn.
+fn convert_through(data: Vec<u8>, kind: KeyKind, formats: &[KeyFormat]) -> Vec<u8> {
+    formats.windows(2).fold(data, |data, step| {
+        convert::convert::<Bn254>(&data, kind, step[0], step[1]).unwrap()
+    })
+}
+
+#[test]
+fn verifying_key_round_trips_through_every_format() {
+    use KeyFormat::*;
+    let bin = read("verification_key.bin");
+    let converted = convert_through(
+        bin.clone(),
+        KeyKind::Verifying,
+        &[Bin, Hex, Base64, BinUncompressed, Snarkjs, Bin],
+    );
+
+    // The header is validated on input but not written
+    assert_eq!(converted, header::strip(&bin));
+    assert_eq!(
+        VerifyingKey::<Bn254>::deserialize_compressed(&converted[..]).unwrap(),
+        vk()
+    );
+}
+
+#[test]
+fn proving_key_round_trips_through_every_format() {
+    use KeyFormat::*;
+    let converted = convert_through(
+        read("proving_key.bin"),
+        KeyKind::Proving,
+        &[Bin, Base64, BinUncompressed, Hex, Bin],
+    );
+    assert_eq!(
+        ProvingKey::<Bn254>::deserialize_compressed(&converted[..]).unwrap(),
+        pk()
+    );
+}
+
+#[test]
+fn hex_matches_the_checked_in_file() {
+    let hex = convert::convert::<Bn254>(
+        &read("verification_key.bin"),
+        KeyKind::Verifying,
+        KeyFormat::Bin,
+        KeyFormat::Hex,
+    )
+    .unwrap();
+    let checked_in = String::from_utf8(read("verification_key.hex")).unwrap();
+    assert_eq!(String::from_utf8(hex).unwrap(), checked_in.trim());
+
+    // A trailing newline, as left by editors, is accepted
+    let back = convert::convert::<Bn254>(
+        format!("{}\n", checked_in.trim()).as_bytes(),
+        KeyKind::Verifying,
+        KeyFormat::Hex,
+        KeyFormat::Bin,
+    )
+    .unwrap();
+    assert_eq!(back, header::strip(&read("verification_key.bin")));
+}
+
+#[test]
+fn unsupported_conversions_are_rejected() {
+    let pk = read("proving_key.bin");
+    let vk = read("verification_key.bin");
+    let convert = |data: &[u8], kind, from, to| convert::convert::<Bn254>(data, kind, from, to);
+
+    assert!(convert(&pk, KeyKind::Proving, KeyFormat::Bin, KeyFormat::Snarkjs).is_err());
+    assert!(convert(&vk, KeyKind::Verifying, KeyFormat::Bin, KeyFormat::Zkey).is_err());
+    assert!(convert(
+        b"not hex",
+        KeyKind::Verifying,
+        KeyFormat::Hex,
+        KeyFormat::Bin
+    )
+    .is_err());
+    assert!(convert(
+        &vk[..40],
+        KeyKind::Verifying,
+        KeyFormat::Bin,
+        KeyFormat::Hex
+    )
+    .is_err());
+}
+
+#[test]
+fn key_names_parse() {
+    assert_eq!("vk".parse::<KeyKind>().unwrap(), KeyKind::Verifying);
+    assert_eq!("proving".parse::<KeyKind>().unwrap(), KeyKind::Proving);
+    assert_eq!(
+        "bin-uncompressed".parse::<KeyFormat>().unwrap(),
+        KeyFormat::BinUncompressed
+    );
+    assert!("key".parse::<KeyKind>().is_err());
+    assert!("pem".parse::<KeyFormat>().is_err());
+}
+
+fn expected(kind: KeyKind, non_trivial: Option<bool>) -> Expected {
+    Expected {
+        kind,
+        curve: Curve::Bn254,
+        non_trivial,
+    }
+}
+
+#[test]
+fn header_round_trips() {
+    let header = KeyHeader {
+        kind: KeyKind::Verifying,
+        curve: Curve::Bls12_381,
+        circuit_id: header::circuit_id(true),
+        compressed: false,
+    };
+    let bytes = header.to_bytes();
+    assert!(bytes.starts_with(header::MAGIC));
+    assert_eq!(KeyHeader::parse(&bytes).unwrap(), Some(header));
+
+    let checked_in = KeyHeader::parse(&read("verification_key.bin")).unwrap();
+    assert_eq!(
+        checked_in,
+        Some(KeyHeader {
+            kind: KeyKind::Verifying,
+            curve: Curve::Bn254,
+            circuit_id: header::circuit_id(false),
+            compressed: true,
+        })
+    );
+}
+
+#[test]
+fn headered_and_headerless_keys_decode() {
+    let bin = read("verification_key.bin");
+    let expected = expected(KeyKind::Verifying, Some(false));
+    let headered: VerifyingKey<Bn254> = header::decode(&bin, &expected).unwrap();
+    let headerless: VerifyingKey<Bn254> = header::decode(header::strip(&bin), &expected).unwrap();
+    assert_eq!(headered, vk());
+    assert_eq!(headerless, vk());
+}
+
+#[test]
+fn mismatched_header_is_explained() {
+    let bin = read("verification_key.bin");
+    let error = |expected: Expected| {
+        header::decode::<VerifyingKey<Bn254>>(&bin, &expected)
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert!(error(expected(KeyKind::Proving, None)).contains("holds a verifying key"));
+    assert!(error(Expected {
+        curve: Curve::Bls12_381,
+        ..expected(KeyKind::Verifying, None)
+    })
+    .contains("is for curve"));
+    assert!(error(expected(KeyKind::Verifying, Some(true))).contains(&header::circuit_label(false)));
+}
+
+#[test]
+fn malformed_header_is_rejected() {
+    let bin = read("verification_key.bin");
+    assert!(KeyHeader::parse(&bin[..header::HEADER_LEN - 1]).is_err());
+
+    let mut newer = bin.clone();
+    newer[4] = header::FORMAT_VERSION + 1;
+    let error = KeyHeader::parse(&newer).unwrap_err().to_string();
+    assert!(error.contains("not supported"), "{}", error);
+
+    let mut unknown_curve = bin;
+    unknown_curve[6] = 0xff;
+    assert!(KeyHeader::parse(&unknown_curve).is_err());
+    assert_eq!(KeyHeader::parse(b"no header").unwrap(), None);
+}
+
+#[test]
+fn matching_key_pair_passes_the_check() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    check::check_pair(&pk(), &vk(), false, &mut rng).unwrap();
+}
+
+#[test]
+fn mismatched_key_pair_fails_the_check() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let other = Groth16::<Bn254>::generate_random_parameters_with_reduction(
+        Circuit::<Fr>::empty(),
+        &mut rng,
+    )
+    .unwrap();
+
+    let error = check::check_pair(&pk(), &other.vk, false, &mut rng)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("alpha_g1"), "{}", error);
+
+    // A vk equal to pk.vk, but for a pk whose queries do not match it
+    let mut forged = other;
+    forged.vk = vk();
+    let error = check::check_pair(&forged, &vk(), false, &mut rng)
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("does not verify"), "{}", error);
+}
+
+#[test]
+fn fingerprint_check_ignores_the_header() {
+    let expected = keys::expected_proving_key_fingerprint();
+    let bin = read("proving_key.bin");
+    let raw = hex::decode(String::from_utf8(read("proving_key.hex")).unwrap().trim()).unwrap();
+
+    assert_eq!(keys::check_fingerprint(&bin, &expected).unwrap(), expected);
+    assert_eq!(keys::check_fingerprint(&raw, &expected).unwrap(), expected);
+    let prefixed = format!("0x{}", expected.to_ascii_uppercase());
+    assert_eq!(keys::check_fingerprint(&raw, &prefixed).unwrap(), expected);
+}
+
+#[test]
+fn fingerprint_check_rejects_other_keys() {
+    let expected = keys::expected_proving_key_fingerprint();
+    let vk = read("verification_key.bin");
+
+    let error = keys::check_fingerprint(&vk, &expected)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        error.contains(&format!("expected {}", expected)),
+        "{}",
+        error
+    );
+}
+
+fn chunked(bytes: &[u8], size: usize) -> ChunkedBytes {
+    let mut chunks = ChunkedBytes::new();
+    for chunk in bytes.chunks(size) {
+        chunks.push(chunk.to_vec());
+    }
+    chunks
+}
+
+#[test]
+fn chunked_keys_decode_like_contiguous_ones() {
+    let bin = read("proving_key.bin");
+    let expected = expected(KeyKind::Proving, Some(false));
+
+    // Chunks of 5 bytes split the header as well as the key
+    for size in [5, 4096, bin.len()] {
+        let mut chunks = chunked(&bin, size);
+        assert_eq!(chunks.len(), bin.len());
+        assert_eq!(
+            chunks.fingerprint(),
+            keys::expected_proving_key_fingerprint()
+        );
+        assert_eq!(chunks.digest(), <[u8; 32]>::from(Sha256::digest(&bin)));
+
+        let pk: ProvingKey<Bn254> = header::decode_reader(&mut chunks, &expected).unwrap();
+        assert_eq!(pk, self::pk());
+        assert!(chunks.is_empty());
+    }
+}
+
+#[test]
+fn truncated_chunks_fail_to_decode() {
+    let bin = read("proving_key.bin");
+    let mut chunks = chunked(&bin[..bin.len() - 1], 4096);
+
+    let error = header::decode_reader::<ProvingKey<Bn254>, _>(
+        &mut chunks,
+        &expected(KeyKind::Proving, Some(false)),
+    )
+    .unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Failed to deserialize proving key"),
+        "{}",
+        error
+    );
+}
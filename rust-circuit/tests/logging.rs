@@ -0,0 +1,67 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::Registry;
+use vortex::api;
+use vortex::logging::{self, LevelLayer};
+
+/// Records the name of every span let through.
+#[derive(Clone, Default)]
+struct Spans(Arc<Mutex<Vec<&'static str>>>);
+
+impl<S: Subscriber> Layer<S> for Spans {
+    fn new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+        self.0.lock().unwrap().push(attrs.metadata().name());
+    }
+}
+
+/// Names of the spans opened while proving the first BN254 test vector.
+fn proving_spans() -> Vec<&'static str> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors/bn254.json");
+    let vectors: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    let input = vectors["cases"][0]["input"].to_string();
+    let pk_hex = vectors["provingKeyHex"].as_str().unwrap();
+
+    let spans = Spans::default();
+    let subscriber = Registry::default().with(LevelLayer).with(spans.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        api::prove(&input, pk_hex, |_| {}).unwrap();
+    });
+
+    let names = spans.0.lock().unwrap().clone();
+    names
+}
+
+// The level is global, so it is only changed in this one test
+#[test]
+fn spans_follow_the_level() {
+    logging::set_level("DEBUG").unwrap();
+    assert_eq!(logging::level(), LevelFilter::DEBUG);
+    let spans = proving_spans();
+    for name in [
+        "deserialize_key",
+        "synthesize",
+        "prove",
+        "msm",
+        "serialize_proof",
+    ] {
+        assert!(spans.contains(&name), "no {} span in {:?}", name, spans);
+    }
+    // Constraint namespaces are left out, however verbose the level
+    assert!(!spans.contains(&"c"), "{:?}", spans);
+
+    logging::set_level("off").unwrap();
+    assert_eq!(logging::level(), LevelFilter::OFF);
+    assert!(proving_spans().is_empty());
+}
+
+#[test]
+fn unknown_levels_are_rejected() {
+    let error = logging::set_level("verbose").unwrap_err().to_string();
+    assert!(error.contains("Unknown log level 'verbose'"), "{}", error);
+}
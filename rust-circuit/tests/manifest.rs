@@ -0,0 +1,103 @@
+use ark_bn254::Fr;
+use std::path::{Path, PathBuf};
+use vortex::circuit::Circuit;
+use vortex::curve::Curve;
+use vortex::keys::manifest::{self, FileEntry, Manifest};
+
+fn keys_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("keys")
+}
+
+#[test]
+fn checked_in_keys_match_this_build() {
+    let manifest = Manifest::read(&keys_dir()).unwrap();
+    assert_eq!(
+        manifest.cs_digest.as_deref(),
+        Some(manifest::cs_digest(Curve::Bn254, false).unwrap().as_str())
+    );
+    manifest.verify(&keys_dir()).unwrap();
+}
+
+#[test]
+fn drifted_constraint_system_is_detected() {
+    let mut manifest = Manifest::read(&keys_dir()).unwrap();
+    manifest.cs_digest = Some(manifest::cs_digest(Curve::Bn254, true).unwrap());
+
+    let error = manifest.verify(&keys_dir()).unwrap_err().to_string();
+    assert!(error.contains("constraint system"), "{}", error);
+}
+
+#[test]
+fn manifest_without_a_digest_is_not_checked_for_one() {
+    let mut manifest = Manifest::read(&keys_dir()).unwrap();
+    manifest.cs_digest = None;
+    manifest.verify(&keys_dir()).unwrap();
+}
+
+#[test]
+fn cs_digest_ignores_the_assignment() {
+    let empty = Circuit::<Fr>::empty().cs_digest().unwrap();
+    let assigned = Circuit::new(Fr::from(6u64), Fr::from(2u64), Fr::from(3u64), false)
+        .unwrap()
+        .cs_digest()
+        .unwrap();
+    assert_eq!(assigned, empty);
+    assert_ne!(
+        Circuit::<Fr>::empty_non_trivial().cs_digest().unwrap(),
+        empty
+    );
+}
+
+#[test]
+fn cs_digest_rejects_disabled_curves() {
+    if Curve::Bls12_381.ensure_enabled().is_err() {
+        assert!(manifest::cs_digest(Curve::Bls12_381, false).is_err());
+    }
+}
+
+#[test]
+fn truncated_or_swapped_files_are_detected() {
+    let mut manifest = Manifest::read(&keys_dir()).unwrap();
+    // As if proving_key.bin had been truncated, then replaced by another key
+    manifest.files[0].size -= 1;
+    manifest.files[1].sha256 = manifest.files[3].sha256.clone();
+
+    let error = manifest.verify(&keys_dir()).unwrap_err().to_string();
+    assert!(
+        error.contains(&format!(
+            "{}: expected {} bytes",
+            manifest.files[0].name, manifest.files[0].size
+        )),
+        "{}",
+        error
+    );
+    assert!(
+        error.contains(&format!(
+            "{}: SHA-256 digest does not match",
+            manifest.files[1].name
+        )),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn missing_file_is_detected() {
+    let mut manifest = Manifest::read(&keys_dir()).unwrap();
+    manifest.files.push(FileEntry::new("missing.bin", b""));
+    let error = manifest.verify(&keys_dir()).unwrap_err().to_string();
+    assert!(error.contains("missing.bin"), "{}", error);
+}
+
+#[test]
+fn manifest_for_circuit_describes_this_build() {
+    let manifest = Manifest::for_circuit(Curve::Bn254, false).unwrap();
+    let checked_in = Manifest::read(&keys_dir()).unwrap();
+    assert_eq!(
+        Manifest {
+            files: checked_in.files.clone(),
+            ..manifest
+        },
+        checked_in
+    );
+}
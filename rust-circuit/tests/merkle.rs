@@ -0,0 +1,150 @@
+use ark_bn254::Fr;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar};
+use ark_relations::r1cs::{self, ConstraintSystem, ConstraintSystemRef};
+use vortex::merkle::sparse::{self, SparseMerkleTree};
+use vortex::merkle::{IncrementalMerkleTree, MerklePathVar, MerkleTree};
+
+fn leaves(count: u64) -> Vec<Fr> {
+    (1..=count).map(|i| Fr::from(i * 1_000 + 7)).collect()
+}
+
+#[test]
+fn incremental_root_matches_full_tree_after_every_insert() {
+    let mut incremental = IncrementalMerkleTree::<Fr>::new(3).unwrap();
+    assert_eq!(
+        incremental.root(),
+        MerkleTree::new(3, vec![]).unwrap().root()
+    );
+
+    let leaves = leaves(8);
+    for (i, leaf) in leaves.iter().enumerate() {
+        assert_eq!(incremental.insert(*leaf).unwrap(), i as u64);
+        let full = MerkleTree::new(3, leaves[..=i].to_vec()).unwrap();
+        assert_eq!(incremental.root(), full.root());
+    }
+    assert_eq!(incremental.next_index(), 8);
+}
+
+#[test]
+fn full_incremental_tree_rejects_insert() {
+    let mut incremental = IncrementalMerkleTree::<Fr>::new(2).unwrap();
+    for leaf in leaves(4) {
+        incremental.insert(leaf).unwrap();
+    }
+    let root = incremental.root();
+
+    assert!(incremental.insert(Fr::from(1u64)).is_err());
+    assert_eq!(incremental.root(), root);
+    assert!(IncrementalMerkleTree::<Fr>::new(64).is_err());
+}
+
+#[test]
+fn incremental_tree_roundtrips_through_json() {
+    let mut incremental = IncrementalMerkleTree::<Fr>::new(4).unwrap();
+    for leaf in leaves(5) {
+        incremental.insert(leaf).unwrap();
+    }
+
+    let json = serde_json::to_string(&incremental).unwrap();
+    let mut decoded: IncrementalMerkleTree<Fr> = serde_json::from_str(&json).unwrap();
+    assert_eq!(decoded, incremental);
+
+    // The frontier survives, so inserts continue from the same state
+    decoded.insert(Fr::from(9u64)).unwrap();
+    incremental.insert(Fr::from(9u64)).unwrap();
+    assert_eq!(decoded.root(), incremental.root());
+}
+
+/// Keys 5 and `5 + 2^8` share slot 5 in a tree of depth 8.
+fn colliding_keys() -> (Fr, Fr) {
+    (Fr::from(5u64), Fr::from(5u64 + (1 << 8)))
+}
+
+#[test]
+fn sparse_insert_then_remove_restores_root() {
+    let mut tree = SparseMerkleTree::<Fr>::new(8).unwrap();
+    tree.insert(Fr::from(3u64), Fr::from(30u64)).unwrap();
+    let root = tree.root();
+
+    tree.insert(Fr::from(200u64), Fr::from(7u64)).unwrap();
+    assert_ne!(tree.root(), root);
+    assert_eq!(tree.get(&Fr::from(200u64)), Some(Fr::from(7u64)));
+
+    assert_eq!(
+        tree.remove(&Fr::from(200u64)).unwrap(),
+        Some(Fr::from(7u64))
+    );
+    assert_eq!(tree.root(), root);
+    assert_eq!(tree.remove(&Fr::from(200u64)).unwrap(), None);
+
+    tree.remove(&Fr::from(3u64)).unwrap();
+    assert!(tree.is_empty());
+    assert_eq!(tree.root(), SparseMerkleTree::<Fr>::new(8).unwrap().root());
+}
+
+#[test]
+fn sparse_slot_collision_is_rejected() {
+    let (key, other) = colliding_keys();
+    let mut tree = SparseMerkleTree::<Fr>::new(8).unwrap();
+    tree.insert(key, Fr::from(1u64)).unwrap();
+    let root = tree.root();
+
+    assert!(tree.insert(other, Fr::from(2u64)).is_err());
+    assert!(tree.path(&other).is_err());
+    assert_eq!(tree.get(&other), None);
+    assert_eq!(tree.remove(&other).unwrap(), None);
+    assert_eq!(tree.root(), root);
+
+    // Replacing the value of the same key is allowed
+    tree.insert(key, Fr::from(3u64)).unwrap();
+    assert_eq!(tree.get(&key), Some(Fr::from(3u64)));
+}
+
+fn sparse_tree() -> SparseMerkleTree<Fr> {
+    let mut tree = SparseMerkleTree::new(8).unwrap();
+    tree.insert(Fr::from(3u64), Fr::from(30u64)).unwrap();
+    tree.insert(Fr::from(200u64), Fr::from(7u64)).unwrap();
+    tree
+}
+
+fn satisfied(build: impl FnOnce(ConstraintSystemRef<Fr>) -> r1cs::Result<()>) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    build(cs.clone()).is_ok() && cs.is_satisfied().unwrap_or(false)
+}
+
+/// Whether `key` is proven absent from `tree` with the path of `path_key`.
+fn non_membership_holds(tree: &SparseMerkleTree<Fr>, key: u64, path_key: u64) -> bool {
+    satisfied(|cs| {
+        let path = tree.path(&Fr::from(path_key)).unwrap();
+        let root = FpVar::new_input(cs.clone(), || Ok(tree.root()))?;
+        let key = FpVar::new_witness(cs.clone(), || Ok(Fr::from(key)))?;
+        let path = MerklePathVar::new_witness(cs.clone(), &path)?;
+        sparse::enforce_non_membership(cs, &root, &key, &path)
+    })
+}
+
+#[test]
+fn sparse_non_membership_holds_only_for_absent_keys() {
+    let tree = sparse_tree();
+    assert!(non_membership_holds(&tree, 4, 4));
+    assert!(!non_membership_holds(&tree, 3, 3));
+    // The empty slot of another key proves nothing about this one
+    assert!(!non_membership_holds(&tree, 3, 4));
+}
+
+#[test]
+fn sparse_membership_holds_only_for_stored_value() {
+    let tree = sparse_tree();
+    let holds = |value: u64| {
+        satisfied(|cs| {
+            let path = tree.path(&Fr::from(200u64)).unwrap();
+            let root = FpVar::new_input(cs.clone(), || Ok(tree.root()))?;
+            let key = FpVar::new_witness(cs.clone(), || Ok(Fr::from(200u64)))?;
+            let value = FpVar::new_witness(cs.clone(), || Ok(Fr::from(value)))?;
+            let path = MerklePathVar::new_witness(cs.clone(), &path)?;
+            sparse::enforce_membership(cs, &root, &key, &value, &path)
+        })
+    };
+    assert!(holds(7));
+    assert!(!holds(8));
+}
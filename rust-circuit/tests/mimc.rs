@@ -0,0 +1,66 @@
+use ark_bn254::Fr;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, R1CSVar};
+use ark_relations::r1cs::ConstraintSystem;
+use std::str::FromStr;
+use vortex::mimc;
+
+/// The first levels of Tornado Cash's `MerkleTreeWithHistory.zeros`: the
+/// empty leaf `keccak256("tornado") % p`, then `hashLeftRight(z, z)`.
+const TORNADO_ZEROS: [&str; 3] = [
+    "21663839004416932945382355908790599225266501822907911457504978515578255421292",
+    "16923532097304556005972200564242292693309333953544141029519619077135960040221",
+    "7833458610320835472520144237082236871909694928684820466656733259024982655488",
+];
+
+fn tornado_zeros() -> Vec<Fr> {
+    TORNADO_ZEROS
+        .iter()
+        .map(|zero| Fr::from_str(zero).unwrap())
+        .collect()
+}
+
+#[test]
+fn hash_matches_tornado_zeros() {
+    let zeros = tornado_zeros();
+    assert_eq!(mimc::keccak_to_field::<Fr>(b"tornado"), zeros[0]);
+    for level in zeros.windows(2) {
+        assert_eq!(mimc::hash(&[level[0], level[0]]), level[1]);
+    }
+}
+
+#[test]
+fn hash_gadget_matches_tornado_zeros() {
+    let zeros = tornado_zeros();
+    for level in zeros.windows(2) {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let zero = FpVar::new_witness(cs.clone(), || Ok(level[0])).unwrap();
+        let hash = mimc::hash_gadget(&[zero.clone(), zero]).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(hash.value().unwrap(), level[1]);
+    }
+}
+
+#[test]
+fn permute_gadget_costs_three_constraints_per_round() {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let [left, right, key] =
+        [1u64, 2, 3].map(|x| FpVar::new_witness(cs.clone(), || Ok(Fr::from(x))).unwrap());
+    let constants = mimc::round_constants::<Fr>();
+    let (left, right) = mimc::permute_gadget(&constants, &left, &right, &key).unwrap();
+
+    assert_eq!(cs.num_constraints(), 3 * mimc::ROUNDS);
+    assert_eq!(
+        (left.value().unwrap(), right.value().unwrap()),
+        mimc::permute(&constants, Fr::from(1u64), Fr::from(2u64), Fr::from(3u64))
+    );
+}
+
+#[test]
+fn sponge_squeezes_the_requested_outputs() {
+    let inputs = [Fr::from(1u64), Fr::from(2u64)];
+    let squeezed = mimc::sponge(&inputs, Fr::from(3u64), 3);
+    assert_eq!(squeezed.len(), 3);
+    assert_eq!(squeezed[..1], mimc::sponge(&inputs, Fr::from(3u64), 1));
+    assert_ne!(squeezed[0], mimc::sponge(&inputs, Fr::from(4u64), 1)[0]);
+}
@@ -0,0 +1,113 @@
+use ark_bn254::Fr;
+use ark_r1cs_std::{fields::fp::FpVar, prelude::*};
+use ark_relations::{
+    ns,
+    r1cs::{self, ConstraintSynthesizer, ConstraintSystemRef},
+};
+use vortex::circuit::{vote::TallyCircuit, Circuit};
+use vortex::gadgets::debug;
+use vortex::inspect;
+use vortex::test_utils::MockProver;
+
+fn multiplication(c: u64) -> Circuit {
+    Circuit::new(Fr::from(c), Fr::from(2u64), Fr::from(3u64), true).unwrap()
+}
+
+/// `out == x^2`, recording the square.
+struct Square {
+    x: Fr,
+    out: Fr,
+}
+
+impl ConstraintSynthesizer<Fr> for Square {
+    fn generate_constraints(self, cs: ConstraintSystemRef<Fr>) -> r1cs::Result<()> {
+        let out = FpVar::new_input(ns!(cs, "out"), || Ok(self.out))?;
+        let x = FpVar::new_witness(ns!(cs, "x"), || Ok(self.x))?;
+        let square = x.square()?;
+        debug::debug_print("square", &square);
+
+        let _ns = ns!(cs, "out == x^2");
+        square.enforce_equal(&out)
+    }
+}
+
+#[test]
+fn honest_circuit_is_satisfied() {
+    let mock = MockProver::run(multiplication(6)).unwrap();
+    mock.assert_satisfied();
+    assert_eq!(mock.public_inputs(), [Fr::from(6u64)]);
+    assert_eq!(
+        mock.num_constraints(),
+        inspect::inspect(Circuit::<Fr>::empty_non_trivial())
+            .unwrap()
+            .num_constraints
+    );
+    assert!(mock.evaluations().iter().all(|e| e.a * e.b == e.c));
+}
+
+#[test]
+fn failing_constraint_is_reported_by_name() {
+    let mock = MockProver::run(multiplication(7)).unwrap();
+    assert!(!mock.is_satisfied());
+    assert_eq!(mock.failures().count(), 1);
+    mock.assert_unsatisfied_at("c == a * b");
+
+    let error = mock.verify().unwrap_err().to_string();
+    assert!(error.contains("1 of"), "{}", error);
+    assert!(error.contains("c == a * b"), "{}", error);
+}
+
+#[test]
+#[should_panic(expected = "not satisfied")]
+fn assert_satisfied_panics_on_failure() {
+    MockProver::run(multiplication(7))
+        .unwrap()
+        .assert_satisfied();
+}
+
+#[test]
+fn failure_report_includes_the_trace() {
+    let mock = MockProver::run(Square {
+        x: Fr::from(3u64),
+        out: Fr::from(10u64),
+    })
+    .unwrap();
+    mock.assert_unsatisfied_at("out == x^2");
+    assert_eq!(mock.trace().len(), 1);
+    assert_eq!(mock.trace()[0].label, "square");
+
+    let error = mock.verify().unwrap_err().to_string();
+    assert!(error.contains("Trace:"), "{}", error);
+    assert!(error.contains("square = "), "{}", error);
+}
+
+#[test]
+fn tampered_witness_is_not_satisfied() {
+    let mock = MockProver::run(multiplication(6)).unwrap();
+    let mut witness = mock.witness().to_vec();
+    witness[0] += Fr::from(1u64);
+
+    let tampered = mock.with_witness(witness).unwrap();
+    assert!(!tampered.is_satisfied());
+    assert!(tampered.trace().is_empty());
+    assert!(mock
+        .with_witness(mock.witness().to_vec())
+        .unwrap()
+        .is_satisfied());
+}
+
+#[test]
+fn witness_of_another_length_is_rejected() {
+    let mock = MockProver::run(multiplication(6)).unwrap();
+    let mut witness = mock.witness().to_vec();
+    witness.push(Fr::from(0u64));
+    assert!(mock.with_witness(witness).is_err());
+}
+
+#[test]
+fn synthesis_error_is_returned() {
+    // The tally's length check rejects a circuit with a vote missing
+    let mut circuit = TallyCircuit::<3>::empty();
+    circuit.votes.pop();
+    assert!(MockProver::run(circuit).is_err());
+}
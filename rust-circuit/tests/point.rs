@@ -0,0 +1,58 @@
+use ark_bn254::{Fq2, G1Affine, G2Affine};
+use ark_ec::AffineRepr;
+use ark_serialize::{CanonicalSerialize, Compress};
+use vortex::curve::point::{self, PointError};
+
+fn compressed<G: CanonicalSerialize>(point: &G) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    point.serialize_compressed(&mut bytes).unwrap();
+    bytes
+}
+
+/// A point on the BN254 twist outside its prime-order subgroup, which has
+/// index the (large) G2 cofactor.
+fn low_order_g2() -> G2Affine {
+    (1u64..)
+        .filter_map(|x| G2Affine::get_point_from_x_unchecked(Fq2::from(x), false))
+        .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+        .unwrap()
+}
+
+#[test]
+fn accepts_subgroup_points() {
+    let g = G2Affine::generator();
+    let decoded = point::deserialize_compressed::<G2Affine>(&compressed(&g), "g").unwrap();
+    assert_eq!(decoded, g);
+}
+
+#[test]
+fn rejects_the_identity() {
+    let error =
+        point::deserialize_compressed::<G1Affine>(&compressed(&G1Affine::zero()), "proof.a")
+            .unwrap_err();
+    assert_eq!(
+        error,
+        PointError::Identity {
+            name: "proof.a".into()
+        }
+    );
+    assert_eq!(error.code(), "E_POINT_IDENTITY");
+}
+
+#[test]
+fn rejects_points_outside_the_subgroup() {
+    let bytes = compressed(&low_order_g2());
+    let error = point::deserialize_compressed::<G2Affine>(&bytes, "proof.b").unwrap_err();
+    assert_eq!(error.code(), "E_POINT_NOT_IN_SUBGROUP");
+    assert!(error.to_string().contains("proof.b"), "{}", error);
+}
+
+#[test]
+fn rejects_malformed_bytes() {
+    let bytes = compressed(&G1Affine::generator());
+    let error = point::deserialize_compressed::<G1Affine>(&bytes[..31], "proof.c").unwrap_err();
+    assert_eq!(error.code(), "E_POINT_MALFORMED");
+
+    let error = point::deserialize_with::<G1Affine>(&bytes, Compress::No, "proof.c").unwrap_err();
+    assert_eq!(error.code(), "E_POINT_MALFORMED");
+}
@@ -0,0 +1,91 @@
+use ark_bn254::Fr;
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, R1CSVar};
+use ark_relations::r1cs::ConstraintSystem;
+use std::str::FromStr;
+use vortex::poseidon;
+
+/// circomlibjs `poseidon` digests: `(inputs, digest)`.
+const CIRCOMLIB: [(&[u64], &str); 4] = [
+    (
+        &[1],
+        "18586133768512220936620570745912940619677854269274689475585506675881198879027",
+    ),
+    (
+        &[1, 2],
+        "7853200120776062878684798364095072458815029376092732009249414926327459813530",
+    ),
+    (
+        &[0],
+        "19014214495641488759237505126948346942972912379615652741039992445865937985820",
+    ),
+    (
+        &[0, 0],
+        "14744269619966411208579211824598458697587494354926760081771325075741142829156",
+    ),
+];
+
+fn inputs(values: &[u64]) -> Vec<Fr> {
+    values.iter().copied().map(Fr::from).collect()
+}
+
+#[test]
+fn circom_hash_matches_circomlibjs() {
+    for (values, digest) in CIRCOMLIB {
+        assert_eq!(
+            poseidon::circom_hash(&inputs(values)).unwrap(),
+            Fr::from_str(digest).unwrap(),
+            "Poseidon({:?})",
+            values
+        );
+    }
+}
+
+#[test]
+fn circom_hash_gadget_matches_native() {
+    for (values, digest) in CIRCOMLIB {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs(values))).unwrap();
+        let hash = poseidon::circom_hash_gadget(cs.clone(), &vars).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(hash.value().unwrap(), Fr::from_str(digest).unwrap());
+    }
+}
+
+#[test]
+fn circom_hash_rejects_input_counts_circomlib_lacks() {
+    assert!(poseidon::circom_hash::<Fr>(&[]).is_err());
+    assert!(poseidon::circom_hash(&inputs(&[0; 17])).is_err());
+}
+
+#[test]
+fn hash_gadget_matches_native() {
+    for values in [&[][..], &[1], &[1, 2], &[1, 2, 3]] {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs(values))).unwrap();
+        let hash = poseidon::hash_gadget(cs.clone(), &vars).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(
+            hash.value().unwrap(),
+            poseidon::hash(&inputs(values)).unwrap(),
+            "Poseidon({:?})",
+            values
+        );
+    }
+}
+
+#[test]
+fn hash_is_not_circomlib_poseidon() {
+    // Same security parameters, different constants: see `poseidon::config`
+    let config = poseidon::config::<Fr>();
+    assert_eq!(
+        (config.full_rounds, config.partial_rounds, config.alpha),
+        (8, 57, 5)
+    );
+    let (values, digest) = CIRCOMLIB[1];
+    assert_ne!(
+        poseidon::hash(&inputs(values)).unwrap(),
+        Fr::from_str(digest).unwrap()
+    );
+}
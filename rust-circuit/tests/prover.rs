@@ -0,0 +1,34 @@
+use ark_bn254::{Bn254, Fr};
+use ark_groth16::{Groth16, ProvingKey};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::circuit::Circuit;
+use vortex::prover::{self, ProofBundle};
+
+fn key_and_proof(seed: u64) -> (ProvingKey<Bn254>, ProofBundle<Bn254>) {
+    let mut rng = ChaCha20Rng::seed_from_u64(seed);
+    let pk = Groth16::<Bn254>::generate_random_parameters_with_reduction(
+        Circuit::<Fr>::empty(),
+        &mut rng,
+    )
+    .unwrap();
+    let circuit = Circuit::new(Fr::from(15u64), Fr::from(3u64), Fr::from(5u64), false).unwrap();
+    let bundle = prover::prove(circuit, &pk, &mut rng).unwrap();
+    (pk, bundle)
+}
+
+// More keys than the per-thread cache of prepared keys holds, verified in
+// turn twice, so both cached and evicted keys are looked up
+#[test]
+fn cached_prepared_keys_are_not_mixed_up() {
+    let keys = (0..10).map(key_and_proof).collect::<Vec<_>>();
+
+    for _ in 0..2 {
+        for (i, (pk, bundle)) in keys.iter().enumerate() {
+            assert!(prover::verify(bundle, &pk.vk).unwrap(), "key {}", i);
+
+            let (other, _) = &keys[(i + 1) % keys.len()];
+            assert!(!prover::verify(bundle, &other.vk).unwrap(), "key {}", i);
+        }
+    }
+}
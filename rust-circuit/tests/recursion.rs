@@ -0,0 +1,101 @@
+use ark_bls12_377::{Bls12_377, Fr};
+use ark_bw6_761::BW6_761;
+use ark_groth16::{Groth16, ProvingKey};
+use ark_relations::r1cs::{ConstraintSynthesizer, ConstraintSystem};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::circuit::Circuit;
+use vortex::prover::{self, ProofBundle};
+use vortex::recursion::RecursiveCircuit;
+
+type Recursive2 = RecursiveCircuit<2>;
+
+fn inner_key(rng: &mut ChaCha20Rng) -> ProvingKey<Bls12_377> {
+    Groth16::<Bls12_377>::generate_random_parameters_with_reduction(Circuit::<Fr>::empty(), rng)
+        .unwrap()
+}
+
+/// Proofs that 15 = 3 * 5 and 77 = 7 * 11.
+fn inner_proofs(pk: &ProvingKey<Bls12_377>, rng: &mut ChaCha20Rng) -> Vec<ProofBundle<Bls12_377>> {
+    [(3u64, 5u64), (7, 11)]
+        .into_iter()
+        .map(|(a, b)| {
+            let circuit = Circuit::new(Fr::from(a * b), Fr::from(a), Fr::from(b), false).unwrap();
+            prover::prove(circuit, pk, rng).unwrap()
+        })
+        .collect()
+}
+
+fn is_satisfied(circuit: Recursive2) -> bool {
+    let cs = ConstraintSystem::new_ref();
+    circuit.generate_constraints(cs.clone()).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+#[test]
+fn valid_proofs_are_satisfied() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = inner_key(&mut rng);
+    let proofs = inner_proofs(&pk, &mut rng);
+
+    assert!(is_satisfied(Recursive2::new(pk.vk, proofs).unwrap()));
+}
+
+#[test]
+fn proof_for_other_inputs_is_not_satisfied() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = inner_key(&mut rng);
+    let mut circuit = Recursive2::new(pk.vk.clone(), inner_proofs(&pk, &mut rng)).unwrap();
+
+    // Claims the first proof was for 16
+    circuit.proofs[0].public_inputs[0] = Fr::from(16u64);
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn proof_for_other_key_is_not_satisfied() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = inner_key(&mut rng);
+    let other = inner_key(&mut rng);
+    let mut proofs = inner_proofs(&pk, &mut rng);
+    proofs[1] = inner_proofs(&other, &mut rng).remove(1);
+
+    assert!(Recursive2::new(pk.vk.clone(), proofs.clone()).is_err());
+    let circuit = Recursive2 { vk: pk.vk, proofs };
+    assert!(!is_satisfied(circuit));
+}
+
+#[test]
+fn wrong_proof_count_is_rejected() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let pk = inner_key(&mut rng);
+    let mut proofs = inner_proofs(&pk, &mut rng);
+    proofs.pop();
+
+    assert!(Recursive2::new(pk.vk, proofs).is_err());
+}
+
+#[test]
+#[ignore = "slow outside release builds; run with --release"]
+fn recursive_proof_verifies() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let inner_pk = inner_key(&mut rng);
+    let circuit = Recursive2::new(inner_pk.vk.clone(), inner_proofs(&inner_pk, &mut rng)).unwrap();
+    let public_inputs = circuit.get_public_inputs();
+    let mut claimed = circuit.clone();
+    claimed.proofs[0].public_inputs[0] = Fr::from(16u64);
+
+    let pk = Groth16::<BW6_761>::generate_random_parameters_with_reduction(
+        Recursive2::empty(inner_pk.vk),
+        &mut rng,
+    )
+    .unwrap();
+    let bundle = prover::prove(circuit, &pk, &mut rng).unwrap();
+    assert_eq!(bundle.public_inputs, public_inputs);
+    assert!(prover::verify(&bundle, &pk.vk).unwrap());
+
+    // The inner public inputs are bound: not valid for a claimed 16 = 3 * 5
+    let mut wrong = bundle.clone();
+    wrong.public_inputs = claimed.get_public_inputs();
+    assert!(!prover::verify(&wrong, &pk.vk).unwrap());
+}
@@ -0,0 +1,57 @@
+use ark_bn254::Fr;
+use ark_r1cs_std::{uint8::UInt8, R1CSVar};
+use ark_relations::r1cs::ConstraintSystem;
+use vortex::gadgets::regex::Dfa;
+
+/// Runs `enforce_match` on `input` as a witness.
+fn enforce_match(dfa: &Dfa, input: &[u8]) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let input = UInt8::new_witness_vec(cs.clone(), input).unwrap();
+    dfa.enforce_match(&input).unwrap();
+    cs.is_satisfied().unwrap()
+}
+
+/// Runs `matches` on `input` as a witness, checking the constraints hold either way.
+fn matches(dfa: &Dfa, input: &[u8]) -> bool {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    let input = UInt8::new_witness_vec(cs.clone(), input).unwrap();
+    let matched = dfa.matches(&input).unwrap().value().unwrap();
+    assert!(cs.is_satisfied().unwrap());
+    matched
+}
+
+#[test]
+fn matching_input_is_satisfied() {
+    let dfa = Dfa::new(r"[a-z]+@[a-z]+\.com").unwrap();
+    assert!(dfa.is_match(b"alice@example.com"));
+    assert!(enforce_match(&dfa, b"alice@example.com"));
+}
+
+#[test]
+fn non_matching_input_is_not_satisfied() {
+    let dfa = Dfa::new(r"[a-z]+@[a-z]+\.com").unwrap();
+    for input in [
+        &b"alice@example.org"[..],
+        b"alice@example.com.",
+        b"@example.com",
+    ] {
+        assert!(!dfa.is_match(input));
+        assert!(!enforce_match(&dfa, input), "{:?}", input);
+    }
+}
+
+#[test]
+fn gadget_agrees_with_native_match() {
+    let dfa = Dfa::new(r"(ab|c)*d?").unwrap();
+    for input in [&b""[..], b"ab", b"abcab", b"abd", b"ba", b"abdd", b"\xff"] {
+        assert_eq!(matches(&dfa, input), dfa.is_match(input), "{:?}", input);
+    }
+}
+
+#[test]
+fn substring_match() {
+    let dfa = Dfa::contains(b"\"sub\"").unwrap();
+    assert!(matches(&dfa, br#"{"sub":"1"}"#));
+    assert!(!matches(&dfa, br#"{"aud":"1"}"#));
+    assert!(!enforce_match(&dfa, br#"{"su":"b"}"#));
+}
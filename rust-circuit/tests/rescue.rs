@@ -0,0 +1,96 @@
+use ark_bn254::Fr;
+use ark_ff::{Field, Zero};
+use ark_r1cs_std::{alloc::AllocVar, fields::fp::FpVar, R1CSVar};
+use ark_relations::r1cs::ConstraintSystem;
+use std::str::FromStr;
+use vortex::rescue::{self, RescueConfig};
+
+// No independent Rescue-Prime implementation covers BN254 with this padding
+// and single-element squeeze, so these digests only pin the current output:
+// a mismatch means the parameter generation or the sponge changed.
+const DIGESTS: [(&[u64], &str); 2] = [
+    (
+        &[],
+        "11859570646544414528448865934361814928682472944063369147923859205431563103349",
+    ),
+    (
+        &[1, 2],
+        "19955277490808493510831169602631407111104744046414437667271324145367080531545",
+    ),
+];
+
+fn inputs(values: &[u64]) -> Vec<Fr> {
+    values.iter().copied().map(Fr::from).collect()
+}
+
+#[test]
+fn config_follows_the_reference_parameters() {
+    let config = rescue::config::<Fr>();
+
+    // 3 divides p - 1 for BN254, so the smallest usable exponent is 5
+    assert_eq!(config.alpha, 5);
+    assert_eq!(config.rounds, 14);
+    assert_eq!(
+        config.round_constants.len(),
+        2 * config.width * config.rounds
+    );
+    for x in inputs(&[2, 3, 12345]) {
+        assert_eq!(x.pow([config.alpha]).pow(&config.alpha_inv), x);
+    }
+}
+
+#[test]
+fn mds_matrix_is_mds() {
+    let mds = rescue::config::<Fr>().mds;
+    let det2 = |r: [usize; 2], c: [usize; 2]| {
+        mds[r[0]][c[0]] * mds[r[1]][c[1]] - mds[r[0]][c[1]] * mds[r[1]][c[0]]
+    };
+    let pairs = [[0, 1], [0, 2], [1, 2]];
+
+    assert!(mds.iter().flatten().all(|x| !x.is_zero()));
+    for rows in pairs {
+        for cols in pairs {
+            assert!(!det2(rows, cols).is_zero());
+        }
+    }
+    let det3 = (0..3)
+        .map(|j| mds[0][j] * det2([1, 2], [(j + 1) % 3, (j + 2) % 3]))
+        .sum::<Fr>();
+    assert!(!det3.is_zero());
+}
+
+#[test]
+fn hash_matches_pinned_digests() {
+    for (values, digest) in DIGESTS {
+        assert_eq!(
+            rescue::hash(&inputs(values)),
+            Fr::from_str(digest).unwrap(),
+            "Rescue({:?})",
+            values
+        );
+    }
+}
+
+#[test]
+fn hash_gadget_matches_native() {
+    for (values, _) in DIGESTS {
+        let cs = ConstraintSystem::<Fr>::new_ref();
+        let vars = Vec::<FpVar<Fr>>::new_witness(cs.clone(), || Ok(inputs(values))).unwrap();
+        let hash = rescue::hash_gadget(&vars).unwrap();
+
+        assert!(cs.is_satisfied().unwrap());
+        assert_eq!(hash.value().unwrap(), rescue::hash(&inputs(values)));
+    }
+}
+
+#[test]
+fn hash_distinguishes_padding() {
+    // The one appended by the padding keeps a trailing zero significant
+    assert_ne!(rescue::hash(&inputs(&[1])), rescue::hash(&inputs(&[1, 0])));
+}
+
+#[test]
+fn config_rejects_capacity_without_rate() {
+    assert!(RescueConfig::<Fr>::new(3, 0, 128).is_err());
+    assert!(RescueConfig::<Fr>::new(3, 3, 128).is_err());
+}
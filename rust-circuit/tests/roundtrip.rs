@@ -0,0 +1,114 @@
+use proptest::test_runner::Config;
+use vortex::test_utils::{roundtrip, roundtrip_with, strategies};
+
+// Every circuit in `vortex::circuit` is covered here except:
+// - `ecdsa` and `ed25519`, whose emulated arithmetic needs several GB of
+//   memory to set up; see their own tests
+// - `builder` and the DSL, which compose the statements below rather than
+//   define one of their own
+
+#[test]
+fn multiplication() {
+    roundtrip(strategies::multiplication(false));
+}
+
+#[test]
+fn multiplication_non_trivial() {
+    roundtrip(strategies::multiplication(true));
+}
+
+// Poseidon-heavy circuits prove slowly in debug builds, so fewer cases
+#[test]
+fn hash_chain() {
+    roundtrip_with(Config::with_cases(4), strategies::hash_chain::<2>());
+}
+
+#[test]
+fn polynomial() {
+    roundtrip_with(Config::with_cases(4), strategies::polynomial::<4>());
+}
+
+#[test]
+fn age() {
+    roundtrip_with(Config::with_cases(4), strategies::age());
+}
+
+#[test]
+fn solvency() {
+    roundtrip_with(Config::with_cases(4), strategies::solvency());
+}
+
+#[test]
+fn airdrop() {
+    roundtrip_with(Config::with_cases(2), strategies::airdrop::<4>());
+}
+
+#[test]
+fn vote() {
+    roundtrip_with(Config::with_cases(2), strategies::vote::<4>());
+}
+
+#[test]
+fn tally() {
+    roundtrip_with(Config::with_cases(2), strategies::tally::<3>());
+}
+
+#[test]
+fn bid() {
+    roundtrip_with(Config::with_cases(4), strategies::bid());
+}
+
+#[test]
+fn auction() {
+    roundtrip_with(Config::with_cases(2), strategies::auction::<3>());
+}
+
+#[test]
+fn opening() {
+    roundtrip_with(Config::with_cases(4), strategies::opening());
+}
+
+#[test]
+fn transfer() {
+    roundtrip_with(Config::with_cases(2), strategies::transfer::<4>());
+}
+
+#[test]
+fn batch_insert() {
+    roundtrip_with(Config::with_cases(2), strategies::batch_insert::<4, 2>());
+}
+
+#[test]
+fn blocklist() {
+    roundtrip_with(Config::with_cases(2), strategies::blocklist::<8>());
+}
+
+#[test]
+fn semaphore() {
+    roundtrip_with(Config::with_cases(2), strategies::semaphore::<4>());
+}
+
+#[test]
+fn credential() {
+    roundtrip_with(Config::with_cases(2), strategies::credential());
+}
+
+#[test]
+fn inference() {
+    roundtrip_with(Config::with_cases(2), strategies::inference::<3, 2>());
+}
+
+#[test]
+fn elgamal() {
+    roundtrip_with(Config::with_cases(2), strategies::elgamal());
+}
+
+#[test]
+fn shuffle() {
+    roundtrip_with(Config::with_cases(2), strategies::shuffle::<3>());
+}
+
+#[test]
+fn jwt() {
+    roundtrip_with(Config::with_cases(2), strategies::jwt());
+}
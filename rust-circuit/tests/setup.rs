@@ -0,0 +1,147 @@
+mod common;
+
+use ark_bn254::{Bn254, Fr};
+use ark_ec::CurveGroup;
+use ark_ff::UniformRand;
+use ark_groth16::{Groth16, ProvingKey};
+use rand_chacha::ChaCha20Rng;
+use rand_core::SeedableRng;
+use vortex::circuit::Circuit;
+use vortex::prover;
+use vortex::setup;
+
+fn random_key(rng: &mut ChaCha20Rng) -> ProvingKey<Bn254> {
+    Groth16::<Bn254>::generate_random_parameters_with_reduction(Circuit::<Fr>::empty(), rng)
+        .unwrap()
+}
+
+#[test]
+fn contribution_verifies() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let before = random_key(&mut rng);
+    let mut after = before.clone();
+
+    let contribution =
+        setup::contribute(&mut after, setup::initial_hash(&before).unwrap(), &mut rng);
+
+    assert_ne!(after.delta_g1, before.delta_g1);
+    assert!(setup::verify_contribution(
+        &before,
+        &after,
+        &contribution,
+        &mut rng
+    ));
+}
+
+#[test]
+fn tampered_delta_does_not_verify() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let before = random_key(&mut rng);
+    let mut after = before.clone();
+    let mut contribution =
+        setup::contribute(&mut after, setup::initial_hash(&before).unwrap(), &mut rng);
+
+    // Consistent with the contribution record, but not with the proof of knowledge
+    after.delta_g1 = (after.delta_g1 * Fr::from(2u64)).into_affine();
+    contribution.delta_after = after.delta_g1;
+
+    assert!(!setup::verify_contribution(
+        &before,
+        &after,
+        &contribution,
+        &mut rng
+    ));
+}
+
+#[test]
+fn tampered_l_query_does_not_verify() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let before = random_key(&mut rng);
+    let mut after = before.clone();
+    let contribution =
+        setup::contribute(&mut after, setup::initial_hash(&before).unwrap(), &mut rng);
+
+    after.l_query[0] = (after.l_query[0] * Fr::from(2u64)).into_affine();
+
+    assert!(!setup::verify_contribution(
+        &before,
+        &after,
+        &contribution,
+        &mut rng
+    ));
+}
+
+#[test]
+fn key_from_ptau_proves_after_contribution() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let ptau = common::ptau(
+        3,
+        Fr::rand(&mut rng),
+        Fr::rand(&mut rng),
+        Fr::rand(&mut rng),
+    );
+
+    let initial = setup::from_ptau::<Bn254, _>(Circuit::<Fr>::empty(), &ptau).unwrap();
+    let mut pk = initial.clone();
+    let contribution = setup::contribute(&mut pk, setup::initial_hash(&initial).unwrap(), &mut rng);
+    assert!(setup::verify_contribution(
+        &initial,
+        &pk,
+        &contribution,
+        &mut rng
+    ));
+
+    let circuit = Circuit::new(Fr::from(15u64), Fr::from(3u64), Fr::from(5u64), false).unwrap();
+    let bundle = prover::prove(circuit, &pk, &mut rng).unwrap();
+    assert!(prover::verify(&bundle, &pk.vk).unwrap());
+
+    // The public input is bound: the same proof does not verify for another product
+    let mut wrong = bundle.clone();
+    wrong.public_inputs[0] = Fr::from(16u64);
+    assert!(!prover::verify(&wrong, &pk.vk).unwrap());
+}
+
+#[test]
+fn chained_contributions_follow_each_other() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let initial = random_key(&mut rng);
+    let mut first = initial.clone();
+    let c1 = setup::contribute(&mut first, setup::initial_hash(&initial).unwrap(), &mut rng);
+    let mut second = first.clone();
+    let c2 = setup::contribute(&mut second, c1.hash().unwrap(), &mut rng);
+
+    let one = [c1.clone()];
+    let two = [c1, c2];
+    assert_eq!(
+        setup::next_contribution(&initial, &[], &one).unwrap(),
+        &one[0]
+    );
+    assert_eq!(
+        setup::next_contribution(&first, &one, &two).unwrap(),
+        &two[1]
+    );
+}
+
+#[test]
+fn broken_chain_is_rejected() {
+    let mut rng = ChaCha20Rng::seed_from_u64(0);
+    let initial = random_key(&mut rng);
+    let mut first = initial.clone();
+    let c1 = setup::contribute(&mut first, setup::initial_hash(&initial).unwrap(), &mut rng);
+    let mut second = first.clone();
+    let c2 = setup::contribute(&mut second, c1.hash().unwrap(), &mut rng);
+    // Built on the initial key again instead of on the first contribution
+    let forked = setup::contribute(
+        &mut first.clone(),
+        setup::initial_hash(&initial).unwrap(),
+        &mut rng,
+    );
+    let history = [c1.clone()];
+
+    assert!(setup::next_contribution(&first, &history, &[c1.clone(), forked]).is_err());
+    // Drops the first contribution from the history
+    assert!(setup::next_contribution(&first, &history, std::slice::from_ref(&c2)).is_err());
+    // Adds two contributions at once, or none
+    assert!(setup::next_contribution(&initial, &[], &[c1, c2]).is_err());
+    assert!(setup::next_contribution(&first, &history, &[]).is_err());
+}
@@ -0,0 +1,89 @@
+use ark_bn254::Fr;
+use ark_relations::r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, OptimizationGoal, SynthesisMode,
+};
+use std::collections::HashSet;
+use vortex::api;
+use vortex::circuit::airdrop::AirdropCircuit;
+use vortex::circuit::auction::AuctionCircuit;
+use vortex::circuit::batch_insert::BatchInsertCircuit;
+use vortex::circuit::blocklist::BlocklistCircuit;
+use vortex::circuit::hash_chain::HashChainCircuit;
+use vortex::circuit::semaphore::SemaphoreCircuit;
+use vortex::circuit::shuffle::ShuffleCircuit;
+use vortex::circuit::sizes::{self, SIZED_CIRCUITS};
+use vortex::circuit::transfer::TransferCircuit;
+use vortex::circuit::vote::{TallyCircuit, VoteCircuit};
+
+/// Constraint and public input counts of `circuit`, synthesized for setup.
+fn counts(circuit: impl ConstraintSynthesizer<Fr>) -> (usize, usize) {
+    let cs = ConstraintSystem::<Fr>::new_ref();
+    cs.set_mode(SynthesisMode::Setup);
+    cs.set_optimization_goal(OptimizationGoal::Constraints);
+    circuit.generate_constraints(cs.clone()).unwrap();
+    cs.finalize();
+    (cs.num_constraints(), cs.num_instance_variables() - 1)
+}
+
+// The Ed25519 sizes need several GB to synthesize, so are left out
+#[test]
+fn table_matches_the_circuits() {
+    let expected = [
+        (
+            "airdrop-claim/depth=16",
+            counts(AirdropCircuit::<16>::empty()),
+        ),
+        (
+            "sealed-bid-auction/others=4",
+            counts(AuctionCircuit::<4>::empty()),
+        ),
+        (
+            "merkle-batch-insert/depth=20,batch_size=4",
+            counts(BatchInsertCircuit::<20, 4>::empty()),
+        ),
+        (
+            "blocklist-non-membership/depth=32",
+            counts(BlocklistCircuit::<32>::empty()),
+        ),
+        (
+            "hash-chain/steps=16",
+            counts(HashChainCircuit::<16>::empty()),
+        ),
+        (
+            "semaphore/depth=16",
+            counts(SemaphoreCircuit::<16>::empty()),
+        ),
+        ("shuffle/size=4", counts(ShuffleCircuit::<4>::empty())),
+        ("vote-tally/votes=16", counts(TallyCircuit::<16>::empty())),
+        (
+            "utxo-transfer-2x2/depth=16",
+            counts(TransferCircuit::<16>::empty()),
+        ),
+        (
+            "anonymous-vote/depth=16",
+            counts(VoteCircuit::<16>::empty()),
+        ),
+    ];
+
+    for (id, (num_constraints, num_public_inputs)) in expected {
+        let info = api::sized_circuit_info(id).unwrap();
+        assert_eq!(info.num_constraints, num_constraints, "{}", id);
+        assert_eq!(info.num_public_inputs, num_public_inputs, "{}", id);
+    }
+}
+
+#[test]
+fn ids_are_unique_and_found() {
+    let ids: HashSet<String> = SIZED_CIRCUITS.iter().map(|circuit| circuit.id()).collect();
+    assert_eq!(ids.len(), SIZED_CIRCUITS.len());
+    for id in &ids {
+        assert_eq!(&sizes::find(id).unwrap().id(), id);
+    }
+}
+
+#[test]
+fn unknown_size_is_rejected() {
+    assert!(sizes::find("semaphore/depth=21").is_err());
+    assert!(api::sized_circuit_info("semaphore").is_err());
+    assert!(api::keygen_sized("semaphore/depth=21").is_err());
+}
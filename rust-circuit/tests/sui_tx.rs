@@ -0,0 +1,80 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use vortex::api::ProofOutput;
+use vortex::sui::tx::{self, MoveCallTarget};
+
+/// The first BN254 test vector proof.
+fn proof() -> ProofOutput {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors/bn254.json");
+    let json: Value = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+    serde_json::from_value(json["cases"][0]["proof"].clone()).unwrap()
+}
+
+#[test]
+fn target_parses_and_pads_the_package() {
+    let target: MoveCallTarget = "0x2::verifier::assert_valid".parse().unwrap();
+    let mut package = [0u8; 32];
+    package[31] = 2;
+    assert_eq!(target.package, package);
+    assert_eq!(target.module, "verifier");
+    assert_eq!(target.function, "assert_valid");
+}
+
+#[test]
+fn invalid_targets_are_rejected() {
+    for target in [
+        "0x2::verifier",
+        "2::verifier::assert_valid",
+        "0x::verifier::assert_valid",
+        &format!("0x{}::verifier::assert_valid", "1".repeat(65)),
+        "0xzz::verifier::assert_valid",
+        "0x2::1verifier::assert_valid",
+        "0x2::verifier::assert-valid",
+    ] {
+        assert!(target.parse::<MoveCallTarget>().is_err(), "{}", target);
+    }
+}
+
+#[test]
+fn pure_arguments_are_the_bcs_byte_vectors() {
+    let proof = proof();
+    let bcs = proof.to_bcs().unwrap();
+    assert_eq!(
+        tx::pure_arguments(&proof).unwrap(),
+        [bcs.proof_points_bytes, bcs.public_inputs_bytes]
+    );
+}
+
+#[test]
+fn transaction_kind_has_the_programmable_transaction_layout() {
+    let proof = proof();
+    let target: MoveCallTarget = "0x2::verifier::assert_valid".parse().unwrap();
+    let [points, inputs] = <[Vec<u8>; 2]>::try_from(tx::pure_arguments(&proof).unwrap()).unwrap();
+
+    // ProgrammableTransaction { inputs: [Pure(points), Pure(inputs)],
+    //   commands: [MoveCall { package, module, function, [], [Input(0), Input(1)] }] }
+    // Each Pure holds the BCS bytes of a vector<u8>, themselves length-prefixed
+    assert_eq!((points.len(), inputs.len()), (130, 33));
+    let mut expected = vec![0, 2];
+    expected.extend([0, 0x82, 0x01]);
+    expected.extend(&points);
+    expected.extend([0, 33]);
+    expected.extend(&inputs);
+    expected.extend([1, 0]);
+    expected.extend(target.package);
+    expected.push(8);
+    expected.extend(b"verifier");
+    expected.push(12);
+    expected.extend(b"assert_valid");
+    expected.extend([0, 2, 1, 0, 0, 1, 1, 0]);
+
+    assert_eq!(tx::transaction_kind(&proof, &target).unwrap(), expected);
+}
+
+#[test]
+fn uncompressed_proof_has_no_arguments() {
+    let mut proof = proof();
+    proof.uncompressed = true;
+    assert!(tx::pure_arguments(&proof).is_err());
+}
@@ -0,0 +1,64 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use vortex::api::{self, ProofOutput};
+use vortex::curve::Curve;
+
+/// Checked-in vectors whose curve is enabled in this build.
+fn vectors() -> Vec<(String, Value)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors");
+    let mut vectors = Vec::new();
+    for entry in fs::read_dir(&dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let json: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let curve: Curve = serde_json::from_value(json["curve"].clone()).unwrap();
+        if curve.ensure_enabled().is_ok() {
+            vectors.push((path.display().to_string(), json));
+        }
+    }
+    assert!(!vectors.is_empty(), "no test vectors in {}", dir.display());
+    vectors
+}
+
+#[test]
+fn checked_in_proofs_verify() {
+    for (name, json) in vectors() {
+        let vk_hex = json["verifyingKeyHex"].as_str().unwrap();
+        for case in json["cases"].as_array().unwrap() {
+            let proof: ProofOutput = serde_json::from_value(case["proof"].clone()).unwrap();
+            assert!(api::verify_typed(&proof, vk_hex).unwrap(), "{}", name);
+            assert!(
+                api::verify_serialized(
+                    proof.curve,
+                    &proof.proof_serialized_hex,
+                    &proof.public_inputs_serialized_hex,
+                    vk_hex,
+                )
+                .unwrap(),
+                "{}",
+                name
+            );
+        }
+    }
+}
+
+// Proving is deterministic for a fixed key and seed, so a mismatch means the
+// serialization (or the prover's use of its randomness) changed: regenerate
+// with the testvectors binary and bump its version only if that is intended.
+#[test]
+fn proofs_are_reproducible() {
+    for (name, json) in vectors() {
+        let pk_hex = json["provingKeyHex"].as_str().unwrap();
+        let seed_hex = json["proofSeed"].as_str().unwrap();
+        for case in json["cases"].as_array().unwrap() {
+            let proof =
+                api::prove_deterministic(&case["input"].to_string(), pk_hex, seed_hex, |_| {})
+                    .unwrap();
+            let proof: Value = serde_json::from_str(&proof).unwrap();
+            assert_eq!(proof, case["proof"], "{}", name);
+        }
+    }
+}
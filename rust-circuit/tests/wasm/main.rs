@@ -0,0 +1,53 @@
+// The wasm entry points, called natively. Their error paths build `JsValue`s,
+// which only exist on wasm targets, so errors are tested on the `api`
+// functions they wrap (see `tests/api.rs`). Run with `--features wasm`, or
+// with `wasm-prover` or `wasm-verifier` alone to test either half of the
+// split build.
+
+#[cfg(feature = "wasm-prover")]
+mod prover;
+#[cfg(feature = "wasm-verifier")]
+mod verifier;
+
+use ark_bn254::Fr;
+use serde_json::Value;
+use vortex::poseidon;
+use vortex::wasm;
+
+/// The BN254 test vectors, made with the keys in `keys/`.
+#[cfg(any(feature = "wasm-prover", feature = "wasm-verifier"))]
+fn vectors() -> Value {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("testvectors/bn254.json");
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+/// The first test vector proof, for `a = 5`, `b = 6`.
+#[cfg(any(feature = "wasm-prover", feature = "wasm-verifier"))]
+fn proof() -> vortex::api::ProofOutput {
+    serde_json::from_value(vectors()["cases"][0]["proof"].clone()).unwrap()
+}
+
+#[cfg(any(feature = "wasm-prover", feature = "wasm-verifier"))]
+fn vk_hex() -> String {
+    vectors()["verifyingKeyHex"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn poseidon_hash_matches_the_native_hash() {
+    let digest = poseidon::hash(&[Fr::from(1u64), Fr::from(30u64)]).unwrap();
+    assert_eq!(
+        wasm::poseidon_hash(vec!["1".into(), "0x1e".into()]).unwrap(),
+        digest.to_string()
+    );
+}
+
+#[test]
+fn circuit_info_returns_json() {
+    let info: Value = serde_json::from_str(&wasm::circuit_info(None).unwrap()).unwrap();
+    assert_eq!(info["name"], "multiplication");
+    assert_eq!(info["publicInputNames"], serde_json::json!(["c"]));
+    assert!(info["provingKeyFingerprint"].is_string());
+
+    let info: Value = serde_json::from_str(&wasm::circuit_info(Some(true)).unwrap()).unwrap();
+    assert!(info.get("provingKeyFingerprint").is_none());
+}
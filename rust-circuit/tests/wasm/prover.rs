@@ -0,0 +1,61 @@
+use crate::{proof, vectors, vk_hex};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use vortex::api;
+use vortex::keys;
+use vortex::wasm;
+
+#[test]
+fn prove_deterministic_reproduces_the_test_vector() {
+    let vectors = vectors();
+    let case = &vectors["cases"][0];
+    let proof = wasm::prove_deterministic(
+        &case["input"].to_string(),
+        vectors["provingKeyHex"].as_str().unwrap(),
+        vectors["proofSeed"].as_str().unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        serde_json::from_str::<Value>(&proof).unwrap(),
+        case["proof"]
+    );
+}
+
+#[test]
+fn rerandomize_proof_returns_a_new_valid_proof() {
+    let json = serde_json::to_string(&proof()).unwrap();
+    let rerandomized = wasm::rerandomize_proof(&json, &vk_hex()).unwrap();
+
+    assert_ne!(rerandomized, json);
+    assert!(api::verify(&rerandomized, &vk_hex()).unwrap());
+}
+
+#[test]
+fn compute_expected_public_returns_the_product() {
+    assert_eq!(
+        wasm::compute_expected_public(r#"{"a": "5", "b": "6"}"#).unwrap(),
+        r#"{"c":"30"}"#
+    );
+}
+
+#[test]
+fn proving_key_loader_assembles_a_reusable_key() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("keys/proving_key.bin");
+    let mut loader = wasm::ProvingKeyLoader::new();
+    for chunk in fs::read(path).unwrap().chunks(1000) {
+        loader.push_chunk(chunk.to_vec());
+    }
+    let handle = loader.finish().unwrap();
+    assert_eq!(
+        handle.fingerprint(),
+        keys::expected_proving_key_fingerprint()
+    );
+
+    let input = vectors()["cases"][0]["input"].to_string();
+    for _ in 0..2 {
+        let proof = handle.prove(&input, None).unwrap();
+        assert!(api::verify(&proof, &vk_hex()).unwrap());
+    }
+}
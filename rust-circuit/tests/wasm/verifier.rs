@@ -0,0 +1,63 @@
+use crate::{proof, vk_hex};
+use vortex::api::ProofOutput;
+use vortex::wasm;
+
+#[test]
+fn verify_with_inputs_returns_a_structured_result() {
+    let proof = proof();
+    let verify = |inputs: &[&str]| {
+        wasm::verify_with_inputs(
+            &proof.proof_serialized_hex,
+            inputs.iter().map(|s| s.to_string()).collect(),
+            &vk_hex(),
+            None,
+        )
+        .unwrap()
+    };
+
+    assert_eq!(verify(&["30"]), r#"{"valid":true}"#);
+    assert_eq!(
+        verify(&["31"]),
+        r#"{"valid":false,"reason":"pairing_mismatch"}"#
+    );
+    assert_eq!(
+        verify(&["30", "1"]),
+        r#"{"valid":false,"reason":"input_count_mismatch"}"#
+    );
+}
+
+#[test]
+fn verify_serialized_returns_a_structured_result() {
+    let proof = proof();
+    let verify = |inputs_hex: &str| {
+        wasm::verify_serialized(&proof.proof_serialized_hex, inputs_hex, &vk_hex(), None).unwrap()
+    };
+
+    assert_eq!(
+        verify(&proof.public_inputs_serialized_hex),
+        r#"{"valid":true}"#
+    );
+    assert_eq!(
+        verify(&format!("1f{}", "00".repeat(31))),
+        r#"{"valid":false,"reason":"pairing_mismatch"}"#
+    );
+    assert_eq!(
+        verify(&"00".repeat(64)),
+        r#"{"valid":false,"reason":"input_count_mismatch"}"#
+    );
+}
+
+#[test]
+fn verify_returns_a_structured_result() {
+    let mut proof = proof();
+    let verify = |proof: &ProofOutput| {
+        wasm::verify(&serde_json::to_string(proof).unwrap(), &vk_hex()).unwrap()
+    };
+
+    assert_eq!(verify(&proof), r#"{"valid":true}"#);
+    proof.public_inputs = vec!["31".into()];
+    assert_eq!(
+        verify(&proof),
+        r#"{"valid":false,"reason":"pairing_mismatch"}"#
+    );
+}
@@ -0,0 +1,73 @@
+use ark_bn254::Fr;
+use vortex::circuit::Circuit;
+use vortex::witness::{self, Witness};
+
+/// Circuits for `c = a * b` with `a = 1..=count`, `b = a + 1`.
+fn circuits(count: u64) -> Vec<Circuit<Fr>> {
+    (1..=count)
+        .map(|a| Circuit::new(Fr::from(a * (a + 1)), Fr::from(a), Fr::from(a + 1), false).unwrap())
+        .collect()
+}
+
+/// Runs `generate_batch` and returns the results ordered by index.
+fn generate_batch(circuits: Vec<Circuit<Fr>>, workers: usize) -> Vec<anyhow::Result<Witness<Fr>>> {
+    let mut results = Vec::new();
+    witness::generate_batch(circuits, workers, 1, |index, result| {
+        results.push((index, result))
+    });
+    results.sort_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[test]
+fn batch_results_are_indexed_by_input_position() {
+    let circuits = circuits(16);
+    let expected = circuits
+        .iter()
+        .map(|circuit| circuit.get_public_inputs())
+        .collect::<Vec<_>>();
+
+    let results = generate_batch(circuits, 4);
+    assert_eq!(results.len(), expected.len());
+    for (result, public_inputs) in results.iter().zip(&expected) {
+        assert_eq!(result.as_ref().unwrap().public_inputs(), &public_inputs[..]);
+    }
+}
+
+#[test]
+fn batch_matches_single_generation() {
+    let single = circuits(4)
+        .into_iter()
+        .map(|circuit| witness::generate(circuit).unwrap())
+        .collect::<Vec<_>>();
+
+    let batch = generate_batch(circuits(4), 3);
+    for (batch, single) in batch.iter().zip(&single) {
+        let batch = batch.as_ref().unwrap();
+        assert_eq!(batch.instance, single.instance);
+        assert_eq!(batch.witness, single.witness);
+    }
+}
+
+#[test]
+fn unsatisfied_circuit_mid_batch_reports_its_error() {
+    let mut circuits = circuits(8);
+    // 3 * 5 != 16
+    circuits[4] = Circuit::new(Fr::from(16u64), Fr::from(3u64), Fr::from(5u64), false).unwrap();
+
+    let results = generate_batch(circuits, 2);
+    assert_eq!(results.len(), 8);
+    for (index, result) in results.iter().enumerate() {
+        assert_eq!(result.is_err(), index == 4, "circuit {}", index);
+    }
+    let error = results[4].as_ref().unwrap_err().to_string();
+    assert!(error.contains("not satisfied"), "{}", error);
+}
+
+#[test]
+fn unsatisfied_circuit_error_names_the_constraint() {
+    // 3 * 5 != 16
+    let circuit = Circuit::new(Fr::from(16u64), Fr::from(3u64), Fr::from(5u64), false).unwrap();
+    let error = witness::generate(circuit).unwrap_err().to_string();
+    assert!(error.contains("c == a * b"), "{}", error);
+}
@@ -69,8 +69,8 @@ interface Proof {
     const proof = JSON.parse(proofJson) as Proof;
 
     // Verify the proof using the extracted verification key
-    const isVerified = verify(proofJson, verificationKey.trim());
-    console.log('isVerified', isVerified);
+    const verification = JSON.parse(verify(proofJson, verificationKey.trim()));
+    console.log('isVerified', verification.valid, verification.reason ?? '');
     tx.moveCall({
       package: VORTEX_PACKAGE_ID,
       module: 'vortex',